@@ -19,16 +19,14 @@ pub async fn get_balance_from_wallet(
 
     let client = Client::new();
     let uri: Uri = { endpoint.parse().expect("URI should be made") };
-    let params = format!(r#"{{"acc_addr": "{}"}}"#, acc_addr)
-        .as_bytes()
-        .to_vec();
+    let params = serde_json::json!({ "acc_addr": acc_addr });
 
     let body = {
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_balance".to_string(),
             params: Some(params),
-            id: "evl_id".to_string(),
+            id: "evl_id".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -73,13 +71,13 @@ pub async fn send_tx_pour(
             ctr_request,
         };
 
-        let params = serde_json::to_vec(&send_req)?;
+        let params = serde_json::to_value(&send_req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "evl_id".to_string(),
+            id: "evl_id".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -111,16 +109,14 @@ pub async fn update_wallet(
     let client = Client::new();
 
     let uri: Uri = { endpoint.parse().expect("URI should be made") };
-    let params = format!(r#"{{"acc_addr": "{}"}}"#, acc_addr)
-        .as_bytes()
-        .to_vec();
+    let params = serde_json::json!({ "acc_addr": acc_addr });
 
     let body = {
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "update_coin_status".to_string(),
             params: Some(params),
-            id: "evl_id".to_string(),
+            id: "evl_id".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -4,7 +4,7 @@ macro_rules! require_some_params {
         match $obj {
             Some(t) => t,
             None => {
-                return hyper_rpc_router::make_error_response(
+                return hyper_rpc_router::make_invalid_params_response(
                     $route_state.resp,
                     Some($route_state.id),
                     $msg.into(),
@@ -16,7 +16,7 @@ macro_rules! require_some_params {
         match $obj {
             Some(t) => t,
             None => {
-                return hyper_rpc_router::make_error_response(
+                return hyper_rpc_router::make_invalid_params_response(
                     $route_state.resp,
                     Some($route_state.id),
                     $msg.into(),
@@ -29,25 +29,25 @@ macro_rules! require_some_params {
 #[macro_export]
 macro_rules! require_params_parsed {
     ($route_state: expr, $params: expr) => {
-        match serde_json::from_slice($params) {
+        match serde_json::from_value(serde_json::Value::clone($params)) {
             Ok(r) => r,
             Err(err) => {
-                return hyper_rpc_router::make_error_response(
+                return hyper_rpc_router::make_invalid_params_response(
                     $route_state.resp,
                     Some($route_state.id),
-                    err.into(),
+                    err.to_string(),
                 );
             }
         }
     };
     ($route_state: expr, $params: expr,) => {
-        match serde_json::from_slice($params) {
+        match serde_json::from_value(serde_json::Value::clone($params)) {
             Ok(r) => r,
             Err(err) => {
-                return router::make_error_response(
+                return hyper_rpc_router::make_invalid_params_response(
                     $route_state.resp,
                     Some($route_state.id),
-                    err.into(),
+                    err.to_string(),
                 );
             }
         }
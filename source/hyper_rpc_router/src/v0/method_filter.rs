@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+// A method has to be allowed (if an allow list is given) and not denied
+#[derive(Debug, Clone, Default)]
+pub struct MethodFilter {
+    allowed: Option<HashSet<String>>,
+    denied: HashSet<String>,
+}
+
+impl MethodFilter {
+    pub fn new(
+        allowed: Option<Vec<String>>,
+        denied: Option<Vec<String>>,
+    ) -> MethodFilter {
+        MethodFilter {
+            allowed: allowed.map(|a| a.into_iter().collect()),
+            denied: denied.unwrap_or_default().into_iter().collect(),
+        }
+    }
+
+    pub fn is_allowed(&self, method: &str) -> bool {
+        if self.denied.contains(method) {
+            return false;
+        }
+
+        match &self.allowed {
+            Some(a) => a.contains(method),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::make_error_response;
+use hyper::{header, Body, Request, Response};
+use hyper_server::{Middleware, MiddlewareResult};
+use sak_rpc_interface::{JsonRPCError, UNAUTHORIZED};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const HMAC_TIMESTAMP_HEADER: &'static str = "x-sak-timestamp";
+
+pub const HMAC_SIGNATURE_HEADER: &'static str = "x-sak-signature";
+
+// A signed request older (or newer) than this, in seconds, is rejected
+pub const HMAC_MAX_CLOCK_SKEW: u64 = 300;
+
+const BEARER_PREFIX: &'static str = "Bearer ";
+
+#[derive(Clone)]
+pub enum RPCAuth {
+    // "Authorization: Bearer <token>"
+    Bearer { token: String },
+    // "x-sak-signature" is the hex encoded HMAC-SHA256 of
+    // "<x-sak-timestamp>.<body>" under the secret
+    Hmac { secret: Vec<u8> },
+}
+
+// Secrets stay out of logs
+impl std::fmt::Debug for RPCAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RPCAuth::Bearer { .. } => write!(f, "Bearer"),
+            RPCAuth::Hmac { .. } => write!(f, "Hmac"),
+        }
+    }
+}
+
+pub fn make_auth_middleware<C>(auth: RPCAuth) -> Middleware<C>
+where
+    C: Send + Sync + 'static,
+{
+    let auth = Arc::new(auth);
+
+    Middleware::new(Box::new(move |req, resp, ctx| match auth.as_ref() {
+        RPCAuth::Bearer { token } => check_bearer(token, req, resp, ctx),
+        RPCAuth::Hmac { secret } => check_hmac(secret.clone(), req, resp, ctx),
+    }))
+}
+
+pub fn make_hmac_signature(
+    secret: &[u8],
+    timestamp: u64,
+    body: &[u8],
+) -> String {
+    let data = make_hmac_data(timestamp, body);
+
+    sak_crypto::encode_hex(&sak_crypto::make_hmac_sha256(secret, &data))
+}
+
+fn check_bearer<C>(
+    token: &String,
+    req: Request<Body>,
+    resp: Response<Body>,
+    ctx: C,
+) -> MiddlewareResult<C> {
+    let given = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix(BEARER_PREFIX));
+
+    match given {
+        Some(t)
+            if is_equal_in_constant_time(t.as_bytes(), token.as_bytes()) =>
+        {
+            MiddlewareResult::Passing(req, resp, ctx)
+        }
+        Some(_) => make_unauthorized_result(resp, "Invalid bearer token"),
+        None => make_unauthorized_result(resp, "Bearer token is missing"),
+    }
+}
+
+fn check_hmac<C>(
+    secret: Vec<u8>,
+    req: Request<Body>,
+    resp: Response<Body>,
+    ctx: C,
+) -> MiddlewareResult<C>
+where
+    C: Send + Sync + 'static,
+{
+    let timestamp = match req
+        .headers()
+        .get(HMAC_TIMESTAMP_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        Some(t) => t,
+        None => {
+            return make_unauthorized_result(resp, "Timestamp is missing");
+        }
+    };
+
+    let sig = match req
+        .headers()
+        .get(HMAC_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        // decode_hex takes two chars at a time
+        .filter(|v| v.len() % 2 == 0)
+        .and_then(|v| sak_crypto::decode_hex(&v.to_string()).ok())
+    {
+        Some(s) => s,
+        None => {
+            return make_unauthorized_result(resp, "Signature is missing");
+        }
+    };
+
+    let now = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs(),
+        Err(_) => 0,
+    };
+
+    let skew = if now > timestamp {
+        now - timestamp
+    } else {
+        timestamp - now
+    };
+
+    if skew > HMAC_MAX_CLOCK_SKEW {
+        return make_unauthorized_result(resp, "Timestamp is out of range");
+    }
+
+    // The signature covers the body, so the body is read here and put back
+    // for the middlewares that follow
+    MiddlewareResult::Deferred(Box::pin(async move {
+        let (parts, body) = req.into_parts();
+
+        let body = match hyper::body::to_bytes(body).await {
+            Ok(b) => b,
+            Err(err) => {
+                return MiddlewareResult::End(Box::pin(async move {
+                    Ok(make_error_response(resp, None, err.into()))
+                }));
+            }
+        };
+
+        let data = make_hmac_data(timestamp, &body);
+
+        if !sak_crypto::verify_hmac_sha256(&secret, &data, &sig) {
+            return make_unauthorized_result(resp, "Invalid signature");
+        }
+
+        let req = Request::from_parts(parts, Body::from(body));
+
+        MiddlewareResult::Passing(req, resp, ctx)
+    }))
+}
+
+fn make_hmac_data(timestamp: u64, body: &[u8]) -> Vec<u8> {
+    let mut data = format!("{}.", timestamp).into_bytes();
+    data.extend_from_slice(body);
+
+    data
+}
+
+fn make_unauthorized_result<C>(
+    resp: Response<Body>,
+    msg: &str,
+) -> MiddlewareResult<C> {
+    let resp = make_error_response(
+        resp,
+        None,
+        JsonRPCError::new(UNAUTHORIZED, msg.to_string()).into(),
+    );
+
+    MiddlewareResult::End(Box::pin(async { Ok(resp) }))
+}
+
+fn is_equal_in_constant_time(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,5 @@
+mod auth;
+mod rate_limit;
+
+pub use auth::*;
+pub use rate_limit::*;
@@ -0,0 +1,134 @@
+use crate::make_error_response;
+use hyper::header::{self, HeaderValue};
+use hyper_server::{ClientAddr, Middleware, MiddlewareResult};
+use log::warn;
+use sak_rpc_interface::{JsonRPCError, RATE_LIMITED};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// Buckets that have refilled completely are dropped beyond this many
+const MAX_TRACKED_IPS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests_per_sec: u32,
+    pub burst: u32,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+// A token bucket per IP. Each request takes a token, and tokens come back
+// at `requests_per_sec` up to `burst`. Each entry of a batch is a request
+// of its own, which the router charges for.
+pub struct RateLimiter {
+    rate_limit: RateLimit,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit: RateLimit) -> RateLimiter {
+        RateLimiter {
+            rate_limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // On failure, returns how long it takes until the next token
+    pub fn try_acquire(&self, ip: IpAddr) -> Result<(), Duration> {
+        let RateLimit {
+            requests_per_sec,
+            burst,
+        } = self.rate_limit;
+
+        let rate = requests_per_sec as f64;
+        let capacity = burst.max(1) as f64;
+        let now = Instant::now();
+
+        let mut buckets = match self.buckets.lock() {
+            Ok(b) => b,
+            Err(err) => err.into_inner(),
+        };
+
+        if buckets.len() >= MAX_TRACKED_IPS && !buckets.contains_key(&ip) {
+            buckets.retain(|_, b| {
+                let elapsed = now.duration_since(b.updated_at).as_secs_f64();
+
+                b.tokens + elapsed * rate < capacity
+            });
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+
+            return Ok(());
+        }
+
+        let wait = if rate > 0.0 {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / rate)
+        } else {
+            Duration::MAX
+        };
+
+        Err(wait)
+    }
+}
+
+// Takes the token of the first entry if the request is a batch. The same
+// limiter should be given to the router for the rest.
+pub fn make_rate_limit_middleware<C>(
+    rate_limiter: Arc<RateLimiter>,
+) -> Middleware<C>
+where
+    C: Send + Sync + 'static,
+{
+    Middleware::new(Box::new(move |req, resp, ctx| {
+        let ip = match req.extensions().get::<ClientAddr>() {
+            Some(a) => a.0.ip(),
+            None => {
+                warn!("Client address is unknown, request is not limited");
+
+                return MiddlewareResult::Passing(req, resp, ctx);
+            }
+        };
+
+        let wait = match rate_limiter.try_acquire(ip) {
+            Ok(_) => return MiddlewareResult::Passing(req, resp, ctx),
+            Err(w) => w,
+        };
+
+        let mut resp = make_error_response(
+            resp,
+            None,
+            JsonRPCError::new(
+                RATE_LIMITED,
+                format!("Too many requests, ip: {}", ip),
+            )
+            .into(),
+        );
+
+        let retry_after = wait.as_secs().max(1).to_string();
+
+        if let Ok(v) = HeaderValue::from_str(&retry_after) {
+            resp.headers_mut().insert(header::RETRY_AFTER, v);
+        }
+
+        MiddlewareResult::End(Box::pin(async { Ok(resp) }))
+    }))
+}
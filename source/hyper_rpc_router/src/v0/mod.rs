@@ -1,10 +1,14 @@
 pub(crate) mod header;
 mod macros;
+mod method_filter;
+mod middlewares;
 mod response;
 mod route_map;
 mod router;
 
 // pub use header::*;
+pub use method_filter::*;
+pub use middlewares::*;
 pub use response::*;
 pub use route_map::*;
 pub use router::*;
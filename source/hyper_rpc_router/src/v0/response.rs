@@ -1,13 +1,15 @@
 use crate::{header, RPCRouterError};
-use hyper::{
-    header::{HeaderValue, CONTENT_TYPE},
-    Body, Response, StatusCode,
+use hyper::{Body, Response, StatusCode};
+use sak_rpc_interface::{
+    JsonRPCError, JsonRPCId, JsonResponse, APPLICATION_ERROR, INTERNAL_ERROR,
+    INVALID_PARAMS, INVALID_REQUEST, JSON_RPC_2, LEDGER_PRUNED,
+    METHOD_NOT_ALLOWED, METHOD_NOT_FOUND, PARSE_ERROR, RATE_LIMITED,
+    UNAUTHORIZED,
 };
-use sak_rpc_interface::{JsonRPCError, JsonResponse, JSON_RPC_2};
 use serde::Serialize;
 
 pub struct RouteState {
-    pub id: String,
+    pub id: JsonRPCId,
     pub resp: Response<Body>,
 }
 
@@ -26,7 +28,7 @@ pub fn make_success_response<D: Serialize>(
             jsonrpc: JSON_RPC_2.into(),
             error: None,
             result: Some(result),
-            id: route_state.id.to_string(),
+            id: route_state.id.clone(),
         };
 
         let body_str = match serde_json::to_string(&response) {
@@ -48,7 +50,7 @@ pub fn make_success_response<D: Serialize>(
 
 pub fn make_serialize_err_response(
     mut resp: Response<Body>,
-    id: String,
+    id: JsonRPCId,
     original_err: Option<RPCRouterError>,
 ) -> Response<Body> {
     header::add_application_json_header(&mut resp);
@@ -62,51 +64,68 @@ pub fn make_serialize_err_response(
     };
 
     *resp.body_mut() = {
-        let msg = format!(
-            r#"
-    {{
-        id: {},
-        error: {{
-            msg: Cannot serialize error, original err (if any): {}
-        }}
-    }}
-    "#,
-            id, err,
+        let error = JsonRPCError::new(
+            INTERNAL_ERROR,
+            format!(
+                "Cannot serialize response, original err (if any): {}",
+                err
+            ),
         );
 
-        Body::from(msg)
+        // Neither the id nor the error carries anything that fails to
+        // serialize
+        let msg = serde_json::json!({
+            "jsonrpc": JSON_RPC_2,
+            "error": error,
+            "id": id,
+        });
+
+        Body::from(msg.to_string())
     };
 
     resp
 }
 
 pub fn make_not_found_response(route_state: RouteState) -> Response<Body> {
-    let mut resp = route_state.resp;
+    make_error_response(
+        route_state.resp,
+        Some(route_state.id),
+        JsonRPCError::new(METHOD_NOT_FOUND, "Method not found".into()).into(),
+    )
+}
 
-    *resp.status_mut() = StatusCode::NOT_FOUND;
-    *resp.body_mut() = Body::from("not found");
-    resp
+pub fn make_invalid_params_response(
+    resp: Response<Body>,
+    id: Option<JsonRPCId>,
+    msg: String,
+) -> Response<Body> {
+    make_error_response(resp, id, JsonRPCError::new(INVALID_PARAMS, msg).into())
 }
 
+// A JsonRPCError is sent with its own code. Any other error is an
+// application error
 pub fn make_error_response(
     mut resp: Response<Body>,
-    id: Option<String>,
+    id: Option<JsonRPCId>,
     error: RPCRouterError,
 ) -> Response<Body> {
-    let id = id.unwrap_or("none".to_string());
+    let id = id.unwrap_or(JsonRPCId::Null);
+
+    let error = match error.downcast::<JsonRPCError>() {
+        Ok(e) => *e,
+        Err(err) => JsonRPCError::new(APPLICATION_ERROR, err.to_string()),
+    };
 
     header::add_application_json_header(&mut resp);
 
-    *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+    *resp.status_mut() = get_status_code(error.code);
 
     *resp.body_mut() = {
         let response: JsonResponse<()> = JsonResponse {
             jsonrpc: JSON_RPC_2.into(),
-            error: Some(JsonRPCError {
-                msg: error.to_string(),
-            }),
+            error: Some(error),
             result: None,
-            id: id.to_string(),
+            id: id.clone(),
         };
 
         let body_str = match serde_json::to_string(&response) {
@@ -126,3 +145,17 @@ pub fn make_error_response(
 
     resp
 }
+
+fn get_status_code(code: i64) -> StatusCode {
+    match code {
+        PARSE_ERROR | INVALID_REQUEST | INVALID_PARAMS => {
+            StatusCode::BAD_REQUEST
+        }
+        METHOD_NOT_FOUND => StatusCode::NOT_FOUND,
+        UNAUTHORIZED => StatusCode::UNAUTHORIZED,
+        METHOD_NOT_ALLOWED => StatusCode::FORBIDDEN,
+        RATE_LIMITED => StatusCode::TOO_MANY_REQUESTS,
+        LEDGER_PRUNED => StatusCode::GONE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
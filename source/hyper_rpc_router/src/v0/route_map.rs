@@ -5,7 +5,7 @@ use std::pin::Pin;
 
 pub type MethodName = &'static str;
 
-pub type Params = Option<Vec<u8>>;
+pub type Params = Option<serde_json::Value>;
 
 pub type Handler<C> = Box<
     dyn Fn(
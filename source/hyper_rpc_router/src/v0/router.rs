@@ -1,21 +1,64 @@
-use super::{response, Handler, RouteState};
-use hyper::{Body, Request, Response};
-use hyper_server::MiddlewareResult;
-use sak_rpc_interface::JsonRequest;
-use std::{collections::HashMap, sync::Arc};
+use super::{header, response, Handler, MethodFilter, RateLimiter, RouteState};
+use hyper::{Body, Request, Response, StatusCode};
+use hyper_server::{ClientAddr, MiddlewareResult};
+use log::error;
+use sak_rpc_interface::{
+    JsonRPCError, JsonRPCId, JsonRequest, INTERNAL_ERROR, INVALID_REQUEST,
+    JSON_RPC_2, METHOD_NOT_ALLOWED, PARSE_ERROR, RATE_LIMITED,
+};
+use serde_json::Value;
+use std::{collections::HashMap, net::IpAddr, sync::Arc};
+
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+type RouteMap<C> = Arc<HashMap<&'static str, Handler<C>>>;
 
 pub struct Router<C> {
-    route_map: Arc<HashMap<&'static str, Handler<C>>>,
+    route_map: RouteMap<C>,
+    max_batch_size: usize,
+    method_filter: Arc<MethodFilter>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl<C> Router<C>
 where
-    C: Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
 {
     pub fn new(route_map: HashMap<&'static str, Handler<C>>) -> Router<C> {
         let route_map = Arc::new(route_map);
 
-        Router { route_map }
+        Router {
+            route_map,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            method_filter: Arc::new(MethodFilter::default()),
+            rate_limiter: None,
+        }
+    }
+
+    pub fn with_max_batch_size(self, max_batch_size: usize) -> Router<C> {
+        Router {
+            max_batch_size,
+            ..self
+        }
+    }
+
+    pub fn with_method_filter(self, method_filter: MethodFilter) -> Router<C> {
+        Router {
+            method_filter: Arc::new(method_filter),
+            ..self
+        }
+    }
+
+    // Entries of a batch but the first are charged here. The first one is
+    // charged by the rate limit middleware as the request itself.
+    pub fn with_rate_limiter(
+        self,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Router<C> {
+        Router {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
     }
 
     pub fn route(
@@ -25,6 +68,15 @@ where
         ctx: C,
     ) -> MiddlewareResult<C> {
         let route_map = self.route_map.clone();
+        let max_batch_size = self.max_batch_size;
+        let method_filter = self.method_filter.clone();
+
+        let client_addr = req.extensions().get::<ClientAddr>();
+
+        let rate_limit = match (&self.rate_limiter, client_addr) {
+            (Some(l), Some(a)) => Some((l.clone(), a.0.ip())),
+            _ => None,
+        };
 
         let result = Box::pin(async move {
             let route_map = route_map.clone();
@@ -40,35 +92,367 @@ where
                 }
             };
 
-            let json_request: JsonRequest = match serde_json::from_slice(&rb) {
-                Ok(r) => r,
+            let body: Value = match serde_json::from_slice(&rb) {
+                Ok(v) => v,
                 Err(err) => {
                     return Ok(response::make_error_response(
                         resp,
                         None,
-                        format!(
-                            "Failed to parse as json_request, err: {}",
-                            err
+                        JsonRPCError::new(
+                            PARSE_ERROR,
+                            format!("Failed to parse as json, err: {}", err),
                         )
                         .into(),
                     ));
                 }
             };
 
+            let resp = match body {
+                Value::Array(requests) => {
+                    route_batch(
+                        route_map,
+                        method_filter,
+                        rate_limit,
+                        requests,
+                        max_batch_size,
+                        resp,
+                        ctx,
+                    )
+                    .await
+                }
+                request => {
+                    route_single(route_map, method_filter, request, resp, ctx)
+                        .await
+                }
+            };
+
+            Ok(resp)
+        });
+
+        MiddlewareResult::End(result)
+    }
+}
+
+async fn route_single<C>(
+    route_map: RouteMap<C>,
+    method_filter: Arc<MethodFilter>,
+    request: Value,
+    resp: Response<Body>,
+    ctx: C,
+) -> Response<Body> {
+    let is_notification = is_notification(&request);
+
+    let json_request = match parse_json_request(request) {
+        Ok(r) => r,
+        Err((id, err)) => {
+            return response::make_error_response(resp, Some(id), err.into());
+        }
+    };
+
+    if !method_filter.is_allowed(&json_request.method) {
+        if is_notification {
+            return make_empty_response(resp);
+        }
+
+        return response::make_error_response(
+            resp,
+            Some(json_request.id),
+            make_not_allowed_error(&json_request.method).into(),
+        );
+    }
+
+    let handler = match route_map.get(json_request.method.as_str()) {
+        Some(h) => h,
+        None => {
+            if is_notification {
+                return make_empty_response(resp);
+            }
+
             let route_state = RouteState {
                 id: json_request.id,
                 resp,
             };
 
-            if let Some(handler) = route_map.get(json_request.method.as_str()) {
-                let resp = handler(route_state, json_request.params, ctx).await;
+            return response::make_not_found_response(route_state);
+        }
+    };
+
+    if is_notification {
+        // The handler still runs, only its response is dropped
+        let route_state = RouteState {
+            id: json_request.id,
+            resp: Response::new(Body::empty()),
+        };
+
+        handler(route_state, json_request.params, ctx).await;
+
+        return make_empty_response(resp);
+    }
+
+    let route_state = RouteState {
+        id: json_request.id,
+        resp,
+    };
+
+    handler(route_state, json_request.params, ctx).await
+}
+
+// Requests of a batch run concurrently. Their responses are put into one
+// array in the order of the requests, leaving out notifications
+async fn route_batch<C>(
+    route_map: RouteMap<C>,
+    method_filter: Arc<MethodFilter>,
+    rate_limit: Option<(Arc<RateLimiter>, IpAddr)>,
+    requests: Vec<Value>,
+    max_batch_size: usize,
+    resp: Response<Body>,
+    ctx: C,
+) -> Response<Body>
+where
+    C: Clone + Send + Sync + 'static,
+{
+    if requests.is_empty() {
+        return response::make_error_response(
+            resp,
+            None,
+            JsonRPCError::new(INVALID_REQUEST, "Batch is empty".into()).into(),
+        );
+    }
+
+    if requests.len() > max_batch_size {
+        return response::make_error_response(
+            resp,
+            None,
+            JsonRPCError::new(
+                INVALID_REQUEST,
+                format!(
+                    "Batch is too large, len: {}, max: {}",
+                    requests.len(),
+                    max_batch_size
+                ),
+            )
+            .into(),
+        );
+    }
+
+    // Tokens are taken in the order of the entries, before any of them runs
+    let rate_limited_ip = |idx: usize| match &rate_limit {
+        Some((l, ip)) if idx > 0 && l.try_acquire(*ip).is_err() => Some(*ip),
+        _ => None,
+    };
+
+    let entries: Vec<(Value, Option<IpAddr>)> = requests
+        .into_iter()
+        .enumerate()
+        .map(|(idx, request)| (request, rate_limited_ip(idx)))
+        .collect();
+
+    let handles = entries.into_iter().map(|(request, rate_limited_ip)| {
+        let route_map = route_map.clone();
+        let method_filter = method_filter.clone();
+        let ctx = ctx.clone();
 
-                Ok(resp)
-            } else {
-                return Ok(response::make_not_found_response(route_state));
+        tokio::spawn(async move {
+            route_batch_entry(
+                route_map,
+                method_filter,
+                rate_limited_ip,
+                request,
+                ctx,
+            )
+            .await
+        })
+    });
+
+    let mut responses = vec![];
+
+    for (idx, handle) in futures::future::join_all(handles)
+        .await
+        .into_iter()
+        .enumerate()
+    {
+        match handle {
+            Ok(Some(r)) => responses.push(r),
+            Ok(None) => {}
+            Err(err) => {
+                error!(
+                    "Request in a batch has failed to complete, idx: {}, \
+                    err: {}",
+                    idx, err,
+                );
+
+                responses.push(make_error_value(
+                    JsonRPCId::Null,
+                    JsonRPCError::new(INTERNAL_ERROR, err.to_string()),
+                ));
             }
-        });
+        }
+    }
 
-        MiddlewareResult::End(result)
+    if responses.is_empty() {
+        return make_empty_response(resp);
+    }
+
+    let mut resp = resp;
+
+    header::add_application_json_header(&mut resp);
+
+    *resp.status_mut() = StatusCode::OK;
+
+    *resp.body_mut() = Body::from(Value::Array(responses).to_string());
+
+    resp
+}
+
+async fn route_batch_entry<C>(
+    route_map: RouteMap<C>,
+    method_filter: Arc<MethodFilter>,
+    rate_limited_ip: Option<IpAddr>,
+    request: Value,
+    ctx: C,
+) -> Option<Value> {
+    let is_notification = is_notification(&request);
+
+    let json_request = match parse_json_request(request) {
+        Ok(r) => r,
+        Err((id, err)) => return Some(make_error_value(id, err)),
+    };
+
+    if let Some(ip) = rate_limited_ip {
+        if is_notification {
+            return None;
+        }
+
+        return Some(make_error_value(
+            json_request.id,
+            JsonRPCError::new(
+                RATE_LIMITED,
+                format!("Too many requests, ip: {}", ip),
+            ),
+        ));
+    }
+
+    if !method_filter.is_allowed(&json_request.method) {
+        if is_notification {
+            return None;
+        }
+
+        return Some(make_error_value(
+            json_request.id,
+            make_not_allowed_error(&json_request.method),
+        ));
+    }
+
+    let route_state = RouteState {
+        id: json_request.id.clone(),
+        resp: Response::new(Body::empty()),
+    };
+
+    let resp = match route_map.get(json_request.method.as_str()) {
+        Some(handler) => handler(route_state, json_request.params, ctx).await,
+        None => response::make_not_found_response(route_state),
+    };
+
+    if is_notification {
+        return None;
+    }
+
+    let b = match hyper::body::to_bytes(resp.into_body()).await {
+        Ok(b) => b,
+        Err(err) => {
+            return Some(make_error_value(
+                json_request.id,
+                JsonRPCError::new(INTERNAL_ERROR, err.to_string()),
+            ));
+        }
+    };
+
+    match serde_json::from_slice(&b) {
+        Ok(v) => Some(v),
+        Err(err) => Some(make_error_value(
+            json_request.id,
+            JsonRPCError::new(
+                INTERNAL_ERROR,
+                format!("Response is not json, err: {}", err),
+            ),
+        )),
+    }
+}
+
+// A request object that leaves out "id" expects no response
+fn is_notification(request: &Value) -> bool {
+    match request {
+        Value::Object(o) => !o.contains_key("id"),
+        _ => false,
+    }
+}
+
+fn make_not_allowed_error(method: &String) -> JsonRPCError {
+    JsonRPCError::new(
+        METHOD_NOT_ALLOWED,
+        format!("Method is not allowed, method: {}", method),
+    )
+}
+
+fn make_empty_response(mut resp: Response<Body>) -> Response<Body> {
+    *resp.status_mut() = StatusCode::NO_CONTENT;
+    *resp.body_mut() = Body::empty();
+
+    resp
+}
+
+fn make_error_value(id: JsonRPCId, error: JsonRPCError) -> Value {
+    serde_json::json!({
+        "jsonrpc": JSON_RPC_2,
+        "error": error,
+        "id": id,
+    })
+}
+
+// On failure, returns the id of the request if it could be read at all
+fn parse_json_request(
+    value: Value,
+) -> Result<JsonRequest, (JsonRPCId, JsonRPCError)> {
+    let id = value
+        .get("id")
+        .and_then(|id| serde_json::from_value(id.clone()).ok())
+        .unwrap_or(JsonRPCId::Null);
+
+    let json_request: JsonRequest = match serde_json::from_value(value) {
+        Ok(r) => r,
+        Err(err) => {
+            return Err((
+                id,
+                JsonRPCError::new(
+                    INVALID_REQUEST,
+                    format!("Failed to parse as json_request, err: {}", err),
+                ),
+            ));
+        }
+    };
+
+    if json_request.jsonrpc != JSON_RPC_2 {
+        return Err((
+            json_request.id,
+            JsonRPCError::new(
+                INVALID_REQUEST,
+                format!("jsonrpc should be {}", JSON_RPC_2),
+            ),
+        ));
     }
+
+    match &json_request.params {
+        None | Some(Value::Object(_)) | Some(Value::Array(_)) => {}
+        Some(_) => {
+            return Err((
+                json_request.id,
+                JsonRPCError::new(
+                    INVALID_REQUEST,
+                    "params should be an object or an array".into(),
+                ),
+            ));
+        }
+    };
+
+    Ok(json_request)
 }
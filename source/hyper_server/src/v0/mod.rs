@@ -1,7 +1,9 @@
 mod middlewares;
 mod server;
+mod ws;
 
 pub use middlewares::*;
 pub use server::*;
+pub use ws::*;
 
 pub type RPCServerError = Box<dyn std::error::Error + Send + Sync>;
@@ -1,10 +1,18 @@
 use super::{Middleware, StateMachine};
 use crate::RPCServerError;
-use hyper::{server::conn::AddrIncoming, service, Body, Response, Server};
+use hyper::{
+    server::conn::{AddrIncoming, AddrStream},
+    service, Body, Response, Server,
+};
 use log::{debug, error};
-use std::{convert::Infallible, sync::Arc};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
 
+// Address of the peer of the connection, set on every request as an
+// extension
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientAddr(pub SocketAddr);
+
 pub struct RPCServer {}
 
 impl RPCServer {
@@ -30,8 +38,9 @@ impl RPCServer {
 
         let middlewares = Arc::new(middlewares);
 
-        let make_svc = service::make_service_fn(move |_conn| {
+        let make_svc = service::make_service_fn(move |conn: &AddrStream| {
             let ctx = ctx.clone();
+            let client_addr = ClientAddr(conn.remote_addr());
 
             let state_machine = {
                 let m = StateMachine {
@@ -42,7 +51,9 @@ impl RPCServer {
             };
 
             async move {
-                Ok::<_, Infallible>(service::service_fn(move |req| {
+                Ok::<_, Infallible>(service::service_fn(move |mut req| {
+                    req.extensions_mut().insert(client_addr);
+
                     debug!(
                         "rpc, method: {}, uri: {}",
                         req.method(),
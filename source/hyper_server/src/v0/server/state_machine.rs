@@ -6,6 +6,9 @@ use std::{pin::Pin, sync::Arc};
 
 pub enum MiddlewareResult<C> {
     Passing(Request<Body>, Response<Body>, C),
+    // For a middleware that has to await something, e.g. the request body,
+    // before it can decide
+    Deferred(Pin<Box<dyn Future<Output = MiddlewareResult<C>> + Send + Sync>>),
     End(
         Pin<
             Box<
@@ -21,7 +24,10 @@ pub struct StateMachine<C> {
     pub middlewares: Arc<Vec<Middleware<C>>>,
 }
 
-impl<C> StateMachine<C> {
+impl<C> StateMachine<C>
+where
+    C: Send + Sync + 'static,
+{
     pub fn run(
         &self,
         req: Request<Body>,
@@ -34,26 +40,35 @@ impl<C> StateMachine<C> {
                 + Sync,
         >,
     > {
-        let mut rq = req;
-        let mut rs = res;
-        let mut ct = ctx;
+        let middlewares = self.middlewares.clone();
 
-        for m in self.middlewares.iter() {
-            let f = &m.0;
+        Box::pin(async move {
+            let mut rq = req;
+            let mut rs = res;
+            let mut ct = ctx;
 
-            match f(rq, rs, ct) {
-                MiddlewareResult::Passing(req, res, ctx) => {
-                    rq = req;
-                    rs = res;
-                    ct = ctx;
+            for m in middlewares.iter() {
+                let f = &m.0;
 
-                    continue;
+                let mut result = f(rq, rs, ct);
+
+                loop {
+                    match result {
+                        MiddlewareResult::Passing(req, res, ctx) => {
+                            rq = req;
+                            rs = res;
+                            ct = ctx;
+
+                            break;
+                        }
+                        MiddlewareResult::Deferred(r) => {
+                            result = r.await;
+                        }
+                        MiddlewareResult::End(res) => return res.await,
+                    }
                 }
-                MiddlewareResult::End(res) => return res,
             }
-        }
 
-        Box::pin(async {
             error!(
                 "State machine reached the end without HandleResult \
                 being terminated"
@@ -0,0 +1,3 @@
+mod upgrade;
+
+pub use upgrade::*;
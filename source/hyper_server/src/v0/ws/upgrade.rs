@@ -0,0 +1,107 @@
+use crate::MiddlewareResult;
+use futures::Future;
+use hyper::{
+    header::{self, HeaderValue},
+    upgrade::Upgraded,
+    Body, Request, Response, StatusCode,
+};
+use log::{debug, error};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role},
+    WebSocketStream,
+};
+
+pub use tokio_tungstenite::tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message as WsMessage,
+};
+
+pub type WsStream = WebSocketStream<Upgraded>;
+
+const WS_VERSION: &'static str = "13";
+
+pub fn is_ws_upgrade_request(req: &Request<Body>) -> bool {
+    let has_header_token = |name: header::HeaderName, token: &str| {
+        req.headers()
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|v| v.trim().eq_ignore_ascii_case(token))
+    };
+
+    has_header_token(header::CONNECTION, "upgrade")
+        && has_header_token(header::UPGRADE, "websocket")
+}
+
+// Answers the handshake with 101 and hands the upgraded connection over to
+// `handle_ws` once hyper is done with it
+pub fn upgrade_ws<C, F, Fut>(
+    req: Request<Body>,
+    mut resp: Response<Body>,
+    ctx: C,
+    handle_ws: F,
+) -> MiddlewareResult<C>
+where
+    C: Send + 'static,
+    F: FnOnce(WsStream, C) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let version = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_VERSION)
+        .and_then(|v| v.to_str().ok());
+
+    let key = req.headers().get(header::SEC_WEBSOCKET_KEY);
+
+    let accept_key = match (version, key) {
+        (Some(WS_VERSION), Some(k)) => derive_accept_key(k.as_bytes()),
+        _ => {
+            *resp.status_mut() = StatusCode::BAD_REQUEST;
+            *resp.body_mut() = Body::from(
+                "websocket upgrade needs a key and version 13".to_string(),
+            );
+
+            return MiddlewareResult::End(Box::pin(async { Ok(resp) }));
+        }
+    };
+
+    let accept_key = match HeaderValue::from_str(&accept_key) {
+        Ok(k) => k,
+        Err(err) => {
+            error!("Cannot make websocket accept key, err: {}", err);
+
+            *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+
+            return MiddlewareResult::End(Box::pin(async { Ok(resp) }));
+        }
+    };
+
+    tokio::spawn(async move {
+        let upgraded = match hyper::upgrade::on(req).await {
+            Ok(u) => u,
+            Err(err) => {
+                error!("Failed to upgrade to websocket, err: {}", err);
+
+                return;
+            }
+        };
+
+        debug!("Connection is upgraded to websocket");
+
+        let ws = WebSocketStream::from_raw_socket(upgraded, Role::Server, None)
+            .await;
+
+        handle_ws(ws, ctx).await;
+    });
+
+    let headers = resp.headers_mut();
+
+    headers.insert(header::CONNECTION, HeaderValue::from_static("Upgrade"));
+    headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(header::SEC_WEBSOCKET_ACCEPT, accept_key);
+
+    *resp.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+
+    MiddlewareResult::End(Box::pin(async { Ok(resp) }))
+}
@@ -1,3 +1,6 @@
+use crate::CryptoError;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
 use k256::SecretKey;
 use k256::{
     ecdsa::{
@@ -7,6 +10,7 @@ use k256::{
     elliptic_curve::ecdh::SharedSecret,
     EncodedPoint, Secp256k1,
 };
+use sha2::Sha256;
 use sha3::{Digest, Keccak256, Sha3_256};
 use std::{fmt::Write, num::ParseIntError};
 
@@ -120,6 +124,46 @@ pub fn compute_hash(values: &[impl AsRef<[u8]>]) -> String {
     return result;
 }
 
+pub fn make_hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // Hmac accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(key)
+        .expect("hmac should take a key of any length");
+
+    mac.update(data);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Compares in constant time
+pub fn verify_hmac_sha256(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let mut mac = match Hmac::<Sha256>::new_from_slice(key) {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+
+    mac.update(data);
+
+    mac.verify_slice(tag).is_ok()
+}
+
+// Output of `len` bytes, `len` being at most 255 * 32
+pub fn make_hkdf_sha256(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, CryptoError> {
+    let h = Hkdf::<Sha256>::new(Some(salt), ikm);
+
+    let mut out = vec![0; len];
+
+    if let Err(err) = h.expand(info, &mut out) {
+        return Err(format!("Could not expand hkdf, err: {}", err).into());
+    }
+
+    Ok(out)
+}
+
 pub fn make_shared_secret(
     my_secret_key: &SecretKey,
     her_public: PublicKey,
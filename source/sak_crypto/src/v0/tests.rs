@@ -118,4 +118,37 @@ mod test {
             assert_eq!(plaintext.as_bytes(), plaintext2);
         };
     }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231, test case 2
+        let key = b"Jefe";
+        let data = b"what do ya want for nothing?";
+
+        let tag = crate::make_hmac_sha256(key, data);
+
+        assert_eq!(
+            crate::encode_hex(&tag),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        );
+
+        assert!(crate::verify_hmac_sha256(key, data, &tag));
+        assert!(!crate::verify_hmac_sha256(b"jefe", data, &tag));
+    }
+
+    #[test]
+    fn test_hkdf_sha256() {
+        // RFC 5869, test case 1
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+
+        let okm = crate::make_hkdf_sha256(&salt, &ikm, &info, 42).unwrap();
+
+        assert_eq!(
+            crate::encode_hex(&okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c\
+            5db02d56ecc4c5bf34007208d5b887185865",
+        );
+    }
 }
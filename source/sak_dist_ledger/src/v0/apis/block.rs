@@ -4,8 +4,31 @@ use sak_proofs::{MerkleTree, CM_TREE_DEPTH};
 use sak_types::{
     Block, BlockHash, BlockHeight, Cm, CmIdx, CtrAddr, Tx, TxCandidate, TxHash,
 };
+use serde::{Deserialize, Serialize};
 
-const GET_BLOCK_HASH_LIST_DEFAULT_SIZE: u128 = 10;
+pub const BLOCK_LIST_PAGE_DEFAULT_SIZE: usize = 10;
+
+pub const BLOCK_LIST_PAGE_MAX_SIZE: usize = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PageDirection {
+    Asc,
+    Desc,
+}
+
+impl Default for PageDirection {
+    fn default() -> PageDirection {
+        PageDirection::Desc
+    }
+}
+
+// `next` is the height the following page starts at, if there is one
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BlockListPage {
+    pub block_list: Vec<Block>,
+    pub next: Option<BlockHeight>,
+}
 
 impl DistLedgerApis {
     pub async fn get_blocks(
@@ -97,6 +120,10 @@ impl DistLedgerApis {
         &self,
         tx_candidate: TxCandidate,
     ) -> Result<TxHash, String> {
+        if let Err(err) = self.authorize_pool_tx(&tx_candidate).await {
+            return Err(err.to_string());
+        }
+
         let tx_hash = match tx_candidate {
             TxCandidate::Mint(_) => {
                 self.sync_pool.insert_tx(tx_candidate).await?
@@ -124,119 +151,78 @@ impl DistLedgerApis {
         // self.get_block(&self.kv_db.db_instance, &self.schema, block_hash)
     }
 
+    // A page of blocks starting at `from_height`. Without it, the page starts
+    // at the latest block when descending, and at the genesis block otherwise
     pub async fn get_block_list(
         &self,
-        offset: Option<u128>,
-        limit: Option<u128>,
-    ) -> Result<Vec<Block>, LedgerError> {
+        from_height: Option<BlockHeight>,
+        direction: PageDirection,
+        limit: Option<usize>,
+    ) -> Result<BlockListPage, LedgerError> {
+        let limit = match limit {
+            Some(0) => {
+                return Err(format!("Page size should be greater than 0").into())
+            }
+            Some(l) => l.min(BLOCK_LIST_PAGE_MAX_SIZE),
+            None => BLOCK_LIST_PAGE_DEFAULT_SIZE,
+        } as BlockHeight;
+
         let latest_bh = match self.get_latest_block_height()? {
             Some(bh) => bh,
             None => {
-                return Err(format!("Cannot find latest block height").into())
-            }
-        };
-
-        let upper = match offset {
-            Some(bh) => {
-                if latest_bh < bh {
-                    latest_bh
-                } else {
-                    bh
-                }
+                return Ok(BlockListPage {
+                    block_list: vec![],
+                    next: None,
+                })
             }
-            None => latest_bh,
         };
 
-        let limit = match limit {
-            Some(l) => l,
-            None => GET_BLOCK_HASH_LIST_DEFAULT_SIZE,
-        };
+        let (heights, next): (Vec<BlockHeight>, Option<BlockHeight>) =
+            match direction {
+                PageDirection::Desc => {
+                    let upper = from_height.unwrap_or(latest_bh).min(latest_bh);
 
-        let lower = {
-            if upper < limit {
-                if upper > GET_BLOCK_HASH_LIST_DEFAULT_SIZE {
-                    upper - GET_BLOCK_HASH_LIST_DEFAULT_SIZE + 1
-                } else {
-                    0
-                }
-            } else {
-                limit
-            }
-        };
+                    let lower = if upper + 1 > limit {
+                        upper + 1 - limit
+                    } else {
+                        0
+                    };
 
-        let mut block_hash_list: Vec<BlockHash> = Vec::new();
+                    let next = if lower > 0 { Some(lower - 1) } else { None };
 
-        for bh in (lower..=upper).rev().step_by(1) {
-            match self.get_block_by_height(&bh).await {
-                Ok(maybe_block) => match maybe_block {
-                    Some(block) => {
-                        block_hash_list.push(block.get_block_hash().to_owned());
-                    }
-                    None => {
-                        break;
-                    }
-                },
-                Err(err) => {
-                    return Err(format!(
-                        "Block hash at height ({}) does not exist",
-                        err
-                    )
-                    .into())
+                    ((lower..=upper).rev().collect(), next)
                 }
-            }
-        }
+                PageDirection::Asc => {
+                    let lower = from_height.unwrap_or(0);
 
-        let block_hash_list_tmp: Vec<&BlockHash> =
-            block_hash_list.iter().collect();
+                    if lower > latest_bh {
+                        (vec![], None)
+                    } else {
+                        let upper = (lower + limit - 1).min(latest_bh);
 
-        let block_list = match self.get_blocks(block_hash_list_tmp).await {
-            Ok(bl) => bl,
-            Err(err) => {
-                return Err(format!(
-                    "some of the block_hashes in ({:?}) is wrong",
-                    err
-                )
-                .into())
-            }
-        };
+                        let next = if upper < latest_bh {
+                            Some(upper + 1)
+                        } else {
+                            None
+                        };
 
-        Ok(block_list)
-    }
+                        ((lower..=upper).collect(), next)
+                    }
+                }
+            };
 
-    pub async fn get_entire_block_info_list(
-        &self,
-    ) -> Result<Vec<(u128, BlockHash)>, LedgerError> {
-        let latest_bh = match self.get_latest_block_height()? {
-            Some(bh) => bh,
-            None => {
-                return Err(format!("Cannot find latest block height").into())
-            }
-        };
+        let mut block_list = Vec::with_capacity(heights.len());
 
-        let mut block_hash_list: Vec<(u128, BlockHash)> = Vec::new();
+        for bh in heights {
+            let block = self
+                .get_block_by_height(&bh)
+                .await?
+                .ok_or(format!("Block at height ({}) does not exist", bh))?;
 
-        for bh in (0..=latest_bh).rev().step_by(1) {
-            match self.get_block_by_height(&bh).await {
-                Ok(maybe_block) => match maybe_block {
-                    Some(block) => {
-                        block_hash_list
-                            .push((bh, block.get_block_hash().to_owned()));
-                    }
-                    None => {
-                        break;
-                    }
-                },
-                Err(err) => {
-                    return Err(format!(
-                        "Block hash at height ({}) does not exist",
-                        err
-                    )
-                    .into())
-                }
-            }
+            block_list.push(block);
         }
 
-        Ok(block_hash_list)
+        Ok(BlockListPage { block_list, next })
     }
 
     pub async fn get_block_by_height(
@@ -304,4 +290,29 @@ impl DistLedgerApis {
     ) -> Result<Option<Storage>, LedgerError> {
         self.ledger_db.get_ctr_state(contract_addr)
     }
+
+    // None if the contract did not exist at the height, or if the history
+    // does not go back that far, e.g. in a ledger made out of a snapshot
+    pub async fn get_ctr_state_at(
+        &self,
+        contract_addr: &CtrAddr,
+        block_height: &BlockHeight,
+    ) -> Result<Option<Storage>, LedgerError> {
+        match self.get_latest_block_height()? {
+            Some(h) if h >= *block_height => (),
+            _ => {
+                return Err(format!(
+                    "Block height is beyond the latest block, \
+                    block_height: {}",
+                    block_height
+                )
+                .into());
+            }
+        };
+
+        self.ledger_db
+            .check_ctr_state_pruned(contract_addr, block_height)?;
+
+        self.ledger_db.get_ctr_state_at(contract_addr, block_height)
+    }
 }
@@ -1,12 +1,15 @@
-use crate::{CtrStateUpdate, DistLedgerApis, LedgerError, MerkleUpdate};
+use crate::{
+    CtrStateUpdate, DistLedgerApis, InvalidBlockError, LedgerError,
+    MerkleUpdate,
+};
 use colored::Colorize;
 use log::{debug, error, info, warn};
-use sak_contract_std::{CtrCallType, CtrRequest, Storage, ERROR_PLACEHOLDER};
+use sak_contract_std::{CtrCallType, CtrRequest, ERROR_PLACEHOLDER};
 use sak_types::{
     Block, BlockCandidate, CmIdx, MintTxCandidate, PourTxCandidate, Tx,
     TxCandidate, TxCtrOp,
 };
-use sak_vm::CtrFn;
+use sak_vm::{CtrFn, InvokeReceipt};
 
 impl DistLedgerApis {
     pub async fn insert_genesis_block(
@@ -105,6 +108,9 @@ impl DistLedgerApis {
             next_cm_idx,
         );
 
+        self.authorize_block_mint_txs(tcs, next_block_height)
+            .await?;
+
         let mut added_cm_count: u128 = 0;
         for tx_candidate in tcs {
             let cm_count = match tx_candidate {
@@ -190,7 +196,40 @@ impl DistLedgerApis {
         block: Block,
         txs: Vec<Tx>,
     ) -> Result<Option<String>, LedgerError> {
-        let tx_candidates = txs.into_iter().map(|tx| tx.downgrade()).collect();
+        let tx_candidates: Vec<TxCandidate> =
+            txs.into_iter().map(|tx| tx.downgrade()).collect();
+
+        self.sync_block_candidates(block, tx_candidates).await
+    }
+
+    // Block whose txs are put together by this node, as with a compact block
+    // whose txs are mostly found in the pool
+    pub async fn sync_block_candidates(
+        &self,
+        block: Block,
+        tx_candidates: Vec<TxCandidate>,
+    ) -> Result<Option<String>, LedgerError> {
+        // The same block may well be relayed by more than one peer
+        if let Some(_b) = self.get_block(block.get_block_hash())? {
+            return Err(format!(
+                "This block is already persisted: block_hash: {}",
+                block.get_block_hash()
+            )
+            .into());
+        };
+
+        for tc in &tx_candidates {
+            if let Err(err) = tc.verify_author_sig() {
+                return Err(InvalidBlockError {
+                    msg: format!(
+                        "tx is not validly signed, tx_hash: {}, err: {}",
+                        tc.get_tx_hash(),
+                        err
+                    ),
+                }
+                .into());
+            }
+        }
 
         let bc_candidate = BlockCandidate {
             validator_sig: block.validator_sig,
@@ -202,6 +241,10 @@ impl DistLedgerApis {
         match self.write_block(Some(bc_candidate)).await {
             Ok(res) => return Ok(res),
             Err(err) => {
+                if InvalidBlockError::is_invalid_block(&err) {
+                    return Err(err);
+                }
+
                 return Err(format!("Block sync failed, err: {}", err).into());
             }
         }
@@ -212,13 +255,14 @@ impl DistLedgerApis {
     }
 }
 
-async fn process_ctr_state_update(
+// Returns the receipt of the contract invocation, if there has been one
+pub(super) async fn process_ctr_state_update(
     apis: &DistLedgerApis,
     ctr_addr: &String,
     data: &[u8],
     tx_ctr_op: TxCtrOp,
     ctr_state_update: &mut CtrStateUpdate,
-) -> Result<(), LedgerError> {
+) -> Result<Option<InvokeReceipt>, LedgerError> {
     let vm = &apis.vm;
 
     match tx_ctr_op {
@@ -226,9 +270,12 @@ async fn process_ctr_state_update(
             let receipt = vm.invoke(&data, CtrFn::Init)?;
             let storage = receipt
                 .updated_storage
+                .clone()
                 .ok_or("Contract state needs to be initialized")?;
 
             ctr_state_update.insert(ctr_addr.clone(), storage);
+
+            return Ok(Some(receipt));
         }
 
         TxCtrOp::ContractCall => {
@@ -242,28 +289,31 @@ async fn process_ctr_state_update(
                     );
                 }
                 CtrCallType::Execute => {
-                    let new_state = match ctr_state_update.get(ctr_addr) {
-                        Some(previous_state) => {
-                            let ctr_wasm = apis
-                                .ledger_db
-                                .get_ctr_data_by_ctr_addr(&ctr_addr)
-                                .await?
-                                .ok_or("ctr data (wasm) should exist")?;
-
-                            let ctr_fn =
-                                CtrFn::Execute(req, previous_state.to_vec());
-
-                            let receipt = vm.invoke(ctr_wasm, ctr_fn)?;
-
-                            receipt
-                                .updated_storage
-                                .ok_or("State needs to be updated")?
-                        }
-                        None => apis.execute_ctr(&ctr_addr, req).await?,
+                    let previous_state = match ctr_state_update.get(ctr_addr) {
+                        Some(s) => s.to_vec(),
+                        None => apis
+                            .ledger_db
+                            .get_ctr_state(ctr_addr)?
+                            .ok_or("ctr state should exist")?,
                     };
 
-                    println!(
-                        "[+] new_state: {:?}",
+                    let ctr_wasm = apis
+                        .ledger_db
+                        .get_ctr_data_by_ctr_addr(&ctr_addr)
+                        .await?
+                        .ok_or("ctr data (wasm) should exist")?;
+
+                    let ctr_fn = CtrFn::Execute(req, previous_state);
+
+                    let receipt = vm.invoke(ctr_wasm, ctr_fn)?;
+
+                    let new_state = receipt
+                        .updated_storage
+                        .clone()
+                        .ok_or("State needs to be updated")?;
+
+                    debug!(
+                        "new_state: {:?}",
                         String::from_utf8(new_state.clone())
                     );
 
@@ -278,9 +328,10 @@ async fn process_ctr_state_update(
                     };
 
                     if maybe_error_placehorder != &ERROR_PLACEHOLDER {
-                        ctr_state_update
-                            .insert(ctr_addr.clone(), new_state.clone());
+                        ctr_state_update.insert(ctr_addr.clone(), new_state);
                     }
+
+                    return Ok(Some(receipt));
                 }
             };
         }
@@ -289,7 +340,7 @@ async fn process_ctr_state_update(
         }
     };
 
-    Ok(())
+    Ok(None)
 }
 
 async fn handle_mint_tx_candidate(
@@ -345,7 +396,7 @@ async fn handle_pour_tx_candidate(
     Ok(cm_count)
 }
 
-async fn process_merkle_update(
+pub(super) async fn process_merkle_update(
     apis: &DistLedgerApis,
     merkle_update: &mut MerkleUpdate,
     cms: Vec<&[u8; 32]>,
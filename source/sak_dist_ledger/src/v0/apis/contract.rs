@@ -2,14 +2,17 @@ use crate::DistLedgerApis;
 use crate::LedgerError;
 use log::info;
 use sak_contract_std::CtrRequest;
-use sak_types::CtrAddr;
+use sak_types::{BlockHeight, CtrAddr};
 use sak_vm::CtrFn;
 
 impl DistLedgerApis {
+    // Runs against the state as of `block_height` if given, otherwise
+    // against the latest one
     pub async fn query_ctr(
         &self,
         ctr_addr: &CtrAddr,
         request: CtrRequest,
+        block_height: Option<BlockHeight>,
     ) -> Result<Vec<u8>, LedgerError> {
         let ctr_wasm = self
             .ledger_db
@@ -17,10 +20,19 @@ impl DistLedgerApis {
             .await?
             .ok_or("ctr data (wasm) should exist")?;
 
-        let ctr_state = self
-            .ledger_db
-            .get_ctr_state(ctr_addr)?
-            .ok_or("ctr state should exist")?;
+        let ctr_state = match block_height {
+            Some(h) => {
+                self.get_ctr_state_at(ctr_addr, &h).await?.ok_or(format!(
+                    "ctr state does not exist at the block height, \
+                    ctr_addr: {}, block_height: {}",
+                    ctr_addr, h
+                ))?
+            }
+            None => self
+                .ledger_db
+                .get_ctr_state(ctr_addr)?
+                .ok_or("ctr state should exist")?,
+        };
 
         let ctr_fn = CtrFn::Query(request, ctr_state);
 
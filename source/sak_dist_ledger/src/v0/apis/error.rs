@@ -0,0 +1,24 @@
+use std::fmt;
+
+// Returned when a block fails a check anyone can verify, such as a tx
+// signature or a mint that does not open to its value. Callers can tell it
+// apart from a local failure, or a block that is already persisted, by
+// downcasting `LedgerError`.
+#[derive(Debug)]
+pub struct InvalidBlockError {
+    pub msg: String,
+}
+
+impl InvalidBlockError {
+    pub fn is_invalid_block(err: &crate::LedgerError) -> bool {
+        err.downcast_ref::<InvalidBlockError>().is_some()
+    }
+}
+
+impl fmt::Display for InvalidBlockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid block, {}", self.msg)
+    }
+}
+
+impl std::error::Error for InvalidBlockError {}
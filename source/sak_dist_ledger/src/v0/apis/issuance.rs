@@ -0,0 +1,133 @@
+use crate::{DistLedgerApis, InvalidBlockError, LedgerError};
+use log::warn;
+use sak_types::{BlockHeight, Cm, MintTxCandidate, TxCandidate};
+use std::collections::HashSet;
+
+impl DistLedgerApis {
+    // The cm of a mint tx has to open to the value it reveals, otherwise the
+    // value actually minted would be hidden from the issuance rule
+    pub(crate) async fn authorize_mint_tx(
+        &self,
+        tc: &MintTxCandidate,
+        block_height: BlockHeight,
+    ) -> Result<(), LedgerError> {
+        let cm = self.hasher.comm2(&tc.s, &tc.v, &tc.k)?;
+
+        if cm.to_bytes() != tc.cm_1 {
+            return Err(format!(
+                "Mint tx cm does not match its value, tx_hash: {}",
+                tc.get_tx_hash(),
+            )
+            .into());
+        }
+
+        if let Err(err) =
+            self.consensus.authorize_mint(self, tc, block_height).await
+        {
+            return Err(format!(
+                "Mint tx is not authorized, tx_hash: {}, err: {}",
+                tc.get_tx_hash(),
+                err,
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // Txs in the pool are meant for the next block
+    pub(crate) async fn authorize_pool_tx(
+        &self,
+        tc: &TxCandidate,
+    ) -> Result<(), LedgerError> {
+        if let TxCandidate::Mint(t) = tc {
+            let next_block_height = match self.get_latest_block_height()? {
+                Some(h) => h + 1,
+                None => 0,
+            };
+
+            self.authorize_mint_tx(t, next_block_height).await?;
+        }
+
+        Ok(())
+    }
+
+    // Every mint of a block has to be authorized, and none of them may mint
+    // a cm that an earlier mint of the same block has minted
+    pub(crate) async fn authorize_block_mint_txs(
+        &self,
+        tcs: &[TxCandidate],
+        block_height: BlockHeight,
+    ) -> Result<(), LedgerError> {
+        let mut minted_cms = HashSet::new();
+
+        for tc in tcs {
+            if let TxCandidate::Mint(t) = tc {
+                self.authorize_block_mint_tx(t, block_height, &mut minted_cms)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Leaves the mints that would fail the block out of the txs a block is
+    // made of, so that one bad mint does not hold back the rest. Resolves
+    // with the txs kept and the ones left out.
+    pub(crate) async fn drop_unauthorized_mint_txs(
+        &self,
+        tcs: Vec<TxCandidate>,
+        block_height: BlockHeight,
+    ) -> (Vec<TxCandidate>, Vec<TxCandidate>) {
+        let mut minted_cms = HashSet::new();
+        let mut authorized_tcs = vec![];
+        let mut unauthorized_tcs = vec![];
+
+        for tc in tcs {
+            if let TxCandidate::Mint(t) = &tc {
+                if let Err(err) = self
+                    .authorize_block_mint_tx(t, block_height, &mut minted_cms)
+                    .await
+                {
+                    warn!("Mint tx is left out of the block, err: {}", err);
+
+                    unauthorized_tcs.push(tc);
+
+                    continue;
+                }
+            }
+
+            authorized_tcs.push(tc);
+        }
+
+        (authorized_tcs, unauthorized_tcs)
+    }
+
+    async fn authorize_block_mint_tx(
+        &self,
+        tc: &MintTxCandidate,
+        block_height: BlockHeight,
+        minted_cms: &mut HashSet<Cm>,
+    ) -> Result<(), LedgerError> {
+        if minted_cms.contains(&tc.cm_1) {
+            return Err(InvalidBlockError {
+                msg: format!(
+                    "cm has already been minted in the block, tx_hash: {}",
+                    tc.get_tx_hash(),
+                ),
+            }
+            .into());
+        }
+
+        if let Err(err) = self.authorize_mint_tx(tc, block_height).await {
+            return Err(InvalidBlockError {
+                msg: err.to_string(),
+            }
+            .into());
+        }
+
+        minted_cms.insert(tc.cm_1);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,16 @@
 mod block;
 mod block_update;
 mod contract;
+mod error;
+mod issuance;
 mod pool;
+mod simulate;
+mod tx_index;
+
+pub use block::*;
+pub use error::*;
+pub use simulate::*;
+pub use tx_index::*;
 
 use crate::{Consensus, LedgerDB, SyncPool};
 use sak_crypto::Hasher;
@@ -3,13 +3,35 @@ use log::warn;
 use sak_types::{BlockCandidate, TxCandidate};
 
 impl DistLedgerApis {
-    // peer_node
-    pub async fn insert_into_pool(&self, tx_candidates: Vec<TxCandidate>) {
+    // peer_node. Returns the number of txs that are not valid, as opposed
+    // to the ones that are only left out (e.g. already in the pool)
+    pub async fn insert_into_pool(
+        &self,
+        tx_candidates: Vec<TxCandidate>,
+    ) -> usize {
+        let mut invalid_tx_count = 0;
+
         for tx in tx_candidates.into_iter() {
+            if self.sync_pool.contains_tx(tx.get_tx_hash()).await {
+                continue;
+            }
+
+            if let Err(err) = self.authorize_pool_tx(&tx).await {
+                warn!("Tx pool insertion aborted, reason: {}", err);
+
+                invalid_tx_count += 1;
+
+                continue;
+            }
+
             if let Err(err) = self.sync_pool.insert_tx(tx).await {
                 warn!("Tx pool insertion aborted, reason: {}", err);
+
+                invalid_tx_count += 1;
             };
         }
+
+        invalid_tx_count
     }
 
     pub async fn tx_pool_contains(&self, tx_hash: &String) -> bool {
@@ -23,6 +45,10 @@ impl DistLedgerApis {
         self.sync_pool.get_tx_pool_diff(tx_hashes).await
     }
 
+    pub async fn get_tx_pool_hashes(&self) -> Vec<String> {
+        self.sync_pool.get_tx_hashes().await
+    }
+
     pub async fn get_txs_from_pool(
         &self,
         tx_hashes: Vec<String>,
@@ -35,6 +61,19 @@ impl DistLedgerApis {
     ) -> Result<Option<BlockCandidate>, LedgerError> {
         let tx_candidates = self.sync_pool.get_all_txs().await?;
 
+        let next_block_height = match self.get_latest_block_height()? {
+            Some(h) => h + 1,
+            None => 0,
+        };
+
+        // A mint that has come to fail since it entered the pool, e.g. its
+        // cm is minted by another tx, would otherwise fail every block
+        let (tx_candidates, unauthorized_tcs) = self
+            .drop_unauthorized_mint_txs(tx_candidates, next_block_height)
+            .await;
+
+        self.sync_pool.remove_tcs(&unauthorized_tcs).await?;
+
         if tx_candidates.is_empty() {
             return Ok(None);
         }
@@ -0,0 +1,139 @@
+use super::block_update::{process_ctr_state_update, process_merkle_update};
+use crate::{CtrStateUpdate, DistLedgerApis, LedgerError, MerkleUpdate};
+use sak_contract_std::Storage;
+use sak_types::{CtrAddr, TxCandidate, TxHash};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CtrStateDiff {
+    pub ctr_addr: CtrAddr,
+    // None if the contract is being deployed
+    pub prev_state: Option<Storage>,
+    pub next_state: Storage,
+}
+
+// Outcome of running a tx the way `write_block` would, with nothing
+// persisted. A simulation that hits a validation error is still reported,
+// with the error in `errors`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TxSimulation {
+    pub tx_hash: TxHash,
+    pub state_diff: Vec<CtrStateDiff>,
+    pub result: Vec<u8>,
+    pub gas_used: usize,
+    pub next_merkle_rt: Option<[u8; 32]>,
+    pub errors: Vec<String>,
+}
+
+impl TxSimulation {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl DistLedgerApis {
+    // Simulates the tx as if it were the only one in the next block
+    pub async fn simulate_tx(
+        &self,
+        tc: TxCandidate,
+    ) -> Result<TxSimulation, LedgerError> {
+        let next_block_height = match self.get_latest_block_height()? {
+            Some(h) => h + 1,
+            None => 0,
+        };
+
+        let next_cm_idx = match self.ledger_db.get_latest_cm_idx()? {
+            Some(i) => i + 1,
+            None => 0,
+        };
+
+        let mut errors = vec![];
+
+        if let Err(err) = tc.verify_author_sig() {
+            errors.push(format!("Tx is not validly signed, err: {}", err));
+        }
+
+        let cms = match &tc {
+            TxCandidate::Mint(t) => {
+                if let Err(err) =
+                    self.authorize_mint_tx(t, next_block_height).await
+                {
+                    errors.push(err.to_string());
+                }
+
+                vec![&t.cm_1]
+            }
+            TxCandidate::Pour(t) => {
+                if let Some(tx_hash) = self
+                    .ledger_db
+                    .get_tx_hash_by_sn(&self.ledger_db.db, &t.sn_1)?
+                {
+                    errors.push(format!(
+                        "Double spend, sn has been spent in tx_hash: {}",
+                        tx_hash
+                    ));
+                }
+
+                if let Err(err) = self.ledger_db.verify_tx(t) {
+                    errors.push(format!("Invalid proof, err: {}", err));
+                }
+
+                vec![&t.cm_1, &t.cm_2]
+            }
+        };
+
+        let ctr_addr = tc.get_ctr_addr();
+        let mut ctr_state_update = CtrStateUpdate::new();
+
+        let receipt = match process_ctr_state_update(
+            self,
+            ctr_addr,
+            tc.get_data(),
+            tc.get_ctr_op(),
+            &mut ctr_state_update,
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(err) => {
+                errors
+                    .push(format!("Contract invocation failed, err: {}", err));
+
+                None
+            }
+        };
+
+        let mut state_diff = vec![];
+
+        for (addr, next_state) in ctr_state_update {
+            let prev_state = self.ledger_db.get_ctr_state(&addr)?;
+
+            state_diff.push(CtrStateDiff {
+                ctr_addr: addr,
+                prev_state,
+                next_state,
+            });
+        }
+
+        let (result, gas_used) = match receipt {
+            Some(r) => (r.result, r.gas_charged),
+            None => (vec![], 0),
+        };
+
+        let mut merkle_update = MerkleUpdate::new();
+
+        process_merkle_update(self, &mut merkle_update, cms, next_cm_idx)
+            .await?;
+
+        let next_merkle_rt = merkle_update.get("16_0").map(|r| r.to_owned());
+
+        Ok(TxSimulation {
+            tx_hash: tc.get_tx_hash().to_string(),
+            state_diff,
+            result,
+            gas_used,
+            next_merkle_rt,
+            errors,
+        })
+    }
+}
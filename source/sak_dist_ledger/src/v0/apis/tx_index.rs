@@ -0,0 +1,110 @@
+use crate::{DistLedgerApis, LedgerError};
+use sak_types::{BlockHeight, Cm, CtrAddr, Tx, TxHash};
+use serde::{Deserialize, Serialize};
+
+pub const TX_INDEX_PAGE_DEFAULT_SIZE: usize = 20;
+
+pub const TX_INDEX_PAGE_MAX_SIZE: usize = 100;
+
+pub type TxPos = u32;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxLocation {
+    pub block_height: BlockHeight,
+    pub tx_pos: TxPos,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct IndexedTxHash {
+    pub tx_loc: TxLocation,
+    pub tx_hash: TxHash,
+}
+
+// `next` is where the following page starts, if there is one
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TxIndexPage {
+    pub tx_hashes: Vec<IndexedTxHash>,
+    pub next: Option<TxLocation>,
+}
+
+impl DistLedgerApis {
+    pub async fn get_txs_by_block(
+        &self,
+        block_height: &BlockHeight,
+        from_pos: Option<TxPos>,
+        limit: Option<usize>,
+    ) -> Result<TxIndexPage, LedgerError> {
+        let limit = get_page_size(limit)?;
+
+        let from = TxLocation {
+            block_height: *block_height,
+            tx_pos: from_pos.unwrap_or(0),
+        };
+
+        let entries = self
+            .ledger_db
+            .get_tx_hashes_by_block_pos(&from, limit + 1)?;
+
+        Ok(make_tx_index_page(entries, limit))
+    }
+
+    // Deploy and call txs of the contract in the order they were written
+    pub async fn get_ctr_call_history(
+        &self,
+        ctr_addr: &CtrAddr,
+        from: Option<TxLocation>,
+        limit: Option<usize>,
+    ) -> Result<TxIndexPage, LedgerError> {
+        let limit = get_page_size(limit)?;
+
+        let from = from.unwrap_or(TxLocation {
+            block_height: 0,
+            tx_pos: 0,
+        });
+
+        let entries = self.ledger_db.get_ctr_call_tx_hashes(
+            ctr_addr,
+            &from,
+            limit + 1,
+        )?;
+
+        Ok(make_tx_index_page(entries, limit))
+    }
+
+    // The tx that created the cm
+    pub async fn get_tx_by_cm(
+        &self,
+        cm: &Cm,
+    ) -> Result<Option<Tx>, LedgerError> {
+        match self.ledger_db.get_tx_hash_by_cm(cm)? {
+            Some(tx_hash) => self.ledger_db.get_tx(&tx_hash).await,
+            None => Ok(None),
+        }
+    }
+}
+
+fn get_page_size(limit: Option<usize>) -> Result<usize, LedgerError> {
+    match limit {
+        Some(0) => Err(format!("Page size should be greater than 0").into()),
+        Some(l) => Ok(l.min(TX_INDEX_PAGE_MAX_SIZE)),
+        None => Ok(TX_INDEX_PAGE_DEFAULT_SIZE),
+    }
+}
+
+fn make_tx_index_page(
+    mut entries: Vec<(TxLocation, TxHash)>,
+    limit: usize,
+) -> TxIndexPage {
+    let next = if entries.len() > limit {
+        entries.pop().map(|(tx_loc, _)| tx_loc)
+    } else {
+        None
+    };
+
+    let tx_hashes = entries
+        .into_iter()
+        .map(|(tx_loc, tx_hash)| IndexedTxHash { tx_loc, tx_hash })
+        .collect();
+
+    TxIndexPage { tx_hashes, next }
+}
@@ -1,6 +1,6 @@
 use crate::DistLedgerApis;
 use async_trait::async_trait;
-use sak_types::{BlockCandidate, TxCandidate};
+use sak_types::{BlockCandidate, BlockHeight, MintTxCandidate, TxCandidate};
 
 #[async_trait]
 pub trait Consensus {
@@ -9,6 +9,15 @@ pub trait Consensus {
         dist_ledger_apis: &DistLedgerApis,
         txs: Vec<TxCandidate>,
     ) -> Result<BlockCandidate, ConsensusError>;
+
+    // Issuance rule. A mint tx creates value out of nothing, so it is only
+    // included in a block at `block_height` if this says so
+    async fn authorize_mint(
+        &self,
+        dist_ledger_apis: &DistLedgerApis,
+        tc: &MintTxCandidate,
+        block_height: BlockHeight,
+    ) -> Result<(), ConsensusError>;
 }
 
 pub type ConsensusError = Box<dyn std::error::Error + Send + Sync>;
@@ -1,24 +1,22 @@
 use crate::cfs;
 use crate::LedgerError;
 use log::info;
-use sak_kv_db::{
-    BoundColumnFamily, ColumnFamilyDescriptor, KeyValueDatabase, Options, DB,
-};
+use sak_kv_db::{ColumnFamilyDescriptor, KeyValueDatabase, Options, DB};
 use std::path::PathBuf;
 use std::sync::Arc;
 
 // TODO This has to be dynamically decided
 const APP_NAME: &'static str = "saksaha";
 
+// Cloning is cheap, every clone shares the same underlying database
+#[derive(Clone)]
 pub struct LedgerDB {
     // pub(crate) schema: LedgerDBSchema,
-    pub(crate) db: DB,
+    pub(crate) db: Arc<KeyValueDatabase>,
 }
 
 impl LedgerDB {
-    pub(crate) async fn init(
-        app_prefix: &String,
-    ) -> Result<LedgerDB, LedgerError> {
+    pub async fn init(app_prefix: &String) -> Result<LedgerDB, LedgerError> {
         let ledger_db_path = {
             let db_path = Self::get_db_path(app_prefix)?;
 
@@ -37,10 +35,14 @@ impl LedgerDB {
             o
         };
 
+        // Fails if the ledger does not exist yet
+        let existing_cf_names =
+            DB::list_cf(&options, &ledger_db_path).unwrap_or(vec![]);
+
         let kv_db = match KeyValueDatabase::new(
             ledger_db_path,
             options,
-            Self::make_cf_descriptors(),
+            Self::make_cf_descriptors(&existing_cf_names),
         ) {
             Ok(d) => d,
             Err(err) => {
@@ -54,11 +56,25 @@ impl LedgerDB {
 
         // let schema = LedgerDBSchema::new(kv_db.db_instance);
 
+        LedgerDB::new(kv_db)
+    }
+
+    // Nothing touches disk, which suits tests and ephemeral nodes. The
+    // ledger is gone once it is dropped
+    pub fn init_in_memory() -> Result<LedgerDB, LedgerError> {
+        let kv_db = KeyValueDatabase::new_in_memory(Self::get_cf_names());
+
+        LedgerDB::new(kv_db)
+    }
+
+    fn new(kv_db: KeyValueDatabase) -> Result<LedgerDB, LedgerError> {
         let database = LedgerDB {
-            db: kv_db.db_instance,
+            db: Arc::new(kv_db),
         };
 
-        info!("Initialized Database");
+        let schema_version = database.migrate()?;
+
+        info!("Initialized Database, schema version: {}", schema_version);
 
         Ok(database)
     }
@@ -71,75 +87,80 @@ impl LedgerDB {
         Ok(db_path)
     }
 
-    pub(crate) fn make_cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+    pub(crate) fn make_cf_descriptors(
+        existing_cf_names: &Vec<String>,
+    ) -> Vec<ColumnFamilyDescriptor> {
+        let cf_names = Self::get_cf_names();
+
+        let mut cf_descriptors: Vec<ColumnFamilyDescriptor> = cf_names
+            .iter()
+            .map(|n| ColumnFamilyDescriptor::new(*n, Options::default()))
+            .collect();
+
+        // Every column family on disk has to be opened, including the ones
+        // created by earlier schemas. They are dropped by a migration
+        for n in existing_cf_names {
+            if n != "default" && !cf_names.contains(&n.as_str()) {
+                cf_descriptors
+                    .push(ColumnFamilyDescriptor::new(n, Options::default()));
+            }
+        }
+
+        cf_descriptors
+    }
+
+    pub(crate) fn get_cf_names() -> Vec<&'static str> {
         vec![
-            // ColumnFamilyDescriptor::new(
-            //     cfs::TX_HASH_BY_HEIGHT,
-            //     Options::default(),
-            // ),
-            ColumnFamilyDescriptor::new(
-                cfs::TX_HASH_BY_CTR_ADDR,
-                Options::default(),
-            ),
-            ColumnFamilyDescriptor::new(cfs::TX_HASH_BY_SN, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::PI, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::AUTHOR_SIG, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::TX_CREATED_AT, Options::default()),
-            ColumnFamilyDescriptor::new(
-                cfs::BLOCK_CREATED_AT,
-                Options::default(),
-            ),
-            ColumnFamilyDescriptor::new(cfs::DATA, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CTR_ADDR, Options::default()),
-            // ColumnFamilyDescriptor::new(cfs::TX_HEIGHT, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::TX_TYPE, Options::default()),
-            // ColumnFamilyDescriptor::new(cfs::CM, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM_IDX, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM_IDX_CM, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::V, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::K, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::S, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::SN_1, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::SN_2, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM_1, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM_2, Options::default()),
-            ColumnFamilyDescriptor::new(
-                cfs::BLOCK_MERKLE_RT,
-                Options::default(),
-            ),
-            ColumnFamilyDescriptor::new(cfs::PRF_MERKLE_RT, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::MERKLE_NODE, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::VALIDATOR_SIG, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::TX_HASHES, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::WITNESS_SIGS, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::BLOCK_HEIGHT, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::BLOCK_HASH, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CTR_STATE, Options::default()),
-            // ColumnFamilyDescriptor::new(
-            //     cfs::BLOCK_CM_COUNT,
-            //     Options::default(),
-            // ),
-            // ColumnFamilyDescriptor::new(
-            //     cfs::LEDGER_CM_COUNT,
-            //     Options::default(),
-            // ),
+            cfs::TX_HASH_BY_BLOCK_POS,
+            cfs::CTR_CALL_TX_HASH,
+            cfs::TX_HASH_BY_CM,
+            cfs::TX_HASH_BY_CTR_ADDR,
+            cfs::TX_HASH_BY_SN,
+            cfs::PI,
+            cfs::AUTHOR_SIG,
+            cfs::TX_CREATED_AT,
+            cfs::BLOCK_CREATED_AT,
+            cfs::DATA,
+            cfs::CTR_ADDR,
+            // cfs::TX_HEIGHT,
+            cfs::TX_TYPE,
+            // cfs::CM,
+            cfs::CM_IDX,
+            cfs::CM_IDX_CM,
+            cfs::V,
+            cfs::K,
+            cfs::S,
+            cfs::SN_1,
+            cfs::SN_2,
+            cfs::CM_1,
+            cfs::CM_2,
+            cfs::BLOCK_MERKLE_RT,
+            cfs::PRF_MERKLE_RT,
+            cfs::MERKLE_NODE,
+            cfs::VALIDATOR_SIG,
+            cfs::TX_HASHES,
+            cfs::WITNESS_SIGS,
+            cfs::BLOCK_HEIGHT,
+            cfs::BLOCK_HASH,
+            cfs::CTR_STATE,
+            cfs::CTR_STATE_BY_HEIGHT,
+            cfs::SCHEMA_VERSION,
+            cfs::PRUNED_BLOCK_HEIGHT,
+            // cfs::BLOCK_CM_COUNT,
+            // cfs::LEDGER_CM_COUNT,
         ]
     }
 
-    pub(crate) fn make_cf_handle<'a>(
+    // Column families are addressed by name. This makes sure the one exists
+    pub(crate) fn make_cf_handle(
         &self,
-        db: &'a DB,
+        db: &KeyValueDatabase,
         col_name: &'static str,
-    ) -> Result<Arc<BoundColumnFamily<'a>>, String> {
-        let cf_handle = match db.cf_handle(col_name) {
-            Some(h) => h,
-            None => {
-                return Err(
-                    format!("Fail to open ledger colums {}", col_name,),
-                );
-            }
-        };
+    ) -> Result<&'static str, String> {
+        if !db.cf_exists(col_name) {
+            return Err(format!("Fail to open ledger colums {}", col_name,));
+        }
 
-        Ok(cf_handle)
+        Ok(col_name)
     }
 }
@@ -0,0 +1,20 @@
+use crate::{LedgerDB, LedgerError};
+use log::info;
+
+// Column families such as `tx_height` or `block_cm_count` used to be created
+// by earlier versions and are left unused on disk
+pub(super) fn run(ledger_db: &LedgerDB) -> Result<(), LedgerError> {
+    let cf_names = LedgerDB::get_cf_names();
+
+    for cf_name in ledger_db.db.cf_names()? {
+        if cf_name == "default" || cf_names.contains(&cf_name.as_str()) {
+            continue;
+        }
+
+        info!("Dropping unused column family, cf: {}", cf_name);
+
+        ledger_db.db.drop_cf(&cf_name)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use crate::{LedgerDB, LedgerError, TxLocation, TxPos};
+use sak_kv_db::WriteBatch;
+use sak_types::{TxCtrOp, TxType};
+
+// Builds the tx indexes for the blocks written before they existed. Bodies of
+// pruned txs are gone, so such txs do not show up in contract call histories
+pub(super) fn run(ledger_db: &LedgerDB) -> Result<(), LedgerError> {
+    let latest_block_height = match ledger_db.get_latest_block_height()? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+
+    let mut batch = WriteBatch::default();
+
+    for h in 0..=latest_block_height {
+        let block_hash = ledger_db
+            .get_block_hash_by_block_height(&h)?
+            .ok_or(format!("Block hash at height ({}) does not exist", h))?;
+
+        let tx_hashes = ledger_db.get_tx_hashes(&block_hash)?.ok_or(
+            format!("Tx hashes do not exist, block_hash: {}", block_hash),
+        )?;
+
+        for (tx_pos, tx_hash) in tx_hashes.iter().enumerate() {
+            let tx_loc = TxLocation {
+                block_height: h,
+                tx_pos: tx_pos as TxPos,
+            };
+
+            ledger_db
+                .batch_put_tx_hash_by_block_pos(&mut batch, &tx_loc, tx_hash)?;
+
+            let tx_type = match ledger_db.get_tx_type(tx_hash)? {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let mut cms = vec![];
+
+            if let Some(cm_1) = ledger_db.get_cm_1(tx_hash)? {
+                cms.push(cm_1);
+            }
+
+            if let TxType::Pour = tx_type {
+                if let Some(cm_2) = ledger_db.get_cm_2(tx_hash)? {
+                    cms.push(cm_2);
+                }
+            }
+
+            for cm in cms {
+                ledger_db.batch_put_tx_hash_by_cm(&mut batch, &cm, tx_hash)?;
+            }
+
+            let ctr_addr = ledger_db.get_ctr_addr(tx_hash)?;
+            let data = ledger_db.get_data(tx_hash)?;
+
+            if let (Some(ctr_addr), Some(data)) = (ctr_addr, data) {
+                match TxCtrOp::new(&ctr_addr, &data) {
+                    TxCtrOp::ContractDeploy | TxCtrOp::ContractCall => {
+                        ledger_db.batch_put_ctr_call_tx_hash(
+                            &mut batch, &ctr_addr, &tx_loc, tx_hash,
+                        )?;
+                    }
+                    TxCtrOp::None => {}
+                }
+            }
+        }
+    }
+
+    ledger_db.db.write(batch)?;
+
+    Ok(())
+}
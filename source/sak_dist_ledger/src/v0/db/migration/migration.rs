@@ -0,0 +1,127 @@
+use super::{drop_unused_cfs, index_txs, reindex_cms, seed_ctr_state_history};
+use crate::{LedgerDB, LedgerError};
+use colored::Colorize;
+use log::{info, warn};
+use sak_kv_db::WriteBatch;
+
+pub type SchemaVersion = u32;
+
+// Ledgers written before the schema got versioned are regarded as version 0
+pub(crate) const UNVERSIONED_SCHEMA_VERSION: SchemaVersion = 0;
+
+pub const LEDGER_SCHEMA_VERSION: SchemaVersion = 4;
+
+pub(crate) struct Migration {
+    // Schema version the migration upgrades the ledger to. The ledger has to
+    // be at `version - 1` for the migration to run
+    pub(crate) version: SchemaVersion,
+
+    pub(crate) desc: &'static str,
+
+    // Migrations need to be idempotent since a node may be killed after
+    // the migration is done but before the schema version is written
+    pub(crate) run: fn(&LedgerDB) -> Result<(), LedgerError>,
+}
+
+// Ordered by version. A new migration is appended here together with the
+// bump of `LEDGER_SCHEMA_VERSION`
+pub(crate) const MIGRATIONS: [Migration; 4] = [
+    Migration {
+        version: 1,
+        desc: "Re-assign cm indices cumulatively over the txs of a block",
+        run: reindex_cms::run,
+    },
+    Migration {
+        version: 2,
+        desc: "Drop the column families that are no longer in the schema",
+        run: drop_unused_cfs::run,
+    },
+    Migration {
+        version: 3,
+        desc: "Index txs by block position, contract and cm",
+        run: index_txs::run,
+    },
+    Migration {
+        version: 4,
+        desc: "Keep contract states by block height",
+        run: seed_ctr_state_history::run,
+    },
+];
+
+impl LedgerDB {
+    pub(crate) fn migrate(&self) -> Result<SchemaVersion, LedgerError> {
+        let schema_version = match self.get_schema_version()? {
+            Some(v) => v,
+            None => match self.get_latest_block_height()? {
+                Some(_) => UNVERSIONED_SCHEMA_VERSION,
+                None => {
+                    info!(
+                        "Ledger is empty, writing the schema version: {}",
+                        LEDGER_SCHEMA_VERSION,
+                    );
+
+                    self.put_schema_version(LEDGER_SCHEMA_VERSION)?;
+
+                    return Ok(LEDGER_SCHEMA_VERSION);
+                }
+            },
+        };
+
+        if schema_version > LEDGER_SCHEMA_VERSION {
+            return Err(format!(
+                "Ledger schema version is newer than the one this node \
+                supports. Upgrade the node to open the ledger, \
+                ledger schema version: {}, supported: {}",
+                schema_version, LEDGER_SCHEMA_VERSION,
+            )
+            .into());
+        }
+
+        if schema_version == LEDGER_SCHEMA_VERSION {
+            return Ok(schema_version);
+        }
+
+        warn!(
+            "Ledger schema is outdated, migrating, schema version: {}, \
+            target: {}",
+            schema_version, LEDGER_SCHEMA_VERSION,
+        );
+
+        for migration in MIGRATIONS.iter() {
+            if migration.version <= schema_version {
+                continue;
+            }
+
+            info!(
+                "Running ledger migration, version: {}, desc: {}",
+                migration.version.to_string().yellow(),
+                migration.desc,
+            );
+
+            (migration.run)(self)?;
+
+            self.put_schema_version(migration.version)?;
+        }
+
+        info!(
+            "Migrated ledger schema, version: {} -> {}",
+            schema_version,
+            LEDGER_SCHEMA_VERSION.to_string().green(),
+        );
+
+        Ok(LEDGER_SCHEMA_VERSION)
+    }
+
+    fn put_schema_version(
+        &self,
+        schema_version: SchemaVersion,
+    ) -> Result<(), LedgerError> {
+        let mut batch = WriteBatch::default();
+
+        self.batch_put_schema_version(&mut batch, schema_version)?;
+
+        self.db.write(batch)?;
+
+        Ok(())
+    }
+}
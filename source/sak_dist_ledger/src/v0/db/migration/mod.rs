@@ -0,0 +1,7 @@
+mod drop_unused_cfs;
+mod index_txs;
+mod migration;
+mod reindex_cms;
+mod seed_ctr_state_history;
+
+pub use migration::*;
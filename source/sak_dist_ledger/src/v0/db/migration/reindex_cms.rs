@@ -0,0 +1,66 @@
+use crate::{LedgerDB, LedgerError};
+use sak_kv_db::WriteBatch;
+use sak_types::{CmIdx, TxType};
+
+// Earlier ledgers gave each tx in a block the cm idx of `next_cm_idx + tx
+// position`, so the cms of a pour tx overlapped with the ones of the next tx.
+// The merkle tree itself has always been built with the cumulative count,
+// hence only `CM_IDX` and `CM_IDX_CM` are rewritten.
+pub(super) fn run(ledger_db: &LedgerDB) -> Result<(), LedgerError> {
+    let latest_block_height = match ledger_db.get_latest_block_height()? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+
+    let mut batch = WriteBatch::default();
+    let mut next_cm_idx: CmIdx = 0;
+
+    for h in 0..=latest_block_height {
+        let block_hash = ledger_db
+            .get_block_hash_by_block_height(&h)?
+            .ok_or(format!("Block hash at height ({}) does not exist", h))?;
+
+        let tx_hashes = ledger_db.get_tx_hashes(&block_hash)?.ok_or(
+            format!("Tx hashes do not exist, block_hash: {}", block_hash),
+        )?;
+
+        for tx_hash in tx_hashes {
+            let tx_type = ledger_db.get_tx_type(&tx_hash)?.ok_or(format!(
+                "Tx type does not exist, tx_hash: {}",
+                tx_hash
+            ))?;
+
+            let cm_1 = ledger_db
+                .get_cm_1(&tx_hash)?
+                .ok_or(format!("cm_1 does not exist, tx_hash: {}", tx_hash))?;
+
+            let mut cms = vec![cm_1];
+
+            if let TxType::Pour = tx_type {
+                let cm_2 = ledger_db.get_cm_2(&tx_hash)?.ok_or(format!(
+                    "cm_2 does not exist, tx_hash: {}",
+                    tx_hash
+                ))?;
+
+                cms.push(cm_2);
+            }
+
+            for cm in cms {
+                ledger_db.batch_put_cm_cm_idx(&mut batch, &cm, &next_cm_idx)?;
+                ledger_db.batch_put_cm_idx_cm(&mut batch, &next_cm_idx, &cm)?;
+
+                next_cm_idx += 1;
+            }
+        }
+    }
+
+    for (cm_idx, _) in ledger_db.get_cms()? {
+        if cm_idx >= next_cm_idx {
+            ledger_db.batch_delete_cm_idx_cm(&mut batch, &cm_idx)?;
+        }
+    }
+
+    ledger_db.db.write(batch)?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::{LedgerDB, LedgerError};
+use sak_kv_db::WriteBatch;
+
+// Earlier ledgers kept only the latest contract states. They become the
+// history as of the latest block, and the states before it can not be
+// queried
+pub(super) fn run(ledger_db: &LedgerDB) -> Result<(), LedgerError> {
+    let latest_block_height = match ledger_db.get_latest_block_height()? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+
+    let mut batch = WriteBatch::default();
+
+    for (ctr_addr, ctr_state) in ledger_db.get_ctr_states()? {
+        ledger_db.batch_put_ctr_state_at(
+            &mut batch,
+            &ctr_addr,
+            &latest_block_height,
+            &ctr_state,
+        )?;
+    }
+
+    ledger_db.db.write(batch)?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 mod ledger_db;
+mod migration;
 mod raw;
 mod schema;
 
 pub use ledger_db::*;
+pub use migration::*;
 pub(crate) use schema::*;
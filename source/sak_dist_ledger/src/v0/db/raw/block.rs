@@ -1,8 +1,8 @@
 use crate::{cfs, LedgerDB};
 use crate::{LedgerError, MerkleNodeLoc};
 use sak_crypto::{Bls12, Hasher, Proof, ScalarExt};
+use sak_kv_db::KeyValueDatabase;
 use sak_kv_db::WriteBatch;
-use sak_kv_db::DB;
 use sak_types::{
     BlockHash, BlockHeight, Cm, CmIdx, MintTx, MintTxCandidate, PourTx,
     PourTxCandidate, Sn, Tx, TxCtrOp, TxHash, TxHeight, TxType,
@@ -1,6 +1,8 @@
-use crate::{cfs, LedgerDB};
+use crate::{cfs, keys, LedgerDB, SchemaVersion};
 use crate::{LedgerError, MerkleNodeLoc};
 use sak_kv_db::{IteratorMode, WriteBatch};
+use sak_types::{BlockHeight, CmIdx};
+use std::convert::TryInto;
 
 impl LedgerDB {
     // pub(crate) fn batch_put_ledger_cm_count(
@@ -23,7 +25,7 @@ impl LedgerDB {
     ) -> Result<Option<u128>, LedgerError> {
         let cf = self.make_cf_handle(&self.db, cfs::CM_IDX_CM)?;
 
-        let mut iter = self.db.iterator_cf(&cf, IteratorMode::End);
+        let mut iter = self.db.iterator_cf(&cf, IteratorMode::End)?;
 
         match iter.next() {
             Some((cm_idx, cm)) => {
@@ -50,4 +52,83 @@ impl LedgerDB {
 
         Ok(())
     }
+
+    pub(crate) fn get_schema_version(
+        &self,
+    ) -> Result<Option<SchemaVersion>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::SCHEMA_VERSION)?;
+
+        match self.db.get_cf(&cf, keys::SINGLETON)? {
+            Some(v) => {
+                let arr: [u8; 4] = match v.as_slice().try_into() {
+                    Ok(a) => a,
+                    Err(err) => {
+                        return Err(format!(
+                            "Schema version should be 4 bytes, err: {}",
+                            err
+                        )
+                        .into());
+                    }
+                };
+
+                return Ok(Some(SchemaVersion::from_be_bytes(arr)));
+            }
+            None => {
+                return Ok(None);
+            }
+        }
+    }
+
+    pub(crate) fn batch_put_schema_version(
+        &self,
+        batch: &mut WriteBatch,
+        schema_version: SchemaVersion,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::SCHEMA_VERSION)?;
+
+        batch.put_cf(&cf, keys::SINGLETON, schema_version.to_be_bytes());
+
+        Ok(())
+    }
+
+    pub(crate) fn batch_delete_cm_idx_cm(
+        &self,
+        batch: &mut WriteBatch,
+        cm_idx: &CmIdx,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CM_IDX_CM)?;
+
+        batch.delete_cf(&cf, cm_idx.to_be_bytes());
+
+        Ok(())
+    }
+
+    pub(crate) fn get_pruned_block_height(
+        &self,
+    ) -> Result<Option<BlockHeight>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::PRUNED_BLOCK_HEIGHT)?;
+
+        match self.db.get_cf(&cf, keys::SINGLETON)? {
+            Some(v) => {
+                let val = type_extension::convert_u8_slice_into_u128(&v)?;
+
+                return Ok(Some(val));
+            }
+            None => {
+                return Ok(None);
+            }
+        }
+    }
+
+    pub(crate) fn batch_put_pruned_block_height(
+        &self,
+        batch: &mut WriteBatch,
+        block_height: &BlockHeight,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::PRUNED_BLOCK_HEIGHT)?;
+
+        batch.put_cf(&cf, keys::SINGLETON, block_height.to_be_bytes());
+
+        Ok(())
+    }
 }
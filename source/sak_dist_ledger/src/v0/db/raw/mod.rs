@@ -1,3 +1,4 @@
 mod block;
 mod ledger;
 mod tx;
+mod tx_index;
@@ -1,8 +1,8 @@
 use crate::LedgerError;
 use crate::{cfs, LedgerDB};
 use sak_crypto::{Bls12, Hasher, Proof, ScalarExt};
-use sak_kv_db::WriteBatch;
-use sak_kv_db::DB;
+use sak_kv_db::KeyValueDatabase;
+use sak_kv_db::{IteratorMode, WriteBatch};
 use sak_types::{
     Cm, CmIdx, MintTx, MintTxCandidate, PourTx, PourTxCandidate, Sn, Tx,
     TxCtrOp, TxHash, TxHeight, TxType,
@@ -33,26 +33,6 @@ impl LedgerDB {
         };
     }
 
-    // pub(crate) fn get_tx_hash_by_height(
-    //     &self,
-    //     tx_height: &u128,
-    // ) -> Result<Option<String>, LedgerError> {
-    //     let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_HEIGHT)?;
-
-    //     let key = tx_height.to_be_bytes();
-
-    //     match self.db.get_cf(&cf, key)? {
-    //         Some(v) => {
-    //             let str = String::from_utf8(v)?;
-
-    //             return Ok(Some(str));
-    //         }
-    //         None => {
-    //             return Ok(None);
-    //         }
-    //     }
-    // }
-
     pub(crate) fn get_tx_created_at(
         &self,
         key: &TxHash,
@@ -285,7 +265,7 @@ impl LedgerDB {
 
     pub(crate) fn get_sn_2(
         &self,
-        db: &DB,
+        db: &KeyValueDatabase,
         key: &TxHash,
     ) -> Result<Option<[u8; 32]>, LedgerError> {
         let cf = self.make_cf_handle(db, cfs::SN_2)?;
@@ -304,7 +284,7 @@ impl LedgerDB {
 
     pub(crate) fn get_tx_hash_by_sn(
         &self,
-        db: &DB,
+        db: &KeyValueDatabase,
         key: &Sn,
     ) -> Result<Option<String>, LedgerError> {
         let cf = self.make_cf_handle(db, cfs::TX_HASH_BY_SN)?;
@@ -321,6 +301,24 @@ impl LedgerDB {
         }
     }
 
+    pub(crate) fn get_sns(&self) -> Result<Vec<(Sn, TxHash)>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_SN)?;
+
+        let iter = self.db.iterator_cf(&cf, IteratorMode::Start)?;
+
+        let mut ret = vec![];
+
+        for (sn, tx_hash) in iter {
+            let sn = type_extension::convert_vec_into_u8_32(sn.to_vec())?;
+
+            let tx_hash = String::from_utf8(tx_hash.to_vec())?;
+
+            ret.push((sn, tx_hash));
+        }
+
+        Ok(ret)
+    }
+
     pub(crate) fn get_cm_1(
         &self,
         key: &TxHash,
@@ -504,22 +502,6 @@ impl LedgerDB {
     //     Ok(())
     // }
 
-    // pub(crate) fn batch_put_tx_hash_by_height(
-    //     &self,
-    //     // db: &DB,
-    //     batch: &mut WriteBatch,
-    //     tx_height: &u128,
-    //     tx_hash: &String,
-    // ) -> Result<(), LedgerError> {
-    //     let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_HEIGHT)?;
-
-    //     let v = tx_height.to_be_bytes();
-
-    //     batch.put_cf(&cf, v, tx_hash);
-
-    //     Ok(())
-    // }
-
     pub(crate) fn batch_put_tx_hash_by_sn(
         &self,
         // db: &DB,
@@ -0,0 +1,155 @@
+use crate::{cfs, keys, LedgerDB, LedgerError, TxLocation, TxPos};
+use sak_kv_db::{Direction, IteratorMode, WriteBatch};
+use sak_types::{BlockHeight, Cm, CtrAddr, TxHash};
+use std::convert::TryInto;
+
+impl LedgerDB {
+    // Tx hashes of the block from `tx_loc` onwards, at most `limit` of them
+    pub(crate) fn get_tx_hashes_by_block_pos(
+        &self,
+        tx_loc: &TxLocation,
+        limit: usize,
+    ) -> Result<Vec<(TxLocation, TxHash)>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_BLOCK_POS)?;
+
+        let from = make_tx_loc_key(tx_loc);
+
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&from, Direction::Forward))?;
+
+        let mut ret = vec![];
+
+        for (k, v) in iter.take(limit) {
+            let loc = parse_tx_loc_key(&k)?;
+
+            if loc.block_height != tx_loc.block_height {
+                break;
+            }
+
+            ret.push((loc, String::from_utf8(v.to_vec())?));
+        }
+
+        Ok(ret)
+    }
+
+    // Txs of the contract from `tx_loc` onwards, at most `limit` of them
+    pub(crate) fn get_ctr_call_tx_hashes(
+        &self,
+        ctr_addr: &CtrAddr,
+        tx_loc: &TxLocation,
+        limit: usize,
+    ) -> Result<Vec<(TxLocation, TxHash)>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_CALL_TX_HASH)?;
+
+        let prefix = keys::make_ctr_addr_prefix(ctr_addr);
+
+        let mut from = prefix.clone();
+        from.extend_from_slice(&make_tx_loc_key(tx_loc));
+
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&from, Direction::Forward))?;
+
+        let mut ret = vec![];
+
+        for (k, v) in iter.take(limit) {
+            if !k.starts_with(&prefix) {
+                break;
+            }
+
+            let loc = parse_tx_loc_key(&k[prefix.len()..])?;
+
+            ret.push((loc, String::from_utf8(v.to_vec())?));
+        }
+
+        Ok(ret)
+    }
+
+    pub(crate) fn get_tx_hash_by_cm(
+        &self,
+        cm: &Cm,
+    ) -> Result<Option<TxHash>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_CM)?;
+
+        match self.db.get_cf(&cf, cm)? {
+            Some(v) => {
+                let str = String::from_utf8(v)?;
+
+                return Ok(Some(str));
+            }
+            None => {
+                return Ok(None);
+            }
+        }
+    }
+
+    pub(crate) fn batch_put_tx_hash_by_block_pos(
+        &self,
+        batch: &mut WriteBatch,
+        tx_loc: &TxLocation,
+        tx_hash: &TxHash,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_BLOCK_POS)?;
+
+        batch.put_cf(&cf, make_tx_loc_key(tx_loc), tx_hash);
+
+        Ok(())
+    }
+
+    pub(crate) fn batch_put_ctr_call_tx_hash(
+        &self,
+        batch: &mut WriteBatch,
+        ctr_addr: &CtrAddr,
+        tx_loc: &TxLocation,
+        tx_hash: &TxHash,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_CALL_TX_HASH)?;
+
+        let mut key = keys::make_ctr_addr_prefix(ctr_addr);
+        key.extend_from_slice(&make_tx_loc_key(tx_loc));
+
+        batch.put_cf(&cf, key, tx_hash);
+
+        Ok(())
+    }
+
+    pub(crate) fn batch_put_tx_hash_by_cm(
+        &self,
+        batch: &mut WriteBatch,
+        cm: &Cm,
+        tx_hash: &TxHash,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::TX_HASH_BY_CM)?;
+
+        batch.put_cf(&cf, cm, tx_hash);
+
+        Ok(())
+    }
+}
+
+// Big endian, so that keys are ordered by block height and then position
+fn make_tx_loc_key(tx_loc: &TxLocation) -> Vec<u8> {
+    let mut key = tx_loc.block_height.to_be_bytes().to_vec();
+    key.extend_from_slice(&tx_loc.tx_pos.to_be_bytes());
+
+    key
+}
+
+fn parse_tx_loc_key(key: &[u8]) -> Result<TxLocation, LedgerError> {
+    if key.len() != 20 {
+        return Err(format!(
+            "Tx location key should be 20 bytes, len: {}",
+            key.len()
+        )
+        .into());
+    }
+
+    let block_height: [u8; 16] = key[..16].try_into()?;
+    let tx_pos: [u8; 4] = key[16..].try_into()?;
+
+    Ok(TxLocation {
+        block_height: BlockHeight::from_be_bytes(block_height),
+        tx_pos: TxPos::from_be_bytes(tx_pos),
+    })
+}
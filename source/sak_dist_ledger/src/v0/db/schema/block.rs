@@ -1,5 +1,5 @@
 use crate::LedgerError;
-use crate::{cfs, CtrStateUpdate, LedgerDB, MerkleUpdate};
+use crate::{cfs, CtrStateUpdate, LedgerDB, MerkleUpdate, TxLocation, TxPos};
 use sak_kv_db::WriteBatch;
 use sak_types::{Block, BlockHash, BlockHeight, Tx};
 
@@ -107,59 +107,33 @@ impl LedgerDB {
 
         let block_hash = block.get_block_hash();
 
-        self.batch_put_validator_sig(
-            &mut batch,
-            block_hash,
-            &block.validator_sig,
-        )?;
-
-        self.batch_put_witness_sigs(
-            &mut batch,
-            block_hash,
-            &block.witness_sigs,
-        )?;
-
-        self.batch_put_tx_hashes(&mut batch, block_hash, &block.tx_hashes)?;
-
-        self.batch_put_block_created_at(
-            &mut batch,
-            block_hash,
-            &block.created_at,
-        )?;
-
-        self.batch_put_block_hash(&mut batch, &block.block_height, block_hash)?;
-
-        // self.batch_put_block_cm_count(
-        //     &mut batch,
-        //     block_hash,
-        //     block.block_cm_count,
-        // )?;
-
-        // self.batch_put_ledger_cm_count(&mut batch, updated_ledger_cm_count)?;
-
-        self.batch_put_block_height(
-            &mut batch,
-            block_hash,
-            &block.block_height,
-        )?;
-
-        self.batch_put_block_merkle_rt(
-            &mut batch,
-            block_hash,
-            &block.merkle_rt,
-        )?;
+        self.batch_put_block_header(&mut batch, block)?;
 
         // let mut cm_idx_count: u128 = ledger_cm_count;
 
-        for tx in txs {
+        for (tx_pos, tx) in txs.iter().enumerate() {
             self.batch_put_tx(
                 &mut batch, tx,
                 // &mut cm_idx_count
             )?;
+
+            let tx_loc = TxLocation {
+                block_height: block.block_height,
+                tx_pos: tx_pos as TxPos,
+            };
+
+            self.batch_put_tx_indexes(&mut batch, &tx_loc, tx)?;
         }
 
         for (ctr_addr, ctr_state) in ctr_state_updates {
             self.batch_put_ctr_state(&mut batch, ctr_addr, ctr_state)?;
+
+            self.batch_put_ctr_state_at(
+                &mut batch,
+                ctr_addr,
+                &block.block_height,
+                ctr_state,
+            )?;
         }
 
         for (loc, node_val) in merkle_updates {
@@ -170,4 +144,45 @@ impl LedgerDB {
 
         return Ok(block_hash.clone());
     }
+
+    pub(crate) fn batch_put_block_header(
+        &self,
+        batch: &mut WriteBatch,
+        block: &Block,
+    ) -> Result<(), LedgerError> {
+        let block_hash = block.get_block_hash();
+
+        self.batch_put_validator_sig(batch, block_hash, &block.validator_sig)?;
+
+        self.batch_put_witness_sigs(batch, block_hash, &block.witness_sigs)?;
+
+        self.batch_put_tx_hashes(batch, block_hash, &block.tx_hashes)?;
+
+        self.batch_put_block_created_at(batch, block_hash, &block.created_at)?;
+
+        self.batch_put_block_hash(batch, &block.block_height, block_hash)?;
+
+        // self.batch_put_block_cm_count(
+        //     batch,
+        //     block_hash,
+        //     block.block_cm_count,
+        // )?;
+
+        // self.batch_put_ledger_cm_count(batch, updated_ledger_cm_count)?;
+
+        self.batch_put_block_height(batch, block_hash, &block.block_height)?;
+
+        self.batch_put_block_merkle_rt(batch, block_hash, &block.merkle_rt)?;
+
+        for (tx_pos, tx_hash) in block.tx_hashes.iter().enumerate() {
+            let tx_loc = TxLocation {
+                block_height: block.block_height,
+                tx_pos: tx_pos as TxPos,
+            };
+
+            self.batch_put_tx_hash_by_block_pos(batch, &tx_loc, tx_hash)?;
+        }
+
+        Ok(())
+    }
 }
@@ -1,9 +1,28 @@
 pub(crate) mod keys {
+    use sak_types::CtrAddr;
+
     pub const SINGLETON: &[u8; 1] = &[0];
+
+    // Contract addresses vary in length, so a separator marks where the
+    // address ends in a key that goes on with something else
+    pub const CTR_ADDR_SEPARATOR: u8 = 0;
+
+    pub fn make_ctr_addr_prefix(ctr_addr: &CtrAddr) -> Vec<u8> {
+        let mut prefix = ctr_addr.as_bytes().to_vec();
+        prefix.push(CTR_ADDR_SEPARATOR);
+
+        prefix
+    }
 }
 
 pub(crate) mod cfs {
-    // pub const TX_HASH_BY_HEIGHT: &str = "tx_hash_by_height";
+    // Keyed by block height and the position of the tx in the block
+    pub const TX_HASH_BY_BLOCK_POS: &str = "tx_hash_by_block_pos";
+
+    // Keyed by ctr addr, block height and tx position. Deploys are included
+    pub const CTR_CALL_TX_HASH: &str = "ctr_call_tx_hash";
+
+    pub const TX_HASH_BY_CM: &str = "tx_hash_by_cm";
 
     pub const TX_HASH_BY_CTR_ADDR: &str = "tx_hash_by_ctr_addr";
 
@@ -70,4 +89,11 @@ pub(crate) mod cfs {
     pub const BLOCK_HASH: &str = "block_hash";
 
     pub const CTR_STATE: &str = "ctr_state";
+
+    // Keyed by ctr addr and the block height at which the state was written
+    pub const CTR_STATE_BY_HEIGHT: &str = "ctr_state_by_height";
+
+    pub const SCHEMA_VERSION: &str = "schema_version";
+
+    pub const PRUNED_BLOCK_HEIGHT: &str = "pruned_block_height";
 }
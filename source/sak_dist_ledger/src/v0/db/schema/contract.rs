@@ -1,8 +1,8 @@
 use crate::LedgerError;
-use crate::{cfs, LedgerDB};
+use crate::{cfs, keys, LedgerDB};
 use sak_contract_std::Storage;
-use sak_kv_db::WriteBatch;
-use sak_types::CtrAddr;
+use sak_kv_db::{Direction, IteratorMode, WriteBatch};
+use sak_types::{BlockHeight, CtrAddr};
 
 impl LedgerDB {
     pub(crate) async fn get_ctr_data_by_ctr_addr(
@@ -70,6 +70,52 @@ impl LedgerDB {
             }
         }
     }
+
+    // The state as of the block height, i.e. the one written at the height
+    // or, if the contract was not touched then, the latest one before it
+    pub(crate) fn get_ctr_state_at(
+        &self,
+        ctr_addr: &CtrAddr,
+        block_height: &BlockHeight,
+    ) -> Result<Option<Storage>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_STATE_BY_HEIGHT)?;
+
+        let prefix = keys::make_ctr_addr_prefix(ctr_addr);
+
+        let mut from = prefix.clone();
+        from.extend_from_slice(&block_height.to_be_bytes());
+
+        let mut iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&from, Direction::Reverse))?;
+
+        match iter.next() {
+            Some((k, v)) if k.starts_with(&prefix) => {
+                return Ok(Some(v.to_vec()));
+            }
+            _ => {
+                return Ok(None);
+            }
+        }
+    }
+
+    pub(crate) fn get_ctr_states(
+        &self,
+    ) -> Result<Vec<(CtrAddr, Storage)>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_STATE)?;
+
+        let iter = self.db.iterator_cf(&cf, IteratorMode::Start)?;
+
+        let mut ret = vec![];
+
+        for (ctr_addr, ctr_state) in iter {
+            let ctr_addr = String::from_utf8(ctr_addr.to_vec())?;
+
+            ret.push((ctr_addr, ctr_state.to_vec()));
+        }
+
+        Ok(ret)
+    }
 }
 
 // writer
@@ -88,6 +134,23 @@ impl LedgerDB {
         Ok(())
     }
 
+    pub(crate) fn batch_put_ctr_state_at(
+        &self,
+        batch: &mut WriteBatch,
+        ctr_addr: &CtrAddr,
+        block_height: &BlockHeight,
+        ctr_state: &Storage,
+    ) -> Result<(), LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_STATE_BY_HEIGHT)?;
+
+        let mut key = keys::make_ctr_addr_prefix(ctr_addr);
+        key.extend_from_slice(&block_height.to_be_bytes());
+
+        batch.put_cf(&cf, key, ctr_state);
+
+        Ok(())
+    }
+
     pub(crate) fn batch_put_tx_hash_by_contract_addr(
         &self,
         // db: &DB,
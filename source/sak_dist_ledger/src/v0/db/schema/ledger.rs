@@ -1,14 +1,10 @@
 use crate::{cfs, keys, LedgerDB};
 use crate::{LedgerError, MerkleNodeLoc};
 use sak_crypto::ScalarExt;
-use sak_kv_db::DB;
-use sak_kv_db::{
-    BoundColumnFamily, ColumnFamilyDescriptor, IteratorMode, Options,
-    WriteBatch,
-};
-use sak_types::{BlockHash, CtrAddr, TxHash, TxType};
+use sak_kv_db::KeyValueDatabase;
+use sak_kv_db::{IteratorMode, WriteBatch};
+use sak_types::{BlockHash, Cm, CmIdx, CtrAddr, TxHash, TxType};
 use std::convert::TryInto;
-use std::sync::Arc;
 use type_extension::U8Array;
 
 impl LedgerDB {
@@ -78,7 +74,7 @@ impl LedgerDB {
     ) -> Result<Option<u128>, LedgerError> {
         let cf = self.make_cf_handle(&self.db, cfs::BLOCK_HASH)?;
 
-        let mut iter = self.db.iterator_cf(&cf, IteratorMode::End);
+        let mut iter = self.db.iterator_cf(&cf, IteratorMode::End)?;
 
         let (height_bytes, _hash) = match iter.next() {
             Some(a) => a,
@@ -90,6 +86,45 @@ impl LedgerDB {
         Ok(Some(height))
     }
 
+    pub(crate) fn get_merkle_nodes(
+        &self,
+    ) -> Result<Vec<(MerkleNodeLoc, [u8; 32])>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::MERKLE_NODE)?;
+
+        let iter = self.db.iterator_cf(&cf, IteratorMode::Start)?;
+
+        let mut ret = vec![];
+
+        for (loc, node_val) in iter {
+            let loc = String::from_utf8(loc.to_vec())?;
+
+            let node_val =
+                type_extension::convert_vec_into_u8_32(node_val.to_vec())?;
+
+            ret.push((loc, node_val));
+        }
+
+        Ok(ret)
+    }
+
+    pub(crate) fn get_cms(&self) -> Result<Vec<(CmIdx, Cm)>, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CM_IDX_CM)?;
+
+        let iter = self.db.iterator_cf(&cf, IteratorMode::Start)?;
+
+        let mut ret = vec![];
+
+        for (cm_idx, cm) in iter {
+            let cm_idx = type_extension::convert_u8_slice_into_u128(&cm_idx)?;
+
+            let cm = type_extension::convert_vec_into_u8_32(cm.to_vec())?;
+
+            ret.push((cm_idx, cm));
+        }
+
+        Ok(ret)
+    }
+
     // pub(crate) fn get_latest_tx_height(
     //     &self,
     //     // db: &DB,
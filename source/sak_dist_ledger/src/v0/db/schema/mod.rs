@@ -4,5 +4,6 @@ mod contract;
 mod ledger;
 mod testing;
 mod tx;
+mod tx_index;
 
 pub(crate) use constants::*;
@@ -3,8 +3,8 @@ use crate::{cfs, LedgerDB};
 use sak_crypto::{Bls12, Hasher, Proof, ScalarExt};
 use sak_kv_db::WriteBatch;
 use sak_types::{
-    Cm, CmIdx, MintTx, MintTxCandidate, PourTx, PourTxCandidate, Sn, Tx,
-    TxCtrOp, TxHash, TxHeight, TxType,
+    AuthorSig, Cm, CmIdx, MintTx, MintTxCandidate, PourTx, PourTxCandidate, Sn,
+    Tx, TxCtrOp, TxHash, TxHeight, TxType,
 };
 
 impl LedgerDB {
@@ -32,6 +32,8 @@ impl LedgerDB {
             .get_tx_type(tx_hash)?
             .ok_or(format!("Tx type does not exist, tx_hash: {}", tx_hash))?;
 
+        self.check_tx_body_pruned(tx_hash)?;
+
         let tx = match tx_type {
             TxType::Mint => self.get_mint_tx(tx_hash),
             TxType::Pour => self.get_pour_tx(tx_hash),
@@ -205,8 +207,6 @@ impl LedgerDB {
 
         // self.batch_put_tx_height(batch, tx_hash, &tx.tx_height)?;
 
-        // self.batch_put_tx_hash_by_height(batch, &tx.tx_height, tx_hash)?;
-
         let tx_ctr_op = tc.get_ctr_op();
 
         // *cm_idx_count = *cm_idx_count + 1;
@@ -249,11 +249,15 @@ impl LedgerDB {
     ) -> Result<(), LedgerError> {
         let hasher = Hasher::new();
 
+        // The proof is bound to whoever signed the tx
+        let author_sig = AuthorSig::parse(&tc.author_sig)?;
+
         let public_inputs = [
             ScalarExt::parse_arr(&tc.merkle_rt)?,
             ScalarExt::parse_arr(&tc.sn_1)?,
             ScalarExt::parse_arr(&tc.cm_1)?,
             ScalarExt::parse_arr(&tc.cm_2)?,
+            sak_types::make_author_pk_hash(&author_sig.public_key_str)?,
         ];
 
         // let pi_des: Proof<Bls12> = match Proof::read(&*tc.pi) {
@@ -309,8 +313,6 @@ impl LedgerDB {
 
         // self.batch_put_tx_height(batch, tx_hash, &tx.tx_height)?;
 
-        // self.batch_put_tx_hash_by_height(batch, &tx.tx_height, tx_hash)?;
-
         self.batch_put_pi(batch, tx_hash, &tc.pi)?;
 
         self.batch_put_sn_1(batch, tx_hash, &tc.sn_1)?;
@@ -0,0 +1,34 @@
+use crate::{LedgerDB, LedgerError, TxLocation};
+use sak_kv_db::WriteBatch;
+use sak_types::{Tx, TxCtrOp};
+
+impl LedgerDB {
+    // The index of txs by block position is written with the block header,
+    // since snapshots hold block headers but not tx bodies
+    pub(crate) fn batch_put_tx_indexes(
+        &self,
+        batch: &mut WriteBatch,
+        tx_loc: &TxLocation,
+        tx: &Tx,
+    ) -> Result<(), LedgerError> {
+        let tx_hash = tx.get_tx_hash();
+
+        match tx.get_ctr_op() {
+            TxCtrOp::ContractDeploy | TxCtrOp::ContractCall => {
+                self.batch_put_ctr_call_tx_hash(
+                    batch,
+                    tx.get_ctr_addr(),
+                    tx_loc,
+                    tx_hash,
+                )?;
+            }
+            TxCtrOp::None => {}
+        }
+
+        for (_, cm) in tx.get_cm_pairs() {
+            self.batch_put_tx_hash_by_cm(batch, &cm, tx_hash)?;
+        }
+
+        Ok(())
+    }
+}
@@ -15,7 +15,7 @@ use tokio::sync::broadcast;
 use tokio::sync::{broadcast::Sender, RwLock};
 
 const BLOCKCHAIN_EVENT_QUEUE_CAPACITY: usize = 32;
-const MERKLE_TREE_HEIGHT: usize = 16;
+pub(crate) const MERKLE_TREE_HEIGHT: usize = 16;
 
 pub struct DistLedger {
     pub apis: DistLedgerApis,
@@ -35,6 +35,9 @@ pub struct DistLedgerArgs {
     pub genesis_block: Option<BlockCandidate>,
     pub consensus: Box<dyn Consensus + Send + Sync>,
     pub block_sync_interval: Option<u64>,
+    pub in_memory_db: bool,
+    // Tx bodies of every block but the latest ones are deleted if set
+    pub prune_keep_blocks: Option<u128>,
 }
 
 impl DistLedger {
@@ -47,9 +50,15 @@ impl DistLedger {
             genesis_block,
             consensus,
             block_sync_interval,
+            in_memory_db,
+            prune_keep_blocks,
         } = dist_ledger_args;
 
-        let ledger_db = LedgerDB::init(&app_prefix).await?;
+        let ledger_db = if in_memory_db {
+            LedgerDB::init_in_memory()?
+        } else {
+            LedgerDB::init(&app_prefix).await?
+        };
 
         let vm = VM::init()?;
 
@@ -71,6 +80,8 @@ impl DistLedger {
                 ledger_event_tx.clone(),
                 tx_sync_interval,
                 block_sync_interval,
+                ledger_db.clone(),
+                prune_keep_blocks,
             );
 
             Arc::new(r)
@@ -0,0 +1,450 @@
+use super::IntegrityReport;
+use crate::{
+    append_merkle_leaf, cfs, DistLedgerApis, LedgerDB, LedgerError,
+    MerkleUpdate, MERKLE_TREE_HEIGHT,
+};
+use colored::Colorize;
+use log::{info, warn};
+use sak_contract_std::{CtrCallType, CtrRequest, Storage, ERROR_PLACEHOLDER};
+use sak_crypto::Hasher;
+use sak_types::{Cm, CmIdx, CtrAddr, Tx, TxCtrOp};
+use sak_vm::{CtrFn, VM};
+use std::collections::{BTreeMap, HashMap};
+use type_extension::U8Array;
+
+impl LedgerDB {
+    // Walks every block by height and re-derives what `write_block` would
+    // have persisted, i.e. block hashes, cm indices, merkle nodes and contract
+    // states. Entries that do not agree with the re-derived ones are
+    // reported rather than returned as an error so that a single pass lists
+    // all of them.
+    pub async fn check_integrity(
+        &self,
+        vm: &VM,
+    ) -> Result<IntegrityReport, LedgerError> {
+        let hasher = Hasher::new();
+
+        let mut report = IntegrityReport::default();
+
+        let latest_block_height = match self.get_latest_block_height()? {
+            Some(h) => h,
+            None => {
+                warn!("Ledger is empty, there is nothing to check");
+
+                return Ok(report);
+            }
+        };
+
+        if let Some(h) = self.get_pruned_block_height()? {
+            return Err(format!(
+                "Ledger is pruned up to block height {}, tx bodies needed \
+                to re-derive the ledger are gone",
+                h
+            )
+            .into());
+        }
+
+        report.latest_block_height = Some(latest_block_height);
+
+        let persisted_cms: BTreeMap<CmIdx, Cm> =
+            self.get_cms()?.into_iter().collect();
+
+        let mut merkle_update = MerkleUpdate::new();
+        let mut ctr_wasms: HashMap<CtrAddr, Vec<u8>> = HashMap::new();
+        let mut ctr_states: HashMap<CtrAddr, Storage> = HashMap::new();
+        let mut next_cm_idx: CmIdx = 0;
+
+        for h in 0..=latest_block_height {
+            let block_hash = match self.get_block_hash_by_block_height(&h)? {
+                Some(b) => b,
+                None => {
+                    report.add(cfs::BLOCK_HASH, h, "block hash is missing");
+
+                    continue;
+                }
+            };
+
+            let block = match self.get_block(&block_hash) {
+                Ok(Some(b)) => b,
+                Ok(None) => {
+                    report.add(
+                        cfs::BLOCK_HEIGHT,
+                        &block_hash,
+                        "block is missing",
+                    );
+
+                    continue;
+                }
+                Err(err) => {
+                    report.add(cfs::BLOCK_HEIGHT, &block_hash, err);
+
+                    continue;
+                }
+            };
+
+            report.block_count += 1;
+
+            if block.block_height != h {
+                report.add(
+                    cfs::BLOCK_HEIGHT,
+                    &block_hash,
+                    format!(
+                        "block height does not match, expected: {}, \
+                        persisted: {}",
+                        h, block.block_height,
+                    ),
+                );
+            }
+
+            if block.get_block_hash() != &block_hash {
+                report.add(
+                    cfs::BLOCK_HASH,
+                    h,
+                    format!(
+                        "block hash does not match the recomputed one, \
+                        persisted: {}, recomputed: {}",
+                        block_hash,
+                        block.get_block_hash(),
+                    ),
+                );
+            }
+
+            for tx_hash in &block.tx_hashes {
+                let tx = match self.get_tx(tx_hash).await {
+                    Ok(Some(t)) => t,
+                    Ok(None) => {
+                        report.add(cfs::TX_TYPE, tx_hash, "tx is missing");
+
+                        continue;
+                    }
+                    Err(err) => {
+                        report.add(cfs::TX_TYPE, tx_hash, err);
+
+                        continue;
+                    }
+                };
+
+                report.tx_count += 1;
+
+                if tx.get_tx_hash() != tx_hash {
+                    report.add(
+                        cfs::TX_HASHES,
+                        &block_hash,
+                        format!(
+                            "tx hash does not match the recomputed one, \
+                            persisted: {}, recomputed: {}",
+                            tx_hash,
+                            tx.get_tx_hash(),
+                        ),
+                    );
+                }
+
+                if let Tx::Pour(t) = &tx {
+                    let sn = t.get_sn();
+
+                    if self.get_tx_hash_by_sn(&self.db, &sn)?.as_ref()
+                        != Some(tx_hash)
+                    {
+                        report.add(
+                            cfs::TX_HASH_BY_SN,
+                            sak_crypto::encode_hex(&sn),
+                            format!("sn does not point to tx: {}", tx_hash),
+                        );
+                    }
+                }
+
+                for (cm_idx, cm) in tx.get_cm_pairs() {
+                    self.check_cm(
+                        &mut report,
+                        &persisted_cms,
+                        next_cm_idx,
+                        cm_idx,
+                        &cm,
+                    );
+
+                    append_merkle_leaf(
+                        &hasher,
+                        &mut merkle_update,
+                        next_cm_idx,
+                        &cm,
+                        MERKLE_TREE_HEIGHT,
+                    )?;
+
+                    next_cm_idx += 1;
+                }
+
+                self.replay_ctr_state_update(
+                    &mut report,
+                    vm,
+                    &tx,
+                    &mut ctr_wasms,
+                    &mut ctr_states,
+                )?;
+            }
+
+            let root_loc = format!("{}_{}", MERKLE_TREE_HEIGHT, 0);
+            let merkle_rt = match merkle_update.get(&root_loc) {
+                Some(r) => *r,
+                None => U8Array::new_empty_32(),
+            };
+
+            if merkle_rt != block.merkle_rt {
+                report.add(
+                    cfs::BLOCK_MERKLE_RT,
+                    &block_hash,
+                    format!(
+                        "merkle root does not match the recomputed one, \
+                        block_height: {}",
+                        h
+                    ),
+                );
+            }
+        }
+
+        report.cm_count = next_cm_idx;
+
+        for (cm_idx, _) in persisted_cms.range(next_cm_idx..) {
+            report.add(
+                cfs::CM_IDX_CM,
+                cm_idx,
+                "cm is not included in any block",
+            );
+        }
+
+        let persisted_merkle_nodes: HashMap<String, [u8; 32]> =
+            self.get_merkle_nodes()?.into_iter().collect();
+
+        for (loc, node) in &merkle_update {
+            match persisted_merkle_nodes.get(loc) {
+                Some(n) if n == node => (),
+                Some(_) => {
+                    report.add(cfs::MERKLE_NODE, loc, "merkle node differs")
+                }
+                None => {
+                    report.add(cfs::MERKLE_NODE, loc, "merkle node is missing")
+                }
+            }
+        }
+
+        for loc in persisted_merkle_nodes.keys() {
+            if !merkle_update.contains_key(loc) {
+                report.add(
+                    cfs::MERKLE_NODE,
+                    loc,
+                    "merkle node is not derived from any cm",
+                );
+            }
+        }
+
+        for (ctr_addr, persisted_state) in self.get_ctr_states()? {
+            match ctr_states.remove(&ctr_addr) {
+                Some(s) if s == persisted_state => (),
+                Some(_) => report.add(
+                    cfs::CTR_STATE,
+                    &ctr_addr,
+                    "ctr state differs from the re-executed one",
+                ),
+                None => report.add(
+                    cfs::CTR_STATE,
+                    &ctr_addr,
+                    "ctr state exists but the contract is never deployed",
+                ),
+            }
+        }
+
+        for ctr_addr in ctr_states.keys() {
+            report.add(cfs::CTR_STATE, ctr_addr, "ctr state is missing");
+        }
+
+        info!(
+            "Checked ledger integrity, latest_block_height: {}, \
+            block count: {}, tx count: {}, cm count: {}, \
+            inconsistency count: {}",
+            latest_block_height,
+            report.block_count,
+            report.tx_count,
+            report.cm_count,
+            report.inconsistencies.len().to_string().yellow(),
+        );
+
+        Ok(report)
+    }
+
+    fn check_cm(
+        &self,
+        report: &mut IntegrityReport,
+        persisted_cms: &BTreeMap<CmIdx, Cm>,
+        expected_cm_idx: CmIdx,
+        cm_idx: CmIdx,
+        cm: &Cm,
+    ) {
+        if cm_idx != expected_cm_idx {
+            report.add(
+                cfs::CM_IDX,
+                sak_crypto::encode_hex(cm),
+                format!(
+                    "cm idx does not match, expected: {}, persisted: {}",
+                    expected_cm_idx, cm_idx,
+                ),
+            );
+        }
+
+        match persisted_cms.get(&expected_cm_idx) {
+            Some(c) if c == cm => (),
+            Some(c) => report.add(
+                cfs::CM_IDX_CM,
+                expected_cm_idx,
+                format!(
+                    "cm does not match, expected: {}, persisted: {}",
+                    sak_crypto::encode_hex(cm),
+                    sak_crypto::encode_hex(c),
+                ),
+            ),
+            None => {
+                report.add(cfs::CM_IDX_CM, expected_cm_idx, "cm is missing")
+            }
+        }
+    }
+
+    // Mirrors the contract state update made in `write_block`. Contract
+    // states of the previous blocks are the ones re-executed here, not the
+    // persisted ones.
+    fn replay_ctr_state_update(
+        &self,
+        report: &mut IntegrityReport,
+        vm: &VM,
+        tx: &Tx,
+        ctr_wasms: &mut HashMap<CtrAddr, Vec<u8>>,
+        ctr_states: &mut HashMap<CtrAddr, Storage>,
+    ) -> Result<(), LedgerError> {
+        let tx_hash = tx.get_tx_hash();
+
+        let (ctr_addr, data, tx_ctr_op) = match tx {
+            Tx::Mint(t) => (
+                &t.tx_candidate.ctr_addr,
+                &t.tx_candidate.data,
+                t.tx_candidate.get_ctr_op(),
+            ),
+            Tx::Pour(t) => (
+                &t.tx_candidate.ctr_addr,
+                &t.tx_candidate.data,
+                t.tx_candidate.get_ctr_op(),
+            ),
+        };
+
+        match tx_ctr_op {
+            TxCtrOp::ContractDeploy => {
+                if self.get_tx_hash_by_ctr_addr(ctr_addr)?.as_ref()
+                    != Some(tx_hash)
+                {
+                    report.add(
+                        cfs::TX_HASH_BY_CTR_ADDR,
+                        ctr_addr,
+                        format!("ctr addr does not point to tx: {}", tx_hash),
+                    );
+                }
+
+                match vm.invoke(data, CtrFn::Init) {
+                    Ok(receipt) => match receipt.updated_storage {
+                        Some(s) => {
+                            ctr_states.insert(ctr_addr.clone(), s);
+                        }
+                        None => report.add(
+                            cfs::DATA,
+                            tx_hash,
+                            "contract state is not initialized",
+                        ),
+                    },
+                    Err(err) => report.add(
+                        cfs::DATA,
+                        tx_hash,
+                        format!("contract init failed, err: {}", err),
+                    ),
+                };
+
+                ctr_wasms.insert(ctr_addr.clone(), data.clone());
+            }
+            TxCtrOp::ContractCall => {
+                let req = match CtrRequest::parse(data) {
+                    Ok(r) => r,
+                    Err(err) => {
+                        report.add(
+                            cfs::DATA,
+                            tx_hash,
+                            format!("invalid ctr request, err: {}", err),
+                        );
+
+                        return Ok(());
+                    }
+                };
+
+                if let CtrCallType::Query = req.ctr_call_type {
+                    return Ok(());
+                }
+
+                let (ctr_wasm, previous_state) =
+                    match (ctr_wasms.get(ctr_addr), ctr_states.get(ctr_addr)) {
+                        (Some(w), Some(s)) => (w, s.to_vec()),
+                        _ => {
+                            report.add(
+                                cfs::CTR_ADDR,
+                                tx_hash,
+                                format!(
+                                    "contract is called before deployed, \
+                                    ctr_addr: {}",
+                                    ctr_addr
+                                ),
+                            );
+
+                            return Ok(());
+                        }
+                    };
+
+                let ctr_fn = CtrFn::Execute(req, previous_state);
+
+                let new_state = match vm.invoke(ctr_wasm, ctr_fn) {
+                    Ok(r) => r.updated_storage,
+                    Err(err) => {
+                        report.add(
+                            cfs::DATA,
+                            tx_hash,
+                            format!("contract execution failed, err: {}", err),
+                        );
+
+                        return Ok(());
+                    }
+                };
+
+                match new_state {
+                    Some(s) => match s.get(0..6) {
+                        Some(ep) if ep == &ERROR_PLACEHOLDER => (),
+                        Some(_) => {
+                            ctr_states.insert(ctr_addr.clone(), s);
+                        }
+                        None => report.add(
+                            cfs::DATA,
+                            tx_hash,
+                            "new_state should be bigger than 6-byte",
+                        ),
+                    },
+                    None => report.add(
+                        cfs::DATA,
+                        tx_hash,
+                        "contract state is not updated",
+                    ),
+                };
+            }
+            TxCtrOp::None => (),
+        };
+
+        Ok(())
+    }
+}
+
+impl DistLedgerApis {
+    pub async fn check_integrity(
+        &self,
+    ) -> Result<IntegrityReport, LedgerError> {
+        self.ledger_db.check_integrity(&self.vm).await
+    }
+}
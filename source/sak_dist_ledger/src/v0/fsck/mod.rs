@@ -0,0 +1,4 @@
+mod check;
+mod report;
+
+pub use report::*;
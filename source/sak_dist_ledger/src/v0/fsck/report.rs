@@ -0,0 +1,49 @@
+use sak_types::BlockHeight;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerInconsistency {
+    // column family in which the inconsistent entry is found
+    pub cf: &'static str,
+
+    pub key: String,
+
+    pub msg: String,
+}
+
+impl std::fmt::Display for LedgerInconsistency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] key: {}, {}", self.cf, self.key, self.msg)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct IntegrityReport {
+    pub latest_block_height: Option<BlockHeight>,
+
+    pub block_count: u128,
+
+    pub tx_count: u128,
+
+    pub cm_count: u128,
+
+    pub inconsistencies: Vec<LedgerInconsistency>,
+}
+
+impl IntegrityReport {
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    pub(crate) fn add(
+        &mut self,
+        cf: &'static str,
+        key: impl ToString,
+        msg: impl ToString,
+    ) {
+        self.inconsistencies.push(LedgerInconsistency {
+            cf,
+            key: key.to_string(),
+            msg: msg.to_string(),
+        });
+    }
+}
@@ -3,7 +3,10 @@ mod consensus;
 mod db;
 mod dist_ledger;
 mod events;
+mod fsck;
+mod pruning;
 mod runtime;
+mod snapshot;
 mod state_update;
 mod sync_pool;
 
@@ -15,7 +18,10 @@ pub use consensus::*;
 pub use db::*;
 pub use dist_ledger::*;
 pub use events::*;
+pub use fsck::*;
+pub use pruning::*;
 pub(crate) use runtime::*;
+pub use snapshot::*;
 pub(crate) use state_update::*;
 pub(crate) use sync_pool::*;
 
@@ -0,0 +1,29 @@
+use sak_types::BlockHeight;
+use std::fmt;
+
+// Returned when the data asked for used to be in the ledger but has been
+// pruned. Callers can tell it apart from the data never having existed by
+// downcasting `LedgerError`.
+#[derive(Debug)]
+pub struct LedgerPrunedError {
+    pub pruned_block_height: BlockHeight,
+    pub msg: String,
+}
+
+impl LedgerPrunedError {
+    pub fn is_pruned(err: &crate::LedgerError) -> bool {
+        err.downcast_ref::<LedgerPrunedError>().is_some()
+    }
+}
+
+impl fmt::Display for LedgerPrunedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pruned, {}, data up to block height {} is no longer kept",
+            self.msg, self.pruned_block_height,
+        )
+    }
+}
+
+impl std::error::Error for LedgerPrunedError {}
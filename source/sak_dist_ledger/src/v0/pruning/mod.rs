@@ -0,0 +1,4 @@
+mod error;
+mod prune;
+
+pub use error::*;
@@ -0,0 +1,233 @@
+use crate::{cfs, LedgerDB, LedgerError, LedgerPrunedError};
+use log::info;
+use sak_kv_db::{IteratorMode, WriteBatch};
+use sak_types::{BlockHeight, CtrAddr, TxHash};
+
+// Column families whose entries go away once a tx body is pruned. Tx type,
+// sn and cms are left as they are. They are what the nullifier set and the
+// commitment tree are made of
+const TX_BODY_CFS: [&str; 9] = [
+    cfs::TX_CREATED_AT,
+    cfs::DATA,
+    cfs::AUTHOR_SIG,
+    cfs::CTR_ADDR,
+    cfs::PI,
+    cfs::PRF_MERKLE_RT,
+    cfs::V,
+    cfs::K,
+    cfs::S,
+];
+
+impl LedgerDB {
+    // Deletes the tx bodies of every block but the latest `keep_blocks`
+    // ones. Block headers, the nullifier set, the commitment tree and the
+    // current contract states stay as they are. Merkle nodes are keyed by
+    // their location, so the current tree is the only version there is. The
+    // wasm of a deployed contract is kept as it is needed to execute the
+    // contract. Of the contract states kept by height, only the ones as of
+    // the pruned height or later are left.
+    //
+    // Blocks are pruned one at a time, each in a single batch with the
+    // pruned height, so an interrupted run resumes where it stopped.
+    pub(crate) fn prune(
+        &self,
+        keep_blocks: u128,
+    ) -> Result<Option<BlockHeight>, LedgerError> {
+        if keep_blocks < 1 {
+            return Err(format!("At least one block has to be kept").into());
+        }
+
+        let latest_block_height = match self.get_latest_block_height()? {
+            Some(h) => h,
+            None => return Ok(None),
+        };
+
+        if latest_block_height < keep_blocks {
+            return Ok(None);
+        }
+
+        // Inclusive
+        let prune_until = latest_block_height - keep_blocks;
+
+        let prune_from = match self.get_pruned_block_height()? {
+            Some(h) if h >= prune_until => return Ok(None),
+            Some(h) => h + 1,
+            None => 0,
+        };
+
+        let mut tx_count = 0;
+
+        for block_height in prune_from..=prune_until {
+            let mut batch = WriteBatch::default();
+
+            let block_hash = self
+                .get_block_hash_by_block_height(&block_height)?
+                .ok_or(format!(
+                    "Block hash does not exist, block_height: {}",
+                    block_height
+                ))?;
+
+            let tx_hashes = self.get_tx_hashes(&block_hash)?.unwrap_or(vec![]);
+
+            for tx_hash in &tx_hashes {
+                let keep_data = self.is_ctr_deploy_tx(tx_hash)?;
+
+                self.batch_delete_tx_body(&mut batch, tx_hash, keep_data)?;
+            }
+
+            self.batch_put_pruned_block_height(&mut batch, &block_height)?;
+
+            self.db.write(batch)?;
+
+            tx_count += tx_hashes.len();
+        }
+
+        // Only once the pruned height is in place, so that a state is never
+        // missing at a height that is not reported as pruned
+        let ctr_state_count = self.prune_ctr_states(prune_until)?;
+
+        for cf in TX_BODY_CFS {
+            self.db.compact_cf(cf)?;
+        }
+
+        self.db.compact_cf(cfs::CTR_STATE_BY_HEIGHT)?;
+
+        info!(
+            "Pruned tx bodies, block height: {} - {}, tx count: {}, \
+            ctr state count: {}",
+            prune_from, prune_until, tx_count, ctr_state_count,
+        );
+
+        Ok(Some(prune_until))
+    }
+
+    pub(crate) fn check_tx_body_pruned(
+        &self,
+        tx_hash: &TxHash,
+    ) -> Result<(), LedgerError> {
+        let pruned_block_height = match self.get_pruned_block_height()? {
+            Some(h) => h,
+            None => return Ok(()),
+        };
+
+        if self.get_tx_created_at(tx_hash)?.is_none() {
+            return Err(LedgerPrunedError {
+                pruned_block_height,
+                msg: format!("tx_hash: {}", tx_hash),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // States below the pruned height are gone, but the one a contract has as
+    // of the pruned height itself is kept
+    pub(crate) fn check_ctr_state_pruned(
+        &self,
+        ctr_addr: &CtrAddr,
+        block_height: &BlockHeight,
+    ) -> Result<(), LedgerError> {
+        let pruned_block_height = match self.get_pruned_block_height()? {
+            Some(h) => h,
+            None => return Ok(()),
+        };
+
+        if *block_height < pruned_block_height {
+            return Err(LedgerPrunedError {
+                pruned_block_height,
+                msg: format!(
+                    "ctr_addr: {}, block_height: {}",
+                    ctr_addr, block_height
+                ),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // Keeps, per contract, the newest state at or below the height, which
+    // is the state as of the height, and deletes the ones before it.
+    // Resolves with the number of states deleted.
+    fn prune_ctr_states(
+        &self,
+        prune_until: BlockHeight,
+    ) -> Result<usize, LedgerError> {
+        let cf = self.make_cf_handle(&self.db, cfs::CTR_STATE_BY_HEIGHT)?;
+
+        let iter = self.db.iterator_cf(&cf, IteratorMode::Start)?;
+
+        let mut batch = WriteBatch::default();
+        let mut count = 0;
+
+        // Keys are the ctr addr prefix followed by the block height, so the
+        // states of a contract come in the order of their height
+        let mut newest_key: Option<Box<[u8]>> = None;
+
+        for (key, _) in iter {
+            let height_idx = match key.len().checked_sub(16) {
+                Some(i) => i,
+                None => {
+                    return Err(format!(
+                        "Ctr state key is too short, key: {:?}",
+                        key
+                    )
+                    .into());
+                }
+            };
+
+            let mut height_bytes = [0; 16];
+            height_bytes.copy_from_slice(&key[height_idx..]);
+
+            if BlockHeight::from_be_bytes(height_bytes) > prune_until {
+                continue;
+            }
+
+            if let Some(k) = &newest_key {
+                if k.len() == key.len() && k[..height_idx] == key[..height_idx]
+                {
+                    batch.delete_cf(&cf, k);
+
+                    count += 1;
+                }
+            }
+
+            newest_key = Some(key);
+        }
+
+        self.db.write(batch)?;
+
+        Ok(count)
+    }
+
+    fn batch_delete_tx_body(
+        &self,
+        batch: &mut WriteBatch,
+        tx_hash: &TxHash,
+        keep_data: bool,
+    ) -> Result<(), LedgerError> {
+        for col_name in TX_BODY_CFS {
+            if keep_data && col_name == cfs::DATA {
+                continue;
+            }
+
+            let cf = self.make_cf_handle(&self.db, col_name)?;
+
+            batch.delete_cf(&cf, tx_hash);
+        }
+
+        Ok(())
+    }
+
+    fn is_ctr_deploy_tx(&self, tx_hash: &TxHash) -> Result<bool, LedgerError> {
+        let ctr_addr = match self.get_ctr_addr(tx_hash)? {
+            Some(a) => a,
+            None => return Ok(false),
+        };
+
+        let deploy_tx_hash = self.get_tx_hash_by_ctr_addr(&ctr_addr)?;
+
+        Ok(deploy_tx_hash.as_ref() == Some(tx_hash))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{DistLedgerEvent, SyncPool};
+use crate::{DistLedgerEvent, LedgerDB, SyncPool};
 use log::{debug, error, warn};
 use std::{
     sync::Arc,
@@ -8,12 +8,15 @@ use tokio::sync::{broadcast::Sender, RwLock};
 
 const TX_SYNC_INTERVAL: u64 = 2000;
 const BLOCK_SYNC_INTERVAL: u64 = 2000;
+const PRUNE_INTERVAL: u64 = 60000;
 
 pub struct Runtime {
     sync_pool: Arc<SyncPool>,
     bc_event_tx: Arc<RwLock<Sender<DistLedgerEvent>>>,
     tx_sync_interval: Duration,
     block_sync_interval: Duration,
+    ledger_db: LedgerDB,
+    prune_keep_blocks: Option<u128>,
 }
 
 impl Runtime {
@@ -22,6 +25,8 @@ impl Runtime {
         bc_event_tx: Arc<RwLock<Sender<DistLedgerEvent>>>,
         tx_sync_interval: Option<u64>,
         block_sync_interval: Option<u64>,
+        ledger_db: LedgerDB,
+        prune_keep_blocks: Option<u128>,
     ) -> Runtime {
         let tx_sync_interval = match tx_sync_interval {
             Some(i) => Duration::from_millis(i.into()),
@@ -38,6 +43,8 @@ impl Runtime {
             bc_event_tx,
             tx_sync_interval,
             block_sync_interval,
+            ledger_db,
+            prune_keep_blocks,
         }
     }
 
@@ -61,6 +68,18 @@ impl Runtime {
         tokio::spawn(async move {
             block_sync_routine.run().await;
         });
+
+        if let Some(keep_blocks) = self.prune_keep_blocks {
+            let prune_routine = PruneRoutine {
+                ledger_db: self.ledger_db.clone(),
+                keep_blocks,
+                prune_interval: Duration::from_millis(PRUNE_INTERVAL),
+            };
+
+            tokio::spawn(async move {
+                prune_routine.run().await;
+            });
+        }
     }
 }
 
@@ -144,3 +163,45 @@ impl BlockSyncRoutine {
         }
     }
 }
+
+struct PruneRoutine {
+    ledger_db: LedgerDB,
+    keep_blocks: u128,
+    prune_interval: Duration,
+}
+
+impl PruneRoutine {
+    pub(crate) async fn run(&self) {
+        loop {
+            let time_since = SystemTime::now();
+
+            let ledger_db = self.ledger_db.clone();
+            let keep_blocks = self.keep_blocks;
+
+            // Deleting and compacting go through the blocking storage calls
+            let res = tokio::task::spawn_blocking(move || {
+                ledger_db.prune(keep_blocks)
+            })
+            .await;
+
+            match res {
+                Ok(Ok(Some(h))) => {
+                    debug!("Ledger pruned up to block height: {}", h);
+                }
+                Ok(Ok(None)) => (),
+                Ok(Err(err)) => {
+                    error!("Could not prune the ledger, err: {}", err);
+                }
+                Err(err) => {
+                    error!("Prune task has failed, err: {}", err);
+                }
+            }
+
+            sak_utils_time::wait_until_min_interval(
+                time_since,
+                self.prune_interval,
+            )
+            .await;
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use super::{CtrSnapshot, LedgerSnapshot};
+use crate::{
+    append_merkle_leaf, DistLedgerApis, LedgerDB, LedgerError, MerkleUpdate,
+    MERKLE_TREE_HEIGHT,
+};
+use colored::Colorize;
+use log::info;
+use sak_crypto::Hasher;
+use sak_types::{Block, BlockHeight, Cm, CmIdx, Sn, TxHash};
+use std::collections::HashSet;
+
+type SnapshotState = (
+    Vec<(String, [u8; 32])>,
+    Vec<(CmIdx, Cm)>,
+    Vec<(Sn, TxHash)>,
+    Vec<CtrSnapshot>,
+);
+
+impl LedgerDB {
+    pub fn export_snapshot(
+        &self,
+        block_height: Option<BlockHeight>,
+    ) -> Result<LedgerSnapshot, LedgerError> {
+        let latest_block_height = self
+            .get_latest_block_height()?
+            .ok_or("Ledger is empty, there is nothing to export")?;
+
+        let block_height = match block_height {
+            Some(h) if h > latest_block_height => {
+                return Err(format!(
+                    "Block height is beyond the latest one, requested: {}, \
+                    latest: {}",
+                    h, latest_block_height,
+                )
+                .into());
+            }
+            Some(h) => h,
+            None => latest_block_height,
+        };
+
+        let mut blocks = vec![];
+
+        for h in 0..=block_height {
+            let block_hash = self.get_block_hash_by_block_height(&h)?.ok_or(
+                format!("Block hash at height ({}) does not exist", h),
+            )?;
+
+            let block = self.get_block(&block_hash)?.ok_or(format!(
+                "Block does not exist, block_hash: {}",
+                block_hash
+            ))?;
+
+            blocks.push(block);
+        }
+
+        let (merkle_nodes, cms, sns, ctrs) =
+            if block_height == latest_block_height {
+                self.get_latest_state(latest_block_height)?
+            } else {
+                self.get_state_at(&blocks)?
+            };
+
+        let block_hash = match blocks.last() {
+            Some(b) => b.get_block_hash().to_string(),
+            None => return Err(format!("Snapshot has no blocks").into()),
+        };
+
+        info!(
+            "Exported ledger snapshot, block_height: {}, block_hash: {}, \
+            cm count: {}, sn count: {}, ctr count: {}",
+            block_height,
+            block_hash.green(),
+            cms.len(),
+            sns.len(),
+            ctrs.len(),
+        );
+
+        let snapshot = LedgerSnapshot {
+            block_height,
+            block_hash,
+            blocks,
+            merkle_nodes,
+            cms,
+            sns,
+            ctrs,
+        };
+
+        Ok(snapshot)
+    }
+
+    fn get_latest_state(
+        &self,
+        latest_block_height: BlockHeight,
+    ) -> Result<SnapshotState, LedgerError> {
+        let merkle_nodes = self.get_merkle_nodes()?;
+
+        let cms = self.get_cms()?;
+
+        let sns = self.get_sns()?;
+
+        let mut ctrs = vec![];
+
+        for (ctr_addr, ctr_state) in self.get_ctr_states()? {
+            let deploy_tx_hash =
+                self.get_tx_hash_by_ctr_addr(&ctr_addr)?.ok_or(format!(
+                    "Deploying tx hash does not exist, ctr_addr: {}",
+                    ctr_addr
+                ))?;
+
+            let wasm = self.get_data(&deploy_tx_hash)?.ok_or(format!(
+                "ctr data (wasm) does not exist, ctr_addr: {}",
+                ctr_addr
+            ))?;
+
+            ctrs.push(CtrSnapshot {
+                ctr_addr,
+                deploy_tx_hash,
+                wasm,
+                ctr_state,
+            });
+        }
+
+        // A block written in the middle of the export would leave us with
+        // state that does not belong to the exported height
+        if self.get_latest_block_height()? != Some(latest_block_height) {
+            return Err(format!(
+                "Ledger has advanced while exporting a snapshot, \
+                block_height: {}",
+                latest_block_height
+            )
+            .into());
+        }
+
+        Ok((merkle_nodes, cms, sns, ctrs))
+    }
+
+    // Merkle nodes and the latest contract states are overwritten by each
+    // block, hence the state at an earlier height is put together out of
+    // what is kept as it is, i.e. the cms in order, the txs of each block and
+    // the contract states by height
+    fn get_state_at(
+        &self,
+        blocks: &[Block],
+    ) -> Result<SnapshotState, LedgerError> {
+        let block = blocks.last().ok_or("Snapshot has no blocks")?;
+
+        let tx_hashes: HashSet<&TxHash> =
+            blocks.iter().flat_map(|b| b.tx_hashes.iter()).collect();
+
+        let hasher = Hasher::new();
+
+        let merkle_rt_loc = format!("{}_0", MERKLE_TREE_HEIGHT);
+
+        // Cms are appended in the order they were written until the merkle
+        // root is the one of the block
+        let mut merkle_update = MerkleUpdate::new();
+        let mut cms: Vec<(CmIdx, Cm)> = vec![];
+
+        for (cm_idx, cm) in self.get_cms()? {
+            if merkle_update.get(&merkle_rt_loc) == Some(&block.merkle_rt) {
+                break;
+            }
+
+            append_merkle_leaf(
+                &hasher,
+                &mut merkle_update,
+                cm_idx,
+                &cm,
+                MERKLE_TREE_HEIGHT,
+            )?;
+
+            cms.push((cm_idx, cm));
+        }
+
+        if merkle_update.get(&merkle_rt_loc) != Some(&block.merkle_rt) {
+            return Err(format!(
+                "Merkle root of the block can not be rebuilt out of the cms, \
+                block_height: {}",
+                block.block_height,
+            )
+            .into());
+        }
+
+        let mut merkle_nodes: Vec<(String, [u8; 32])> =
+            merkle_update.into_iter().collect();
+
+        merkle_nodes.sort();
+
+        let sns: Vec<(Sn, TxHash)> = self
+            .get_sns()?
+            .into_iter()
+            .filter(|(_, tx_hash)| tx_hashes.contains(tx_hash))
+            .collect();
+
+        let mut ctrs = vec![];
+
+        for (ctr_addr, _) in self.get_ctr_states()? {
+            let deploy_tx_hash =
+                self.get_tx_hash_by_ctr_addr(&ctr_addr)?.ok_or(format!(
+                    "Deploying tx hash does not exist, ctr_addr: {}",
+                    ctr_addr
+                ))?;
+
+            // Deployed after the height
+            if !tx_hashes.contains(&deploy_tx_hash) {
+                continue;
+            }
+
+            self.check_ctr_state_pruned(&ctr_addr, &block.block_height)?;
+
+            let ctr_state = self
+                .get_ctr_state_at(&ctr_addr, &block.block_height)?
+                .ok_or(format!(
+                    "Contract state at the height is not kept, \
+                    ctr_addr: {}, block_height: {}",
+                    ctr_addr, block.block_height,
+                ))?;
+
+            let wasm = self.get_data(&deploy_tx_hash)?.ok_or(format!(
+                "ctr data (wasm) does not exist, ctr_addr: {}",
+                ctr_addr
+            ))?;
+
+            ctrs.push(CtrSnapshot {
+                ctr_addr,
+                deploy_tx_hash,
+                wasm,
+                ctr_state,
+            });
+        }
+
+        Ok((merkle_nodes, cms, sns, ctrs))
+    }
+}
+
+impl DistLedgerApis {
+    pub async fn export_snapshot(
+        &self,
+        block_height: Option<BlockHeight>,
+    ) -> Result<LedgerSnapshot, LedgerError> {
+        self.ledger_db.export_snapshot(block_height)
+    }
+}
@@ -0,0 +1,81 @@
+use super::LedgerSnapshot;
+use crate::{LedgerDB, LedgerError};
+use colored::Colorize;
+use log::info;
+use sak_crypto::Hasher;
+use sak_kv_db::WriteBatch;
+use sak_types::{BlockHash, BlockHeight};
+
+impl LedgerDB {
+    pub fn import_snapshot(
+        &self,
+        snapshot: &LedgerSnapshot,
+        trusted_block_hash: &BlockHash,
+    ) -> Result<BlockHeight, LedgerError> {
+        if let Some(h) = self.get_latest_block_height()? {
+            return Err(format!(
+                "Snapshot can only be imported into an empty ledger, \
+                latest block height: {}",
+                h
+            )
+            .into());
+        }
+
+        let hasher = Hasher::new();
+
+        snapshot.verify(&hasher, trusted_block_hash)?;
+
+        let mut batch = WriteBatch::default();
+
+        for block in &snapshot.blocks {
+            self.batch_put_block_header(&mut batch, block)?;
+        }
+
+        for (loc, node_val) in &snapshot.merkle_nodes {
+            self.batch_put_merkle_node(&mut batch, loc, node_val)?;
+        }
+
+        for (cm_idx, cm) in &snapshot.cms {
+            self.batch_put_cm_cm_idx(&mut batch, cm, cm_idx)?;
+            self.batch_put_cm_idx_cm(&mut batch, cm_idx, cm)?;
+        }
+
+        for (sn, tx_hash) in &snapshot.sns {
+            self.batch_put_tx_hash_by_sn(&mut batch, sn, tx_hash)?;
+        }
+
+        for ctr in &snapshot.ctrs {
+            self.batch_put_tx_hash_by_contract_addr(
+                &mut batch,
+                &ctr.ctr_addr,
+                &ctr.deploy_tx_hash,
+            )?;
+
+            self.batch_put_data(&mut batch, &ctr.deploy_tx_hash, &ctr.wasm)?;
+
+            self.batch_put_ctr_state(
+                &mut batch,
+                &ctr.ctr_addr,
+                &ctr.ctr_state,
+            )?;
+
+            // History before the snapshot is not there to be queried
+            self.batch_put_ctr_state_at(
+                &mut batch,
+                &ctr.ctr_addr,
+                &snapshot.block_height,
+                &ctr.ctr_state,
+            )?;
+        }
+
+        self.db.write(batch)?;
+
+        info!(
+            "Imported ledger snapshot, block_height: {}, block_hash: {}",
+            snapshot.block_height,
+            snapshot.block_hash.green(),
+        );
+
+        Ok(snapshot.block_height)
+    }
+}
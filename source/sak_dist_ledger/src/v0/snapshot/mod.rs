@@ -0,0 +1,5 @@
+mod export;
+mod import;
+mod snapshot;
+
+pub use snapshot::*;
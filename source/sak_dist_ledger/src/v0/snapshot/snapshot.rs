@@ -0,0 +1,183 @@
+use crate::{make_merkle_update_from_leaves, LedgerError, MERKLE_TREE_HEIGHT};
+use sak_contract_std::Storage;
+use sak_crypto::Hasher;
+use sak_types::{
+    Block, BlockHash, BlockHeight, Cm, CmIdx, CtrAddr, Sn, TxHash,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// Snapshot of the ledger at a given block height. Holds what a node needs
+// to keep validating and writing blocks without replaying history from the
+// genesis block. Tx bodies are not included, except for the wasm of the
+// contract deploying txs.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LedgerSnapshot {
+    pub block_height: BlockHeight,
+    pub block_hash: BlockHash,
+    pub blocks: Vec<Block>,
+    pub merkle_nodes: Vec<(String, [u8; 32])>,
+    pub cms: Vec<(CmIdx, Cm)>,
+    pub sns: Vec<(Sn, TxHash)>,
+    pub ctrs: Vec<CtrSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CtrSnapshot {
+    pub ctr_addr: CtrAddr,
+    pub deploy_tx_hash: TxHash,
+    #[serde(with = "serde_bytes")]
+    pub wasm: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub ctr_state: Storage,
+}
+
+impl LedgerSnapshot {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, LedgerError> {
+        let bytes = serde_json::to_vec(self)?;
+
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<LedgerSnapshot, LedgerError> {
+        let snapshot = serde_json::from_slice(bytes)?;
+
+        Ok(snapshot)
+    }
+
+    // Contract states can not be checked against block headers (there is no
+    // state root yet). They are as trustworthy as the source of the snapshot
+    pub(crate) fn verify(
+        &self,
+        hasher: &Hasher,
+        trusted_block_hash: &BlockHash,
+    ) -> Result<(), LedgerError> {
+        if &self.block_hash != trusted_block_hash {
+            return Err(format!(
+                "Snapshot block hash is not the trusted one, \
+                snapshot: {}, trusted: {}",
+                self.block_hash, trusted_block_hash,
+            )
+            .into());
+        }
+
+        if self.blocks.len() as u128 != self.block_height + 1 {
+            return Err(format!(
+                "Snapshot should hold every block up to the height, \
+                block_height: {}, block count: {}",
+                self.block_height,
+                self.blocks.len(),
+            )
+            .into());
+        }
+
+        for (idx, block) in self.blocks.iter().enumerate() {
+            if block.block_height != idx as u128 {
+                return Err(format!(
+                    "Block is out of order, expected height: {}, \
+                    block_height: {}",
+                    idx, block.block_height,
+                )
+                .into());
+            }
+
+            let recomputed_block = Block::new(
+                block.validator_sig.clone(),
+                block.tx_hashes.clone(),
+                block.witness_sigs.clone(),
+                block.created_at.clone(),
+                block.block_height,
+                block.merkle_rt,
+            );
+
+            if recomputed_block.get_block_hash() != block.get_block_hash() {
+                return Err(format!(
+                    "Block hash does not match its contents, \
+                    block_height: {}, block_hash: {}",
+                    block.block_height,
+                    block.get_block_hash(),
+                )
+                .into());
+            }
+        }
+
+        let last_block = self.blocks.last().ok_or("Snapshot has no blocks")?;
+
+        if last_block.get_block_hash() != &self.block_hash {
+            return Err(format!(
+                "Last block of the snapshot is not the trusted one, \
+                block_hash: {}",
+                last_block.get_block_hash(),
+            )
+            .into());
+        }
+
+        let mut leaves = BTreeMap::new();
+
+        for (loc, node_val) in &self.merkle_nodes {
+            if let Some(("0", idx)) = loc.split_once('_') {
+                let idx: CmIdx = idx.parse()?;
+
+                leaves.insert(idx, *node_val);
+            }
+        }
+
+        let merkle_update = make_merkle_update_from_leaves(
+            hasher,
+            &leaves,
+            MERKLE_TREE_HEIGHT,
+        )?;
+
+        if merkle_update.len() != self.merkle_nodes.len() {
+            return Err(format!(
+                "Merkle node count does not match the leaves, \
+                expected: {}, merkle node count: {}",
+                merkle_update.len(),
+                self.merkle_nodes.len(),
+            )
+            .into());
+        }
+
+        for (loc, node_val) in &self.merkle_nodes {
+            match merkle_update.get(loc) {
+                Some(n) if n == node_val => (),
+                _ => {
+                    return Err(format!(
+                        "Merkle node does not match the leaves, loc: {}",
+                        loc
+                    )
+                    .into());
+                }
+            };
+        }
+
+        let merkle_rt_loc = format!("{}_0", MERKLE_TREE_HEIGHT);
+
+        match merkle_update.get(&merkle_rt_loc) {
+            Some(rt) if rt == &last_block.merkle_rt => (),
+            _ => {
+                return Err(format!(
+                    "Merkle root does not match the one of the trusted block"
+                )
+                .into());
+            }
+        };
+
+        // Each cm has to be the leaf at its own index, or the cm idx found
+        // by it would not be the one a merkle proof is made at
+        for (cm_idx, cm) in &self.cms {
+            match leaves.get(cm_idx) {
+                Some(leaf) if leaf == cm => (),
+                _ => {
+                    return Err(format!(
+                        "Cm is not the leaf at its index, cm_idx: {}",
+                        cm_idx
+                    )
+                    .into());
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
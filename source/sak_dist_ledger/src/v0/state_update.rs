@@ -1,9 +1,72 @@
+use crate::LedgerError;
 use sak_contract_std::Storage;
-use sak_types::CtrAddr;
-use std::collections::HashMap;
+use sak_crypto::Hasher;
+use sak_types::{Cm, CmIdx, CtrAddr};
+use std::collections::{BTreeMap, HashMap};
+use type_extension::U8Array;
 
 pub(crate) type CtrStateUpdate = HashMap<CtrAddr, Storage>;
 
 pub(crate) type MerkleUpdate = HashMap<MerkleNodeLoc, [u8; 32]>;
 
 pub(crate) type MerkleNodeLoc = String;
+
+// Appends a leaf and updates its ancestors the same way blocks are written,
+// i.e. a parent node is hashed as (sibling, updated child). Nodes missing in
+// `merkle_update` are regarded as empty.
+pub(crate) fn append_merkle_leaf(
+    hasher: &Hasher,
+    merkle_update: &mut MerkleUpdate,
+    cm_idx: CmIdx,
+    cm: &Cm,
+    tree_height: usize,
+) -> Result<(), LedgerError> {
+    let empty_node = U8Array::new_empty_32();
+
+    let mut curr_idx = cm_idx;
+    let mut curr_node = *cm;
+
+    merkle_update.insert(format!("{}_{}", 0, curr_idx), curr_node);
+
+    for height in 0..tree_height {
+        let sibling_idx = if curr_idx % 2 == 0 {
+            curr_idx + 1
+        } else {
+            curr_idx - 1
+        };
+
+        let sibling_node =
+            match merkle_update.get(&format!("{}_{}", height, sibling_idx)) {
+                Some(n) => *n,
+                None => empty_node,
+            };
+
+        curr_node = hasher.mimc(&sibling_node, &curr_node)?.to_bytes();
+        curr_idx = sak_proofs::get_parent_idx(curr_idx);
+
+        merkle_update.insert(format!("{}_{}", height + 1, curr_idx), curr_node);
+    }
+
+    Ok(())
+}
+
+// Rebuilds every merkle node out of the leaves, which are appended in order
+pub(crate) fn make_merkle_update_from_leaves(
+    hasher: &Hasher,
+    leaves: &BTreeMap<CmIdx, Cm>,
+    tree_height: usize,
+) -> Result<MerkleUpdate, LedgerError> {
+    let mut merkle_update = MerkleUpdate::new();
+
+    for (cm_idx, cm) in leaves {
+        append_merkle_leaf(
+            hasher,
+            &mut merkle_update,
+            *cm_idx,
+            cm,
+            tree_height,
+        )?;
+    }
+
+    Ok(merkle_update)
+}
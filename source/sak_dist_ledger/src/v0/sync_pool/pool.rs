@@ -89,6 +89,10 @@ impl SyncPool {
         &self,
         tc: TxCandidate,
     ) -> Result<TxHash, String> {
+        if let Err(err) = tc.verify_author_sig() {
+            return Err(format!("Not a validly signed tx, err: {}", err));
+        }
+
         {
             // Check if tx is valid ctr deploying type
             // let (tx_ctr_op, tx_coin_op) = tc.get_tx_op();
@@ -168,6 +172,12 @@ impl SyncPool {
         tx_pool
     }
 
+    pub(crate) async fn get_tx_hashes(&self) -> Vec<TxHash> {
+        let tx_map_lock = self.tx_map.read().await;
+
+        tx_map_lock.keys().map(|k| k.clone()).collect()
+    }
+
     pub(crate) async fn contains_tx(&self, tx_hash: &String) -> bool {
         let tx_map_lock = self.tx_map.read().await;
 
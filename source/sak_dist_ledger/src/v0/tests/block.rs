@@ -1,4 +1,7 @@
 use super::{test_util::TestUtil, utils};
+use crate::{BlockListPage, InvalidBlockError, PageDirection};
+use sak_contract_std::{CtrCallType, CtrRequest};
+use sak_kv_db::WriteBatch;
 use sak_types::{Block, BlockCandidate, TxCandidate};
 
 #[tokio::test(flavor = "multi_thread")]
@@ -82,6 +85,78 @@ async fn test_deploy_ctr_and_invoke_query_when_dist_ledger_writes_new_blocks() {
         .expect("Block_2 must be written");
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_ctr_at_past_block_height() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let ctr_addr = utils::make_dummy_genesis_block_1().tx_candidates[0]
+        .get_ctr_addr()
+        .to_string();
+
+    let genesis_state = dist_ledger
+        .apis
+        .get_ctr_state(&ctr_addr)
+        .await
+        .unwrap()
+        .expect("Contract state should exist");
+
+    dist_ledger
+        .apis
+        .write_block(utils::make_dummy_block_candidate_1())
+        .await
+        .expect("Block should be written");
+
+    // As if the contract got executed at block height 1
+    let next_state = b"{\"validators\":[]}".to_vec();
+
+    {
+        let ledger_db = &dist_ledger.apis.ledger_db;
+
+        let mut batch = WriteBatch::default();
+
+        ledger_db
+            .batch_put_ctr_state_at(&mut batch, &ctr_addr, &1, &next_state)
+            .unwrap();
+
+        ledger_db.db.write(batch).unwrap();
+    }
+
+    let apis = &dist_ledger.apis;
+
+    assert_eq!(
+        apis.get_ctr_state_at(&ctr_addr, &0).await.unwrap(),
+        Some(genesis_state),
+    );
+    assert_eq!(
+        apis.get_ctr_state_at(&ctr_addr, &1).await.unwrap(),
+        Some(next_state),
+    );
+    assert!(apis.get_ctr_state_at(&ctr_addr, &2).await.is_err());
+
+    assert_eq!(
+        apis.get_ctr_state_at(&String::from("no_such_ctr"), &1)
+            .await
+            .unwrap(),
+        None,
+    );
+
+    let request = CtrRequest {
+        req_type: "get_validator".to_string(),
+        args: vec![],
+        ctr_call_type: CtrCallType::Query,
+    };
+
+    let validator = apis
+        .query_ctr(&ctr_addr, request, Some(0))
+        .await
+        .expect("Contract should be queried at the genesis block");
+
+    assert!(!validator.is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_sequential_write_block() {
     sak_test_utils::init_test_log();
@@ -104,6 +179,72 @@ async fn test_sequential_write_block() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_block_list_pages() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    for i in 0..5 as u64 {
+        let block = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![sak_types::mock_pour_tc_m1_to_p3_p4()],
+            witness_sigs: vec![String::from("1"), String::from("2")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger.apis.write_block(Some(block)).await.unwrap();
+    }
+
+    let heights = |page: &BlockListPage| -> Vec<u128> {
+        page.block_list.iter().map(|b| b.block_height).collect()
+    };
+
+    // Latest block height is 5
+    let page = dist_ledger
+        .apis
+        .get_block_list(None, PageDirection::Desc, Some(4))
+        .await
+        .unwrap();
+
+    assert_eq!(heights(&page), vec![5, 4, 3, 2]);
+    assert_eq!(page.next, Some(1));
+
+    let page = dist_ledger
+        .apis
+        .get_block_list(page.next, PageDirection::Desc, Some(4))
+        .await
+        .unwrap();
+
+    assert_eq!(heights(&page), vec![1, 0]);
+    assert_eq!(page.next, None);
+
+    let page = dist_ledger
+        .apis
+        .get_block_list(Some(3), PageDirection::Asc, Some(2))
+        .await
+        .unwrap();
+
+    assert_eq!(heights(&page), vec![3, 4]);
+    assert_eq!(page.next, Some(5));
+
+    let page = dist_ledger
+        .apis
+        .get_block_list(Some(6), PageDirection::Asc, None)
+        .await
+        .unwrap();
+
+    assert!(page.block_list.is_empty());
+    assert_eq!(page.next, None);
+
+    dist_ledger
+        .apis
+        .get_block_list(None, PageDirection::Asc, Some(0))
+        .await
+        .expect_err("Empty page should not be requested");
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_sequential_write_block_and_get_tx_height() {
     sak_test_utils::init_test_log();
@@ -209,6 +350,49 @@ async fn test_sequential_sync_block_if_block_is_correct() {
     assert_eq!(latest_block_height, repeat - 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sync_of_a_block_already_persisted_is_not_an_invalid_block() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let block = Block::new(
+        String::from("validator_sig"),
+        vec![String::from("tx_hashes")],
+        vec![String::from("witness_sigs")],
+        String::from("1"),
+        1,
+        [0; 32],
+    );
+
+    dist_ledger
+        .apis
+        .sync_block(block, utils::make_dummy_txs())
+        .await
+        .unwrap();
+
+    // As another peer would relay it
+    let block = dist_ledger
+        .apis
+        .get_block_by_height(&1)
+        .await
+        .unwrap()
+        .expect("Block should have been persisted");
+
+    let txs = dist_ledger.apis.get_txs(&block.tx_hashes).await.unwrap();
+
+    let err = dist_ledger
+        .apis
+        .sync_block(block, txs)
+        .await
+        .expect_err("Block should not be persisted twice");
+
+    assert!(!InvalidBlockError::is_invalid_block(&err));
+
+    assert_eq!(dist_ledger.apis.get_latest_block_height().unwrap(), Some(1));
+}
+
 #[test]
 fn deserialize_test() {
     let v = [
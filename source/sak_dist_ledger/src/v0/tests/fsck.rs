@@ -0,0 +1,78 @@
+use super::utils;
+use crate::cfs;
+use sak_kv_db::WriteBatch;
+use sak_types::BlockCandidate;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_check_integrity_of_ledger() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    for i in 0..3 as u8 {
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![
+                sak_types::new_dummy_valid_pour(
+                    vec![i, 1],
+                    [i + 10; 32],
+                    [i + 20; 32],
+                    [i + 30; 32],
+                    [0; 32],
+                ),
+                sak_types::new_dummy_valid_pour(
+                    vec![i, 2],
+                    [i + 40; 32],
+                    [i + 50; 32],
+                    [i + 60; 32],
+                    [0; 32],
+                ),
+            ],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    let report = dist_ledger
+        .apis
+        .check_integrity()
+        .await
+        .expect("Integrity check should be done");
+
+    assert!(
+        report.is_consistent(),
+        "Ledger should be consistent, inconsistencies: {:?}",
+        report.inconsistencies
+    );
+    assert_eq!(report.latest_block_height, Some(3));
+    assert_eq!(report.block_count, 4);
+    assert_eq!(report.cm_count, 14);
+
+    let ledger_db = &dist_ledger.apis.ledger_db;
+
+    let mut batch = WriteBatch::default();
+
+    ledger_db
+        .batch_put_merkle_node(&mut batch, &String::from("0_3"), &[7; 32])
+        .unwrap();
+
+    ledger_db.db.write(batch).unwrap();
+
+    let report = dist_ledger
+        .apis
+        .check_integrity()
+        .await
+        .expect("Integrity check should be done");
+
+    assert!(!report.is_consistent());
+    assert!(report
+        .inconsistencies
+        .iter()
+        .any(|i| i.cf == cfs::MERKLE_NODE && i.key == "0_3"));
+}
@@ -0,0 +1,150 @@
+use super::{test_util::TestUtil, utils};
+use crate::{cfs, keys, LedgerDB, LEDGER_SCHEMA_VERSION};
+use sak_kv_db::WriteBatch;
+use sak_types::BlockCandidate;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_new_ledger_has_latest_schema_version() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_migration_1"]);
+
+    let ledger_db = LedgerDB::init(&String::from("test_migration_1"))
+        .await
+        .expect("Ledger db should be initialized");
+
+    assert_eq!(
+        ledger_db.get_schema_version().unwrap(),
+        Some(LEDGER_SCHEMA_VERSION),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_migrate_unversioned_ledger() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_migration_2"]);
+
+    let dist_ledger =
+        utils::make_dist_ledger_with_app_prefix("test_migration_2").await;
+
+    let cm_1: [u8; 32] = [31; 32];
+    let cm_2: [u8; 32] = [32; 32];
+    let cm_3: [u8; 32] = [33; 32];
+    let cm_4: [u8; 32] = [34; 32];
+
+    let bc = BlockCandidate {
+        validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+        tx_candidates: vec![
+            sak_types::new_dummy_valid_pour(
+                vec![1],
+                [11; 32],
+                cm_1,
+                cm_2,
+                [0; 32],
+            ),
+            sak_types::new_dummy_valid_pour(
+                vec![2],
+                [12; 32],
+                cm_3,
+                cm_4,
+                [0; 32],
+            ),
+        ],
+        witness_sigs: vec![String::from("1")],
+        created_at: String::from("1"),
+    };
+
+    dist_ledger
+        .apis
+        .write_block(Some(bc))
+        .await
+        .expect("Block should be written");
+
+    let cm_idx_3 = {
+        let ledger_db = &dist_ledger.apis.ledger_db;
+
+        let cm_idx_3 = ledger_db.get_cm_idx_by_cm(&cm_3).unwrap().unwrap();
+
+        // Make the ledger look like the ones written before versioning,
+        // in which the cms of the second tx overlap with the first one's
+        let mut batch = WriteBatch::default();
+
+        ledger_db
+            .batch_put_cm_cm_idx(&mut batch, &cm_3, &(cm_idx_3 - 1))
+            .unwrap();
+        ledger_db
+            .batch_put_cm_cm_idx(&mut batch, &cm_4, &cm_idx_3)
+            .unwrap();
+        ledger_db
+            .batch_put_cm_idx_cm(&mut batch, &(cm_idx_3 - 1), &cm_3)
+            .unwrap();
+        ledger_db
+            .batch_put_cm_idx_cm(&mut batch, &cm_idx_3, &cm_4)
+            .unwrap();
+        ledger_db
+            .batch_delete_cm_idx_cm(&mut batch, &(cm_idx_3 + 1))
+            .unwrap();
+
+        let cf = ledger_db
+            .make_cf_handle(&ledger_db.db, cfs::SCHEMA_VERSION)
+            .unwrap();
+        batch.delete_cf(&cf, keys::SINGLETON);
+
+        // Tx indexes did not exist either
+        let cf = ledger_db
+            .make_cf_handle(&ledger_db.db, cfs::TX_HASH_BY_CM)
+            .unwrap();
+        batch.delete_cf(&cf, &cm_3);
+
+        ledger_db.db.write(batch).unwrap();
+
+        ledger_db.db.create_cf("tx_height").unwrap();
+
+        cm_idx_3
+    };
+
+    drop(dist_ledger);
+
+    let ledger_db = LedgerDB::init(&String::from("test_migration_2"))
+        .await
+        .expect("Ledger db should be migrated");
+
+    assert_eq!(
+        ledger_db.get_schema_version().unwrap(),
+        Some(LEDGER_SCHEMA_VERSION),
+    );
+
+    assert_eq!(ledger_db.get_cm_idx_by_cm(&cm_3).unwrap(), Some(cm_idx_3));
+    assert_eq!(
+        ledger_db.get_cm_idx_by_cm(&cm_4).unwrap(),
+        Some(cm_idx_3 + 1),
+    );
+    assert_eq!(ledger_db.get_latest_cm_idx().unwrap(), Some(cm_idx_3 + 1));
+
+    assert!(ledger_db.get_tx_hash_by_cm(&cm_3).unwrap().is_some());
+
+    assert!(!ledger_db.db.cf_exists("tx_height"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_refuse_ledger_of_newer_schema_version() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_migration_3"]);
+
+    {
+        let ledger_db = LedgerDB::init(&String::from("test_migration_3"))
+            .await
+            .expect("Ledger db should be initialized");
+
+        let mut batch = WriteBatch::default();
+
+        ledger_db
+            .batch_put_schema_version(&mut batch, LEDGER_SCHEMA_VERSION + 1)
+            .unwrap();
+
+        ledger_db.db.write(batch).unwrap();
+    }
+
+    assert!(LedgerDB::init(&String::from("test_migration_3"))
+        .await
+        .is_err());
+}
@@ -1,5 +1,9 @@
 mod block;
+mod fsck;
+mod migration;
 mod others;
+mod pruning;
+mod snapshot;
 mod test_util;
 mod tx;
 mod utils;
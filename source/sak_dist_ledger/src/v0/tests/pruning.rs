@@ -0,0 +1,180 @@
+use super::utils;
+use crate::LedgerPrunedError;
+use sak_kv_db::WriteBatch;
+use sak_types::BlockCandidate;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prune_tx_bodies_of_old_blocks() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    for i in 0..3 as u8 {
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![sak_types::new_dummy_valid_pour(
+                vec![i, 1],
+                [i + 10; 32],
+                [i + 20; 32],
+                [i + 30; 32],
+                [0; 32],
+            )],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    let ledger_db = &dist_ledger.apis.ledger_db;
+
+    let cms_before = ledger_db.get_cms().unwrap();
+
+    let pruned_block_height =
+        ledger_db.prune(2).expect("Ledger should be pruned");
+
+    assert_eq!(pruned_block_height, Some(1));
+    assert_eq!(ledger_db.prune(2).unwrap(), None);
+
+    let get_tx_hashes = |block_height: u128| {
+        let block_hash = ledger_db
+            .get_block_hash_by_block_height(&block_height)
+            .unwrap()
+            .expect("Block hash should exist");
+
+        ledger_db.get_tx_hashes(&block_hash).unwrap().unwrap()
+    };
+
+    let pruned_tx_hash = &get_tx_hashes(1)[0];
+
+    let err = dist_ledger
+        .apis
+        .get_tx(pruned_tx_hash)
+        .await
+        .expect_err("Pruned tx should not be returned");
+
+    assert!(LedgerPrunedError::is_pruned(&err));
+
+    let kept_tx_hash = &get_tx_hashes(3)[0];
+
+    let tx = dist_ledger
+        .apis
+        .get_tx(kept_tx_hash)
+        .await
+        .expect("Tx should be returned")
+        .expect("Tx should exist");
+
+    assert_eq!(tx.get_tx_hash(), kept_tx_hash);
+
+    // Nullifiers and the commitment tree are not pruned
+    let sn = ledger_db.get_sn_1(pruned_tx_hash).unwrap().unwrap();
+
+    assert_eq!(
+        ledger_db
+            .get_tx_hash_by_sn(&ledger_db.db, &sn)
+            .unwrap()
+            .as_ref(),
+        Some(pruned_tx_hash),
+    );
+
+    assert_eq!(ledger_db.get_cms().unwrap(), cms_before);
+
+    let block = dist_ledger
+        .apis
+        .get_block_by_height(&1)
+        .await
+        .expect("Block should be returned")
+        .expect("Block header should be kept");
+
+    assert_eq!(&block.tx_hashes[0], pruned_tx_hash);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_prune_ctr_states_older_than_the_pruned_height() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let ctr_addr = utils::make_dummy_genesis_block_1().tx_candidates[0]
+        .get_ctr_addr()
+        .to_string();
+
+    for i in 0..3 as u8 {
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![sak_types::new_dummy_valid_pour(
+                vec![i, 1],
+                [i + 10; 32],
+                [i + 20; 32],
+                [i + 30; 32],
+                [0; 32],
+            )],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    let ledger_db = &dist_ledger.apis.ledger_db;
+
+    // As if the contract got executed at block height 1
+    let state_1 = b"{\"validators\":[]}".to_vec();
+
+    {
+        let mut batch = WriteBatch::default();
+
+        ledger_db
+            .batch_put_ctr_state_at(&mut batch, &ctr_addr, &1, &state_1)
+            .unwrap();
+
+        ledger_db.db.write(batch).unwrap();
+    }
+
+    assert!(ledger_db.get_ctr_state_at(&ctr_addr, &0).unwrap().is_some());
+
+    let pruned_block_height =
+        ledger_db.prune(1).expect("Ledger should be pruned");
+
+    assert_eq!(pruned_block_height, Some(2));
+
+    // Newest one at or below the pruned height is the state as of it
+    assert_eq!(
+        dist_ledger
+            .apis
+            .get_ctr_state_at(&ctr_addr, &2)
+            .await
+            .unwrap(),
+        Some(state_1.clone()),
+    );
+
+    assert_eq!(
+        dist_ledger
+            .apis
+            .get_ctr_state_at(&ctr_addr, &3)
+            .await
+            .unwrap(),
+        Some(state_1),
+    );
+
+    let err = dist_ledger
+        .apis
+        .get_ctr_state_at(&ctr_addr, &1)
+        .await
+        .expect_err("State below the pruned height should not be returned");
+
+    assert!(LedgerPrunedError::is_pruned(&err));
+
+    // Genesis state has been deleted
+    assert_eq!(ledger_db.get_ctr_state_at(&ctr_addr, &0).unwrap(), None);
+
+    assert!(ledger_db.get_ctr_state(&ctr_addr).unwrap().is_some());
+}
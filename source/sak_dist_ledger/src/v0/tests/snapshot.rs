@@ -0,0 +1,193 @@
+use super::{test_util::TestUtil, utils};
+use crate::{LedgerDB, LedgerSnapshot};
+use sak_types::BlockCandidate;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_and_import_ledger_snapshot() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_snapshot_1", "test_snapshot_1_import"]);
+
+    let dist_ledger =
+        utils::make_dist_ledger_with_app_prefix("test_snapshot_1").await;
+
+    for i in 0..3 as u64 {
+        let cm: [u8; 32] = [i as u8 + 1; 32];
+
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![sak_types::mock_pour_tc_variant_cm(cm)],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    let snapshot = dist_ledger
+        .apis
+        .export_snapshot(None)
+        .await
+        .expect("Snapshot should be exported");
+
+    let snapshot = {
+        let bytes = snapshot.to_bytes().unwrap();
+
+        LedgerSnapshot::from_bytes(&bytes).unwrap()
+    };
+
+    let (latest_height, latest_hash) = dist_ledger
+        .apis
+        .get_latest_block_hash()
+        .await
+        .unwrap()
+        .expect("Latest block should exist");
+
+    assert_eq!(snapshot.block_height, latest_height);
+    assert_eq!(snapshot.block_hash, latest_hash);
+
+    let ledger_db = LedgerDB::init(&String::from("test_snapshot_1_import"))
+        .await
+        .expect("Ledger db should be initialized");
+
+    ledger_db
+        .import_snapshot(&snapshot, &latest_hash)
+        .expect("Snapshot should be imported");
+
+    assert_eq!(
+        ledger_db.get_latest_block_height().unwrap(),
+        Some(latest_height),
+    );
+
+    assert_eq!(
+        ledger_db.get_latest_cm_idx().unwrap(),
+        dist_ledger.apis.ledger_db.get_latest_cm_idx().unwrap(),
+    );
+
+    let ctr_addr = String::from("test_validator_1");
+
+    assert_eq!(
+        ledger_db.get_ctr_state(&ctr_addr).unwrap(),
+        dist_ledger.apis.get_ctr_state(&ctr_addr).await.unwrap(),
+    );
+
+    assert!(ledger_db.import_snapshot(&snapshot, &latest_hash).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_ledger_snapshot_at_an_earlier_height() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_snapshot_3", "test_snapshot_3_import"]);
+
+    let dist_ledger =
+        utils::make_dist_ledger_with_app_prefix("test_snapshot_3").await;
+
+    for i in 0..3 as u64 {
+        let cm: [u8; 32] = [i as u8 + 1; 32];
+
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![sak_types::mock_pour_tc_variant_cm(cm)],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    assert!(dist_ledger.apis.export_snapshot(Some(4)).await.is_err());
+
+    let snapshot = dist_ledger
+        .apis
+        .export_snapshot(Some(2))
+        .await
+        .expect("Snapshot should be exported");
+
+    let block_hash = dist_ledger
+        .apis
+        .ledger_db
+        .get_block_hash_by_block_height(&2)
+        .unwrap()
+        .expect("Block hash at height 2 should exist");
+
+    assert_eq!(snapshot.block_height, 2);
+    assert_eq!(snapshot.block_hash, block_hash);
+    assert_eq!(snapshot.blocks.len(), 3);
+
+    let ledger_db = LedgerDB::init(&String::from("test_snapshot_3_import"))
+        .await
+        .expect("Ledger db should be initialized");
+
+    ledger_db
+        .import_snapshot(&snapshot, &block_hash)
+        .expect("Snapshot should be imported");
+
+    assert_eq!(ledger_db.get_latest_block_height().unwrap(), Some(2));
+
+    // Two genesis mints and a pour in each of the blocks 1 and 2
+    assert_eq!(ledger_db.get_latest_cm_idx().unwrap(), Some(5));
+
+    let ctr_addr = String::from("test_validator_1");
+
+    assert_eq!(
+        ledger_db.get_ctr_state(&ctr_addr).unwrap(),
+        dist_ledger
+            .apis
+            .ledger_db
+            .get_ctr_state_at(&ctr_addr, &2)
+            .unwrap(),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_import_ledger_snapshot_with_untrusted_block_hash() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_snapshot_2", "test_snapshot_2_import"]);
+
+    let dist_ledger =
+        utils::make_dist_ledger_with_app_prefix("test_snapshot_2").await;
+
+    let mut snapshot = dist_ledger
+        .apis
+        .export_snapshot(None)
+        .await
+        .expect("Snapshot should be exported");
+
+    let ledger_db = LedgerDB::init(&String::from("test_snapshot_2_import"))
+        .await
+        .expect("Ledger db should be initialized");
+
+    let trusted_block_hash = snapshot.block_hash.clone();
+
+    assert!(ledger_db
+        .import_snapshot(&snapshot, &String::from("false hash"))
+        .is_err());
+
+    // Cms that are all leaves, but not at their own indices
+    let (cm_0, cm_1) = (snapshot.cms[0].1, snapshot.cms[1].1);
+
+    snapshot.cms[0].1 = cm_1;
+    snapshot.cms[1].1 = cm_0;
+
+    assert!(ledger_db
+        .import_snapshot(&snapshot, &trusted_block_hash)
+        .is_err());
+
+    snapshot.cms[0].1 = cm_0;
+    snapshot.cms[1].1 = cm_1;
+
+    snapshot.merkle_nodes[0].1 = [7; 32];
+
+    assert!(ledger_db
+        .import_snapshot(&snapshot, &trusted_block_hash)
+        .is_err());
+
+    assert_eq!(ledger_db.get_latest_block_height().unwrap(), None);
+}
@@ -1,7 +1,12 @@
 use super::{test_util::TestUtil, utils};
+use crate::{InvalidBlockError, TxLocation};
+use sak_contract_std::{CtrCallType, CtrRequest};
 use sak_kv_db::WriteBatch;
-use sak_types::{BlockCandidate, TxCandidate};
+use sak_types::{
+    Block, BlockCandidate, MintTxCandidate, PourTxCandidate, Tx, TxCandidate,
+};
 use std::time::Duration;
+use type_extension::U8Array;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_put_and_get_transaction() {
@@ -240,3 +245,322 @@ async fn test_dist_ledger_double_spending() {
         println!("[+] dummy pour_tx hash: {:?}", dummy_tx_hash);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_reject_unauthorized_mint_tx() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_mint_restricted_dist_ledger().await;
+
+    let mint_tc = TxCandidate::Mint(MintTxCandidate::new_dummy_2());
+
+    dist_ledger
+        .apis
+        .send_tx(mint_tc.clone())
+        .await
+        .expect_err("Unauthorized mint tx should not enter the pool");
+
+    assert!(
+        !dist_ledger
+            .apis
+            .tx_pool_contains(mint_tc.get_tx_hash())
+            .await
+    );
+
+    let block_candidate = BlockCandidate {
+        validator_sig: "validator_sig".to_string(),
+        tx_candidates: vec![mint_tc],
+        witness_sigs: vec![],
+        created_at: "created_at".to_string(),
+    };
+
+    dist_ledger
+        .apis
+        .write_block(Some(block_candidate))
+        .await
+        .expect_err("Block with an unauthorized mint tx should be rejected");
+
+    assert_eq!(dist_ledger.apis.get_latest_block_height().unwrap(), Some(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_mint_a_cm_only_once_in_a_block() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let mint_tc_1 = MintTxCandidate::new_dummy_2();
+
+    // Another tx minting the same cm
+    let mint_tc_2 = MintTxCandidate::new(
+        String::from("created_at_other"),
+        mint_tc_1.data.clone(),
+        String::new(),
+        Some(mint_tc_1.ctr_addr.clone()),
+        mint_tc_1.cm_1,
+        mint_tc_1.v,
+        mint_tc_1.k,
+        mint_tc_1.s,
+    )
+    .sign(&sak_types::get_dummy_author_secret_key());
+
+    let tcs = vec![TxCandidate::Mint(mint_tc_1), TxCandidate::Mint(mint_tc_2)];
+
+    let block_candidate = BlockCandidate {
+        validator_sig: "validator_sig".to_string(),
+        tx_candidates: tcs.clone(),
+        witness_sigs: vec![],
+        created_at: "created_at".to_string(),
+    };
+
+    dist_ledger
+        .apis
+        .write_block(Some(block_candidate))
+        .await
+        .expect_err("Block minting a cm twice should be rejected");
+
+    let (authorized_tcs, unauthorized_tcs) = dist_ledger
+        .apis
+        .drop_unauthorized_mint_txs(tcs.clone(), 1)
+        .await;
+
+    assert_eq!(authorized_tcs.len(), 1);
+    assert_eq!(authorized_tcs[0].get_tx_hash(), tcs[0].get_tx_hash());
+    assert_eq!(unauthorized_tcs.len(), 1);
+    assert_eq!(unauthorized_tcs[0].get_tx_hash(), tcs[1].get_tx_hash());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_reject_mint_tx_hiding_its_value() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let tc = MintTxCandidate::new_dummy_2();
+
+    // cm does not commit to the revealed v
+    let mint_tc = TxCandidate::Mint(MintTxCandidate::new_dummy_custom(
+        [1; 32], tc.v, tc.k, tc.s,
+    ));
+
+    dist_ledger
+        .apis
+        .send_tx(mint_tc)
+        .await
+        .expect_err("Mint tx whose cm does not match should be rejected");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_reject_tx_with_tampered_author_sig() {
+    sak_test_utils::init_test_log();
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let tc = PourTxCandidate::new_dummy_2();
+
+    // A relayer swaps the ctr_addr, keeping the author's signature
+    let tampered_tc = PourTxCandidate::new(
+        tc.created_at,
+        tc.data,
+        tc.author_sig,
+        Some(String::from("ctr_addr_relayer")),
+        tc.pi,
+        tc.sn_1,
+        tc.cm_1,
+        tc.cm_2,
+        tc.merkle_rt,
+    );
+
+    let tampered_tc = TxCandidate::Pour(tampered_tc);
+
+    dist_ledger
+        .apis
+        .send_tx(tampered_tc.clone())
+        .await
+        .expect_err("Tx with a tampered author_sig should be rejected");
+
+    assert!(
+        !dist_ledger
+            .apis
+            .tx_pool_contains(tampered_tc.get_tx_hash())
+            .await
+    );
+
+    let block = Block::new(
+        String::from("validator_sig"),
+        vec![tampered_tc.get_tx_hash().to_string()],
+        vec![],
+        String::from("created_at"),
+        1,
+        [0; 32],
+    );
+
+    let tx: Tx = tampered_tc.upgrade(0);
+
+    let err = dist_ledger
+        .apis
+        .sync_block(block, vec![tx])
+        .await
+        .expect_err("Synced block with a tampered tx should be rejected");
+
+    assert!(InvalidBlockError::is_invalid_block(&err));
+
+    assert_eq!(dist_ledger.apis.get_latest_block_height().unwrap(), Some(0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_tx_indexes() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let dist_ledger = utils::make_dist_ledger().await;
+
+    let genesis_block = utils::make_dummy_genesis_block_1();
+
+    let tx_hashes: Vec<String> = genesis_block
+        .tx_candidates
+        .iter()
+        .map(|tc| tc.get_tx_hash().to_string())
+        .collect();
+
+    let page = dist_ledger
+        .apis
+        .get_txs_by_block(&0, None, Some(1))
+        .await
+        .expect("Txs of the block should be paged");
+
+    assert_eq!(page.tx_hashes.len(), 1);
+    assert_eq!(page.tx_hashes[0].tx_hash, tx_hashes[0]);
+    assert_eq!(
+        page.next,
+        Some(TxLocation {
+            block_height: 0,
+            tx_pos: 1
+        })
+    );
+
+    let page = dist_ledger
+        .apis
+        .get_txs_by_block(&0, Some(1), Some(1))
+        .await
+        .expect("Txs of the block should be paged");
+
+    assert_eq!(page.tx_hashes.len(), 1);
+    assert_eq!(page.tx_hashes[0].tx_hash, tx_hashes[1]);
+    assert_eq!(page.next, None);
+
+    let ctr_addr = genesis_block.tx_candidates[0].get_ctr_addr();
+
+    let page = dist_ledger
+        .apis
+        .get_ctr_call_history(ctr_addr, None, None)
+        .await
+        .expect("Contract call history should be paged");
+
+    assert_eq!(page.tx_hashes.len(), 1);
+    assert_eq!(page.tx_hashes[0].tx_hash, tx_hashes[0]);
+
+    let cm = genesis_block.tx_candidates[1].get_cms()[0];
+
+    let tx = dist_ledger
+        .apis
+        .get_tx_by_cm(&cm)
+        .await
+        .expect("Tx should be found")
+        .expect("Tx should exist");
+
+    assert_eq!(tx.get_tx_hash(), &tx_hashes[1]);
+
+    assert!(dist_ledger
+        .apis
+        .get_tx_by_cm(&[0; 32])
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dist_ledger_simulate_tx() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let dist_ledger = utils::make_dist_ledger().await;
+    let apis = &dist_ledger.apis;
+
+    let ctr_addr = utils::make_dummy_genesis_block_1().tx_candidates[0]
+        .get_ctr_addr()
+        .to_string();
+
+    let prev_state = apis
+        .get_ctr_state(&ctr_addr)
+        .await
+        .unwrap()
+        .expect("Contract state should exist");
+
+    let request = CtrRequest {
+        req_type: "add_validator".to_string(),
+        args: serde_json::to_vec(&serde_json::json!({
+            "validator": "validator_simulated"
+        }))
+        .unwrap(),
+        ctr_call_type: CtrCallType::Execute,
+    };
+
+    let tc = TxCandidate::Pour(
+        PourTxCandidate::new(
+            String::from("created_at_simulated"),
+            serde_json::to_vec(&request).unwrap(),
+            String::new(),
+            Some(ctr_addr.clone()),
+            vec![0],
+            [7; 32],
+            U8Array::new_empty_32(),
+            U8Array::new_empty_32(),
+            U8Array::new_empty_32(),
+        )
+        .sign(&sak_types::get_dummy_author_secret_key()),
+    );
+
+    let simulation = apis
+        .simulate_tx(tc.clone())
+        .await
+        .expect("Tx should be simulated");
+
+    assert!(simulation.is_valid(), "errors: {:?}", simulation.errors);
+    assert_eq!(simulation.tx_hash, tc.get_tx_hash().to_string());
+    assert!(simulation.next_merkle_rt.is_some());
+    assert_eq!(simulation.state_diff.len(), 1);
+
+    let diff = &simulation.state_diff[0];
+
+    assert_eq!(diff.ctr_addr, ctr_addr);
+    assert_eq!(diff.prev_state, Some(prev_state.clone()));
+    assert!(String::from_utf8(diff.next_state.clone())
+        .unwrap()
+        .contains("validator_simulated"));
+
+    // Nothing is persisted
+    assert_eq!(
+        apis.get_ctr_state(&ctr_addr).await.unwrap(),
+        Some(prev_state)
+    );
+    assert_eq!(apis.get_latest_block_height().unwrap(), Some(0));
+    assert!(!apis.tx_pool_contains(tc.get_tx_hash()).await);
+
+    let block_candidate = utils::make_dummy_block_candidate_1().unwrap();
+    let spent_tc = block_candidate.tx_candidates[0].clone();
+
+    apis.write_block(Some(block_candidate))
+        .await
+        .expect("Block should be written");
+
+    let simulation = apis
+        .simulate_tx(spent_tc)
+        .await
+        .expect("Tx should be simulated");
+
+    assert!(simulation
+        .errors
+        .iter()
+        .any(|e| e.starts_with("Double spend")));
+}
@@ -6,12 +6,16 @@ use sak_contract_std::{CtrCallType, CtrRequest};
 use sak_crypto::{rand, Hasher, Scalar, ScalarExt};
 use sak_proofs::{CoinProof, MerkleTree, NewCoin, OldCoin, CM_TREE_DEPTH};
 use sak_types::{
-    BlockCandidate, PourTxCandidate, Tx, TxCandidate, WASM_MAGIC_NUMBER,
+    BlockCandidate, BlockHeight, MintTxCandidate, PourTxCandidate, Tx,
+    TxCandidate, WASM_MAGIC_NUMBER,
 };
 use std::collections::HashMap;
 use type_extension::U8Array;
 
-pub struct DummyPos {}
+pub struct DummyPos {
+    // Mints past the genesis block are authorized only if set
+    pub allow_mint: bool,
+}
 
 #[async_trait]
 impl Consensus for DummyPos {
@@ -22,6 +26,19 @@ impl Consensus for DummyPos {
     ) -> Result<BlockCandidate, ConsensusError> {
         return Err("awel".into());
     }
+
+    async fn authorize_mint(
+        &self,
+        _dist_ledger_apis: &DistLedgerApis,
+        _tc: &MintTxCandidate,
+        block_height: BlockHeight,
+    ) -> Result<(), ConsensusError> {
+        if block_height == 0 || self.allow_mint {
+            return Ok(());
+        }
+
+        return Err("Minting is not allowed".into());
+    }
 }
 
 #[cfg(test)]
@@ -40,14 +57,34 @@ pub(crate) fn make_dummy_genesis_block_1() -> BlockCandidate {
 }
 
 pub(crate) async fn make_dist_ledger() -> DistLedger {
-    let pos = make_dummy_pos();
+    init_dist_ledger("test", true, make_dummy_pos()).await
+}
 
+pub(crate) async fn make_dist_ledger_with_app_prefix(
+    app_prefix: &str,
+) -> DistLedger {
+    init_dist_ledger(app_prefix, false, make_dummy_pos()).await
+}
+
+pub(crate) async fn make_mint_restricted_dist_ledger() -> DistLedger {
+    let pos = Box::new(DummyPos { allow_mint: false });
+
+    init_dist_ledger("test", true, pos).await
+}
+
+async fn init_dist_ledger(
+    app_prefix: &str,
+    in_memory_db: bool,
+    pos: Box<DummyPos>,
+) -> DistLedger {
     let dist_ledger_args = DistLedgerArgs {
-        app_prefix: String::from("test"),
+        app_prefix: String::from(app_prefix),
         tx_sync_interval: None,
         genesis_block: Some(make_dummy_genesis_block_1()),
         consensus: pos,
         block_sync_interval: None,
+        in_memory_db,
+        prune_keep_blocks: None,
     };
 
     let dist_ledger = DistLedger::init(dist_ledger_args)
@@ -91,9 +128,13 @@ pub(crate) async fn make_dummy_valid_pour_tx() -> Tx {
         v: Some(proof_context.v_2),
     };
 
-    let pi =
-        CoinProof::generate_proof_1_to_2(coin_1_old, coin_1_new, coin_2_new)
-            .unwrap();
+    let pi = CoinProof::generate_proof_1_to_2(
+        coin_1_old,
+        coin_1_new,
+        coin_2_new,
+        sak_types::get_dummy_author_pk_hash(),
+    )
+    .unwrap();
 
     let pi_ser = CoinProof::serialize_pi(&pi).unwrap();
 
@@ -337,7 +378,7 @@ pub(crate) fn make_dummy_state() -> (String, String) {
 }
 
 pub(crate) fn make_dummy_pos() -> Box<DummyPos> {
-    Box::new(DummyPos {})
+    Box::new(DummyPos { allow_mint: true })
 }
 
 #[cfg(test)]
@@ -366,17 +407,20 @@ pub(crate) fn make_dummy_block_candidate_with_query_tx(
                 }
             };
 
-            TxCandidate::Pour(PourTxCandidate::new(
-                String::from("created_at_1"),
-                WASM_MAGIC_NUMBER.to_vec(),
-                String::from("author_sig_1"),
-                Some(String::from("ctr_addr_1")),
-                vec![0],
-                U8Array::new_empty_32(),
-                U8Array::new_empty_32(),
-                U8Array::new_empty_32(),
-                U8Array::new_empty_32(),
-            ))
+            TxCandidate::Pour(
+                PourTxCandidate::new(
+                    String::from("created_at_1"),
+                    WASM_MAGIC_NUMBER.to_vec(),
+                    String::new(),
+                    Some(String::from("ctr_addr_1")),
+                    vec![0],
+                    U8Array::new_empty_32(),
+                    U8Array::new_empty_32(),
+                    U8Array::new_empty_32(),
+                    U8Array::new_empty_32(),
+                )
+                .sign(&sak_types::get_dummy_author_secret_key()),
+            )
         };
 
         BlockCandidate {
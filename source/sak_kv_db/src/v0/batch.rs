@@ -0,0 +1,52 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchOp {
+    Put {
+        cf: String,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Delete {
+        cf: String,
+        key: Vec<u8>,
+    },
+}
+
+// Operations are applied atomically, in the order they are added, when the
+// batch is written by a `KVStore`
+#[derive(Debug, Default)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    pub fn put_cf<K, V>(&mut self, cf: &str, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.ops.push(BatchOp::Put {
+            cf: cf.to_string(),
+            key: key.as_ref().to_vec(),
+            value: value.as_ref().to_vec(),
+        });
+    }
+
+    pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf: &str, key: K) {
+        self.ops.push(BatchOp::Delete {
+            cf: cf.to_string(),
+            key: key.as_ref().to_vec(),
+        });
+    }
+
+    pub fn ops(&self) -> &Vec<BatchOp> {
+        &self.ops
+    }
+
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
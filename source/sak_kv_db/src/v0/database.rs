@@ -1,11 +1,14 @@
+use crate::{
+    InMemoryStore, IteratorMode, KVIterator, KVStore, KvDBError, RocksDBStore,
+    WriteBatch,
+};
 use colored::Colorize;
 use log::info;
 use rocksdb::{ColumnFamilyDescriptor, Options, DB};
 use std::path::PathBuf;
 
 pub struct KeyValueDatabase {
-    pub db_instance: DB,
-    db_path_str: String,
+    store: Box<dyn KVStore>,
 }
 
 impl KeyValueDatabase {
@@ -47,25 +50,102 @@ impl KeyValueDatabase {
             }
         };
 
-        Ok(KeyValueDatabase {
-            db_instance,
-            db_path_str,
-        })
+        let store = RocksDBStore::new(db_instance, db_path_str);
+
+        Ok(KeyValueDatabase::new_with_store(Box::new(store)))
     }
 
-    pub fn destroy(&self) -> Result<(), String> {
-        let destroy = DB::destroy(&Options::default(), &self.db_path_str);
+    pub fn new_in_memory(cf_names: Vec<&str>) -> KeyValueDatabase {
+        info!("Initialized in-memory KeyValueDatabase");
+
+        let store = InMemoryStore::new(cf_names);
+
+        KeyValueDatabase::new_with_store(Box::new(store))
+    }
+
+    pub fn new_with_store(store: Box<dyn KVStore>) -> KeyValueDatabase {
+        KeyValueDatabase { store }
+    }
+
+    pub fn get_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &str,
+        key: K,
+    ) -> Result<Option<Vec<u8>>, KvDBError> {
+        self.store.get_cf(cf, key.as_ref())
+    }
+
+    pub fn put_cf<K, V>(
+        &self,
+        cf: &str,
+        key: K,
+        value: V,
+    ) -> Result<(), KvDBError>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::default();
+
+        batch.put_cf(cf, key, value);
+
+        self.store.write(batch)
+    }
+
+    pub fn delete_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &str,
+        key: K,
+    ) -> Result<(), KvDBError> {
+        let mut batch = WriteBatch::default();
+
+        batch.delete_cf(cf, key);
+
+        self.store.write(batch)
+    }
 
-        match destroy {
+    pub fn write(&self, batch: WriteBatch) -> Result<(), KvDBError> {
+        self.store.write(batch)
+    }
+
+    pub fn iterator_cf<'a>(
+        &'a self,
+        cf: &str,
+        mode: IteratorMode,
+    ) -> Result<KVIterator<'a>, KvDBError> {
+        self.store.iterator_cf(cf, mode)
+    }
+
+    pub fn cf_exists(&self, cf: &str) -> bool {
+        self.store.cf_exists(cf)
+    }
+
+    pub fn cf_names(&self) -> Result<Vec<String>, KvDBError> {
+        self.store.cf_names()
+    }
+
+    pub fn create_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        self.store.create_cf(cf)
+    }
+
+    pub fn drop_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        self.store.drop_cf(cf)
+    }
+
+    pub fn compact_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        self.store.compact_cf(cf)
+    }
+
+    pub fn destroy(&self) -> Result<(), String> {
+        match self.store.destroy() {
             Ok(_) => {
                 info!("Successfully destroyed db path");
 
                 Ok(())
             }
-            Err(err) => Err(format!(
-                "Error destroying KeyValueDatabase path, path: {}, err: {}",
-                self.db_path_str, err
-            )),
+            Err(err) => {
+                Err(format!("Error destroying KeyValueDatabase, err: {}", err))
+            }
         }
     }
 }
@@ -0,0 +1,210 @@
+use crate::{
+    BatchOp, Direction, IteratorMode, KVIterator, KVStore, KvDBError,
+    WriteBatch,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::sync::RwLock;
+
+type ColumnFamily = BTreeMap<Vec<u8>, Vec<u8>>;
+
+// Keeps every column family in memory. Nothing is written to disk, so the
+// data is gone once the store is dropped.
+pub struct InMemoryStore {
+    cfs: RwLock<HashMap<String, ColumnFamily>>,
+}
+
+impl InMemoryStore {
+    pub fn new(cf_names: Vec<&str>) -> InMemoryStore {
+        let cfs = cf_names
+            .into_iter()
+            .map(|n| (n.to_string(), ColumnFamily::new()))
+            .collect();
+
+        InMemoryStore {
+            cfs: RwLock::new(cfs),
+        }
+    }
+}
+
+impl KVStore for InMemoryStore {
+    fn get_cf(
+        &self,
+        cf: &str,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>, KvDBError> {
+        let cfs = self.cfs.read().map_err(|err| err.to_string())?;
+
+        let column_family = cfs
+            .get(cf)
+            .ok_or(format!("Column family does not exist, cf: {}", cf))?;
+
+        Ok(column_family.get(key).cloned())
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), KvDBError> {
+        let mut cfs = self.cfs.write().map_err(|err| err.to_string())?;
+
+        // Nothing is applied unless every column family exists
+        for op in batch.ops() {
+            let cf = match op {
+                BatchOp::Put { cf, .. } => cf,
+                BatchOp::Delete { cf, .. } => cf,
+            };
+
+            if !cfs.contains_key(cf) {
+                return Err(format!(
+                    "Column family does not exist, cf: {}",
+                    cf
+                )
+                .into());
+            }
+        }
+
+        for op in batch.ops() {
+            match op {
+                BatchOp::Put { cf, key, value } => {
+                    if let Some(c) = cfs.get_mut(cf) {
+                        c.insert(key.clone(), value.clone());
+                    }
+                }
+                BatchOp::Delete { cf, key } => {
+                    if let Some(c) = cfs.get_mut(cf) {
+                        c.remove(key);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn iterator_cf<'a>(
+        &'a self,
+        cf: &str,
+        mode: IteratorMode,
+    ) -> Result<KVIterator<'a>, KvDBError> {
+        if !self.cf_exists(cf) {
+            return Err(
+                format!("Column family does not exist, cf: {}", cf).into()
+            );
+        }
+
+        let (is_forward, from) = match mode {
+            IteratorMode::Start => (true, None),
+            IteratorMode::End => (false, None),
+            IteratorMode::From(key, Direction::Forward) => {
+                (true, Some(key.to_vec()))
+            }
+            IteratorMode::From(key, Direction::Reverse) => {
+                (false, Some(key.to_vec()))
+            }
+        };
+
+        let iter = InMemoryIterator {
+            cfs: &self.cfs,
+            cf: cf.to_string(),
+            is_forward,
+            from,
+            is_from_inclusive: true,
+        };
+
+        Ok(Box::new(iter))
+    }
+
+    fn cf_exists(&self, cf: &str) -> bool {
+        match self.cfs.read() {
+            Ok(cfs) => cfs.contains_key(cf),
+            Err(_) => false,
+        }
+    }
+
+    fn cf_names(&self) -> Result<Vec<String>, KvDBError> {
+        let cfs = self.cfs.read().map_err(|err| err.to_string())?;
+
+        Ok(cfs.keys().cloned().collect())
+    }
+
+    fn create_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        let mut cfs = self.cfs.write().map_err(|err| err.to_string())?;
+
+        cfs.entry(cf.to_string()).or_insert(ColumnFamily::new());
+
+        Ok(())
+    }
+
+    fn drop_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        let mut cfs = self.cfs.write().map_err(|err| err.to_string())?;
+
+        match cfs.remove(cf) {
+            Some(_) => Ok(()),
+            None => {
+                Err(format!("Column family does not exist, cf: {}", cf).into())
+            }
+        }
+    }
+
+    // Deleted entries are removed from the map right away, there is nothing
+    // left to reclaim
+    fn compact_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        if !self.cf_exists(cf) {
+            return Err(
+                format!("Column family does not exist, cf: {}", cf).into()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn destroy(&self) -> Result<(), KvDBError> {
+        let mut cfs = self.cfs.write().map_err(|err| err.to_string())?;
+
+        cfs.clear();
+
+        Ok(())
+    }
+}
+
+// Looks up one entry at a time past the last one it has yielded, taking the
+// read lock for each lookup only. Nothing is copied but the entries yielded,
+// and the store can be written to in the middle of an iteration. Unlike
+// RocksDB there is no snapshot, so entries written ahead of the iterator are
+// seen.
+struct InMemoryIterator<'a> {
+    cfs: &'a RwLock<HashMap<String, ColumnFamily>>,
+    cf: String,
+    is_forward: bool,
+    from: Option<Vec<u8>>,
+    is_from_inclusive: bool,
+}
+
+impl<'a> Iterator for InMemoryIterator<'a> {
+    type Item = (Box<[u8]>, Box<[u8]>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cfs = self.cfs.read().ok()?;
+
+        let column_family = cfs.get(&self.cf)?;
+
+        let from: Bound<&[u8]> = match &self.from {
+            Some(k) if self.is_from_inclusive => Bound::Included(k.as_slice()),
+            Some(k) => Bound::Excluded(k.as_slice()),
+            None => Bound::Unbounded,
+        };
+
+        let (k, v) = if self.is_forward {
+            column_family
+                .range::<[u8], _>((from, Bound::Unbounded))
+                .next()
+        } else {
+            column_family
+                .range::<[u8], _>((Bound::Unbounded, from))
+                .next_back()
+        }?;
+
+        self.from = Some(k.clone());
+        self.is_from_inclusive = false;
+
+        Some((k.clone().into_boxed_slice(), v.clone().into_boxed_slice()))
+    }
+}
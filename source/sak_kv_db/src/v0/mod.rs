@@ -1,13 +1,21 @@
+mod batch;
 pub mod database;
+mod memory;
+mod rocks;
+mod store;
 mod utils;
 
+#[cfg(test)]
+mod tests;
+
+pub use batch::*;
 pub use database::*;
+pub use memory::*;
+pub use rocks::*;
 pub use rocksdb::{
-    BoundColumnFamily, ColumnFamily, ColumnFamilyDescriptor,
-    DBIteratorWithThreadMode, DBRawIteratorWithThreadMode, DBWithThreadMode,
-    Direction, IteratorMode, MultiThreaded, Options, SingleThreaded,
-    ThreadMode, WriteBatch, DB,
+    ColumnFamilyDescriptor, Direction, IteratorMode, Options, DB,
 };
+pub use store::*;
 pub use utils::*;
 
-pub(crate) type KvDBError = Box<dyn std::error::Error + Send + Sync>;
+pub type KvDBError = Box<dyn std::error::Error + Send + Sync>;
@@ -0,0 +1,112 @@
+use crate::{
+    BatchOp, IteratorMode, KVIterator, KVStore, KvDBError, WriteBatch,
+};
+use rocksdb::{Options, DB};
+
+pub struct RocksDBStore {
+    db: DB,
+    db_path_str: String,
+}
+
+impl RocksDBStore {
+    pub fn new(db: DB, db_path_str: String) -> RocksDBStore {
+        RocksDBStore { db, db_path_str }
+    }
+
+    fn cf_handle(
+        &self,
+        cf: &str,
+    ) -> Result<std::sync::Arc<rocksdb::BoundColumnFamily<'_>>, KvDBError> {
+        match self.db.cf_handle(cf) {
+            Some(h) => Ok(h),
+            None => {
+                Err(format!("Column family does not exist, cf: {}", cf).into())
+            }
+        }
+    }
+}
+
+impl KVStore for RocksDBStore {
+    fn get_cf(
+        &self,
+        cf: &str,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>, KvDBError> {
+        let cf = self.cf_handle(cf)?;
+
+        let v = self.db.get_cf(&cf, key)?;
+
+        Ok(v)
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), KvDBError> {
+        let mut rocksdb_batch = rocksdb::WriteBatch::default();
+
+        for op in batch.ops() {
+            match op {
+                BatchOp::Put { cf, key, value } => {
+                    let cf = self.cf_handle(cf)?;
+
+                    rocksdb_batch.put_cf(&cf, key, value);
+                }
+                BatchOp::Delete { cf, key } => {
+                    let cf = self.cf_handle(cf)?;
+
+                    rocksdb_batch.delete_cf(&cf, key);
+                }
+            }
+        }
+
+        self.db.write(rocksdb_batch)?;
+
+        Ok(())
+    }
+
+    fn iterator_cf<'a>(
+        &'a self,
+        cf: &str,
+        mode: IteratorMode,
+    ) -> Result<KVIterator<'a>, KvDBError> {
+        let cf = self.cf_handle(cf)?;
+
+        let iter = self.db.iterator_cf(&cf, mode);
+
+        Ok(Box::new(iter))
+    }
+
+    fn cf_exists(&self, cf: &str) -> bool {
+        self.db.cf_handle(cf).is_some()
+    }
+
+    fn cf_names(&self) -> Result<Vec<String>, KvDBError> {
+        let cf_names = DB::list_cf(&Options::default(), self.db.path())?;
+
+        Ok(cf_names)
+    }
+
+    fn create_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        self.db.create_cf(cf, &Options::default())?;
+
+        Ok(())
+    }
+
+    fn drop_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        self.db.drop_cf(cf)?;
+
+        Ok(())
+    }
+
+    fn compact_cf(&self, cf: &str) -> Result<(), KvDBError> {
+        let cf = self.cf_handle(cf)?;
+
+        self.db.compact_range_cf(&cf, None::<&[u8]>, None::<&[u8]>);
+
+        Ok(())
+    }
+
+    fn destroy(&self) -> Result<(), KvDBError> {
+        DB::destroy(&Options::default(), &self.db_path_str)?;
+
+        Ok(())
+    }
+}
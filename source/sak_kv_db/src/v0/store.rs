@@ -0,0 +1,34 @@
+use crate::{IteratorMode, KvDBError, WriteBatch};
+
+pub type KVIterator<'a> = Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>;
+
+// Storage backend of a `KeyValueDatabase`. Keys are ordered bytewise within
+// a column family, the way RocksDB orders them by default.
+pub trait KVStore: Send + Sync {
+    fn get_cf(
+        &self,
+        cf: &str,
+        key: &[u8],
+    ) -> Result<Option<Vec<u8>>, KvDBError>;
+
+    fn write(&self, batch: WriteBatch) -> Result<(), KvDBError>;
+
+    fn iterator_cf<'a>(
+        &'a self,
+        cf: &str,
+        mode: IteratorMode,
+    ) -> Result<KVIterator<'a>, KvDBError>;
+
+    fn cf_exists(&self, cf: &str) -> bool;
+
+    fn cf_names(&self) -> Result<Vec<String>, KvDBError>;
+
+    fn create_cf(&self, cf: &str) -> Result<(), KvDBError>;
+
+    fn drop_cf(&self, cf: &str) -> Result<(), KvDBError>;
+
+    // Reclaims the space taken by deleted entries of a column family
+    fn compact_cf(&self, cf: &str) -> Result<(), KvDBError>;
+
+    fn destroy(&self) -> Result<(), KvDBError>;
+}
@@ -0,0 +1,96 @@
+use crate::{Direction, IteratorMode, KeyValueDatabase, WriteBatch};
+
+const CF_1: &str = "cf_1";
+const CF_2: &str = "cf_2";
+
+fn make_in_memory_db() -> KeyValueDatabase {
+    KeyValueDatabase::new_in_memory(vec![CF_1, CF_2])
+}
+
+#[test]
+fn test_in_memory_db_put_get_and_delete() {
+    let db = make_in_memory_db();
+
+    db.put_cf(CF_1, "key_1", "value_1").unwrap();
+
+    assert_eq!(db.get_cf(CF_1, "key_1").unwrap(), Some(b"value_1".to_vec()));
+    assert_eq!(db.get_cf(CF_2, "key_1").unwrap(), None);
+
+    db.delete_cf(CF_1, "key_1").unwrap();
+
+    assert_eq!(db.get_cf(CF_1, "key_1").unwrap(), None);
+    assert!(db.get_cf("cf_3", "key_1").is_err());
+}
+
+#[test]
+fn test_in_memory_db_write_batch_atomically() {
+    let db = make_in_memory_db();
+
+    let mut batch = WriteBatch::default();
+    batch.put_cf(CF_1, "key_1", "value_1");
+    batch.put_cf("cf_3", "key_1", "value_1");
+
+    assert!(db.write(batch).is_err());
+    assert_eq!(db.get_cf(CF_1, "key_1").unwrap(), None);
+
+    let mut batch = WriteBatch::default();
+    batch.put_cf(CF_1, "key_1", "value_1");
+    batch.put_cf(CF_2, "key_2", "value_2");
+    batch.delete_cf(CF_1, "key_1");
+
+    db.write(batch).unwrap();
+
+    assert_eq!(db.get_cf(CF_1, "key_1").unwrap(), None);
+    assert_eq!(db.get_cf(CF_2, "key_2").unwrap(), Some(b"value_2".to_vec()));
+}
+
+#[test]
+fn test_in_memory_db_iterate_in_key_order() {
+    let db = make_in_memory_db();
+
+    for i in [3u128, 1, 2] {
+        db.put_cf(CF_1, i.to_be_bytes(), [i as u8]).unwrap();
+    }
+
+    let values = |mode| -> Vec<u8> {
+        db.iterator_cf(CF_1, mode)
+            .unwrap()
+            .map(|(_k, v)| v[0])
+            .collect()
+    };
+
+    assert_eq!(values(IteratorMode::Start), vec![1, 2, 3]);
+    assert_eq!(values(IteratorMode::End), vec![3, 2, 1]);
+
+    let key = 2u128.to_be_bytes();
+
+    assert_eq!(
+        values(IteratorMode::From(&key, Direction::Forward)),
+        vec![2, 3],
+    );
+    assert_eq!(
+        values(IteratorMode::From(&key, Direction::Reverse)),
+        vec![2, 1],
+    );
+}
+
+#[test]
+fn test_in_memory_db_write_in_the_middle_of_an_iteration() {
+    let db = make_in_memory_db();
+
+    for i in [1u128, 2, 3] {
+        db.put_cf(CF_1, i.to_be_bytes(), [i as u8]).unwrap();
+    }
+
+    let mut iter = db.iterator_cf(CF_1, IteratorMode::Start).unwrap();
+
+    assert_eq!(iter.next().map(|(_k, v)| v[0]), Some(1));
+
+    // Neither blocks on the iterator
+    db.delete_cf(CF_1, 2u128.to_be_bytes()).unwrap();
+    db.put_cf(CF_1, 4u128.to_be_bytes(), [4]).unwrap();
+
+    let values: Vec<u8> = iter.map(|(_k, v)| v[0]).collect();
+
+    assert_eq!(values, vec![3, 4]);
+}
@@ -1,5 +1,6 @@
 use super::AddrStatus;
 use sak_crypto::{PublicKey, Signature};
+use std::net::SocketAddr;
 use tokio::sync::RwLock;
 
 #[derive(Debug)]
@@ -7,6 +8,9 @@ pub struct KnownAddr {
     pub ip: String,
     pub disc_port: u16,
     pub p2p_port: u16,
+    // Where her whoareyou has come from, which need not be the endpoint she
+    // advertises if she is behind a NAT
+    pub observed_disc_addr: SocketAddr,
     pub sig: Signature,
     pub public_key_str: String,
     pub public_key: PublicKey,
@@ -1,20 +1,35 @@
 use super::task::DiscoveryTask;
+use crate::AddrTable;
 use log::{info, warn};
 use sak_p2p_addr::UnknownAddr;
 use sak_task_queue::TaskQueue;
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 const DISC_DIAL_INTERVAL: u64 = 2000;
 
+const FIND_NODE_INTERVAL: u64 = 3000;
+
+// Number of the closest nodes asked for their neighbours in a single round
+const FIND_NODE_ALPHA: usize = 3;
+
+// Number of the addrs from the addr book dialed when starting up
+const ADDR_BOOK_DIAL_COUNT: usize = 32;
+
 pub(crate) struct DialScheduler {
     disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
     bootstrap_addrs: Vec<UnknownAddr>,
+    addr_table: Arc<AddrTable>,
+    find_node_interval: Duration,
 }
 
 pub(crate) struct DialSchedulerArgs {
     pub(crate) disc_dial_interval: Option<u16>,
     pub(crate) bootstrap_addrs: Vec<UnknownAddr>,
     pub(crate) disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
+    pub(crate) addr_table: Arc<AddrTable>,
 }
 
 impl DialScheduler {
@@ -23,6 +38,7 @@ impl DialScheduler {
             disc_task_queue,
             bootstrap_addrs,
             disc_dial_interval,
+            addr_table,
         } = dial_schd_args;
 
         let disc_dial_interval = match disc_dial_interval {
@@ -33,6 +49,8 @@ impl DialScheduler {
         let d = DialScheduler {
             disc_task_queue: disc_task_queue.clone(),
             bootstrap_addrs,
+            addr_table,
+            find_node_interval: Duration::from_millis(FIND_NODE_INTERVAL),
         };
 
         info!(
@@ -71,7 +89,85 @@ impl DialScheduler {
         }
     }
 
+    // Addrs discovered in the previous runs, so that the node can rejoin the
+    // network even when the bootstrap nodes are gone
+    async fn enqueue_addr_book_addrs(
+        &self,
+        bootstrap_addrs: &Vec<UnknownAddr>,
+    ) {
+        let addrs: Vec<UnknownAddr> = self
+            .addr_table
+            .get_addr_book_dialable_addrs(ADDR_BOOK_DIAL_COUNT)
+            .await
+            .into_iter()
+            .filter(|a| {
+                !bootstrap_addrs.iter().any(|b| {
+                    b.disc_endpoint() == a.disc_endpoint()
+                        || (b.public_key_str.is_some()
+                            && b.public_key_str == a.public_key_str)
+                })
+            })
+            .collect();
+
+        info!("Enqueueing addr book addrs, total count: {}", addrs.len());
+
+        for addr in addrs {
+            let task = DiscoveryTask::InitiateWhoAreYou { addr };
+
+            match self.disc_task_queue.push_back(task).await {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Cannot enqueue an addr book addr, err: {}", err,);
+                }
+            };
+        }
+    }
+
+    // Each round asks the nodes closest to myself for their neighbours, and
+    // the least recently seen node as well so that a dead one can be told
+    // apart from a live one when its bucket fills up
+    async fn enqueue_find_nodes(&self) {
+        let my_id = self.addr_table.get_my_node_id().await;
+
+        let mut addrs = self
+            .addr_table
+            .get_closest_addrs(&my_id, FIND_NODE_ALPHA)
+            .await;
+
+        if let Some(lrs) = self.addr_table.get_least_recently_seen_addr().await
+        {
+            if !addrs.iter().any(|a| Arc::ptr_eq(a, &lrs)) {
+                addrs.push(lrs);
+            }
+        }
+
+        for addr in addrs {
+            let task = DiscoveryTask::InitiateFindNode { addr };
+
+            match self.disc_task_queue.push_back(task).await {
+                Ok(_) => {}
+                Err(err) => {
+                    warn!("Cannot enqueue a find node, err: {}", err,);
+                }
+            };
+        }
+    }
+
     pub async fn run(&self) {
         self.enqueue_bootstrap_addrs(&self.bootstrap_addrs).await;
+
+        self.enqueue_addr_book_addrs(&self.bootstrap_addrs).await;
+
+        loop {
+            let time_since = SystemTime::now();
+
+            self.enqueue_find_nodes().await;
+
+            sak_utils_time::wait_until_min_interval(
+                time_since,
+                self.find_node_interval,
+            )
+            .await;
+        }
     }
 }
@@ -1,12 +1,19 @@
 use super::dial_scheduler::{DialScheduler, DialSchedulerArgs};
 use super::server::{Server, ServerArgs};
 use super::task::runtime::DiscTaskRuntime;
-use crate::{AddrTable, Connection, DiscRuntime};
+#[cfg(feature = "port_mapping")]
+use crate::{find_default_gateway, PortMapper, NAT_PMP_PORT, SSDP_ADDR};
+use crate::{
+    AddrTable, Connection, DiscRuntime, ExternalAddr, ExternalEndpoint,
+};
 use colored::Colorize;
 use log::info;
 use sak_p2p_addr::UnknownAddr;
 use sak_p2p_id::Identity;
 use sak_task_queue::TaskQueue;
+#[cfg(feature = "port_mapping")]
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::UdpSocket;
@@ -20,7 +27,10 @@ pub struct Discovery {
     server: Server,
     dial_scheduler: DialScheduler,
     task_runtime: DiscTaskRuntime,
+    #[cfg(feature = "port_mapping")]
+    port_mapper: PortMapper,
     pub addr_table: Arc<AddrTable>,
+    pub external_addr: Arc<ExternalAddr>,
 }
 
 pub struct DiscoveryArgs {
@@ -32,6 +42,9 @@ pub struct DiscoveryArgs {
     pub disc_task_queue_capacity: Option<u16>,
     pub p2p_port: u16,
     pub bootstrap_addrs: Vec<UnknownAddr>,
+    pub addr_book_path: Option<PathBuf>,
+    // One of "<ip>", "<ip>:<disc_port>" and "<ip>:<disc_port>:<p2p_port>"
+    pub external_addr: Option<String>,
     pub udp_socket: UdpSocket,
     pub identity: Arc<Identity>,
 }
@@ -61,6 +74,49 @@ impl Discovery {
             (Arc::new(udp_conn), socket_addr.port())
         };
 
+        let external_addr = {
+            let configured = match &disc_args.external_addr {
+                Some(a) => {
+                    let e = ExternalEndpoint::new_from_str(
+                        a,
+                        disc_port,
+                        disc_args.p2p_port,
+                    )?;
+
+                    info!("External endpoint is configured, {}", e);
+
+                    Some(e)
+                }
+                None => None,
+            };
+
+            let a =
+                ExternalAddr::new(disc_port, disc_args.p2p_port, configured);
+
+            Arc::new(a)
+        };
+
+        #[cfg(feature = "port_mapping")]
+        let port_mapper = {
+            let nat_pmp_gateway = find_default_gateway()
+                .map(|ip| SocketAddr::new(ip.into(), NAT_PMP_PORT));
+
+            let ssdp_addr = match SSDP_ADDR.parse() {
+                Ok(a) => a,
+                Err(err) => {
+                    return Err(format!("Invalid ssdp addr, err: {}", err));
+                }
+            };
+
+            PortMapper::new(
+                disc_port,
+                disc_args.p2p_port,
+                nat_pmp_gateway,
+                ssdp_addr,
+                external_addr.clone(),
+            )
+        };
+
         let addr_expire_duration = match disc_args.addr_expire_duration {
             Some(d) => d,
             None => ADDR_EXPIRE_DURATION,
@@ -72,7 +128,14 @@ impl Discovery {
         };
 
         let addr_table = {
-            let t = match AddrTable::init(disc_args.disc_table_capacity).await {
+            let t = match AddrTable::init(
+                &disc_args.identity.credential.public_key_str,
+                disc_args.disc_table_capacity,
+                addr_expire_duration,
+                disc_args.addr_book_path,
+            )
+            .await
+            {
                 Ok(t) => t,
                 Err(err) => {
                     return Err(
@@ -98,6 +161,7 @@ impl Discovery {
             disc_dial_interval: disc_args.disc_dial_interval,
             bootstrap_addrs: disc_args.bootstrap_addrs,
             disc_task_queue: disc_task_queue.clone(),
+            addr_table: addr_table.clone(),
         };
 
         let dial_scheduler = {
@@ -112,6 +176,8 @@ impl Discovery {
                 identity: disc_args.identity.clone(),
                 addr_table: addr_table.clone(),
                 addr_expire_duration,
+                disc_task_queue: disc_task_queue.clone(),
+                external_addr: external_addr.clone(),
             };
 
             let s = Server::new(server_args);
@@ -135,6 +201,7 @@ impl Discovery {
                 disc_args.identity.clone(),
                 addr_table.clone(),
                 udp_conn,
+                external_addr.clone(),
             );
 
             h
@@ -144,7 +211,10 @@ impl Discovery {
             server,
             task_runtime,
             dial_scheduler,
+            #[cfg(feature = "port_mapping")]
+            port_mapper,
             addr_table,
+            external_addr,
             disc_runtime,
         };
 
@@ -152,12 +222,18 @@ impl Discovery {
     }
 
     pub async fn run(&self) {
+        let port_mapping = async {
+            #[cfg(feature = "port_mapping")]
+            self.port_mapper.run().await;
+        };
+
         tokio::join!(
             //
             self.server.run(),
             self.task_runtime.run(),
             self.dial_scheduler.run(),
             self.disc_runtime.run(),
+            port_mapping,
         );
     }
 }
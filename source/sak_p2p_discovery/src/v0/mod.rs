@@ -1,5 +1,6 @@
 mod dial_scheduler;
 mod discovery;
+mod nat;
 mod net;
 mod ops;
 mod runtime;
@@ -11,6 +12,7 @@ mod task;
 mod tests;
 
 pub use discovery::{Discovery, DiscoveryArgs};
+pub use nat::*;
 pub(crate) use net::*;
 pub(crate) use ops::*;
 pub(crate) use runtime::*;
@@ -0,0 +1,250 @@
+use super::ExternalIpVotes;
+use log::info;
+use std::net::{IpAddr, SocketAddr};
+use tokio::sync::RwLock;
+
+// Endpoint other nodes can reach this one at from outside its own network,
+// advertised in whoareyou in place of the local ports
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalEndpoint {
+    pub ip: String,
+    pub disc_port: u16,
+    pub p2p_port: u16,
+}
+
+impl ExternalEndpoint {
+    // One of "<ip>", "<ip>:<disc_port>" and "<ip>:<disc_port>:<p2p_port>".
+    // Ports left out are the local ones.
+    pub fn new_from_str(
+        s: &str,
+        disc_port: u16,
+        p2p_port: u16,
+    ) -> Result<ExternalEndpoint, String> {
+        if let Ok(ip) = s.parse::<IpAddr>() {
+            return Ok(ExternalEndpoint {
+                ip: ip.to_string(),
+                disc_port,
+                p2p_port,
+            });
+        }
+
+        let parts: Vec<&str> = s.split(':').collect();
+
+        let ip = match parts[0].parse::<IpAddr>() {
+            Ok(ip) => ip,
+            Err(err) => {
+                return Err(format!(
+                    "External addr does not start with an ip, addr: {}, \
+                    err: {}",
+                    s, err,
+                ));
+            }
+        };
+
+        let parse_port = |p: &str| {
+            p.parse::<u16>().map_err(|err| {
+                format!(
+                    "External addr has an invalid port, addr: {}, err: {}",
+                    s, err,
+                )
+            })
+        };
+
+        let (disc_port, p2p_port) = match parts.len() {
+            2 => (parse_port(parts[1])?, p2p_port),
+            3 => (parse_port(parts[1])?, parse_port(parts[2])?),
+            _ => {
+                return Err(format!(
+                    "External addr should be <ip>[:<disc_port>[:<p2p_port>]], \
+                    addr: {}",
+                    s,
+                ));
+            }
+        };
+
+        Ok(ExternalEndpoint {
+            ip: ip.to_string(),
+            disc_port,
+            p2p_port,
+        })
+    }
+}
+
+impl std::fmt::Display for ExternalEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ip: {}, disc_port: {}, p2p_port: {}",
+            self.ip, self.disc_port, self.p2p_port,
+        )
+    }
+}
+
+// Whether an ip can be reached from anywhere, as opposed to a loopback,
+// private or link local one
+pub fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+
+            // 100.64.0.0/10, the shared space of carrier grade NAT
+            let is_shared = octets[0] == 100 && (octets[1] & 0xc0) == 64;
+
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || is_shared)
+        }
+        IpAddr::V6(ip) => {
+            let segments = ip.segments();
+
+            // fc00::/7 and fe80::/10
+            let is_unique_local = (segments[0] & 0xfe00) == 0xfc00;
+            let is_link_local = (segments[0] & 0xffc0) == 0xfe80;
+
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || is_unique_local
+                || is_link_local)
+        }
+    }
+}
+
+// Endpoint to reach her at, given where her msg has come from and what she
+// advertises. Ports she advertises are taken as they are. Her ip is taken
+// only when the one she is observed at is not a public one, so that she
+// cannot have others dial an arbitrary host in her name.
+pub(crate) fn resolve_her_endpoint(
+    observed_addr: &SocketAddr,
+    disc_port: u16,
+    p2p_port: u16,
+    her_external_endpoint: &Option<ExternalEndpoint>,
+) -> (String, u16, u16) {
+    let observed_ip = observed_addr.ip();
+
+    match her_external_endpoint {
+        Some(e) => {
+            let ip = if is_public_ip(&observed_ip) {
+                observed_ip.to_string()
+            } else {
+                e.ip.clone()
+            };
+
+            (ip, e.disc_port, e.p2p_port)
+        }
+        None => (observed_ip.to_string(), disc_port, p2p_port),
+    }
+}
+
+// What this node knows of its endpoint outside its own network. The one given
+// in the config wins, then the one mapped on the gateway, then the ip most
+// peers observe this node at, with the local ports.
+pub struct ExternalAddr {
+    disc_port: u16,
+    p2p_port: u16,
+    configured: Option<ExternalEndpoint>,
+    mapped: RwLock<Option<ExternalEndpoint>>,
+    ip_votes: RwLock<ExternalIpVotes>,
+}
+
+impl ExternalAddr {
+    pub(crate) fn new(
+        disc_port: u16,
+        p2p_port: u16,
+        configured: Option<ExternalEndpoint>,
+    ) -> ExternalAddr {
+        ExternalAddr {
+            disc_port,
+            p2p_port,
+            configured,
+            mapped: RwLock::new(None),
+            ip_votes: RwLock::new(ExternalIpVotes::new()),
+        }
+    }
+
+    pub async fn get_endpoint(&self) -> Option<ExternalEndpoint> {
+        if let Some(e) = &self.configured {
+            return Some(e.clone());
+        }
+
+        let voted_ip = self.ip_votes.read().await.get_majority();
+        let mapped = self.mapped.read().await.clone();
+
+        match (mapped, voted_ip) {
+            (Some(m), voted_ip) => {
+                let is_mapped_ip_public = match m.ip.parse::<IpAddr>() {
+                    Ok(ip) => is_public_ip(&ip),
+                    Err(_) => false,
+                };
+
+                // Gateway might be behind yet another NAT
+                match voted_ip {
+                    Some(ip) if !is_mapped_ip_public => {
+                        Some(ExternalEndpoint {
+                            ip: ip.to_string(),
+                            ..m
+                        })
+                    }
+                    _ => Some(m),
+                }
+            }
+            (None, Some(ip)) => Some(ExternalEndpoint {
+                ip: ip.to_string(),
+                disc_port: self.disc_port,
+                p2p_port: self.p2p_port,
+            }),
+            (None, None) => None,
+        }
+    }
+
+    #[cfg(feature = "port_mapping")]
+    pub(crate) fn is_configured(&self) -> bool {
+        self.configured.is_some()
+    }
+
+    #[cfg(feature = "port_mapping")]
+    pub(crate) async fn set_mapped(&self, endpoint: Option<ExternalEndpoint>) {
+        let mut mapped = self.mapped.write().await;
+
+        if *mapped != endpoint {
+            info!("External endpoint mapped on the gateway, {:?}", endpoint);
+        }
+
+        *mapped = endpoint;
+    }
+
+    // Only public ips count. A peer in the same network would tell an ip
+    // that nobody outside can reach.
+    pub(crate) async fn vote_observed_addr(
+        &self,
+        voter: &String,
+        observed_addr: &String,
+    ) {
+        let ip = match observed_addr.parse::<SocketAddr>() {
+            Ok(a) => a.ip(),
+            Err(_) => return,
+        };
+
+        if !is_public_ip(&ip) {
+            return;
+        }
+
+        let mut ip_votes = self.ip_votes.write().await;
+
+        let prev = ip_votes.get_majority();
+
+        ip_votes.vote(voter, ip);
+
+        let curr = ip_votes.get_majority();
+
+        if curr.is_some() && curr != prev {
+            info!(
+                "Peers agree on the external ip, ip: {:?}, votes: {}",
+                curr,
+                ip_votes.len(),
+            );
+        }
+    }
+}
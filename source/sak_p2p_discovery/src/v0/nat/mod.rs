@@ -0,0 +1,17 @@
+mod external;
+#[cfg(feature = "port_mapping")]
+mod natpmp;
+#[cfg(feature = "port_mapping")]
+mod port_mapper;
+#[cfg(feature = "port_mapping")]
+mod upnp;
+mod vote;
+
+pub use external::*;
+#[cfg(feature = "port_mapping")]
+pub(crate) use natpmp::*;
+#[cfg(feature = "port_mapping")]
+pub(crate) use port_mapper::*;
+#[cfg(feature = "port_mapping")]
+pub(crate) use upnp::*;
+pub(crate) use vote::*;
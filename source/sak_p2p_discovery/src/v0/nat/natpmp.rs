@@ -0,0 +1,167 @@
+use crate::P2PDiscError;
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+// RFC 6886
+pub const NAT_PMP_PORT: u16 = 5351;
+
+const NAT_PMP_VERSION: u8 = 0;
+const OPCODE_EXTERNAL_ADDR: u8 = 0;
+const OPCODE_RESPONSE: u8 = 128;
+const RESULT_SUCCESS: u16 = 0;
+
+// Gateway is asked this many times, waiting twice as long every time
+const NAT_PMP_ATTEMPTS: u32 = 3;
+const NAT_PMP_INITIAL_TIMEOUT: u64 = 250;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MappingProtocol {
+    Udp,
+    Tcp,
+}
+
+impl MappingProtocol {
+    fn nat_pmp_opcode(&self) -> u8 {
+        match self {
+            MappingProtocol::Udp => 1,
+            MappingProtocol::Tcp => 2,
+        }
+    }
+
+    pub(crate) fn upnp_name(&self) -> &'static str {
+        match self {
+            MappingProtocol::Udp => "UDP",
+            MappingProtocol::Tcp => "TCP",
+        }
+    }
+}
+
+pub async fn get_external_ip_nat_pmp(
+    gateway: SocketAddr,
+) -> Result<Ipv4Addr, P2PDiscError> {
+    let req = [NAT_PMP_VERSION, OPCODE_EXTERNAL_ADDR];
+
+    let resp = send_request(gateway, &req, OPCODE_EXTERNAL_ADDR, 12).await?;
+
+    let ip = Ipv4Addr::new(resp[8], resp[9], resp[10], resp[11]);
+
+    Ok(ip)
+}
+
+// Maps the internal port onto the gateway, preferably as the same external
+// port. Resolves with the external port the gateway has actually given, and
+// the lifetime of the mapping in seconds.
+pub async fn map_port_nat_pmp(
+    gateway: SocketAddr,
+    protocol: MappingProtocol,
+    internal_port: u16,
+    lifetime: u32,
+) -> Result<(u16, u32), P2PDiscError> {
+    let opcode = protocol.nat_pmp_opcode();
+
+    let mut req = Vec::with_capacity(12);
+    req.push(NAT_PMP_VERSION);
+    req.push(opcode);
+    req.extend_from_slice(&[0, 0]);
+    req.extend_from_slice(&internal_port.to_be_bytes());
+    req.extend_from_slice(&internal_port.to_be_bytes());
+    req.extend_from_slice(&lifetime.to_be_bytes());
+
+    let resp = send_request(gateway, &req, opcode, 16).await?;
+
+    let external_port = u16::from_be_bytes([resp[10], resp[11]]);
+    let lifetime = u32::from_be_bytes([resp[12], resp[13], resp[14], resp[15]]);
+
+    Ok((external_port, lifetime))
+}
+
+async fn send_request(
+    gateway: SocketAddr,
+    req: &[u8],
+    opcode: u8,
+    resp_len: usize,
+) -> Result<Vec<u8>, P2PDiscError> {
+    let bind_addr = match gateway {
+        SocketAddr::V4(_) => "0.0.0.0:0",
+        SocketAddr::V6(_) => "[::]:0",
+    };
+
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.connect(gateway).await?;
+
+    let mut buf = [0; 64];
+    let mut timeout = Duration::from_millis(NAT_PMP_INITIAL_TIMEOUT);
+
+    for _ in 0..NAT_PMP_ATTEMPTS {
+        socket.send(req).await?;
+
+        let len =
+            match tokio::time::timeout(timeout, socket.recv(&mut buf)).await {
+                Ok(res) => res?,
+                Err(_) => {
+                    timeout *= 2;
+
+                    continue;
+                }
+            };
+
+        let resp = &buf[..len];
+
+        if resp.len() < resp_len
+            || resp[0] != NAT_PMP_VERSION
+            || resp[1] != OPCODE_RESPONSE + opcode
+        {
+            return Err(format!(
+                "Gateway has sent a malformed NAT-PMP response, \
+                gateway: {}, len: {}",
+                gateway, len,
+            )
+            .into());
+        }
+
+        let result_code = u16::from_be_bytes([resp[2], resp[3]]);
+
+        if result_code != RESULT_SUCCESS {
+            return Err(format!(
+                "Gateway has refused the NAT-PMP request, gateway: {}, \
+                result_code: {}",
+                gateway, result_code,
+            )
+            .into());
+        }
+
+        return Ok(resp.to_vec());
+    }
+
+    Err(
+        format!("NAT-PMP gateway does not respond, gateway: {}", gateway)
+            .into(),
+    )
+}
+
+// Default gateway of the host, as the kernel routes it. Linux only.
+pub fn find_default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+
+    for line in routes.lines().skip(1) {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+
+        if cols.len() < 3 || cols[1] != "00000000" {
+            continue;
+        }
+
+        // Written as a little endian hex
+        let gateway = u32::from_str_radix(cols[2], 16).ok()?;
+
+        if gateway == 0 {
+            continue;
+        }
+
+        return Some(Ipv4Addr::from(gateway.to_le_bytes()));
+    }
+
+    None
+}
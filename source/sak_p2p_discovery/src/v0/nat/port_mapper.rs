@@ -0,0 +1,151 @@
+use super::{
+    get_external_ip_nat_pmp, map_port_nat_pmp, ExternalAddr, ExternalEndpoint,
+    MappingProtocol, UpnpGateway,
+};
+use crate::P2PDiscError;
+use log::{debug, warn};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+// Lifetime asked of the gateway for a mapping, in seconds. Mappings are
+// renewed halfway through.
+pub(crate) const PORT_MAPPING_LIFETIME: u32 = 7200;
+
+const PORT_MAPPING_RETRY_INTERVAL: u64 = 60_000;
+
+// Keeps the disc and p2p ports mapped on the gateway, over NAT-PMP if the
+// gateway speaks it and UPnP otherwise
+pub(crate) struct PortMapper {
+    disc_port: u16,
+    p2p_port: u16,
+    nat_pmp_gateway: Option<SocketAddr>,
+    ssdp_addr: SocketAddr,
+    external_addr: Arc<ExternalAddr>,
+}
+
+impl PortMapper {
+    pub(crate) fn new(
+        disc_port: u16,
+        p2p_port: u16,
+        nat_pmp_gateway: Option<SocketAddr>,
+        ssdp_addr: SocketAddr,
+        external_addr: Arc<ExternalAddr>,
+    ) -> PortMapper {
+        PortMapper {
+            disc_port,
+            p2p_port,
+            nat_pmp_gateway,
+            ssdp_addr,
+            external_addr,
+        }
+    }
+
+    pub(crate) async fn run(&self) {
+        // Whoever has configured the endpoint has mapped the ports as well
+        if self.external_addr.is_configured() {
+            return;
+        }
+
+        loop {
+            let interval = match self.map_ports().await {
+                Ok((endpoint, lifetime)) => {
+                    self.external_addr.set_mapped(Some(endpoint)).await;
+
+                    Duration::from_secs((lifetime / 2).max(1) as u64)
+                }
+                Err(err) => {
+                    warn!("Could not map ports on the gateway, err: {}", err);
+
+                    self.external_addr.set_mapped(None).await;
+
+                    Duration::from_millis(PORT_MAPPING_RETRY_INTERVAL)
+                }
+            };
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    // Resolves with the mapped endpoint and the shorter of the lifetimes the
+    // gateway has given
+    pub(crate) async fn map_ports(
+        &self,
+    ) -> Result<(ExternalEndpoint, u32), P2PDiscError> {
+        if let Some(gateway) = self.nat_pmp_gateway {
+            match self.map_ports_nat_pmp(gateway).await {
+                Ok(r) => return Ok(r),
+                Err(err) => {
+                    debug!(
+                        "Gateway does not map ports over NAT-PMP, trying \
+                        UPnP, err: {}",
+                        err
+                    );
+                }
+            };
+        }
+
+        self.map_ports_upnp().await
+    }
+
+    async fn map_ports_nat_pmp(
+        &self,
+        gateway: SocketAddr,
+    ) -> Result<(ExternalEndpoint, u32), P2PDiscError> {
+        let ip = get_external_ip_nat_pmp(gateway).await?;
+
+        let (disc_port, disc_lifetime) = map_port_nat_pmp(
+            gateway,
+            MappingProtocol::Udp,
+            self.disc_port,
+            PORT_MAPPING_LIFETIME,
+        )
+        .await?;
+
+        let (p2p_port, p2p_lifetime) = map_port_nat_pmp(
+            gateway,
+            MappingProtocol::Tcp,
+            self.p2p_port,
+            PORT_MAPPING_LIFETIME,
+        )
+        .await?;
+
+        let endpoint = ExternalEndpoint {
+            ip: ip.to_string(),
+            disc_port,
+            p2p_port,
+        };
+
+        Ok((endpoint, disc_lifetime.min(p2p_lifetime)))
+    }
+
+    async fn map_ports_upnp(
+        &self,
+    ) -> Result<(ExternalEndpoint, u32), P2PDiscError> {
+        let gateway = UpnpGateway::search(self.ssdp_addr).await?;
+
+        let ip = gateway.get_external_ip().await?;
+
+        gateway
+            .add_port_mapping(
+                MappingProtocol::Udp,
+                self.disc_port,
+                PORT_MAPPING_LIFETIME,
+            )
+            .await?;
+
+        gateway
+            .add_port_mapping(
+                MappingProtocol::Tcp,
+                self.p2p_port,
+                PORT_MAPPING_LIFETIME,
+            )
+            .await?;
+
+        let endpoint = ExternalEndpoint {
+            ip: ip.to_string(),
+            disc_port: self.disc_port,
+            p2p_port: self.p2p_port,
+        };
+
+        Ok((endpoint, PORT_MAPPING_LIFETIME))
+    }
+}
@@ -0,0 +1,418 @@
+use super::MappingProtocol;
+use crate::P2PDiscError;
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, UdpSocket},
+};
+
+// Multicast group that internet gateway devices answer a search at
+pub const SSDP_ADDR: &str = "239.255.255.250:1900";
+
+const UPNP_TIMEOUT: u64 = 3_000;
+
+// Gateway responses beyond this are not read
+const MAX_HTTP_RESPONSE_LEN: usize = 64 * 1024;
+
+const WAN_SERVICE_TYPES: [&str; 2] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+// WAN connection service of an internet gateway device, which takes the SOAP
+// requests mapping the ports
+#[derive(Debug, Clone)]
+pub struct UpnpGateway {
+    pub control_addr: SocketAddr,
+    pub control_path: String,
+    pub service_type: String,
+}
+
+impl UpnpGateway {
+    // Searches for the gateway over SSDP and reads its device description
+    pub async fn search(
+        ssdp_addr: SocketAddr,
+    ) -> Result<UpnpGateway, P2PDiscError> {
+        let location = search_location(ssdp_addr).await?;
+
+        let (desc_addr, desc_path) = parse_http_url(&location)?;
+
+        let desc = http_request(
+            desc_addr,
+            format!(
+                "GET {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n",
+                desc_path, desc_addr,
+            ),
+        )
+        .await?;
+
+        for service in find_all_tags(&desc, "service") {
+            let service_type = match find_tag(service, "serviceType") {
+                Some(t) => t.trim(),
+                None => continue,
+            };
+
+            if !WAN_SERVICE_TYPES.contains(&service_type) {
+                continue;
+            }
+
+            let control_url = match find_tag(service, "controlURL") {
+                Some(u) => u.trim(),
+                None => continue,
+            };
+
+            let (control_addr, control_path) = if control_url.starts_with('/') {
+                (desc_addr, control_url.to_string())
+            } else {
+                parse_http_url(control_url)?
+            };
+
+            return Ok(UpnpGateway {
+                control_addr,
+                control_path,
+                service_type: service_type.to_string(),
+            });
+        }
+
+        Err(format!(
+            "Gateway does not have a WAN connection service, location: {}",
+            location,
+        )
+        .into())
+    }
+
+    pub async fn get_external_ip(&self) -> Result<IpAddr, P2PDiscError> {
+        let resp = self.soap_request("GetExternalIPAddress", "").await?;
+
+        let ip = match find_tag(&resp, "NewExternalIPAddress") {
+            Some(ip) => ip.trim().parse::<IpAddr>()?,
+            None => {
+                return Err(format!(
+                    "Gateway has not told the external ip, resp: {}",
+                    resp,
+                )
+                .into());
+            }
+        };
+
+        Ok(ip)
+    }
+
+    // Maps the port onto the gateway as the same external port, for this
+    // host as the gateway sees it
+    pub async fn add_port_mapping(
+        &self,
+        protocol: MappingProtocol,
+        port: u16,
+        lifetime: u32,
+    ) -> Result<(), P2PDiscError> {
+        // Local addr of the route to the gateway. Connecting a udp socket
+        // sends nothing.
+        let internal_client = {
+            let socket = UdpSocket::bind("0.0.0.0:0").await?;
+            socket.connect(self.control_addr).await?;
+            socket.local_addr()?.ip()
+        };
+
+        let args = format!(
+            "<NewRemoteHost></NewRemoteHost>\
+            <NewExternalPort>{}</NewExternalPort>\
+            <NewProtocol>{}</NewProtocol>\
+            <NewInternalPort>{}</NewInternalPort>\
+            <NewInternalClient>{}</NewInternalClient>\
+            <NewEnabled>1</NewEnabled>\
+            <NewPortMappingDescription>saksaha</NewPortMappingDescription>\
+            <NewLeaseDuration>{}</NewLeaseDuration>",
+            port,
+            protocol.upnp_name(),
+            port,
+            internal_client,
+            lifetime,
+        );
+
+        self.soap_request("AddPortMapping", &args).await?;
+
+        Ok(())
+    }
+
+    async fn soap_request(
+        &self,
+        action: &str,
+        args: &str,
+    ) -> Result<String, P2PDiscError> {
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+            <s:Envelope \
+            xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+            s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+            <s:Body><u:{action} xmlns:u=\"{service_type}\">{args}\
+            </u:{action}></s:Body></s:Envelope>",
+            action = action,
+            service_type = self.service_type,
+            args = args,
+        );
+
+        let req = format!(
+            "POST {} HTTP/1.1\r\n\
+            Host: {}\r\n\
+            Content-Type: text/xml; charset=\"utf-8\"\r\n\
+            SOAPAction: \"{}#{}\"\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n\r\n{}",
+            self.control_path,
+            self.control_addr,
+            self.service_type,
+            action,
+            body.len(),
+            body,
+        );
+
+        http_request(self.control_addr, req).await
+    }
+}
+
+async fn search_location(
+    ssdp_addr: SocketAddr,
+) -> Result<String, P2PDiscError> {
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+
+    let req = format!(
+        "M-SEARCH * HTTP/1.1\r\n\
+        HOST: {}\r\n\
+        MAN: \"ssdp:discover\"\r\n\
+        MX: 2\r\n\
+        ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n",
+        ssdp_addr,
+    );
+
+    socket.send_to(req.as_bytes(), ssdp_addr).await?;
+
+    let mut buf = [0; 2048];
+
+    let (len, _) = match tokio::time::timeout(
+        Duration::from_millis(UPNP_TIMEOUT),
+        socket.recv_from(&mut buf),
+    )
+    .await
+    {
+        Ok(res) => res?,
+        Err(_) => {
+            return Err(format!(
+                "No gateway has answered the search, ssdp_addr: {}",
+                ssdp_addr
+            )
+            .into());
+        }
+    };
+
+    let resp = String::from_utf8_lossy(&buf[..len]);
+
+    for line in resp.lines() {
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("location") {
+                return Ok(value.trim().to_string());
+            }
+        }
+    }
+
+    Err(format!("Search response has no location, resp: {}", resp).into())
+}
+
+async fn http_request(
+    addr: SocketAddr,
+    req: String,
+) -> Result<String, P2PDiscError> {
+    let exchange = async {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream.write_all(req.as_bytes()).await?;
+
+        let mut resp = vec![];
+        (&mut stream)
+            .take(MAX_HTTP_RESPONSE_LEN as u64)
+            .read_to_end(&mut resp)
+            .await?;
+
+        Ok::<_, std::io::Error>(resp)
+    };
+
+    let resp = match tokio::time::timeout(
+        Duration::from_millis(UPNP_TIMEOUT),
+        exchange,
+    )
+    .await
+    {
+        Ok(res) => res?,
+        Err(_) => {
+            return Err(
+                format!("Gateway does not respond, addr: {}", addr).into()
+            );
+        }
+    };
+
+    let resp = String::from_utf8_lossy(&resp).to_string();
+
+    let (head, body) = match resp.split_once("\r\n\r\n") {
+        Some(r) => r,
+        None => {
+            return Err(
+                format!("Malformed http response, resp: {}", resp).into()
+            );
+        }
+    };
+
+    let status_line = head.lines().next().unwrap_or("");
+
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(format!(
+            "Gateway has failed the request, status: {}, body: {}",
+            status_line, body,
+        )
+        .into());
+    }
+
+    let is_chunked = head.lines().any(|line| match line.split_once(':') {
+        Some((name, value)) => {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        }
+        None => false,
+    });
+
+    if is_chunked {
+        return dechunk(body);
+    }
+
+    Ok(body.to_string())
+}
+
+fn dechunk(body: &str) -> Result<String, P2PDiscError> {
+    let mut dechunked = String::new();
+    let mut rest = body;
+
+    loop {
+        let (size_line, after_size) = match rest.split_once("\r\n") {
+            Some(s) => s,
+            None => break,
+        };
+
+        let size_str = size_line.split(';').next().unwrap_or("").trim();
+        let size = usize::from_str_radix(size_str, 16)?;
+
+        if size == 0 || after_size.len() < size {
+            break;
+        }
+
+        dechunked.push_str(&after_size[..size]);
+        rest = after_size[size..].trim_start_matches("\r\n");
+    }
+
+    Ok(dechunked)
+}
+
+fn parse_http_url(url: &str) -> Result<(SocketAddr, String), P2PDiscError> {
+    let rest = match url.strip_prefix("http://") {
+        Some(r) => r,
+        None => {
+            return Err(
+                format!("Only http urls are supported, url: {}", url).into()
+            );
+        }
+    };
+
+    let (host, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], rest[idx..].to_string()),
+        None => (rest, "/".to_string()),
+    };
+
+    let addr = match host.parse::<SocketAddr>() {
+        Ok(a) => a,
+        Err(_) => format!("{}:80", host).parse::<SocketAddr>()?,
+    };
+
+    Ok((addr, path))
+}
+
+// Text of the first element of the name, whatever the namespace prefix
+fn find_tag<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    find_all_tags(xml, name).into_iter().next()
+}
+
+fn find_all_tags<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = xml;
+
+    while let Some(start) = find_open_tag(rest, name) {
+        let after_open = &rest[start..];
+
+        let content_start = match after_open.find('>') {
+            Some(idx) => start + idx + 1,
+            None => break,
+        };
+
+        let content = &rest[content_start..];
+
+        let close = match find_close_tag(content, name) {
+            Some(idx) => idx,
+            None => break,
+        };
+
+        found.push(&content[..close]);
+        rest = &content[close..];
+    }
+
+    found
+}
+
+fn find_open_tag(xml: &str, name: &str) -> Option<usize> {
+    let mut offset = 0;
+
+    while let Some(idx) = xml[offset..].find('<') {
+        let start = offset + idx;
+        let tag = &xml[start + 1..];
+
+        let tag_name_end = tag
+            .find(|c: char| c == '>' || c.is_whitespace())
+            .unwrap_or(tag.len());
+        let tag_name = &tag[..tag_name_end];
+
+        let local_name = match tag_name.split_once(':') {
+            Some((_, n)) => n,
+            None => tag_name,
+        };
+
+        if local_name == name {
+            return Some(start);
+        }
+
+        offset = start + 1;
+    }
+
+    None
+}
+
+fn find_close_tag(xml: &str, name: &str) -> Option<usize> {
+    let mut offset = 0;
+
+    while let Some(idx) = xml[offset..].find("</") {
+        let start = offset + idx;
+        let tag = &xml[start + 2..];
+
+        let tag_name = tag.split('>').next().unwrap_or("");
+
+        let local_name = match tag_name.split_once(':') {
+            Some((_, n)) => n,
+            None => tag_name,
+        };
+
+        if local_name.trim() == name {
+            return Some(start);
+        }
+
+        offset = start + 2;
+    }
+
+    None
+}
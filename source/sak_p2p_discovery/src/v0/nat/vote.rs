@@ -0,0 +1,63 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+};
+
+// Number of peers that should agree on the ip this node is observed at before
+// it is taken as the public one
+pub(crate) const MIN_EXTERNAL_IP_VOTES: usize = 3;
+
+// Votes kept, oldest out first, so that the outcome follows an ip change
+pub(crate) const MAX_EXTERNAL_IP_VOTES: usize = 32;
+
+// Ip that each peer answering a whoareyou says she sees this node at. One
+// peer has one vote, however many times she answers.
+pub(crate) struct ExternalIpVotes {
+    votes: HashMap<String, IpAddr>,
+    order: VecDeque<String>,
+}
+
+impl ExternalIpVotes {
+    pub(crate) fn new() -> ExternalIpVotes {
+        ExternalIpVotes {
+            votes: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn vote(&mut self, voter: &String, ip: IpAddr) {
+        if self.votes.insert(voter.clone(), ip).is_some() {
+            self.order.retain(|v| v != voter);
+        }
+
+        self.order.push_back(voter.clone());
+
+        if self.order.len() > MAX_EXTERNAL_IP_VOTES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.votes.remove(&oldest);
+            }
+        }
+    }
+
+    // Ip that more than half of the votes are for, if there are enough votes
+    pub(crate) fn get_majority(&self) -> Option<IpAddr> {
+        if self.votes.len() < MIN_EXTERNAL_IP_VOTES {
+            return None;
+        }
+
+        let mut counts: HashMap<&IpAddr, usize> = HashMap::new();
+
+        for ip in self.votes.values() {
+            *counts.entry(ip).or_default() += 1;
+        }
+
+        counts
+            .into_iter()
+            .find(|(_, count)| count * 2 > self.votes.len())
+            .map(|(ip, _)| *ip)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.votes.len()
+    }
+}
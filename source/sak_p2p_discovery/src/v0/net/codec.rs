@@ -1,4 +1,8 @@
-use crate::{whoareyou::WhoAreYou, Msg, P2PDiscError};
+use crate::{
+    findnode::{FindNode, Neighbours},
+    whoareyou::WhoAreYou,
+    Msg, P2PDiscError,
+};
 use bytes::BytesMut;
 use sak_p2p_frame::{frame_io, Parse};
 use std::error::Error;
@@ -63,6 +67,38 @@ impl Encoder<Msg> for UdpCodec {
                     }
                 };
 
+                return Ok(());
+            }
+            Msg::FindNodeSyn(find_node) => {
+                let frame = find_node.into_frame();
+
+                match frame_io::write_frame(dst, &frame) {
+                    Ok(_) => (),
+                    Err(err) => {
+                        return Err(format!(
+                            "Error writing find_node_syn_frame, err: {}",
+                            err
+                        )
+                        .into());
+                    }
+                };
+
+                return Ok(());
+            }
+            Msg::FindNodeAck(neighbours) => {
+                let frame = neighbours.into_frame();
+
+                match frame_io::write_frame(dst, &frame) {
+                    Ok(_) => (),
+                    Err(err) => {
+                        return Err(format!(
+                            "Error writing find_node_ack_frame, err: {}",
+                            err
+                        )
+                        .into());
+                    }
+                };
+
                 return Ok(());
             }
         }
@@ -115,6 +151,35 @@ impl Decoder for UdpCodec {
 
                     return Ok(Some(Msg::WhoAreYouAck(way)));
                 }
+                "fn_syn" => {
+                    let find_node = match FindNode::parse_frames(&mut parse) {
+                        Ok(f) => f,
+                        Err(err) => {
+                            return Err(format!(
+                                "Error creating find_node, err: {}",
+                                err
+                            )
+                            .into());
+                        }
+                    };
+
+                    return Ok(Some(Msg::FindNodeSyn(find_node)));
+                }
+                "fn_ack" => {
+                    let neighbours = match Neighbours::parse_frames(&mut parse)
+                    {
+                        Ok(n) => n,
+                        Err(err) => {
+                            return Err(format!(
+                                "Error creating neighbours, err: {}",
+                                err
+                            )
+                            .into());
+                        }
+                    };
+
+                    return Ok(Some(Msg::FindNodeAck(neighbours)));
+                }
                 _ => {
                     return Err(format!(
                         "Msg type is unknown, cannot parse, msg_type: {}",
@@ -0,0 +1,173 @@
+use crate::{
+    v0::ops::msg_type::{FIND_NODE_ACK_TYPE, FIND_NODE_SYN_TYPE},
+    AddrTable, DiscAddr, NodeId, P2PDiscError,
+};
+use bytes::{BufMut, Bytes, BytesMut};
+use sak_p2p_frame::{Frame, Parse};
+use std::{net::SocketAddr, sync::Arc};
+
+// A single ack never carries more than this many addrs
+pub(crate) const MAX_NEIGHBOURS: usize = 16;
+
+pub(crate) struct FindNode {
+    pub(crate) src_public_key_str: String,
+    pub(crate) target: NodeId,
+}
+
+pub(crate) struct NeighbourAddr {
+    pub(crate) ip: String,
+    pub(crate) disc_port: u16,
+    pub(crate) public_key_str: String,
+}
+
+pub(crate) struct Neighbours {
+    pub(crate) src_public_key_str: String,
+    pub(crate) addrs: Vec<NeighbourAddr>,
+}
+
+impl FindNode {
+    pub(crate) fn into_frame(&self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from(FIND_NODE_SYN_TYPE.as_bytes()));
+        frame.push_bulk(into_bytes(self.src_public_key_str.as_bytes()));
+        frame.push_bulk(into_bytes(&self.target));
+
+        frame
+    }
+
+    pub(crate) fn parse_frames(
+        parse: &mut Parse,
+    ) -> Result<FindNode, P2PDiscError> {
+        let src_public_key_str = parse_string(parse)?;
+
+        let target = {
+            let target_bytes = parse.next_bytes()?;
+
+            if target_bytes.len() != 32 {
+                return Err(format!(
+                    "Target of find node should be 32 bytes long, len: {}",
+                    target_bytes.len(),
+                )
+                .into());
+            }
+
+            let mut t = [0; 32];
+            t.copy_from_slice(&target_bytes);
+            t
+        };
+
+        parse.finish()?;
+
+        let find_node = FindNode {
+            src_public_key_str,
+            target,
+        };
+
+        Ok(find_node)
+    }
+}
+
+impl Neighbours {
+    pub(crate) fn into_frame(&self) -> Frame {
+        let mut frame = Frame::array();
+        frame.push_bulk(Bytes::from(FIND_NODE_ACK_TYPE.as_bytes()));
+        frame.push_bulk(into_bytes(self.src_public_key_str.as_bytes()));
+        frame.push_int(self.addrs.len() as u128);
+
+        for addr in &self.addrs {
+            frame.push_bulk(into_bytes(addr.ip.as_bytes()));
+            frame.push_int(addr.disc_port as u128);
+            frame.push_bulk(into_bytes(addr.public_key_str.as_bytes()));
+        }
+
+        frame
+    }
+
+    pub(crate) fn parse_frames(
+        parse: &mut Parse,
+    ) -> Result<Neighbours, P2PDiscError> {
+        let src_public_key_str = parse_string(parse)?;
+
+        let count = parse.next_int()? as usize;
+
+        if count > MAX_NEIGHBOURS {
+            return Err(format!(
+                "Too many neighbours in a single msg, count: {}",
+                count
+            )
+            .into());
+        }
+
+        let mut addrs = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let ip = parse_string(parse)?;
+            let disc_port = parse.next_int()? as u16;
+            let public_key_str = parse_string(parse)?;
+
+            addrs.push(NeighbourAddr {
+                ip,
+                disc_port,
+                public_key_str,
+            });
+        }
+
+        parse.finish()?;
+
+        let neighbours = Neighbours {
+            src_public_key_str,
+            addrs,
+        };
+
+        Ok(neighbours)
+    }
+}
+
+fn into_bytes(src: &[u8]) -> Bytes {
+    let mut b = BytesMut::new();
+    b.put(src);
+    b.into()
+}
+
+fn parse_string(parse: &mut Parse) -> Result<String, P2PDiscError> {
+    let bytes = parse.next_bytes()?;
+
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(s) => Ok(s),
+        Err(err) => Err(format!(
+            "Error parsing string from byte array, err: {}",
+            err,
+        )
+        .into()),
+    }
+}
+
+// Find node msgs carry no signature, so they are only taken from addrs that
+// have already been through whoareyou, and only from where her whoareyou has
+// come from. The endpoint she advertises may well be a different one.
+pub(super) async fn get_verified_sender(
+    addr_table: &AddrTable,
+    public_key_str: &String,
+    socket_addr: &SocketAddr,
+) -> Result<Arc<DiscAddr>, String> {
+    let addr = match addr_table.get_mapped_addr(public_key_str).await {
+        Some(a) => a,
+        None => {
+            return Err(format!(
+                "Find node msg from an addr that is not mapped, \
+                socket_addr: {}",
+                socket_addr,
+            ));
+        }
+    };
+
+    if addr.known_addr.observed_disc_addr != *socket_addr {
+        return Err(format!(
+            "Find node msg is not from where she has been observed, \
+            socket_addr: {}, observed_disc_addr: {}",
+            socket_addr, addr.known_addr.observed_disc_addr,
+        ));
+    }
+
+    Ok(addr)
+}
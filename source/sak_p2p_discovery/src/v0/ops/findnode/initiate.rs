@@ -0,0 +1,91 @@
+use super::{get_verified_sender, FindNode, Neighbours};
+use crate::{v0::task::DiscoveryTask, AddrTable, Connection, DiscAddr, Msg};
+use futures::SinkExt;
+use sak_p2p_addr::{AddrStatus, UnknownAddr};
+use sak_p2p_id::Identity;
+use sak_task_queue::TaskQueue;
+use std::{net::SocketAddr, sync::Arc};
+
+pub(crate) async fn init_find_node(
+    addr: Arc<DiscAddr>,
+    identity: Arc<Identity>,
+    addr_table: Arc<AddrTable>,
+    udp_conn: Arc<Connection>,
+) -> Result<(), String> {
+    // Where she has been observed, as the endpoint she advertises need not
+    // be the one her NAT lets us through
+    let her_socket_addr = addr.known_addr.observed_disc_addr;
+
+    // Nodes closest to myself are the ones that I am most likely to be
+    // missing
+    let find_node = FindNode {
+        src_public_key_str: identity.credential.public_key_str.clone(),
+        target: addr_table.get_my_node_id().await,
+    };
+
+    let mut tx_lock = udp_conn.tx.write().await;
+
+    if let Err(err) = tx_lock
+        .send((Msg::FindNodeSyn(find_node), her_socket_addr))
+        .await
+    {
+        return Err(format!(
+            "Can't send a message through udp socket, err: {}",
+            err
+        ));
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn handle_find_node_ack(
+    neighbours: Neighbours,
+    socket_addr: SocketAddr,
+    identity: Arc<Identity>,
+    addr_table: Arc<AddrTable>,
+    disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
+) -> Result<(), String> {
+    let Neighbours {
+        src_public_key_str: her_public_key_str,
+        addrs,
+    } = neighbours;
+
+    get_verified_sender(&addr_table, &her_public_key_str, &socket_addr).await?;
+
+    addr_table.touch(&her_public_key_str).await;
+
+    for addr in addrs {
+        if addr.public_key_str == identity.credential.public_key_str {
+            continue;
+        }
+
+        if let Some(_) = addr_table.get_mapped_addr(&addr.public_key_str).await
+        {
+            continue;
+        }
+
+        if !addr_table.has_room_for(&addr.public_key_str).await {
+            continue;
+        }
+
+        let unknown_addr = UnknownAddr {
+            ip: addr.ip,
+            disc_port: addr.disc_port,
+            p2p_port: None,
+            sig: None,
+            public_key_str: Some(addr.public_key_str),
+            status: AddrStatus::Initialized,
+        };
+
+        let task = DiscoveryTask::InitiateWhoAreYou { addr: unknown_addr };
+
+        if let Err(err) = disc_task_queue.push_back(task).await {
+            return Err(format!(
+                "Cannot enqueue a neighbour addr, err: {}",
+                err
+            ));
+        }
+    }
+
+    Ok(())
+}
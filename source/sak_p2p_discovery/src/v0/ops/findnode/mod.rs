@@ -0,0 +1,7 @@
+mod findnode;
+mod initiate;
+mod receive;
+
+pub(crate) use findnode::*;
+pub(crate) use initiate::*;
+pub(crate) use receive::*;
@@ -0,0 +1,55 @@
+use super::{get_verified_sender, FindNode, NeighbourAddr, Neighbours};
+use crate::{AddrTable, Connection, Msg, MAX_NEIGHBOURS};
+use futures::SinkExt;
+use sak_p2p_id::Identity;
+use std::{net::SocketAddr, sync::Arc};
+
+pub(crate) async fn recv_find_node(
+    socket_addr: SocketAddr,
+    udp_conn: Arc<Connection>,
+    find_node: FindNode,
+    identity: Arc<Identity>,
+    addr_table: Arc<AddrTable>,
+) -> Result<(), String> {
+    let FindNode {
+        src_public_key_str: her_public_key_str,
+        target,
+    } = find_node;
+
+    get_verified_sender(&addr_table, &her_public_key_str, &socket_addr).await?;
+
+    addr_table.touch(&her_public_key_str).await;
+
+    // One more is looked up in case she is one of the closest
+    let addrs = addr_table
+        .get_closest_addrs(&target, MAX_NEIGHBOURS + 1)
+        .await
+        .iter()
+        .filter(|a| a.known_addr.public_key_str != her_public_key_str)
+        .take(MAX_NEIGHBOURS)
+        .map(|a| NeighbourAddr {
+            ip: a.known_addr.ip.clone(),
+            disc_port: a.known_addr.disc_port,
+            public_key_str: a.known_addr.public_key_str.clone(),
+        })
+        .collect();
+
+    let neighbours = Neighbours {
+        src_public_key_str: identity.credential.public_key_str.clone(),
+        addrs,
+    };
+
+    let mut tx_lock = udp_conn.tx.write().await;
+
+    if let Err(err) = tx_lock
+        .send((Msg::FindNodeAck(neighbours), socket_addr))
+        .await
+    {
+        return Err(format!(
+            "Can't send a message through udp socket, err: {}",
+            err
+        ));
+    }
+
+    Ok(())
+}
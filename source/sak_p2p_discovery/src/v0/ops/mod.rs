@@ -1,4 +1,6 @@
+pub(crate) mod findnode;
 mod msg;
 pub(crate) mod whoareyou;
 
+pub(crate) use findnode::MAX_NEIGHBOURS;
 pub(crate) use msg::*;
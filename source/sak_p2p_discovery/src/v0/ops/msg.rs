@@ -1,11 +1,16 @@
+use super::findnode::{FindNode, Neighbours};
 use super::whoareyou::WhoAreYou;
 
 pub(crate) mod msg_type {
     pub(crate) const WHO_ARE_YOU_SYN_TYPE: &str = "way_syn";
     pub(crate) const WHO_ARE_YOU_ACK_TYPE: &str = "way_ack";
+    pub(crate) const FIND_NODE_SYN_TYPE: &str = "fn_syn";
+    pub(crate) const FIND_NODE_ACK_TYPE: &str = "fn_ack";
 }
 
 pub(crate) enum Msg {
     WhoAreYouSyn(WhoAreYou),
     WhoAreYouAck(WhoAreYou),
+    FindNodeSyn(FindNode),
+    FindNodeAck(Neighbours),
 }
@@ -1,12 +1,20 @@
 use super::{check, WhoAreYou};
-use crate::{AddrTable, Connection, DiscAddr, Msg};
+use crate::{
+    resolve_her_endpoint, AddrTable, Connection, DiscAddr, ExternalAddr, Msg,
+};
 use chrono::Utc;
 use futures::SinkExt;
+use sak_logger::tdebug;
 use sak_p2p_addr::{AddrStatus, KnownAddr, UnknownAddr};
 use sak_p2p_id::Identity;
 use std::{net::SocketAddr, sync::Arc};
 use thiserror::Error;
 use tokio::sync::RwLock;
+use tokio::time::Duration;
+
+// How long a syn with the trailing fields waits for her ack before she is
+// taken for a node that does not know them
+const WHO_ARE_YOU_ACK_TIMEOUT: Duration = Duration::from_secs(2);
 
 #[derive(Error, Debug)]
 pub(crate) enum WhoAreYouInitError {
@@ -31,6 +39,7 @@ pub(crate) async fn init_who_are_you(
     identity: Arc<Identity>,
     addr_table: Arc<AddrTable>,
     udp_conn: Arc<Connection>,
+    external_addr: Arc<ExternalAddr>,
 ) -> Result<(), WhoAreYouInitError> {
     let her_disc_endpoint = unknown_addr.disc_endpoint();
     let my_disc_port = identity.disc_port;
@@ -56,11 +65,12 @@ pub(crate) async fn init_who_are_you(
         src_sig,
         src_disc_port,
         src_p2p_port,
-        src_public_key_str,
+        src_public_key_str: src_public_key_str.clone(),
+        observed_addr: None,
+        src_external_endpoint: external_addr.get_endpoint().await,
+        has_trailing_fields: true,
     };
 
-    let mut tx_lock = udp_conn.tx.write().await;
-
     let her_socket_addr: SocketAddr = match &her_disc_endpoint.parse() {
         Ok(a) => *a,
         Err(err) => {
@@ -70,6 +80,48 @@ pub(crate) async fn init_who_are_you(
         }
     };
 
+    send_syn(way, her_socket_addr, &udp_conn).await?;
+
+    // A node that does not know the trailing fields drops the syn without a
+    // word, so she is asked once more with a syn that has none of them
+    let way = WhoAreYou {
+        src_sig,
+        src_disc_port,
+        src_p2p_port,
+        src_public_key_str,
+        observed_addr: None,
+        src_external_endpoint: None,
+        has_trailing_fields: false,
+    };
+
+    tokio::spawn(async move {
+        tokio::time::sleep(WHO_ARE_YOU_ACK_TIMEOUT).await;
+
+        if addr_table.is_mapped_at(&her_socket_addr).await {
+            return;
+        }
+
+        if let Err(err) = send_syn(way, her_socket_addr, &udp_conn).await {
+            tdebug!(
+                "p2p_discovery",
+                "whoareyou",
+                "Whoareyou syn without the trailing fields is not sent, \
+                err: {}",
+                err,
+            );
+        }
+    });
+
+    Ok(())
+}
+
+async fn send_syn(
+    way: WhoAreYou,
+    her_socket_addr: SocketAddr,
+    udp_conn: &Connection,
+) -> Result<(), WhoAreYouInitError> {
+    let mut tx_lock = udp_conn.tx.write().await;
+
     if let Err(err) = tx_lock
         .send((Msg::WhoAreYouSyn(way), her_socket_addr))
         .await
@@ -87,14 +139,24 @@ pub(crate) async fn handle_who_are_you_ack(
     socket_addr: SocketAddr,
     _udp_conn: Arc<Connection>,
     addr_table: Arc<AddrTable>,
+    external_addr: Arc<ExternalAddr>,
 ) -> Result<(), String> {
     let WhoAreYou {
         src_sig: her_sig,
         src_disc_port: her_disc_port,
         src_p2p_port: her_p2p_port,
         src_public_key_str: her_public_key_str,
+        observed_addr,
+        src_external_endpoint: her_external_endpoint,
+        ..
     } = way_ack;
 
+    if let Some(observed_addr) = &observed_addr {
+        external_addr
+            .vote_observed_addr(&her_public_key_str, observed_addr)
+            .await;
+    }
+
     if let Some(_) = addr_table.get_mapped_addr(&her_public_key_str).await {
         return Err(format!("Address is already mapped."));
     }
@@ -107,12 +169,22 @@ pub(crate) async fn handle_who_are_you_ack(
             Err(err) => return Err(err),
         };
 
-    let slot_guard = addr_table.get_empty_slot().await?;
+    if !addr_table.has_room_for(&her_public_key_str).await {
+        return Err(format!("Bucket of the address is full of live nodes."));
+    }
+
+    let (her_ip, her_disc_port, her_p2p_port) = resolve_her_endpoint(
+        &socket_addr,
+        her_disc_port,
+        her_p2p_port,
+        &her_external_endpoint,
+    );
 
     let known_addr = KnownAddr {
-        ip: socket_addr.ip().to_string(),
+        ip: her_ip,
         disc_port: her_disc_port,
         p2p_port: her_p2p_port,
+        observed_disc_addr: socket_addr,
         sig: her_sig,
         public_key_str: her_public_key_str.clone(),
         public_key: her_public_key,
@@ -120,10 +192,7 @@ pub(crate) async fn handle_who_are_you_ack(
     };
 
     let addr = {
-        let a = DiscAddr {
-            known_addr,
-            _addr_slot_guard: slot_guard,
-        };
+        let a = DiscAddr { known_addr };
 
         Arc::new(a)
     };
@@ -1,5 +1,7 @@
 use super::{check, WhoAreYou};
-use crate::{AddrTable, Connection, DiscAddr, Msg};
+use crate::{
+    resolve_her_endpoint, AddrTable, Connection, DiscAddr, ExternalAddr, Msg,
+};
 use chrono::Utc;
 use futures::sink::SinkExt;
 use log::error;
@@ -29,11 +31,11 @@ pub(crate) enum WhoAreYouRecvError {
     )]
     AddrAlreadyMapped { disc_endpoint: String },
 
-    #[error("Could not reserve addr slot")]
-    AddrSlotReserveFail,
-
-    #[error("Could not parse her endpoint into SocketAddr, err: {err}")]
-    EndpointParseFail { err: String },
+    #[error(
+        "Bucket of the addr is full of live nodes, disc_endpoint: \
+        {disc_endpoint}"
+    )]
+    BucketFull { disc_endpoint: String },
 }
 
 pub(crate) async fn recv_who_are_you(
@@ -43,12 +45,16 @@ pub(crate) async fn recv_who_are_you(
     // identity: Arc<DiscIdentity>,
     identity: Arc<Identity>,
     addr_table: Arc<AddrTable>,
+    external_addr: Arc<ExternalAddr>,
 ) -> Result<(), WhoAreYouRecvError> {
     let WhoAreYou {
         src_sig: her_sig,
         src_disc_port: her_disc_port,
         src_p2p_port: her_p2p_port,
         src_public_key_str: her_public_key_str,
+        src_external_endpoint: her_external_endpoint,
+        has_trailing_fields,
+        ..
     } = way_syn;
 
     let her_disc_endpoint = sak_utils_net::make_endpoint(
@@ -60,20 +66,17 @@ pub(crate) async fn recv_who_are_you(
         return Err(WhoAreYouRecvError::MyEndpoint);
     }
 
-    let slot_guard = match addr_table.get_mapped_addr(&her_public_key_str).await
-    {
-        Some(_) => {
-            return Err(WhoAreYouRecvError::AddrAlreadyMapped {
-                disc_endpoint: her_disc_endpoint.to_string(),
-            });
-        }
-        None => match addr_table.get_empty_slot().await {
-            Ok(s) => s,
-            Err(_) => {
-                return Err(WhoAreYouRecvError::AddrSlotReserveFail);
-            }
-        },
-    };
+    if let Some(_) = addr_table.get_mapped_addr(&her_public_key_str).await {
+        return Err(WhoAreYouRecvError::AddrAlreadyMapped {
+            disc_endpoint: her_disc_endpoint.to_string(),
+        });
+    }
+
+    if !addr_table.has_room_for(&her_public_key_str).await {
+        return Err(WhoAreYouRecvError::BucketFull {
+            disc_endpoint: her_disc_endpoint.to_string(),
+        });
+    }
 
     let my_disc_port = identity.disc_port;
     let my_p2p_port = identity.p2p_port;
@@ -85,21 +88,17 @@ pub(crate) async fn recv_who_are_you(
         src_disc_port: my_disc_port,
         src_p2p_port: my_p2p_port,
         src_public_key_str: my_public_key_str,
+        observed_addr: Some(socket_addr.to_string()),
+        src_external_endpoint: external_addr.get_endpoint().await,
+        has_trailing_fields,
     };
 
     let mut tx_lock = udp_conn.tx.write().await;
 
-    let her_disc_socket_addr: SocketAddr = match her_disc_endpoint.parse() {
-        Ok(a) => a,
-        Err(err) => {
-            return Err(WhoAreYouRecvError::EndpointParseFail {
-                err: err.to_string(),
-            });
-        }
-    };
-
+    // Ack goes back to where the syn has come from, which is the port a NAT
+    // in between keeps open for her
     if let Err(err) = tx_lock
-        .send((Msg::WhoAreYouAck(way_ack), her_disc_socket_addr))
+        .send((Msg::WhoAreYouAck(way_ack), socket_addr))
         .await
     {
         return Err(WhoAreYouRecvError::MsgSendFail {
@@ -120,10 +119,18 @@ pub(crate) async fn recv_who_are_you(
             }
         };
 
+    let (her_ip, her_disc_port, her_p2p_port) = resolve_her_endpoint(
+        &socket_addr,
+        her_disc_port,
+        her_p2p_port,
+        &her_external_endpoint,
+    );
+
     let known_addr = KnownAddr {
-        ip: socket_addr.ip().to_string(),
+        ip: her_ip,
         disc_port: her_disc_port,
         p2p_port: her_p2p_port,
+        observed_disc_addr: socket_addr,
         sig: her_sig,
         public_key_str: her_public_key_str.clone(),
         public_key: her_public_key,
@@ -131,10 +138,7 @@ pub(crate) async fn recv_who_are_you(
     };
 
     let addr = {
-        let a = DiscAddr {
-            known_addr,
-            _addr_slot_guard: slot_guard,
-        };
+        let a = DiscAddr { known_addr };
 
         Arc::new(a)
     };
@@ -1,16 +1,22 @@
 use crate::{
     v0::ops::msg_type::{WHO_ARE_YOU_ACK_TYPE, WHO_ARE_YOU_SYN_TYPE},
-    P2PDiscError,
+    ExternalEndpoint, P2PDiscError,
 };
 use bytes::{BufMut, Bytes, BytesMut};
 use sak_crypto::Signature;
-use sak_p2p_frame::{Frame, Parse};
+use sak_p2p_frame::{Frame, Parse, ParseError};
 
 pub(crate) struct WhoAreYou {
     pub(crate) src_sig: Signature,
     pub(crate) src_disc_port: u16,
     pub(crate) src_p2p_port: u16,
     pub(crate) src_public_key_str: String,
+    // Endpoint she has seen the msg this one answers come from. Only in ack.
+    pub(crate) observed_addr: Option<String>,
+    pub(crate) src_external_endpoint: Option<ExternalEndpoint>,
+    // Nodes before the two fields above were added reject a frame that has
+    // them, so an ack carries them only if the syn it answers has
+    pub(crate) has_trailing_fields: bool,
 }
 
 impl WhoAreYou {
@@ -42,6 +48,27 @@ impl WhoAreYou {
         frame.push_int(self.src_disc_port as u128);
         frame.push_bulk(src_public_key_bytes.into());
 
+        if !self.has_trailing_fields {
+            return Ok(frame);
+        }
+
+        // An empty string stands for none
+        let observed_addr = self.observed_addr.clone().unwrap_or_default();
+        frame.push_bulk(Bytes::from(observed_addr));
+
+        match &self.src_external_endpoint {
+            Some(e) => {
+                frame.push_bulk(Bytes::from(e.ip.clone()));
+                frame.push_int(e.disc_port as u128);
+                frame.push_int(e.p2p_port as u128);
+            }
+            None => {
+                frame.push_bulk(Bytes::new());
+                frame.push_int(0);
+                frame.push_int(0);
+            }
+        };
+
         Ok(frame)
     }
 
@@ -85,6 +112,38 @@ impl WhoAreYou {
             s
         };
 
+        let (observed_addr, src_external_endpoint, has_trailing_fields) =
+            match parse.next_bytes() {
+                Ok(observed_addr_bytes) => {
+                    let observed_addr =
+                        String::from_utf8(observed_addr_bytes.to_vec())?;
+
+                    let ip = String::from_utf8(parse.next_bytes()?.to_vec())?;
+                    let disc_port = parse.next_int()? as u16;
+                    let p2p_port = parse.next_int()? as u16;
+
+                    let src_external_endpoint = if ip.is_empty() {
+                        None
+                    } else {
+                        Some(ExternalEndpoint {
+                            ip,
+                            disc_port,
+                            p2p_port,
+                        })
+                    };
+
+                    let observed_addr = if observed_addr.is_empty() {
+                        None
+                    } else {
+                        Some(observed_addr)
+                    };
+
+                    (observed_addr, src_external_endpoint, true)
+                }
+                Err(ParseError::EndOfStream) => (None, None, false),
+                Err(err) => return Err(err.into()),
+            };
+
         parse.finish()?;
 
         let way = WhoAreYou {
@@ -92,6 +151,9 @@ impl WhoAreYou {
             src_sig,
             src_disc_port,
             src_public_key_str,
+            observed_addr,
+            src_external_endpoint,
+            has_trailing_fields,
         };
 
         return Ok(way);
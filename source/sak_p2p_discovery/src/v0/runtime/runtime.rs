@@ -1,6 +1,6 @@
 use crate::AddrTable;
 use crate::PublicKey;
-use log::debug;
+use log::{debug, warn};
 use sak_p2p_addr::AddrStatus;
 use std::sync::Arc;
 use std::time::Duration;
@@ -37,6 +37,10 @@ impl DiscRuntime {
                 .await;
             }
 
+            if let Err(err) = self.addr_table.persist_addr_book().await {
+                warn!("Failed to persist the addr book, err: {}", err);
+            }
+
             sak_utils_time::wait_until_min_interval(
                 time_since,
                 rest_after_one_iteration,
@@ -1,9 +1,12 @@
 use crate::{
+    findnode,
+    v0::task::DiscoveryTask,
     whoareyou::{self, WhoAreYouRecvError},
-    AddrTable, Connection, Msg, P2PDiscError,
+    AddrTable, Connection, ExternalAddr, Msg, P2PDiscError,
 };
 use log::warn;
 use sak_p2p_id::Identity;
+use sak_task_queue::TaskQueue;
 use std::{net::SocketAddr, sync::Arc, time::Duration};
 use tokio::sync::Semaphore;
 
@@ -20,6 +23,8 @@ impl Handler {
         identity: Arc<Identity>,
         addr_table: Arc<AddrTable>,
         _addr_expire_duration: Duration,
+        disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
+        external_addr: Arc<ExternalAddr>,
     ) -> Result<(), P2PDiscError> {
         match msg {
             Msg::WhoAreYouSyn(way_syn) => {
@@ -29,6 +34,7 @@ impl Handler {
                     way_syn,
                     identity,
                     addr_table,
+                    external_addr,
                 )
                 .await;
 
@@ -54,9 +60,26 @@ impl Handler {
                     udp_conn,
                     // identity,
                     addr_table,
+                    external_addr,
                 )
                 .await?)
             }
+            Msg::FindNodeSyn(find_node) => Ok(findnode::recv_find_node(
+                socket_addr,
+                udp_conn,
+                find_node,
+                identity,
+                addr_table,
+            )
+            .await?),
+            Msg::FindNodeAck(neighbours) => Ok(findnode::handle_find_node_ack(
+                neighbours,
+                socket_addr,
+                identity,
+                addr_table,
+                disc_task_queue,
+            )
+            .await?),
         }
     }
 }
@@ -1,8 +1,9 @@
 use super::handler::Handler;
-use crate::{AddrTable, Connection};
+use crate::{v0::task::DiscoveryTask, AddrTable, Connection, ExternalAddr};
 use futures::StreamExt;
 use sak_logger::{terr, tinfo, twarn};
 use sak_p2p_id::Identity;
+use sak_task_queue::TaskQueue;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::Semaphore;
 
@@ -14,6 +15,8 @@ pub(crate) struct Server {
     identity: Arc<Identity>,
     addr_table: Arc<AddrTable>,
     addr_expire_duration: Duration,
+    disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
+    external_addr: Arc<ExternalAddr>,
 }
 
 pub(crate) struct ServerArgs {
@@ -21,6 +24,8 @@ pub(crate) struct ServerArgs {
     pub(crate) identity: Arc<Identity>,
     pub(crate) addr_table: Arc<AddrTable>,
     pub(crate) addr_expire_duration: u64,
+    pub(crate) disc_task_queue: Arc<TaskQueue<DiscoveryTask>>,
+    pub(crate) external_addr: Arc<ExternalAddr>,
 }
 
 impl Server {
@@ -36,6 +41,8 @@ impl Server {
             conn_semaphore,
             addr_table: server_args.addr_table,
             addr_expire_duration,
+            disc_task_queue: server_args.disc_task_queue,
+            external_addr: server_args.external_addr,
         }
     }
 
@@ -68,6 +75,8 @@ impl Server {
                             let table = self.addr_table.clone();
                             let addr_expire_duration =
                                 self.addr_expire_duration;
+                            let disc_task_queue = self.disc_task_queue.clone();
+                            let external_addr = self.external_addr.clone();
 
                             tokio::spawn(async move {
                                 match handler
@@ -78,6 +87,8 @@ impl Server {
                                         identity,
                                         table,
                                         addr_expire_duration,
+                                        disc_task_queue,
+                                        external_addr,
                                     )
                                     .await
                                 {
@@ -1,10 +1,8 @@
-use super::SlotGuard;
 use sak_p2p_addr::AddrStatus;
 use sak_p2p_addr::KnownAddr;
 
 pub struct DiscAddr {
     pub known_addr: KnownAddr,
-    pub(crate) _addr_slot_guard: SlotGuard,
 }
 
 impl DiscAddr {
@@ -0,0 +1,250 @@
+use super::PublicKey;
+use chrono::Utc;
+use log::{info, warn};
+use sak_p2p_addr::{AddrStatus, KnownAddr, UnknownAddr};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tokio::sync::RwLock;
+
+const ADDR_BOOK_CAPACITY: usize = 1024;
+
+// Entries not seen for this long are dropped when the book is loaded
+const ADDR_BOOK_EXPIRE_DURATION: i64 = 60 * 60 * 24 * 7;
+
+// An addr that keeps failing without ever succeeding is not dialed again
+const MAX_FAILURES_OVER_SUCCESSES: u32 = 5;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AddrBookEntry {
+    pub public_key_str: String,
+    pub ip: String,
+    pub disc_port: u16,
+    pub p2p_port: u16,
+    // Unix timestamps, in seconds
+    pub last_seen: i64,
+    pub success_count: u32,
+    pub failure_count: u32,
+}
+
+impl AddrBookEntry {
+    fn is_dialable(&self) -> bool {
+        self.failure_count < self.success_count + MAX_FAILURES_OVER_SUCCESSES
+    }
+
+    fn to_unknown_addr(&self) -> UnknownAddr {
+        UnknownAddr {
+            ip: self.ip.clone(),
+            disc_port: self.disc_port,
+            p2p_port: Some(self.p2p_port),
+            sig: None,
+            public_key_str: Some(self.public_key_str.clone()),
+            status: AddrStatus::Initialized,
+        }
+    }
+}
+
+// Every addr this node has ever discovered, outliving the table that only
+// holds the live ones. It is written to the disk by the addr monitor so that
+// a restarted node can rejoin without the bootstrap nodes. A book without a
+// path is only kept in memory.
+pub(crate) struct AddrBook {
+    entries: RwLock<HashMap<PublicKey, AddrBookEntry>>,
+    // Ban expiry, in unix seconds. Bans are kept on the disk by whoever
+    // decides them and handed over at every startup, so they are not
+    // written here.
+    bans: RwLock<HashMap<PublicKey, i64>>,
+    is_dirty: AtomicBool,
+    path: Option<PathBuf>,
+}
+
+impl AddrBook {
+    pub(crate) fn load(path: Option<PathBuf>) -> Result<AddrBook, String> {
+        let mut entries: HashMap<PublicKey, AddrBookEntry> = HashMap::new();
+
+        if let Some(p) = &path {
+            if p.exists() {
+                let data = std::fs::read(p).map_err(|err| {
+                    format!(
+                        "Cannot read addr book, path: {:?}, err: {}",
+                        p, err
+                    )
+                })?;
+
+                let list: Vec<AddrBookEntry> = serde_json::from_slice(&data)
+                    .map_err(|err| {
+                        format!("Addr book is malformed, err: {}", err)
+                    })?;
+
+                let now = Utc::now().timestamp();
+
+                for entry in list {
+                    if now - entry.last_seen < ADDR_BOOK_EXPIRE_DURATION {
+                        entries.insert(entry.public_key_str.clone(), entry);
+                    }
+                }
+
+                info!(
+                    "Loaded addr book, path: {:?}, addr count: {}",
+                    p,
+                    entries.len()
+                );
+            }
+        }
+
+        let b = AddrBook {
+            entries: RwLock::new(entries),
+            bans: RwLock::new(HashMap::new()),
+            is_dirty: AtomicBool::new(false),
+            path,
+        };
+
+        Ok(b)
+    }
+
+    pub(crate) async fn record_success(&self, known_addr: &KnownAddr) {
+        let mut entries = self.entries.write().await;
+
+        let now = Utc::now().timestamp();
+
+        let entry = entries
+            .entry(known_addr.public_key_str.clone())
+            .or_insert_with(|| AddrBookEntry {
+                public_key_str: known_addr.public_key_str.clone(),
+                ip: known_addr.ip.clone(),
+                disc_port: known_addr.disc_port,
+                p2p_port: known_addr.p2p_port,
+                last_seen: now,
+                success_count: 0,
+                failure_count: 0,
+            });
+
+        entry.ip = known_addr.ip.clone();
+        entry.disc_port = known_addr.disc_port;
+        entry.p2p_port = known_addr.p2p_port;
+        entry.last_seen = now;
+        entry.success_count = entry.success_count.saturating_add(1);
+
+        if entries.len() > ADDR_BOOK_CAPACITY {
+            evict_oldest(&mut entries);
+        }
+
+        self.is_dirty.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) async fn touch(&self, public_key_str: &String) {
+        let mut entries = self.entries.write().await;
+
+        if let Some(entry) = entries.get_mut(public_key_str) {
+            entry.last_seen = Utc::now().timestamp();
+
+            self.is_dirty.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) async fn record_failure(&self, public_key_str: &String) {
+        let mut entries = self.entries.write().await;
+
+        if let Some(entry) = entries.get_mut(public_key_str) {
+            entry.failure_count = entry.failure_count.saturating_add(1);
+
+            self.is_dirty.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) async fn set_banned(
+        &self,
+        public_key_str: &String,
+        banned_until: i64,
+    ) {
+        let mut bans = self.bans.write().await;
+
+        let now = Utc::now().timestamp();
+
+        bans.retain(|_, t| now < *t);
+        bans.insert(public_key_str.clone(), banned_until);
+    }
+
+    pub(crate) async fn is_banned(&self, public_key_str: &String) -> bool {
+        let bans = self.bans.read().await;
+
+        match bans.get(public_key_str) {
+            Some(t) => Utc::now().timestamp() < *t,
+            None => false,
+        }
+    }
+
+    // The most recently seen addrs first
+    pub(crate) async fn get_dialable_addrs(
+        &self,
+        count: usize,
+    ) -> Vec<UnknownAddr> {
+        let entries = self.entries.read().await;
+        let bans = self.bans.read().await;
+
+        let now = Utc::now().timestamp();
+
+        let mut dialable: Vec<&AddrBookEntry> = entries
+            .values()
+            .filter(|e| {
+                e.is_dialable()
+                    && bans.get(&e.public_key_str).map_or(true, |t| now >= *t)
+            })
+            .collect();
+
+        dialable.sort_by_key(|e| -e.last_seen);
+        dialable.truncate(count);
+
+        dialable.iter().map(|e| e.to_unknown_addr()).collect()
+    }
+
+    pub(crate) async fn get_entries(&self) -> Vec<AddrBookEntry> {
+        let entries = self.entries.read().await;
+
+        entries.values().map(|e| e.clone()).collect()
+    }
+
+    // Written only if something has changed since the last time
+    pub(crate) async fn persist(&self) -> Result<(), String> {
+        let path = match &self.path {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        if !self.is_dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let data = {
+            let entries = self.entries.read().await;
+
+            let list: Vec<&AddrBookEntry> = entries.values().collect();
+
+            serde_json::to_vec(&list).map_err(|err| err.to_string())?
+        };
+
+        if let Err(err) = std::fs::write(path, data) {
+            warn!("Could not persist addr book, path: {:?}", path);
+
+            self.is_dirty.store(true, Ordering::SeqCst);
+
+            return Err(err.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn evict_oldest(entries: &mut HashMap<PublicKey, AddrBookEntry>) {
+    let oldest = entries
+        .values()
+        .min_by_key(|e| e.last_seen)
+        .map(|e| e.public_key_str.clone());
+
+    if let Some(k) = oldest {
+        entries.remove(&k);
+    }
+}
@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use sak_crypto::sha3::{Digest, Sha3_256};
+use std::collections::VecDeque;
+
+pub type NodeId = [u8; 32];
+
+pub(crate) const BUCKET_COUNT: usize = 256;
+
+pub fn make_node_id(public_key_str: &str) -> NodeId {
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key_str.as_bytes());
+
+    let mut node_id = [0; 32];
+    node_id.copy_from_slice(&hasher.finalize());
+
+    node_id
+}
+
+pub(crate) fn xor_distance(a: &NodeId, b: &NodeId) -> NodeId {
+    let mut d = [0; 32];
+
+    for i in 0..32 {
+        d[i] = a[i] ^ b[i];
+    }
+
+    d
+}
+
+// Bucket `i` holds the nodes whose distance from mine is in [2^i, 2^(i+1)).
+// None if the two ids are the same
+pub(crate) fn get_bucket_idx(my_id: &NodeId, her_id: &NodeId) -> Option<usize> {
+    let d = xor_distance(my_id, her_id);
+
+    for (i, byte) in d.iter().enumerate() {
+        if *byte != 0 {
+            let leading_zeros = i * 8 + byte.leading_zeros() as usize;
+
+            return Some(BUCKET_COUNT - 1 - leading_zeros);
+        }
+    }
+
+    None
+}
+
+pub(crate) struct BucketEntry {
+    pub(crate) node_id: NodeId,
+    pub(crate) public_key_str: String,
+    pub(crate) last_seen: DateTime<Utc>,
+}
+
+pub(crate) enum BucketSpot {
+    Vacant,
+    Occupied,
+    // Public key of the least recently seen node of the full bucket
+    Full { lrs_public_key_str: String },
+    Myself,
+}
+
+// Each bucket is ordered from the least recently seen node to the most
+// recently seen one
+pub(crate) struct KBuckets {
+    my_id: NodeId,
+    bucket_size: usize,
+    buckets: Vec<VecDeque<BucketEntry>>,
+}
+
+impl KBuckets {
+    pub(crate) fn new(my_public_key_str: &str, bucket_size: usize) -> KBuckets {
+        let buckets = (0..BUCKET_COUNT).map(|_| VecDeque::new()).collect();
+
+        KBuckets {
+            my_id: make_node_id(my_public_key_str),
+            bucket_size,
+            buckets,
+        }
+    }
+
+    pub(crate) fn get_my_id(&self) -> &NodeId {
+        &self.my_id
+    }
+
+    // Where the node would go, if it were to be inserted
+    pub(crate) fn find_spot(&self, public_key_str: &str) -> BucketSpot {
+        let node_id = make_node_id(public_key_str);
+
+        let bucket_idx = match get_bucket_idx(&self.my_id, &node_id) {
+            Some(i) => i,
+            None => return BucketSpot::Myself,
+        };
+
+        let bucket = &self.buckets[bucket_idx];
+
+        if bucket.iter().any(|e| e.node_id == node_id) {
+            return BucketSpot::Occupied;
+        }
+
+        match bucket.front() {
+            Some(lrs) if bucket.len() >= self.bucket_size => BucketSpot::Full {
+                lrs_public_key_str: lrs.public_key_str.clone(),
+            },
+            _ => BucketSpot::Vacant,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, public_key_str: &str) -> BucketSpot {
+        let spot = self.find_spot(public_key_str);
+
+        if let BucketSpot::Vacant = spot {
+            let node_id = make_node_id(public_key_str);
+
+            if let Some(i) = get_bucket_idx(&self.my_id, &node_id) {
+                self.buckets[i].push_back(BucketEntry {
+                    node_id,
+                    public_key_str: public_key_str.to_string(),
+                    last_seen: Utc::now(),
+                });
+            }
+        }
+
+        spot
+    }
+
+    // Node has just been heard from, it moves to the tail of its bucket
+    pub(crate) fn touch(&mut self, public_key_str: &str) -> bool {
+        let node_id = make_node_id(public_key_str);
+
+        let bucket = match get_bucket_idx(&self.my_id, &node_id) {
+            Some(i) => &mut self.buckets[i],
+            None => return false,
+        };
+
+        match bucket.iter().position(|e| e.node_id == node_id) {
+            Some(pos) => {
+                if let Some(mut entry) = bucket.remove(pos) {
+                    entry.last_seen = Utc::now();
+                    bucket.push_back(entry);
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn remove(&mut self, public_key_str: &str) -> bool {
+        let node_id = make_node_id(public_key_str);
+
+        let bucket = match get_bucket_idx(&self.my_id, &node_id) {
+            Some(i) => &mut self.buckets[i],
+            None => return false,
+        };
+
+        match bucket.iter().position(|e| e.node_id == node_id) {
+            Some(pos) => bucket.remove(pos).is_some(),
+            None => false,
+        }
+    }
+
+    pub(crate) fn get_entry(
+        &self,
+        public_key_str: &str,
+    ) -> Option<&BucketEntry> {
+        let node_id = make_node_id(public_key_str);
+
+        let bucket_idx = get_bucket_idx(&self.my_id, &node_id)?;
+
+        self.buckets[bucket_idx]
+            .iter()
+            .find(|e| e.node_id == node_id)
+    }
+
+    // The node that has been silent for the longest time in the whole table
+    pub(crate) fn get_least_recently_seen(&self) -> Option<&BucketEntry> {
+        self.buckets
+            .iter()
+            .filter_map(|b| b.front())
+            .min_by_key(|e| e.last_seen)
+    }
+
+    pub(crate) fn get_closest(
+        &self,
+        target: &NodeId,
+        count: usize,
+    ) -> Vec<&BucketEntry> {
+        let mut entries: Vec<&BucketEntry> =
+            self.buckets.iter().flat_map(|b| b.iter()).collect();
+
+        entries.sort_by_key(|e| xor_distance(&e.node_id, target));
+        entries.truncate(count);
+
+        entries
+    }
+}
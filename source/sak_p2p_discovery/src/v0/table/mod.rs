@@ -1,11 +1,15 @@
 mod addr;
+mod book;
+mod bucket;
 mod iter;
-mod slot;
 mod table;
 pub mod testing;
 
 pub use addr::DiscAddr;
+pub use book::AddrBookEntry;
+pub(crate) use book::*;
+pub(crate) use bucket::*;
+pub use bucket::{make_node_id, NodeId};
 pub use iter::AddrsIterator;
-pub(crate) use slot::*;
 pub use table::AddrTable;
 pub(crate) use table::*;
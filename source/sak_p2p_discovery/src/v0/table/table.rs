@@ -1,36 +1,46 @@
 use super::{
     addr::DiscAddr,
-    slot::{Slot, SlotGuard},
+    book::{AddrBook, AddrBookEntry},
+    bucket::{BucketSpot, KBuckets, NodeId},
 };
 use crate::AddrsIterator;
+use chrono::{Duration, Utc};
 use colored::Colorize;
 use log::debug;
-use std::{collections::HashMap, sync::Arc};
+use sak_p2p_addr::{AddrStatus, UnknownAddr};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc};
 use tokio::sync::{
-    mpsc::{self, Receiver, Sender, UnboundedReceiver, UnboundedSender},
-    Mutex, OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock,
+    mpsc::{self, Receiver, Sender},
+    Mutex, OwnedRwLockReadGuard, RwLock,
 };
 
 pub(crate) type PublicKey = String;
 pub(crate) type AddrMap = HashMap<PublicKey, Arc<DiscAddr>>;
 
-// const DISC_TABLE_CAPACITY: usize = 100;
-const DISC_TABLE_CAPACITY: usize = 5;
+// Nodes per bucket, i.e. "k" of Kademlia
+const DISC_TABLE_CAPACITY: usize = 16;
 
-/// TODO Table shall have Kademlia flavored buckets later on
+const KNOWN_ADDRS_QUEUE_CAPACITY: usize = 64;
+
+// Addrs are kept in XOR distance buckets keyed by the hash of the public key.
+// A full bucket takes in a new addr only if its least recently seen one is
+// no longer alive.
 pub struct AddrTable {
     addr_map: Arc<RwLock<AddrMap>>,
-    slots_tx: Arc<UnboundedSender<Arc<Slot>>>,
-    slots_rx: RwLock<UnboundedReceiver<Arc<Slot>>>,
+    buckets: RwLock<KBuckets>,
+    addr_expire_duration: Duration,
     known_addrs_tx: Arc<Sender<Arc<DiscAddr>>>,
     known_addrs_rx: Arc<RwLock<Receiver<Arc<DiscAddr>>>>,
-    addr_recycle_tx: Arc<UnboundedSender<Arc<DiscAddr>>>,
     addrs_it_mutex: Arc<Mutex<usize>>,
+    addr_book: AddrBook,
 }
 
 impl AddrTable {
     pub(crate) async fn init(
+        my_public_key_str: &String,
         disc_table_capacity: Option<u16>,
+        addr_expire_duration: u64,
+        addr_book_path: Option<PathBuf>,
     ) -> Result<AddrTable, String> {
         let addr_map = {
             let m = HashMap::new();
@@ -43,53 +53,37 @@ impl AddrTable {
             None => DISC_TABLE_CAPACITY,
         };
 
-        let (slots_tx, slots_rx) = {
-            let (tx, rx) = mpsc::unbounded_channel();
-            let slots_tx = Arc::new(tx);
-            let slots_rx = RwLock::new(rx);
+        if disc_table_capacity == 0 {
+            return Err(format!("Disc table capacity should be at least 1"));
+        }
 
-            for _idx in 0..disc_table_capacity {
-                let slot = {
-                    let s = Slot { _idx };
-                    Arc::new(s)
-                };
+        let buckets = {
+            let b = KBuckets::new(my_public_key_str, disc_table_capacity);
 
-                match slots_tx.send(slot) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        return Err(format!(
-                            "Error initializing slots queue, err: {}",
-                            err
-                        ));
-                    }
-                };
-            }
-
-            (slots_tx, slots_rx)
+            RwLock::new(b)
         };
 
+        let addr_expire_duration =
+            Duration::seconds(addr_expire_duration as i64);
+
         let (known_addrs_tx, known_addrs_rx) = {
-            let (tx, rx) = mpsc::channel(disc_table_capacity);
+            let (tx, rx) = mpsc::channel(KNOWN_ADDRS_QUEUE_CAPACITY);
 
             (Arc::new(tx), Arc::new(RwLock::new(rx)))
         };
 
-        let (addr_recycle_tx, addr_recycle_rx) = {
-            let (tx, rx) = mpsc::unbounded_channel();
-
-            (Arc::new(tx), RwLock::new(rx))
-        };
-
         let addrs_it_mutex = Arc::new(Mutex::new(0));
 
+        let addr_book = AddrBook::load(addr_book_path)?;
+
         let table = AddrTable {
             addr_map,
-            slots_tx,
-            slots_rx,
+            buckets,
+            addr_expire_duration,
             known_addrs_tx,
             known_addrs_rx,
-            addr_recycle_tx,
             addrs_it_mutex,
+            addr_book,
         };
 
         Ok(table)
@@ -104,26 +98,103 @@ impl AddrTable {
         addr_map.get(public_key_str).map(|n| n.clone())
     }
 
-    pub(crate) async fn get_empty_slot(&self) -> Result<SlotGuard, String> {
-        let mut slots_rx = self.slots_rx.write().await;
+    // Whether an addr that has been observed at this socket addr is mapped
+    pub(crate) async fn is_mapped_at(&self, socket_addr: &SocketAddr) -> bool {
+        let addr_map = self.addr_map.read().await;
+
+        addr_map
+            .values()
+            .any(|a| a.known_addr.observed_disc_addr == *socket_addr)
+    }
+
+    pub(crate) async fn get_my_node_id(&self) -> NodeId {
+        let buckets = self.buckets.read().await;
 
-        match slots_rx.recv().await {
-            Some(_slot) => {
-                let slot_guard = SlotGuard {
-                    _slot,
-                    slots_tx: self.slots_tx.clone(),
-                };
+        buckets.get_my_id().to_owned()
+    }
 
-                return Ok(slot_guard);
+    // Whether an addr of this public key would make it into the table
+    pub(crate) async fn has_room_for(&self, public_key_str: &String) -> bool {
+        let addr_map = self.addr_map.read().await;
+        let buckets = self.buckets.read().await;
+
+        match buckets.find_spot(public_key_str) {
+            BucketSpot::Vacant => true,
+            BucketSpot::Occupied | BucketSpot::Myself => false,
+            BucketSpot::Full { lrs_public_key_str } => {
+                !self
+                    .is_alive(&addr_map, &buckets, &lrs_public_key_str)
+                    .await
             }
-            None => {
-                return Err(format!(
-                "All slots channels have been closed. Unexpected circumstance",
-            ))
+        }
+    }
+
+    async fn is_alive(
+        &self,
+        addr_map: &AddrMap,
+        buckets: &KBuckets,
+        public_key_str: &String,
+    ) -> bool {
+        let addr = match addr_map.get(public_key_str) {
+            Some(a) => a,
+            None => return false,
+        };
+
+        match *addr.known_addr.status.read().await {
+            AddrStatus::Disconnected | AddrStatus::Invalid { .. } => {
+                return false;
             }
+            _ => (),
+        };
+
+        match buckets.get_entry(public_key_str) {
+            Some(e) => Utc::now() - e.last_seen < self.addr_expire_duration,
+            None => false,
         }
     }
 
+    // Node has just been heard from
+    pub(crate) async fn touch(&self, public_key_str: &String) -> bool {
+        let is_touched = {
+            let mut buckets = self.buckets.write().await;
+
+            buckets.touch(public_key_str)
+        };
+
+        if is_touched {
+            self.addr_book.touch(public_key_str).await;
+        }
+
+        is_touched
+    }
+
+    pub(crate) async fn get_closest_addrs(
+        &self,
+        target: &NodeId,
+        count: usize,
+    ) -> Vec<Arc<DiscAddr>> {
+        let addr_map = self.addr_map.read().await;
+        let buckets = self.buckets.read().await;
+
+        buckets
+            .get_closest(target, count)
+            .iter()
+            .filter_map(|e| addr_map.get(&e.public_key_str))
+            .map(|a| a.clone())
+            .collect()
+    }
+
+    pub(crate) async fn get_least_recently_seen_addr(
+        &self,
+    ) -> Option<Arc<DiscAddr>> {
+        let addr_map = self.addr_map.read().await;
+        let buckets = self.buckets.read().await;
+
+        let entry = buckets.get_least_recently_seen()?;
+
+        addr_map.get(&entry.public_key_str).map(|a| a.clone())
+    }
+
     pub(crate) async fn enqueue_known_addr(
         &self,
         node: Arc<DiscAddr>,
@@ -166,17 +237,70 @@ impl AddrTable {
         &self,
         addr: Arc<DiscAddr>,
     ) -> Result<Option<Arc<DiscAddr>>, String> {
-        // let mut addr_map = self.addr_map.write().await;
-        let mut addr_map = self.get_addr_map_write().await;
-
         let key = &addr.known_addr.public_key_str;
 
-        debug!(
-            "Insert mapping! key: {}, value: (p2p_ep: {})",
-            addr.known_addr.get_public_ket_short().green(),
-            addr.known_addr.get_p2p_endpoint(),
-        );
+        if self.addr_book.is_banned(key).await {
+            return Err(format!(
+                "Addr is banned, public_key: {}",
+                addr.known_addr.get_public_ket_short(),
+            ));
+        }
+
+        let evicted = {
+            let mut addr_map = self.addr_map.write().await;
+            let mut buckets = self.buckets.write().await;
+
+            let evicted = match buckets.find_spot(key) {
+                BucketSpot::Vacant | BucketSpot::Occupied => None,
+                BucketSpot::Myself => {
+                    return Err(format!("Cannot map my own addr"));
+                }
+                BucketSpot::Full { lrs_public_key_str } => {
+                    if self
+                        .is_alive(&addr_map, &buckets, &lrs_public_key_str)
+                        .await
+                    {
+                        return Err(format!(
+                            "Bucket is full and its nodes are alive, \
+                            public_key: {}",
+                            addr.known_addr.get_public_ket_short(),
+                        ));
+                    }
 
+                    buckets.remove(&lrs_public_key_str);
+                    addr_map.remove(&lrs_public_key_str)
+                }
+            };
+
+            if let BucketSpot::Occupied = buckets.insert(key) {
+                buckets.touch(key);
+            }
+
+            debug!(
+                "Insert mapping! key: {}, value: (p2p_ep: {})",
+                addr.known_addr.get_public_ket_short().green(),
+                addr.known_addr.get_p2p_endpoint(),
+            );
+
+            addr_map.insert(key.to_string(), addr.clone());
+
+            evicted
+        };
+
+        self.addr_book.record_success(&addr.known_addr).await;
+
+        if let Some(a) = &evicted {
+            debug!(
+                "Evicted an addr that is not alive, public_key: {}",
+                a.get_public_key_short(),
+            );
+
+            self.addr_book
+                .record_failure(&a.known_addr.public_key_str)
+                .await;
+        }
+
+        // Not holding the locks, the queue may take a while to have room
         match self.enqueue_known_addr(addr.clone()).await {
             Ok(_) => {}
             Err(err) => {
@@ -188,15 +312,7 @@ impl AddrTable {
             }
         };
 
-        return Ok(addr_map.insert(key.to_string(), addr.clone()));
-    }
-
-    pub(crate) async fn get_addr_map_write(
-        &self,
-    ) -> OwnedRwLockWriteGuard<AddrMap> {
-        let addr_map = self.addr_map.clone().write_owned().await;
-
-        addr_map
+        Ok(evicted)
     }
 
     pub(crate) async fn get_addr_map_read(
@@ -207,13 +323,49 @@ impl AddrTable {
         addr_map
     }
 
+    // The addr has gone dead, which the addr book keeps count of
     pub(crate) async fn remove_mapping(
         &self,
         public_key_str: &String,
     ) -> Option<Arc<DiscAddr>> {
-        let mut addr_map = self.addr_map.write().await;
+        let removed = {
+            let mut addr_map = self.addr_map.write().await;
+            let mut buckets = self.buckets.write().await;
+
+            buckets.remove(public_key_str);
+
+            addr_map.remove(public_key_str)
+        };
+
+        if removed.is_some() {
+            self.addr_book.record_failure(public_key_str).await;
+        }
+
+        removed
+    }
+
+    pub(crate) async fn get_addr_book_dialable_addrs(
+        &self,
+        count: usize,
+    ) -> Vec<UnknownAddr> {
+        self.addr_book.get_dialable_addrs(count).await
+    }
+
+    pub(crate) async fn persist_addr_book(&self) -> Result<(), String> {
+        self.addr_book.persist().await
+    }
+
+    // Bans are decided, and kept, outside of discovery. The addr book only
+    // remembers them while running so that a banned addr is neither mapped
+    // nor dialed.
+    pub async fn ban_addr(&self, public_key_str: &String, banned_until: i64) {
+        self.addr_book
+            .set_banned(public_key_str, banned_until)
+            .await;
+    }
 
-        addr_map.remove(public_key_str)
+    pub async fn get_addr_book(&self) -> Vec<AddrBookEntry> {
+        self.addr_book.get_entries().await
     }
 
     pub async fn get_status(&self) -> Vec<String> {
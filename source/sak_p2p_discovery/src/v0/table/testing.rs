@@ -1,10 +1,8 @@
 use super::*;
-use crate::Slot;
 use chrono::Utc;
 use sak_crypto::{PublicKey, Signature};
 use sak_p2p_addr::{AddrStatus, KnownAddr};
-use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::RwLock;
 
 impl DiscAddr {
     pub fn new_dummy(
@@ -14,13 +12,12 @@ impl DiscAddr {
         disc_port: u16,
         p2p_port: u16,
     ) -> DiscAddr {
-        let (slots_tx, _rx) = mpsc::unbounded_channel();
-
         let addr = DiscAddr {
             known_addr: KnownAddr {
                 ip: "127.0.0.1".to_string(),
                 disc_port,
                 p2p_port,
+                observed_disc_addr: ([127, 0, 0, 1], disc_port).into(),
                 sig,
                 public_key_str,
                 status: RwLock::new(AddrStatus::WhoAreYouSuccess {
@@ -28,10 +25,6 @@ impl DiscAddr {
                 }),
                 public_key,
             },
-            _addr_slot_guard: SlotGuard {
-                _slot: Arc::new(Slot { _idx: 0 }),
-                slots_tx: Arc::new(slots_tx),
-            },
         };
 
         addr
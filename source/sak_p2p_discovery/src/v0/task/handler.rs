@@ -1,5 +1,5 @@
 use super::DiscoveryTask;
-use crate::{whoareyou, AddrTable, Connection};
+use crate::{findnode, whoareyou, AddrTable, Connection, ExternalAddr};
 use sak_logger::tdebug;
 use sak_p2p_id::Identity;
 use std::sync::Arc;
@@ -9,23 +9,41 @@ pub(crate) async fn run(
     identity: Arc<Identity>,
     addr_table: Arc<AddrTable>,
     udp_conn: Arc<Connection>,
+    external_addr: Arc<ExternalAddr>,
 ) {
-    let result = match task {
+    match task {
         DiscoveryTask::InitiateWhoAreYou { addr } => {
-            whoareyou::init_who_are_you(addr, identity, addr_table, udp_conn)
-                .await
+            let result = whoareyou::init_who_are_you(
+                addr,
+                identity,
+                addr_table,
+                udp_conn,
+                external_addr,
+            )
+            .await;
+
+            if let Err(err) = result {
+                tdebug!(
+                    "p2p_discovery",
+                    "task",
+                    "WhoAreYouInit stopped, err: {}",
+                    err,
+                );
+            }
         }
-    };
+        DiscoveryTask::InitiateFindNode { addr } => {
+            let result =
+                findnode::init_find_node(addr, identity, addr_table, udp_conn)
+                    .await;
 
-    match result {
-        Ok(_) => (),
-        Err(err) => {
-            tdebug!(
-                "p2p_discovery",
-                "task",
-                "WhoAreYouInit stopped, err: {}",
-                err,
-            );
+            if let Err(err) = result {
+                tdebug!(
+                    "p2p_discovery",
+                    "task",
+                    "FindNodeInit stopped, err: {}",
+                    err,
+                );
+            }
         }
     }
 }
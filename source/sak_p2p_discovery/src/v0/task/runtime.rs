@@ -1,5 +1,5 @@
 use super::{handler, DiscoveryTask};
-use crate::{AddrTable, Connection};
+use crate::{AddrTable, Connection, ExternalAddr};
 use sak_logger::{tdebug, terr};
 use sak_p2p_id::Identity;
 use sak_task_queue::TaskQueue;
@@ -16,6 +16,7 @@ pub(crate) struct DiscTaskRuntime {
     pub(crate) identity: Arc<Identity>,
     pub(crate) addr_table: Arc<AddrTable>,
     pub(crate) udp_conn: Arc<Connection>,
+    pub(crate) external_addr: Arc<ExternalAddr>,
 }
 
 impl DiscTaskRuntime {
@@ -26,6 +27,7 @@ impl DiscTaskRuntime {
 
         addr_table: Arc<AddrTable>,
         udp_conn: Arc<Connection>,
+        external_addr: Arc<ExternalAddr>,
     ) -> DiscTaskRuntime {
         let disc_task_interval = match disc_task_interval {
             Some(i) => Duration::from_millis(i.into()),
@@ -38,6 +40,7 @@ impl DiscTaskRuntime {
             identity,
             addr_table,
             udp_conn,
+            external_addr,
         }
     }
 
@@ -71,6 +74,7 @@ impl DiscTaskRuntime {
                 self.identity.clone(),
                 self.addr_table.clone(),
                 self.udp_conn.clone(),
+                self.external_addr.clone(),
             )
             .await;
 
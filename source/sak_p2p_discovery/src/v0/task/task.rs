@@ -1,7 +1,10 @@
+use crate::DiscAddr;
 use sak_p2p_addr::UnknownAddr;
+use std::sync::Arc;
 
 pub(crate) enum DiscoveryTask {
     InitiateWhoAreYou { addr: UnknownAddr },
+    InitiateFindNode { addr: Arc<DiscAddr> },
 }
 
 impl std::fmt::Display for DiscoveryTask {
@@ -10,6 +13,11 @@ impl std::fmt::Display for DiscoveryTask {
             Self::InitiateWhoAreYou { addr } => {
                 write!(f, "InitiateWhoAreYou [dest: {}]", addr.disc_endpoint())
             }
+            Self::InitiateFindNode { addr } => write!(
+                f,
+                "InitiateFindNode [dest: {}]",
+                addr.known_addr.get_disc_endpoint()
+            ),
         }
     }
 }
@@ -1,9 +1,18 @@
 #[cfg(test)]
 mod utils;
 
+#[cfg(test)]
+mod test_addr_book;
+
+#[cfg(test)]
+mod test_external_addr;
+
 #[cfg(test)]
 mod test_multiple_agents;
 
+#[cfg(all(test, feature = "port_mapping"))]
+mod test_port_mapping;
+
 #[cfg(test)]
 mod test {
     use super::utils;
@@ -0,0 +1,115 @@
+use crate::{AddrTable, DiscAddr};
+use chrono::Utc;
+use sak_p2p_id::Identity;
+use std::sync::Arc;
+
+const MY_PUBLIC_KEY_STR: &str = "\
+    04ce80d8c998044270b26eb7597bd92eb188807ace620644a34bf3be145422e61\
+    af51724079002c17758c33b88ade2e789a2153c1fd5b808c1f971127c2592009a";
+
+fn make_dummy_addr() -> Arc<DiscAddr> {
+    let identity = Identity::new(
+        &String::from(
+            "aa99cfd91cc6f3b541d28f3e0707f9c7\
+            bcf05cf495308294786ca450b501b5f2",
+        ),
+        &String::from(
+            "04240874d8c323c22a571f735e835ed2\
+            f0619893a3989e557b1c9b4c699ac92b\
+            84d0dc478108629c0353f2876941f90d\
+            4b36346bcc19c6b625422adffb53b3a6af",
+        ),
+        1,
+        35521,
+    )
+    .unwrap();
+
+    let credential = &identity.credential;
+
+    let addr = DiscAddr::new_dummy(
+        credential.public_key,
+        credential.public_key_str.clone(),
+        credential.sig,
+        35521,
+        1,
+    );
+
+    Arc::new(addr)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_addr_book_survives_a_restart() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let addr_book_path =
+        std::env::temp_dir().join("sak_p2p_discovery_test_addr_book.json");
+
+    let _ = std::fs::remove_file(&addr_book_path);
+
+    let addr = make_dummy_addr();
+    let her_public_key_str = addr.known_addr.public_key_str.clone();
+
+    {
+        let addr_table = AddrTable::init(
+            &MY_PUBLIC_KEY_STR.to_string(),
+            None,
+            3600,
+            Some(addr_book_path.clone()),
+        )
+        .await
+        .unwrap();
+
+        addr_table.insert_mapping(addr.clone()).await.unwrap();
+
+        addr_table.persist_addr_book().await.unwrap();
+    }
+
+    let addr_table = AddrTable::init(
+        &MY_PUBLIC_KEY_STR.to_string(),
+        None,
+        3600,
+        Some(addr_book_path.clone()),
+    )
+    .await
+    .unwrap();
+
+    let dialable = addr_table.get_addr_book_dialable_addrs(32).await;
+
+    assert_eq!(dialable.len(), 1);
+    assert_eq!(dialable[0].public_key_str, Some(her_public_key_str.clone()));
+    assert_eq!(dialable[0].disc_port, 35521);
+
+    let entries = addr_table.get_addr_book().await;
+
+    assert_eq!(entries[0].success_count, 1);
+    assert_eq!(entries[0].failure_count, 0);
+
+    addr_table
+        .ban_addr(&her_public_key_str, Utc::now().timestamp() + 60)
+        .await;
+
+    assert!(addr_table.get_addr_book_dialable_addrs(32).await.is_empty());
+
+    addr_table.persist_addr_book().await.unwrap();
+
+    // Bans are not the book's to keep, they are handed over again on startup
+    let addr_table = AddrTable::init(
+        &MY_PUBLIC_KEY_STR.to_string(),
+        None,
+        3600,
+        Some(addr_book_path.clone()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(addr_table.get_addr_book_dialable_addrs(32).await.len(), 1);
+
+    addr_table
+        .ban_addr(&her_public_key_str, Utc::now().timestamp() + 60)
+        .await;
+
+    assert!(addr_table.get_addr_book_dialable_addrs(32).await.is_empty());
+    assert!(addr_table.insert_mapping(addr).await.is_err());
+
+    let _ = std::fs::remove_file(&addr_book_path);
+}
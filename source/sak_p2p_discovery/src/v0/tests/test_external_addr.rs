@@ -0,0 +1,474 @@
+use super::utils;
+use crate::{
+    resolve_her_endpoint, whoareyou::WhoAreYou, Connection, ExternalAddr,
+    ExternalEndpoint, ExternalIpVotes, Msg, UdpCodec,
+};
+use bytes::{Bytes, BytesMut};
+use futures::{SinkExt, StreamExt};
+use sak_p2p_addr::UnknownAddr;
+use sak_p2p_frame::{frame_io, Frame};
+use sak_p2p_id::Identity;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio_util::codec::{Decoder, Encoder};
+
+fn make_identity() -> Identity {
+    Identity::new(
+        &String::from(
+            "aa99cfd91cc6f3b541d28f3e0707f9c7\
+            bcf05cf495308294786ca450b501b5f2",
+        ),
+        &String::from(
+            "04240874d8c323c22a571f735e835ed2\
+            f0619893a3989e557b1c9b4c699ac92b\
+            84d0dc478108629c0353f2876941f90d\
+            4b36346bcc19c6b625422adffb53b3a6af",
+        ),
+        35519,
+        35518,
+    )
+    .unwrap()
+}
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_ip_votes_take_the_majority() {
+    let mut votes = ExternalIpVotes::new();
+
+    votes.vote(&"peer_1".to_string(), ip("1.2.3.4"));
+    votes.vote(&"peer_2".to_string(), ip("1.2.3.4"));
+
+    assert_eq!(votes.get_majority(), None);
+
+    // Same peer again is not another vote
+    votes.vote(&"peer_2".to_string(), ip("1.2.3.4"));
+
+    assert_eq!(votes.get_majority(), None);
+
+    votes.vote(&"peer_3".to_string(), ip("5.6.7.8"));
+
+    assert_eq!(votes.get_majority(), Some(ip("1.2.3.4")));
+
+    // 2 against 2
+    votes.vote(&"peer_4".to_string(), ip("5.6.7.8"));
+
+    assert_eq!(votes.get_majority(), None);
+
+    // A peer may change her mind
+    votes.vote(&"peer_1".to_string(), ip("5.6.7.8"));
+
+    assert_eq!(votes.get_majority(), Some(ip("5.6.7.8")));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_external_endpoint_follows_the_votes_of_public_ips() {
+    let external_addr = ExternalAddr::new(35518, 35519, None);
+
+    for idx in 0..3 {
+        external_addr
+            .vote_observed_addr(
+                &format!("peer_lan_{}", idx),
+                &"192.168.0.2:35518".to_string(),
+            )
+            .await;
+    }
+
+    assert_eq!(external_addr.get_endpoint().await, None);
+
+    for idx in 0..3 {
+        external_addr
+            .vote_observed_addr(
+                &format!("peer_{}", idx),
+                &format!("1.2.3.4:{}", 40000 + idx),
+            )
+            .await;
+    }
+
+    assert_eq!(
+        external_addr.get_endpoint().await,
+        Some(ExternalEndpoint {
+            ip: "1.2.3.4".to_string(),
+            disc_port: 35518,
+            p2p_port: 35519,
+        })
+    );
+
+    let configured =
+        ExternalEndpoint::new_from_str("5.6.7.8:40000", 35518, 35519).unwrap();
+
+    let external_addr = ExternalAddr::new(35518, 35519, Some(configured));
+
+    for idx in 0..3 {
+        external_addr
+            .vote_observed_addr(
+                &format!("peer_{}", idx),
+                &"1.2.3.4:35518".to_string(),
+            )
+            .await;
+    }
+
+    assert_eq!(
+        external_addr.get_endpoint().await,
+        Some(ExternalEndpoint {
+            ip: "5.6.7.8".to_string(),
+            disc_port: 40000,
+            p2p_port: 35519,
+        })
+    );
+}
+
+#[test]
+fn test_external_addr_config_is_parsed() {
+    let e = ExternalEndpoint::new_from_str("1.2.3.4", 35518, 35519).unwrap();
+    assert_eq!(
+        (e.ip.as_str(), e.disc_port, e.p2p_port),
+        ("1.2.3.4", 35518, 35519)
+    );
+
+    let e = ExternalEndpoint::new_from_str("1.2.3.4:40000:40001", 35518, 35519)
+        .unwrap();
+    assert_eq!(
+        (e.ip.as_str(), e.disc_port, e.p2p_port),
+        ("1.2.3.4", 40000, 40001)
+    );
+
+    let e =
+        ExternalEndpoint::new_from_str("2001:db8::1", 35518, 35519).unwrap();
+    assert_eq!(e.ip, "2001:db8::1");
+
+    assert!(ExternalEndpoint::new_from_str("localhost", 1, 2).is_err());
+    assert!(ExternalEndpoint::new_from_str("1.2.3.4:x", 1, 2).is_err());
+    assert!(ExternalEndpoint::new_from_str("1.2.3.4:1:2:3", 1, 2).is_err());
+}
+
+#[test]
+fn test_her_advertised_ip_is_taken_only_if_she_is_not_observed_at_a_public_one()
+{
+    let advertised = Some(ExternalEndpoint {
+        ip: "5.6.7.8".to_string(),
+        disc_port: 40000,
+        p2p_port: 40001,
+    });
+
+    let observed: SocketAddr = "1.2.3.4:35518".parse().unwrap();
+
+    assert_eq!(
+        resolve_her_endpoint(&observed, 35518, 35519, &advertised),
+        ("1.2.3.4".to_string(), 40000, 40001),
+    );
+
+    let observed: SocketAddr = "10.0.0.2:35518".parse().unwrap();
+
+    assert_eq!(
+        resolve_her_endpoint(&observed, 35518, 35519, &advertised),
+        ("5.6.7.8".to_string(), 40000, 40001),
+    );
+
+    assert_eq!(
+        resolve_her_endpoint(&observed, 35518, 35519, &None),
+        ("10.0.0.2".to_string(), 35518, 35519),
+    );
+}
+
+#[test]
+fn test_whoareyou_ack_carries_the_observed_addr() {
+    let identity = make_identity();
+
+    let way_ack = WhoAreYou {
+        src_sig: identity.credential.sig,
+        src_disc_port: 35518,
+        src_p2p_port: 35519,
+        src_public_key_str: identity.credential.public_key_str.clone(),
+        observed_addr: Some("1.2.3.4:40000".to_string()),
+        src_external_endpoint: Some(ExternalEndpoint {
+            ip: "5.6.7.8".to_string(),
+            disc_port: 40000,
+            p2p_port: 40001,
+        }),
+        has_trailing_fields: true,
+    };
+
+    let mut codec = UdpCodec {};
+    let mut buf = BytesMut::new();
+
+    codec.encode(Msg::WhoAreYouAck(way_ack), &mut buf).unwrap();
+
+    match codec.decode(&mut buf).unwrap() {
+        Some(Msg::WhoAreYouAck(way)) => {
+            assert_eq!(way.src_disc_port, 35518);
+            assert_eq!(way.observed_addr, Some("1.2.3.4:40000".to_string()));
+            assert_eq!(
+                way.src_external_endpoint,
+                Some(ExternalEndpoint {
+                    ip: "5.6.7.8".to_string(),
+                    disc_port: 40000,
+                    p2p_port: 40001,
+                })
+            );
+        }
+        _ => panic!("Whoareyou ack should be decoded"),
+    };
+}
+
+#[test]
+fn test_whoareyou_of_an_older_node_is_still_parsed() {
+    let identity = make_identity();
+
+    // As the nodes that do not send the trailing fields put it
+    let mut frame = Frame::array();
+    frame.push_bulk(Bytes::from("way_syn"));
+    frame.push_int(35519);
+    frame.push_bulk(Bytes::from(
+        identity.credential.sig.to_der().to_bytes().to_vec(),
+    ));
+    frame.push_int(35518);
+    frame.push_bulk(Bytes::from(identity.credential.public_key_str.clone()));
+
+    let mut buf = BytesMut::new();
+    frame_io::write_frame(&mut buf, &frame).unwrap();
+
+    let mut codec = UdpCodec {};
+
+    match codec.decode(&mut buf).unwrap() {
+        Some(Msg::WhoAreYouSyn(way)) => {
+            assert_eq!(way.src_p2p_port, 35519);
+            assert_eq!(way.observed_addr, None);
+            assert_eq!(way.src_external_endpoint, None);
+            assert_eq!(way.has_trailing_fields, false);
+        }
+        _ => panic!("Whoareyou syn should be decoded"),
+    };
+}
+
+// Stands in for a node that does not know the trailing fields. A frame that
+// has them is dropped, as such a node would fail to parse it.
+async fn recv_syn_of_an_older_node(
+    conn: &Connection,
+) -> Option<(WhoAreYou, SocketAddr)> {
+    let mut rx_lock = conn.rx.write().await;
+
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(5), rx_lock.next())
+            .await
+            .ok()??;
+
+        if let Ok((Msg::WhoAreYouSyn(way), socket_addr)) = msg {
+            if !way.has_trailing_fields {
+                return Some((way, socket_addr));
+            }
+        }
+    }
+}
+
+fn make_way_of_an_older_node(disc_port: u16) -> WhoAreYou {
+    let identity = make_identity();
+
+    WhoAreYou {
+        src_sig: identity.credential.sig,
+        src_disc_port: disc_port,
+        src_p2p_port: disc_port,
+        src_public_key_str: identity.credential.public_key_str.clone(),
+        observed_addr: None,
+        src_external_endpoint: None,
+        has_trailing_fields: false,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_a_node_that_does_not_know_the_trailing_fields_is_discovered() {
+    utils::init();
+
+    let (socket, socket_addr) =
+        sak_utils_net::setup_udp_socket(None).await.unwrap();
+
+    let older_node = Connection::new(socket);
+
+    let older_node_addr = UnknownAddr::new_from_socket_addr(
+        ([127, 0, 0, 1], socket_addr.port()).into(),
+    );
+
+    let (disc_1, _) = utils::create_disc_on_any_port(
+        1,
+        vec![older_node_addr],
+        Some("5.6.7.8:40000".to_string()),
+    )
+    .await;
+
+    utils::discovery_run(disc_1.clone());
+
+    let (_, disc_1_socket_addr) = recv_syn_of_an_older_node(&older_node)
+        .await
+        .expect("Disc_1 should send a syn without the trailing fields");
+
+    older_node
+        .tx
+        .write()
+        .await
+        .send((
+            Msg::WhoAreYouAck(make_way_of_an_older_node(socket_addr.port())),
+            disc_1_socket_addr,
+        ))
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let public_key_str = make_identity().credential.public_key_str;
+
+    disc_1
+        .addr_table
+        .get_mapped_addr(&public_key_str)
+        .await
+        .expect("Disc_1 should have mapped the older node");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ack_to_a_syn_without_the_trailing_fields_has_none() {
+    utils::init();
+
+    let (socket, socket_addr) =
+        sak_utils_net::setup_udp_socket(None).await.unwrap();
+
+    let older_node = Connection::new(socket);
+
+    let (disc_2, disc_2_addr) = utils::create_disc_on_any_port(
+        2,
+        vec![],
+        Some("5.6.7.8:40000".to_string()),
+    )
+    .await;
+
+    utils::discovery_run(disc_2.clone());
+
+    let disc_2_socket_addr: SocketAddr =
+        ([127, 0, 0, 1], disc_2_addr.disc_port).into();
+
+    older_node
+        .tx
+        .write()
+        .await
+        .send((
+            Msg::WhoAreYouSyn(make_way_of_an_older_node(socket_addr.port())),
+            disc_2_socket_addr,
+        ))
+        .await
+        .unwrap();
+
+    let msg = {
+        let mut rx_lock = older_node.rx.write().await;
+
+        tokio::time::timeout(Duration::from_secs(5), rx_lock.next())
+            .await
+            .expect("Disc_2 should ack")
+            .unwrap()
+            .unwrap()
+    };
+
+    match msg {
+        (Msg::WhoAreYouAck(way), _) => {
+            assert_eq!(way.has_trailing_fields, false);
+            assert_eq!(way.observed_addr, None);
+            assert_eq!(way.src_external_endpoint, None);
+        }
+        _ => panic!("Whoareyou ack should be received"),
+    };
+}
+
+// Disc_2 advertises an endpoint nobody answers at, as it would be if her NAT
+// did not forward it. Find node msgs have to go both ways through where she
+// has actually been observed. Disc_3 can only get to her through disc_1, and
+// disc_1 can only learn of the node standing in at the end through her.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_find_node_goes_through_even_if_she_advertises_another_endpoint() {
+    utils::init();
+
+    let (silent_socket, silent_addr) =
+        sak_utils_net::setup_udp_socket(None).await.unwrap();
+
+    let (socket, socket_addr) =
+        sak_utils_net::setup_udp_socket(None).await.unwrap();
+
+    // Known only to disc_2, and never asks anyone herself
+    let stand_in = Connection::new(socket);
+
+    let (disc_1, disc_1_addr) =
+        utils::create_disc_on_any_port(1, vec![], None).await;
+
+    let (disc_2, disc_2_addr) = utils::create_disc_on_any_port(
+        2,
+        vec![disc_1_addr.clone()],
+        Some(format!("127.0.0.1:{}", silent_addr.port())),
+    )
+    .await;
+
+    let (disc_3, disc_3_addr) =
+        utils::create_disc_on_any_port(3, vec![disc_1_addr.clone()], None)
+            .await;
+
+    utils::discovery_run(disc_1.clone());
+    utils::discovery_run(disc_2.clone());
+    utils::discovery_run(disc_3.clone());
+
+    stand_in
+        .tx
+        .write()
+        .await
+        .send((
+            Msg::WhoAreYouSyn(make_way_of_an_older_node(socket_addr.port())),
+            ([127, 0, 0, 1], disc_2_addr.disc_port).into(),
+        ))
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_secs(8)).await;
+
+    let disc_1_pk = disc_1_addr.public_key_str.clone().unwrap();
+    let disc_2_pk = disc_2_addr.public_key_str.clone().unwrap();
+    let disc_3_pk = disc_3_addr.public_key_str.clone().unwrap();
+
+    let disc_2_of_disc_1 = disc_1
+        .addr_table
+        .get_mapped_addr(&disc_2_pk)
+        .await
+        .expect("Disc_1 should have mapped disc_2");
+
+    assert_eq!(disc_2_of_disc_1.known_addr.disc_port, silent_addr.port());
+    assert_eq!(
+        disc_2_of_disc_1.known_addr.observed_disc_addr,
+        SocketAddr::from(([127, 0, 0, 1], disc_2_addr.disc_port)),
+    );
+
+    disc_2
+        .addr_table
+        .get_mapped_addr(&disc_3_pk)
+        .await
+        .expect("Disc_2 should have discovered disc_3 through disc_1");
+
+    let is_asked_by_disc_1 = {
+        let mut rx_lock = stand_in.rx.write().await;
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while let Some(msg) = rx_lock.next().await {
+                if let Ok((Msg::WhoAreYouSyn(way), _)) = msg {
+                    if way.src_public_key_str == disc_1_pk {
+                        return true;
+                    }
+                }
+            }
+
+            false
+        })
+        .await
+        .unwrap_or(false)
+    };
+
+    assert!(
+        is_asked_by_disc_1,
+        "Disc_1 should have discovered the stand-in through disc_2"
+    );
+
+    drop(silent_socket);
+}
@@ -328,4 +328,39 @@ mod test {
 
         test_thread.await.unwrap();
     }
+
+    // [6] ---> [7] ---> [8]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_find_node_discovers_addrs_beyond_bootstrap() {
+        utils::init();
+
+        let (disc_6, pk_6) = utils::create_disc(6).await;
+        let (disc_7, pk_7) = utils::create_disc(7).await;
+        let (disc_8, pk_8) = utils::create_disc(8).await;
+
+        utils::discovery_run(disc_6.clone());
+        utils::discovery_run(disc_7.clone());
+        utils::discovery_run(disc_8.clone());
+
+        println!("Sleeping for 12 seconds, for a few find node rounds");
+        tokio::time::sleep(Duration::from_secs(12)).await;
+
+        disc_6
+            .addr_table
+            .get_mapped_addr(&pk_7)
+            .await
+            .expect("Disc6 should have discovered its bootstrap node");
+
+        disc_6
+            .addr_table
+            .get_mapped_addr(&pk_8)
+            .await
+            .expect("Disc6 should have been told about disc8 by disc7");
+
+        disc_8
+            .addr_table
+            .get_mapped_addr(&pk_6)
+            .await
+            .expect("Disc8 should have been reached by disc6");
+    }
 }
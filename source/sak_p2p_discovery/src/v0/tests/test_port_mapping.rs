@@ -0,0 +1,214 @@
+use crate::{
+    ExternalAddr, ExternalEndpoint, PortMapper, PORT_MAPPING_LIFETIME,
+};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, UdpSocket},
+};
+
+const GATEWAY_EXTERNAL_IP: [u8; 4] = [1, 2, 3, 4];
+
+// Stand-in gateway that speaks NAT-PMP, mapping every internal port onto the
+// external port 1000 higher
+async fn run_nat_pmp_gateway() -> SocketAddr {
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let addr = socket.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let mut buf = [0; 64];
+
+        loop {
+            let (len, from) = socket.recv_from(&mut buf).await.unwrap();
+            let req = &buf[..len];
+
+            let mut resp = vec![0, 128 + req[1], 0, 0, 0, 0, 0, 1];
+
+            match req[1] {
+                0 => resp.extend_from_slice(&GATEWAY_EXTERNAL_IP),
+                _ => {
+                    let internal_port = u16::from_be_bytes([req[4], req[5]]);
+                    let external_port = internal_port + 1000;
+
+                    resp.extend_from_slice(&internal_port.to_be_bytes());
+                    resp.extend_from_slice(&external_port.to_be_bytes());
+                    resp.extend_from_slice(&req[8..12]);
+                }
+            };
+
+            socket.send_to(&resp, from).await.unwrap();
+        }
+    });
+
+    addr
+}
+
+// Stand-in gateway that speaks UPnP, answering the search over udp and the
+// description and SOAP requests over http. Resolves with the ssdp addr and
+// the SOAP actions it has been asked of.
+async fn run_upnp_gateway() -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let http_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let http_addr = http_listener.local_addr().unwrap();
+
+    let ssdp_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let ssdp_addr = ssdp_socket.local_addr().unwrap();
+
+    let actions = Arc::new(Mutex::new(vec![]));
+
+    tokio::spawn(async move {
+        let mut buf = [0; 2048];
+
+        loop {
+            let (_, from) = ssdp_socket.recv_from(&mut buf).await.unwrap();
+
+            let resp = format!(
+                "HTTP/1.1 200 OK\r\n\
+                ST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\
+                LOCATION: http://{}/rootDesc.xml\r\n\r\n",
+                http_addr,
+            );
+
+            ssdp_socket.send_to(resp.as_bytes(), from).await.unwrap();
+        }
+    });
+
+    let actions_clone = actions.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = http_listener.accept().await.unwrap();
+
+            let mut buf = vec![0; 8192];
+            let len = match stream.read(&mut buf).await {
+                Ok(l) if l > 0 => l,
+                _ => continue,
+            };
+            let req = String::from_utf8_lossy(&buf[..len]).to_string();
+
+            let body = if req.starts_with("GET /rootDesc.xml") {
+                "<?xml version=\"1.0\"?>\
+                <root><device><serviceList>\
+                <service>\
+                <serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1\
+                </serviceType>\
+                <controlURL>/ctl/L3F</controlURL>\
+                </service>\
+                <service>\
+                <serviceType>urn:schemas-upnp-org:service:WANIPConnection:1\
+                </serviceType>\
+                <controlURL>/ctl/IPConn</controlURL>\
+                </service>\
+                </serviceList></device></root>"
+                    .to_string()
+            } else if req.contains("#GetExternalIPAddress") {
+                actions_clone
+                    .lock()
+                    .unwrap()
+                    .push("GetExternalIPAddress".to_string());
+
+                "<s:Envelope><s:Body><u:GetExternalIPAddressResponse>\
+                <NewExternalIPAddress>1.2.3.4</NewExternalIPAddress>\
+                </u:GetExternalIPAddressResponse></s:Body></s:Envelope>"
+                    .to_string()
+            } else if req.contains("#AddPortMapping") {
+                let protocol = if req.contains("<NewProtocol>UDP") {
+                    "UDP"
+                } else {
+                    "TCP"
+                };
+
+                actions_clone
+                    .lock()
+                    .unwrap()
+                    .push(format!("AddPortMapping {}", protocol));
+
+                "<s:Envelope><s:Body><u:AddPortMappingResponse/>\
+                </s:Body></s:Envelope>"
+                    .to_string()
+            } else {
+                String::new()
+            };
+
+            let resp = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+
+            let _ = stream.write_all(resp.as_bytes()).await;
+        }
+    });
+
+    (ssdp_addr, actions)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ports_are_mapped_over_nat_pmp() {
+    let gateway = run_nat_pmp_gateway().await;
+
+    let external_addr = Arc::new(ExternalAddr::new(35518, 35519, None));
+
+    // Nothing answers the search
+    let ssdp_addr = "127.0.0.1:9".parse().unwrap();
+
+    let port_mapper =
+        PortMapper::new(35518, 35519, Some(gateway), ssdp_addr, external_addr);
+
+    let (endpoint, lifetime) = port_mapper.map_ports().await.unwrap();
+
+    assert_eq!(
+        endpoint,
+        ExternalEndpoint {
+            ip: "1.2.3.4".to_string(),
+            disc_port: 36518,
+            p2p_port: 36519,
+        }
+    );
+    assert_eq!(lifetime, PORT_MAPPING_LIFETIME);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ports_are_mapped_over_upnp_if_nat_pmp_is_not_there() {
+    let (ssdp_addr, actions) = run_upnp_gateway().await;
+
+    // Bound, but never answers
+    let silent_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let nat_pmp_gateway = silent_socket.local_addr().unwrap();
+
+    let external_addr = Arc::new(ExternalAddr::new(35518, 35519, None));
+
+    let port_mapper = PortMapper::new(
+        35518,
+        35519,
+        Some(nat_pmp_gateway),
+        ssdp_addr,
+        external_addr.clone(),
+    );
+
+    let (endpoint, _) = port_mapper.map_ports().await.unwrap();
+
+    assert_eq!(
+        endpoint,
+        ExternalEndpoint {
+            ip: "1.2.3.4".to_string(),
+            disc_port: 35518,
+            p2p_port: 35519,
+        }
+    );
+
+    assert_eq!(
+        *actions.lock().unwrap(),
+        vec![
+            "GetExternalIPAddress".to_string(),
+            "AddPortMapping UDP".to_string(),
+            "AddPortMapping TCP".to_string(),
+        ]
+    );
+
+    external_addr.set_mapped(Some(endpoint.clone())).await;
+
+    assert_eq!(external_addr.get_endpoint().await, Some(endpoint));
+}
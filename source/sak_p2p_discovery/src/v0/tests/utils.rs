@@ -322,12 +322,15 @@ lazy_static! {
     ];
 }
 
-async fn make_disc_args(test_disc_args: &TestDiscArgs) -> DiscoveryArgs {
+async fn make_disc_args(
+    test_disc_args: &TestDiscArgs,
+    disc_port: Option<u16>,
+    bootstrap_addrs: Vec<UnknownAddr>,
+    external_addr: Option<String>,
+) -> DiscoveryArgs {
     let (disc_socket, disc_port) = {
         let (socket, socket_addr) =
-            sak_utils_net::setup_udp_socket(Some(test_disc_args.disc_port))
-                .await
-                .unwrap();
+            sak_utils_net::setup_udp_socket(disc_port).await.unwrap();
 
         info!(
             "Bound udp socket for P2P discovery, addr: {}",
@@ -359,7 +362,9 @@ async fn make_disc_args(test_disc_args: &TestDiscArgs) -> DiscoveryArgs {
         udp_socket: disc_socket,
         identity,
         p2p_port: 1,
-        bootstrap_addrs: test_disc_args.bootstrap_addrs.clone(),
+        bootstrap_addrs,
+        addr_book_path: None,
+        external_addr,
     };
 
     args
@@ -388,7 +393,13 @@ pub(super) async fn create_disc(disc_idx: u16) -> (Arc<Discovery>, String) {
         .get(idx)
         .expect("Discovery arg should be provided");
 
-    let disc_args = make_disc_args(test_disc_args).await;
+    let disc_args = make_disc_args(
+        test_disc_args,
+        Some(test_disc_args.disc_port),
+        test_disc_args.bootstrap_addrs.clone(),
+        None,
+    )
+    .await;
 
     let public_key_str = disc_args.identity.credential.public_key_str.clone();
 
@@ -398,3 +409,38 @@ pub(super) async fn create_disc(disc_idx: u16) -> (Arc<Discovery>, String) {
 
     (Arc::new(disc), public_key_str)
 }
+
+// Identity of the given one, on whatever port is free. Resolves with the
+// bootstrap addr others can reach it at.
+pub(super) async fn create_disc_on_any_port(
+    disc_idx: u16,
+    bootstrap_addrs: Vec<UnknownAddr>,
+    external_addr: Option<String>,
+) -> (Arc<Discovery>, UnknownAddr) {
+    let idx: usize = disc_idx as usize;
+
+    let test_disc_args = TEST_DISC_ARGS
+        .get(idx)
+        .expect("Discovery arg should be provided");
+
+    let disc_args =
+        make_disc_args(test_disc_args, None, bootstrap_addrs, external_addr)
+            .await;
+
+    let public_key_str = disc_args.identity.credential.public_key_str.clone();
+
+    let (disc, disc_port) = Discovery::init(disc_args)
+        .await
+        .expect("Discovery should be initialized");
+
+    let addr = UnknownAddr {
+        ip: String::from("127.0.0.1"),
+        disc_port,
+        p2p_port: None,
+        sig: None,
+        public_key_str: Some(public_key_str),
+        status: AddrStatus::Initialized,
+    };
+
+    (Arc::new(disc), addr)
+}
@@ -0,0 +1,119 @@
+use crate::{PeerTableError, PublicKey};
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+use tokio::sync::RwLock;
+
+pub const BAN_DURATION: i64 = 60 * 60 * 24;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BanEntry {
+    pub public_key_str: String,
+    pub ip: String,
+    pub reason: String,
+    // Unix timestamps, in seconds
+    pub banned_at: i64,
+    pub banned_until: i64,
+}
+
+impl BanEntry {
+    pub fn is_expired(&self) -> bool {
+        Utc::now().timestamp() >= self.banned_until
+    }
+}
+
+// Banned peers, written to the disk on every change so that a ban outlives
+// the process. A list without a path is only kept in memory.
+pub(crate) struct BanList {
+    entries: RwLock<HashMap<PublicKey, BanEntry>>,
+    path: Option<PathBuf>,
+}
+
+impl BanList {
+    pub(crate) fn load(
+        path: Option<PathBuf>,
+    ) -> Result<BanList, PeerTableError> {
+        let mut entries: HashMap<PublicKey, BanEntry> = HashMap::new();
+
+        if let Some(p) = &path {
+            if p.exists() {
+                let data = std::fs::read(p)?;
+
+                let list: Vec<BanEntry> = serde_json::from_slice(&data)?;
+
+                for entry in list {
+                    if !entry.is_expired() {
+                        entries.insert(entry.public_key_str.clone(), entry);
+                    }
+                }
+
+                info!(
+                    "Loaded ban list, path: {:?}, banned count: {}",
+                    p,
+                    entries.len()
+                );
+            }
+        }
+
+        let b = BanList {
+            entries: RwLock::new(entries),
+            path,
+        };
+
+        Ok(b)
+    }
+
+    pub(crate) async fn is_banned(&self, public_key_str: &String) -> bool {
+        let entries = self.entries.read().await;
+
+        match entries.get(public_key_str) {
+            Some(e) => !e.is_expired(),
+            None => false,
+        }
+    }
+
+    pub(crate) async fn insert(
+        &self,
+        entry: BanEntry,
+    ) -> Result<(), PeerTableError> {
+        let mut entries = self.entries.write().await;
+
+        entries.retain(|_, e| !e.is_expired());
+        entries.insert(entry.public_key_str.clone(), entry);
+
+        self.persist(&entries)
+    }
+
+    pub(crate) async fn get_entries(&self) -> Vec<BanEntry> {
+        let entries = self.entries.read().await;
+
+        entries
+            .values()
+            .filter(|e| !e.is_expired())
+            .map(|e| e.clone())
+            .collect()
+    }
+
+    fn persist(
+        &self,
+        entries: &HashMap<PublicKey, BanEntry>,
+    ) -> Result<(), PeerTableError> {
+        let path = match &self.path {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        let list: Vec<&BanEntry> = entries.values().collect();
+
+        let data = serde_json::to_vec(&list)?;
+
+        if let Err(err) = std::fs::write(path, data) {
+            warn!("Could not persist ban list, path: {:?}", path);
+
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+}
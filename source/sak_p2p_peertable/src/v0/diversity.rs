@@ -0,0 +1,31 @@
+use std::net::IpAddr;
+
+// Peers sharing an ip, or an addr group, beyond these are turned away
+pub const MAX_PEERS_PER_IP: usize = 2;
+pub const MAX_PEERS_PER_ADDR_GROUP: usize = 4;
+
+// Addr group is the /24 of an IPv4 addr, and the /48 of an IPv6 one. Peers of
+// the same group are likely to be run by the same party.
+pub fn get_addr_group(ip: &str) -> String {
+    match ip.parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => {
+            let o = v4.octets();
+
+            format!("{}.{}.{}", o[0], o[1], o[2])
+        }
+        Ok(IpAddr::V6(v6)) => {
+            let s = v6.segments();
+
+            format!("{:x}:{:x}:{:x}", s[0], s[1], s[2])
+        }
+        Err(_) => ip.to_string(),
+    }
+}
+
+// Local nodes, as in a dev network, are not subject to diversity rules
+pub fn is_exempt_from_diversity(ip: &str) -> bool {
+    match ip.parse::<IpAddr>() {
+        Ok(a) => a.is_loopback(),
+        Err(_) => false,
+    }
+}
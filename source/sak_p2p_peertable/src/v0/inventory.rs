@@ -0,0 +1,47 @@
+use std::collections::{HashSet, VecDeque};
+
+// Hashes a peer is known to have, per kind, before the oldest are forgotten
+pub const KNOWN_TX_CAPACITY: usize = 4096;
+pub const KNOWN_BLOCK_CAPACITY: usize = 1024;
+
+// Tx or block hashes that a peer has announced, or that have been sent to her.
+// These are not announced to her again. Forgetting one only costs a redundant
+// announcement, so the set is bounded, oldest out first.
+pub struct KnownInventory {
+    hashes: HashSet<String>,
+    order: VecDeque<String>,
+    capacity: usize,
+}
+
+impl KnownInventory {
+    pub fn new(capacity: usize) -> KnownInventory {
+        KnownInventory {
+            hashes: HashSet::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn insert(&mut self, hash: &String) {
+        if self.capacity == 0 || self.hashes.contains(hash) {
+            return;
+        }
+
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+
+        self.hashes.insert(hash.clone());
+        self.order.push_back(hash.clone());
+    }
+
+    pub fn contains(&self, hash: &String) -> bool {
+        self.hashes.contains(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+}
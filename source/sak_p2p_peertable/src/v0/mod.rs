@@ -1,11 +1,23 @@
+mod ban;
+mod diversity;
+mod inventory;
 mod iter;
 mod peer;
+mod reputation;
 mod runtime;
 mod slot;
 mod table;
 
+#[cfg(test)]
+mod tests;
+
+pub use ban::BanEntry;
+pub(crate) use ban::*;
+pub use diversity::*;
+pub use inventory::*;
 pub use iter::*;
 pub use peer::*;
+pub use reputation::*;
 pub(crate) use runtime::*;
 pub use slot::*;
 pub use table::*;
@@ -1,16 +1,36 @@
-use crate::SlotGuard;
+use crate::{
+    KnownInventory, PeerBehavior, PeerDirection, SlotGuard, BAN_THRESHOLD,
+    KNOWN_BLOCK_CAPACITY, KNOWN_TX_CAPACITY, REPUTATION_MAX, REPUTATION_MIN,
+};
 use chrono::{DateTime, Utc};
 use sak_p2p_addr::AddrStatus;
 use sak_p2p_discovery::DiscAddr;
 use sak_p2p_transport::Transport;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::{Notify, RwLock};
 
 pub struct Peer {
     transport: Transport,
     peer_status: RwLock<PeerStatus>,
     addr: Arc<DiscAddr>,
     peer_slot_guard: SlotGuard,
+    reputation: RwLock<i32>,
+    last_seen: RwLock<DateTime<Utc>>,
+    rtt: RwLock<Option<Duration>>,
+    close_notify: Notify,
+    known_txs: RwLock<KnownInventory>,
+    known_blocks: RwLock<KnownInventory>,
+}
+
+// How lively a peer is, as shown to the outside
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PeerLiveness {
+    pub public_key_str: String,
+    pub p2p_endpoint: String,
+    pub direction: String,
+    pub rtt_ms: Option<u128>,
+    pub last_seen: String,
 }
 
 pub enum PeerStatus {
@@ -34,6 +54,14 @@ impl Peer {
             peer_status,
             addr,
             peer_slot_guard,
+            reputation: RwLock::new(0),
+            last_seen: RwLock::new(Utc::now()),
+            rtt: RwLock::new(None),
+            close_notify: Notify::new(),
+            known_txs: RwLock::new(KnownInventory::new(KNOWN_TX_CAPACITY)),
+            known_blocks: RwLock::new(KnownInventory::new(
+                KNOWN_BLOCK_CAPACITY,
+            )),
         }
     }
 
@@ -57,6 +85,112 @@ impl Peer {
         &self.addr
     }
 
+    pub fn get_ip(&self) -> &str {
+        &self.addr.known_addr.ip
+    }
+
+    pub fn get_direction(&self) -> PeerDirection {
+        self.peer_slot_guard.direction
+    }
+
+    // Resolves once the table has asked the routine holding her to let go
+    pub async fn closed(&self) {
+        self.close_notify.notified().await
+    }
+
+    pub(crate) fn close(&self) {
+        self.close_notify.notify_one();
+    }
+
+    pub async fn get_reputation(&self) -> i32 {
+        *self.reputation.read().await
+    }
+
+    // Returns the reputation after the behavior is taken into account
+    pub async fn report_behavior(&self, behavior: PeerBehavior) -> i32 {
+        let mut reputation = self.reputation.write().await;
+
+        *reputation = (*reputation + behavior.get_score_delta())
+            .clamp(REPUTATION_MIN, REPUTATION_MAX);
+
+        *reputation
+    }
+
+    pub async fn should_be_banned(&self) -> bool {
+        self.get_reputation().await <= BAN_THRESHOLD
+    }
+
+    // Peer has just been heard from
+    pub async fn touch(&self) {
+        let mut last_seen = self.last_seen.write().await;
+        *last_seen = Utc::now();
+    }
+
+    pub async fn get_last_seen(&self) -> DateTime<Utc> {
+        *self.last_seen.read().await
+    }
+
+    pub async fn set_rtt(&self, rtt: Duration) {
+        let mut rtt_lock = self.rtt.write().await;
+        *rtt_lock = Some(rtt);
+    }
+
+    pub async fn get_rtt(&self) -> Option<Duration> {
+        *self.rtt.read().await
+    }
+
+    pub async fn get_liveness(&self) -> PeerLiveness {
+        PeerLiveness {
+            public_key_str: self.get_public_key().to_string(),
+            p2p_endpoint: self.addr.known_addr.get_p2p_endpoint(),
+            direction: self.get_direction().to_string(),
+            rtt_ms: self.get_rtt().await.map(|r| r.as_millis()),
+            last_seen: self.get_last_seen().await.to_rfc3339(),
+        }
+    }
+
+    pub async fn mark_known_txs(&self, tx_hashes: &[String]) {
+        let mut known_txs = self.known_txs.write().await;
+
+        for h in tx_hashes {
+            known_txs.insert(h);
+        }
+    }
+
+    // Returns the tx hashes she is not known to have
+    pub async fn filter_known_txs(
+        &self,
+        tx_hashes: Vec<String>,
+    ) -> Vec<String> {
+        let known_txs = self.known_txs.read().await;
+
+        tx_hashes
+            .into_iter()
+            .filter(|h| !known_txs.contains(h))
+            .collect()
+    }
+
+    pub async fn mark_known_blocks(&self, block_hashes: &[String]) {
+        let mut known_blocks = self.known_blocks.write().await;
+
+        for h in block_hashes {
+            known_blocks.insert(h);
+        }
+    }
+
+    // Returns the (height, hash) of the blocks she is not known to have
+    pub async fn filter_known_blocks(
+        &self,
+        new_blocks: Vec<(u128, String)>,
+    ) -> Vec<(u128, String)> {
+        let known_blocks = self.known_blocks.read().await;
+
+        new_blocks
+            .into_iter()
+            .filter(|(_, h)| !known_blocks.contains(h))
+            .collect()
+    }
+
     pub async fn set_peer_status(&self, peer_status: PeerStatus) {
         match &peer_status {
             PeerStatus::Disconnected => {
@@ -71,6 +205,11 @@ impl Peer {
         let mut peer_status_lock = self.peer_status.write().await;
         *peer_status_lock = peer_status;
     }
+
+    pub(crate) async fn set_disconnected_keeping_addr(&self) {
+        let mut peer_status_lock = self.peer_status.write().await;
+        *peer_status_lock = PeerStatus::Disconnected;
+    }
 }
 
 impl std::fmt::Display for Peer {
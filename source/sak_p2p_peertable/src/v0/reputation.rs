@@ -0,0 +1,45 @@
+pub const REPUTATION_MAX: i32 = 100;
+
+pub const REPUTATION_MIN: i32 = -100;
+
+// Peers whose reputation falls to this point are disconnected and banned
+pub const BAN_THRESHOLD: i32 = -50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeerBehavior {
+    ValidBlock,
+    ValidTx,
+    MalformedMsg,
+    UnexpectedMsg,
+    InvalidTx,
+    InvalidBlock,
+}
+
+impl PeerBehavior {
+    // No single msg has a fresh peer banned, as even an invalid block may
+    // have been relayed in good faith. Two of them are enough, whereas a
+    // garbled frame or a bad tx may as well be a glitch.
+    pub fn get_score_delta(&self) -> i32 {
+        match self {
+            PeerBehavior::ValidBlock => 2,
+            PeerBehavior::ValidTx => 1,
+            PeerBehavior::MalformedMsg => -10,
+            PeerBehavior::UnexpectedMsg => -10,
+            PeerBehavior::InvalidTx => -20,
+            PeerBehavior::InvalidBlock => -30,
+        }
+    }
+}
+
+impl std::fmt::Display for PeerBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerBehavior::ValidBlock => write!(f, "valid_block"),
+            PeerBehavior::ValidTx => write!(f, "valid_tx"),
+            PeerBehavior::MalformedMsg => write!(f, "malformed_msg"),
+            PeerBehavior::UnexpectedMsg => write!(f, "unexpected_msg"),
+            PeerBehavior::InvalidTx => write!(f, "invalid_tx"),
+            PeerBehavior::InvalidBlock => write!(f, "invalid_block"),
+        }
+    }
+}
@@ -2,13 +2,33 @@ use sak_logger::terr;
 use std::sync::Arc;
 use tokio::sync::mpsc::UnboundedSender;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeerDirection {
+    // She has dialed me
+    Inbound,
+    // I have dialed her
+    Outbound,
+}
+
+impl std::fmt::Display for PeerDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerDirection::Inbound => write!(f, "inbound"),
+            PeerDirection::Outbound => write!(f, "outbound"),
+        }
+    }
+}
+
 pub struct Slot {
     pub idx: isize,
 }
 
+// Inbound and outbound peers take their slots out of separate pools, to
+// which the slot goes back when the guard is dropped
 pub struct SlotGuard {
     pub slot: Slot,
     pub slots_tx: Arc<UnboundedSender<Slot>>,
+    pub direction: PeerDirection,
 }
 
 impl Drop for SlotGuard {
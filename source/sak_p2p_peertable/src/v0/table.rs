@@ -1,9 +1,18 @@
-use crate::{Peer, PeerIterator, PeerTableError, Runtime, Slot, SlotGuard};
+use crate::{
+    get_addr_group, is_exempt_from_diversity, BanEntry, BanList, Peer,
+    PeerDirection, PeerIterator, PeerLiveness, PeerStatus, PeerTableError,
+    Runtime, Slot, SlotGuard, BAN_DURATION, MAX_PEERS_PER_ADDR_GROUP,
+    MAX_PEERS_PER_IP,
+};
+use chrono::Utc;
 use colored::Colorize;
 use log::{debug, error, info};
+use sak_p2p_discovery::DiscAddr;
 use std::{
     collections::{hash_map::Values, HashMap},
+    path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 use tokio::sync::{
     mpsc::{self, UnboundedReceiver, UnboundedSender},
@@ -12,45 +21,63 @@ use tokio::sync::{
 
 const PEER_TABLE_CAPACITY: isize = 30;
 
+// Outbound peers are the ones I have chosen, which an attacker can hardly
+// steer. A third of the table by default.
+const OUTBOUND_PEER_CAPACITY_RATIO: isize = 3;
+
+// The last outbound slots only take peers of an addr group that none of the
+// outbound peers belongs to yet
+const PROTECTED_OUTBOUND_SLOTS: isize = 4;
+
+// How long an evicted peer is given to release her slot
+const SLOT_RELEASE_TIMEOUT: u64 = 3000;
+
 pub type PublicKey = String;
 pub type PeerMap = HashMap<PublicKey, Arc<Peer>>;
 
 pub struct PeerTable {
     peer_map: Arc<RwLock<PeerMap>>,
-    slots_rx: RwLock<UnboundedReceiver<Slot>>,
-    slots_tx: Arc<UnboundedSender<Slot>>,
+    inbound_slots: SlotPool,
+    outbound_slots: SlotPool,
+    outbound_capacity: isize,
     peer_queue_tx: Arc<UnboundedSender<Arc<Peer>>>,
     peer_queue_iter: Arc<RwLock<PeerIterator>>,
+    ban_list: BanList,
+    redial_tx: UnboundedSender<(Arc<DiscAddr>, PeerDirection)>,
+    redial_rx: RwLock<UnboundedReceiver<(Arc<DiscAddr>, PeerDirection)>>,
+    ban_tx: UnboundedSender<BanEntry>,
+    ban_rx: RwLock<UnboundedReceiver<BanEntry>>,
 }
 
 impl PeerTable {
     pub async fn init(
         peer_table_capacity: Option<i16>,
+        outbound_peer_capacity: Option<i16>,
+        ban_list_path: Option<PathBuf>,
     ) -> Result<PeerTable, PeerTableError> {
         let capacity = match peer_table_capacity {
             Some(c) => c.into(),
             None => PEER_TABLE_CAPACITY,
         };
 
-        let (slots_tx, slots_rx) = {
-            let (tx, rx) = mpsc::unbounded_channel();
-            let slots_tx = Arc::new(tx);
-            let slots_rx = RwLock::new(rx);
-
-            for idx in 0..capacity {
-                let s = Slot { idx };
-
-                match slots_tx.send(s) {
-                    Ok(_) => (),
-                    Err(err) => {
-                        error!("slots channel has been closed, err: {}", err,);
-                    }
-                };
-            }
-
-            (slots_tx, slots_rx)
+        let outbound_capacity = match outbound_peer_capacity {
+            Some(c) => c.into(),
+            None => (capacity / OUTBOUND_PEER_CAPACITY_RATIO).max(1),
         };
 
+        if outbound_capacity > capacity {
+            return Err(format!(
+                "Outbound peer capacity ({}) cannot exceed the peer table \
+                capacity ({})",
+                outbound_capacity, capacity,
+            )
+            .into());
+        }
+
+        let inbound_slots = SlotPool::new(0, capacity - outbound_capacity);
+        let outbound_slots =
+            SlotPool::new(capacity - outbound_capacity, capacity);
+
         let (peer_queue_tx, peer_queue_iter) = {
             let (tx, rx) = mpsc::unbounded_channel();
             let peers_tx = Arc::new(tx);
@@ -66,6 +93,20 @@ impl PeerTable {
             Arc::new(RwLock::new(m))
         };
 
+        let ban_list = BanList::load(ban_list_path)?;
+
+        let (redial_tx, redial_rx) = {
+            let (tx, rx) = mpsc::unbounded_channel();
+
+            (tx, RwLock::new(rx))
+        };
+
+        let (ban_tx, ban_rx) = {
+            let (tx, rx) = mpsc::unbounded_channel();
+
+            (tx, RwLock::new(rx))
+        };
+
         let runtime = Runtime {
             peer_map: peer_map.clone(),
         };
@@ -76,13 +117,22 @@ impl PeerTable {
 
         let ps = PeerTable {
             peer_map,
-            slots_rx,
-            slots_tx,
+            inbound_slots,
+            outbound_slots,
+            outbound_capacity,
             peer_queue_tx,
             peer_queue_iter,
+            ban_list,
+            redial_tx,
+            redial_rx,
+            ban_tx,
+            ban_rx,
         };
 
-        info!("Initializing peer table, capacity: {}", capacity);
+        info!(
+            "Initializing peer table, capacity: {}, outbound capacity: {}",
+            capacity, outbound_capacity,
+        );
 
         Ok(ps)
     }
@@ -107,25 +157,171 @@ impl PeerTable {
         &self.peer_map
     }
 
-    pub async fn get_empty_slot(&self) -> Result<SlotGuard, PeerTableError> {
-        let mut slots_rx = self.slots_rx.write().await;
-
-        match slots_rx.recv().await {
-            Some(s) => {
-                let slot_guard = SlotGuard {
-                    slot: s,
-                    slots_tx: self.slots_tx.clone(),
-                };
+    // A slot for a peer who has dialed me. When there is none left, an
+    // inbound peer of poor reputation or of a crowded addr group makes room.
+    pub async fn get_inbound_slot(
+        &self,
+        ip: &str,
+    ) -> Result<SlotGuard, PeerTableError> {
+        self.check_addr_caps(ip, None).await?;
+
+        if let Some(s) =
+            self.inbound_slots.try_take(PeerDirection::Inbound).await
+        {
+            return Ok(s);
+        }
 
-                return Ok(slot_guard);
-            }
+        let evicted = match self.evict_inbound_peer().await {
+            Some(p) => p,
             None => {
                 return Err(format!(
-                    "Peer slots have beeen closed. Critical error"
+                    "Inbound slots are full and no peer can be evicted"
+                )
+                .into());
+            }
+        };
+
+        info!(
+            "Evicted an inbound peer to make room, her_public_key: {}",
+            evicted.get_public_key_short(),
+        );
+
+        drop(evicted);
+
+        self.inbound_slots
+            .take(
+                PeerDirection::Inbound,
+                Duration::from_millis(SLOT_RELEASE_TIMEOUT),
+            )
+            .await
+    }
+
+    // A slot for a peer I am about to dial
+    pub async fn get_outbound_slot(
+        &self,
+        ip: &str,
+    ) -> Result<SlotGuard, PeerTableError> {
+        self.check_addr_caps(ip, None).await?;
+
+        if !is_exempt_from_diversity(ip) {
+            let addr_group = get_addr_group(ip);
+
+            let (outbound_count, is_group_taken) = {
+                let peer_map = self.peer_map.read().await;
+
+                let outbound_peers: Vec<&Arc<Peer>> = peer_map
+                    .values()
+                    .filter(|p| p.get_direction() == PeerDirection::Outbound)
+                    .collect();
+
+                let is_group_taken = outbound_peers
+                    .iter()
+                    .any(|p| get_addr_group(p.get_ip()) == addr_group);
+
+                (outbound_peers.len() as isize, is_group_taken)
+            };
+
+            if is_group_taken
+                && outbound_count
+                    >= self.outbound_capacity - PROTECTED_OUTBOUND_SLOTS
+            {
+                return Err(format!(
+                    "Remaining outbound slots are kept for new addr groups, \
+                    addr_group: {}",
+                    addr_group,
                 )
                 .into());
             }
         }
+
+        match self.outbound_slots.try_take(PeerDirection::Outbound).await {
+            Some(s) => Ok(s),
+            None => Err(format!("Outbound slots are full").into()),
+        }
+    }
+
+    async fn check_addr_caps(
+        &self,
+        ip: &str,
+        her_public_key_str: Option<&str>,
+    ) -> Result<(), PeerTableError> {
+        if is_exempt_from_diversity(ip) {
+            return Ok(());
+        }
+
+        let addr_group = get_addr_group(ip);
+
+        let peer_map = self.peer_map.read().await;
+
+        let others = peer_map
+            .values()
+            .filter(|p| Some(p.get_public_key()) != her_public_key_str);
+
+        let mut ip_count = 0;
+        let mut group_count = 0;
+
+        for p in others {
+            if p.get_ip() == ip {
+                ip_count += 1;
+            }
+
+            if get_addr_group(p.get_ip()) == addr_group {
+                group_count += 1;
+            }
+        }
+
+        if ip_count >= MAX_PEERS_PER_IP {
+            return Err(format!("Too many peers of the ip, ip: {}", ip).into());
+        }
+
+        if group_count >= MAX_PEERS_PER_ADDR_GROUP {
+            return Err(format!(
+                "Too many peers of the addr group, addr_group: {}",
+                addr_group,
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    // Peers with a negative reputation go first, then the ones whose addr
+    // group is shared with other inbound peers. A table of well behaved
+    // peers of distinct groups evicts no one.
+    async fn evict_inbound_peer(&self) -> Option<Arc<Peer>> {
+        let inbound_peers: Vec<Arc<Peer>> = {
+            let peer_map = self.peer_map.read().await;
+
+            peer_map
+                .values()
+                .filter(|p| p.get_direction() == PeerDirection::Inbound)
+                .map(|p| p.clone())
+                .collect()
+        };
+
+        let mut candidates = Vec::with_capacity(inbound_peers.len());
+
+        for p in &inbound_peers {
+            candidates.push(EvictionCandidate {
+                public_key_str: p.get_public_key().to_string(),
+                addr_group: get_addr_group(p.get_ip()),
+                reputation: p.get_reputation().await,
+            });
+        }
+
+        let public_key_str = select_eviction_candidate(&candidates)?;
+
+        let peer = inbound_peers
+            .into_iter()
+            .find(|p| p.get_public_key() == public_key_str)?;
+
+        peer.set_disconnected_keeping_addr().await;
+
+        self.remove_peer(&peer).await;
+
+        peer.close();
+
+        Some(peer)
     }
 
     pub async fn insert_mapping(
@@ -134,6 +330,19 @@ impl PeerTable {
     ) -> Result<Option<Arc<Peer>>, PeerTableError> {
         let public_key_str = peer.get_public_key().to_string();
 
+        if self.ban_list.is_banned(&public_key_str).await {
+            return Err(format!(
+                "Peer is banned, her_public_key: {}",
+                peer.get_public_key_short(),
+            )
+            .into());
+        }
+
+        // Caps are checked again, as more than one peer of the same addr may
+        // have been handshaking at the same time
+        self.check_addr_caps(peer.get_ip(), Some(peer.get_public_key()))
+            .await?;
+
         debug!(
             "Peer table insert mapping, her_public_key: {},",
             peer.get_public_key_short().green(),
@@ -164,7 +373,213 @@ impl PeerTable {
         peer_vec
     }
 
+    pub async fn is_banned(&self, public_key_str: &String) -> bool {
+        self.ban_list.is_banned(public_key_str).await
+    }
+
+    // Bans the peer for BAN_DURATION and drops her from the table, which
+    // ends the connection once her routine lets go of it. The ban is handed
+    // over to whoever keeps the addrs as well.
+    pub async fn ban_peer(
+        &self,
+        peer: &Arc<Peer>,
+        reason: String,
+    ) -> Result<(), PeerTableError> {
+        let now = Utc::now().timestamp();
+
+        let entry = BanEntry {
+            public_key_str: peer.get_public_key().to_string(),
+            ip: peer.get_addr().known_addr.ip.clone(),
+            reason,
+            banned_at: now,
+            banned_until: now + BAN_DURATION,
+        };
+
+        info!(
+            "Banning peer, her_public_key: {}, reason: {}",
+            peer.get_public_key_short().yellow(),
+            entry.reason,
+        );
+
+        peer.set_peer_status(PeerStatus::Disconnected).await;
+
+        self.remove_peer(peer).await;
+
+        // In effect even if it fails to persist
+        if let Err(err) = self.ban_tx.send(entry.clone()) {
+            error!("Cannot enqueue a ban entry, err: {}", err);
+        }
+
+        self.ban_list.insert(entry).await
+    }
+
+    // Frees her slot once her routine lets go of her. A peer in good
+    // standing is handed over to be redialed, and her addr is left as it is
+    // so that discovery keeps it meanwhile.
+    pub async fn disconnect_peer(&self, peer: &Arc<Peer>) {
+        let is_good = peer.get_reputation().await >= 0
+            && !self.is_banned(&peer.get_public_key().to_string()).await;
+
+        if is_good {
+            peer.set_disconnected_keeping_addr().await;
+
+            let redial = (peer.get_addr().clone(), peer.get_direction());
+
+            if let Err(err) = self.redial_tx.send(redial) {
+                error!("Cannot enqueue an addr to redial, err: {}", err);
+            }
+        } else {
+            peer.set_peer_status(PeerStatus::Disconnected).await;
+        }
+
+        self.remove_peer(peer).await;
+    }
+
+    // Only if she has not been replaced by a newer connection in between
+    async fn remove_peer(&self, peer: &Arc<Peer>) {
+        let mut peer_map = self.peer_map.write().await;
+
+        if let Some(p) = peer_map.get(peer.get_public_key()) {
+            if Arc::ptr_eq(p, peer) {
+                peer_map.remove(peer.get_public_key());
+            }
+        }
+    }
+
+    // Resolves with the direction she was connected in as well
+    pub async fn next_redial_addr(
+        &self,
+    ) -> Result<(Arc<DiscAddr>, PeerDirection), PeerTableError> {
+        let mut redial_rx = self.redial_rx.write().await;
+
+        match redial_rx.recv().await {
+            Some(a) => Ok(a),
+            None => Err(format!("Redial queue has been closed").into()),
+        }
+    }
+
+    pub async fn next_ban_entry(&self) -> Result<BanEntry, PeerTableError> {
+        let mut ban_rx = self.ban_rx.write().await;
+
+        match ban_rx.recv().await {
+            Some(e) => Ok(e),
+            None => Err(format!("Ban queue has been closed").into()),
+        }
+    }
+
+    pub async fn get_peer_liveness(&self) -> Vec<PeerLiveness> {
+        let peers: Vec<Arc<Peer>> = {
+            let peer_map = self.peer_map.read().await;
+
+            peer_map.values().map(|p| p.clone()).collect()
+        };
+
+        let mut liveness = Vec::with_capacity(peers.len());
+
+        for peer in peers {
+            liveness.push(peer.get_liveness().await);
+        }
+
+        liveness
+    }
+
+    pub async fn get_ban_list(&self) -> Vec<BanEntry> {
+        self.ban_list.get_entries().await
+    }
+
     pub fn peer_queue_iter(&self) -> Arc<RwLock<PeerIterator>> {
         self.peer_queue_iter.clone()
     }
 }
+
+struct SlotPool {
+    slots_tx: Arc<UnboundedSender<Slot>>,
+    slots_rx: RwLock<UnboundedReceiver<Slot>>,
+}
+
+impl SlotPool {
+    fn new(idx_from: isize, idx_to: isize) -> SlotPool {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let slots_tx = Arc::new(tx);
+        let slots_rx = RwLock::new(rx);
+
+        for idx in idx_from..idx_to {
+            let s = Slot { idx };
+
+            match slots_tx.send(s) {
+                Ok(_) => (),
+                Err(err) => {
+                    error!("slots channel has been closed, err: {}", err,);
+                }
+            };
+        }
+
+        SlotPool { slots_tx, slots_rx }
+    }
+
+    fn make_guard(&self, slot: Slot, direction: PeerDirection) -> SlotGuard {
+        SlotGuard {
+            slot,
+            slots_tx: self.slots_tx.clone(),
+            direction,
+        }
+    }
+
+    async fn try_take(&self, direction: PeerDirection) -> Option<SlotGuard> {
+        let mut slots_rx = self.slots_rx.write().await;
+
+        match slots_rx.try_recv() {
+            Ok(s) => Some(self.make_guard(s, direction)),
+            Err(_) => None,
+        }
+    }
+
+    async fn take(
+        &self,
+        direction: PeerDirection,
+        timeout: Duration,
+    ) -> Result<SlotGuard, PeerTableError> {
+        let mut slots_rx = self.slots_rx.write().await;
+
+        match tokio::time::timeout(timeout, slots_rx.recv()).await {
+            Ok(Some(s)) => Ok(self.make_guard(s, direction)),
+            Ok(None) => {
+                Err(format!("Peer slots have beeen closed. Critical error")
+                    .into())
+            }
+            Err(_) => {
+                Err(format!("Timed out waiting for a slot to be released")
+                    .into())
+            }
+        }
+    }
+}
+
+pub(crate) struct EvictionCandidate {
+    pub(crate) public_key_str: String,
+    pub(crate) addr_group: String,
+    pub(crate) reputation: i32,
+}
+
+pub(crate) fn select_eviction_candidate(
+    candidates: &[EvictionCandidate],
+) -> Option<String> {
+    let mut group_counts: HashMap<&str, usize> = HashMap::new();
+
+    for c in candidates {
+        *group_counts.entry(&c.addr_group).or_insert(0) += 1;
+    }
+
+    candidates
+        .iter()
+        .map(|c| (c, group_counts[c.addr_group.as_str()]))
+        .filter(|(c, group_count)| c.reputation < 0 || *group_count > 1)
+        .min_by_key(|(c, group_count)| {
+            (
+                c.reputation.min(0),
+                std::cmp::Reverse(*group_count),
+                c.reputation,
+            )
+        })
+        .map(|(c, _)| c.public_key_str.clone())
+}
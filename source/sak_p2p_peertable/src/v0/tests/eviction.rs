@@ -0,0 +1,61 @@
+use crate::{
+    get_addr_group, is_exempt_from_diversity, select_eviction_candidate,
+    EvictionCandidate,
+};
+
+fn make_candidate(
+    public_key_str: &str,
+    ip: &str,
+    reputation: i32,
+) -> EvictionCandidate {
+    EvictionCandidate {
+        public_key_str: public_key_str.to_string(),
+        addr_group: get_addr_group(ip),
+        reputation,
+    }
+}
+
+#[test]
+fn test_addr_group_is_the_subnet() {
+    assert_eq!(get_addr_group("203.0.113.7"), "203.0.113");
+    assert_eq!(get_addr_group("203.0.113.250"), "203.0.113");
+    assert_ne!(get_addr_group("203.0.114.7"), "203.0.113");
+    assert_eq!(get_addr_group("2001:db8:1:2::1"), "2001:db8:1");
+
+    assert!(is_exempt_from_diversity("127.0.0.1"));
+    assert!(!is_exempt_from_diversity("203.0.113.7"));
+}
+
+#[test]
+fn test_eviction_favors_low_score_peers() {
+    let candidates = vec![
+        make_candidate("a", "198.51.100.1", 10),
+        make_candidate("b", "198.51.100.2", 0),
+        make_candidate("c", "203.0.113.1", -20),
+    ];
+
+    assert_eq!(select_eviction_candidate(&candidates), Some("c".into()));
+}
+
+#[test]
+fn test_eviction_favors_redundant_peers() {
+    let candidates = vec![
+        make_candidate("a", "198.51.100.1", 10),
+        make_candidate("b", "198.51.100.2", 3),
+        make_candidate("c", "198.51.100.3", 5),
+        make_candidate("d", "203.0.113.1", 0),
+    ];
+
+    // Of the crowded group, the one of the lowest score
+    assert_eq!(select_eviction_candidate(&candidates), Some("b".into()));
+}
+
+#[test]
+fn test_no_eviction_among_diverse_and_well_behaved_peers() {
+    let candidates = vec![
+        make_candidate("a", "198.51.100.1", 0),
+        make_candidate("b", "203.0.113.1", 4),
+    ];
+
+    assert_eq!(select_eviction_candidate(&candidates), None);
+}
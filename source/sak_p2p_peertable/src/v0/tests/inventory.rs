@@ -0,0 +1,17 @@
+use crate::KnownInventory;
+
+#[test]
+fn test_known_inventory_forgets_the_oldest_first() {
+    let mut inventory = KnownInventory::new(3);
+
+    for h in ["a", "b", "c", "b", "d"] {
+        inventory.insert(&h.to_string());
+    }
+
+    assert_eq!(inventory.len(), 3);
+    assert!(!inventory.contains(&"a".to_string()));
+
+    for h in ["b", "c", "d"] {
+        assert!(inventory.contains(&h.to_string()));
+    }
+}
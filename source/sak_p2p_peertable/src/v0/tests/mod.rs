@@ -0,0 +1,3 @@
+mod eviction;
+mod inventory;
+mod reputation;
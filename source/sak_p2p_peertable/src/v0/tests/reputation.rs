@@ -0,0 +1,23 @@
+use crate::{PeerBehavior, BAN_THRESHOLD};
+
+#[test]
+fn test_no_single_behavior_has_a_fresh_peer_banned() {
+    let behaviors = [
+        PeerBehavior::ValidBlock,
+        PeerBehavior::ValidTx,
+        PeerBehavior::MalformedMsg,
+        PeerBehavior::UnexpectedMsg,
+        PeerBehavior::InvalidTx,
+        PeerBehavior::InvalidBlock,
+    ];
+
+    for behavior in behaviors {
+        assert!(
+            behavior.get_score_delta() > BAN_THRESHOLD,
+            "A single {} should not have a fresh peer banned",
+            behavior,
+        );
+    }
+
+    assert!(PeerBehavior::InvalidBlock.get_score_delta() * 2 <= BAN_THRESHOLD);
+}
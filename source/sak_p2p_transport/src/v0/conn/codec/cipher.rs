@@ -0,0 +1,113 @@
+use crate::TrptError;
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+// Frames sent (or received) under a key before both sides move on to the
+// next one
+pub const DEFAULT_REKEY_INTERVAL: u64 = 10_000;
+
+pub(crate) const AEAD_TAG_LEN: usize = 16;
+
+const REKEY_INFO: &'static [u8] = b"saksaha_p2p_rekey";
+
+// One direction of an upgraded connection. Each frame is sealed under the
+// current key with the frame counter as its nonce, so a frame that is
+// dropped, reordered or replayed fails to open.
+pub(crate) struct CipherState {
+    key: [u8; 32],
+    cipher: ChaCha20Poly1305,
+    counter: u64,
+    // 0 disables rekeying
+    rekey_interval: u64,
+}
+
+impl CipherState {
+    pub(crate) fn new(key: [u8; 32], rekey_interval: u64) -> CipherState {
+        CipherState {
+            key,
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+            counter: 0,
+            rekey_interval,
+        }
+    }
+
+    pub(crate) fn encrypt(
+        &mut self,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, TrptError> {
+        let nonce = self.make_nonce();
+        let nonce = Nonce::from_slice(&nonce);
+
+        let ciphertext = match self.cipher.encrypt(nonce, plaintext) {
+            Ok(c) => c,
+            Err(err) => {
+                return Err(
+                    format!("Cannot seal the frame, err: {}", err).into()
+                );
+            }
+        };
+
+        self.advance()?;
+
+        Ok(ciphertext)
+    }
+
+    pub(crate) fn decrypt(
+        &mut self,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, TrptError> {
+        let nonce = self.make_nonce();
+        let nonce = Nonce::from_slice(&nonce);
+
+        let plaintext = match self.cipher.decrypt(nonce, ciphertext) {
+            Ok(p) => p,
+            Err(_) => {
+                return Err(format!(
+                    "Cannot open the frame, it may have been tampered with, \
+                    counter: {}",
+                    self.counter
+                )
+                .into());
+            }
+        };
+
+        self.advance()?;
+
+        Ok(plaintext)
+    }
+
+    fn make_nonce(&self) -> [u8; 12] {
+        let mut nonce = [0; 12];
+        nonce[4..].copy_from_slice(&self.counter.to_le_bytes());
+
+        nonce
+    }
+
+    fn advance(&mut self) -> Result<(), TrptError> {
+        if self.counter == u64::MAX {
+            return Err("Nonces of the key are exhausted".into());
+        }
+
+        self.counter += 1;
+
+        if self.rekey_interval > 0 && self.counter >= self.rekey_interval {
+            self.rekey()?;
+        }
+
+        Ok(())
+    }
+
+    // The next key is derived from the current one, which is then forgotten
+    fn rekey(&mut self) -> Result<(), TrptError> {
+        let next_key =
+            sak_crypto::make_hkdf_sha256(&[], &self.key, REKEY_INFO, 32)?;
+
+        self.key.copy_from_slice(&next_key);
+        self.cipher = ChaCha20Poly1305::new(Key::from_slice(&self.key));
+        self.counter = 0;
+
+        Ok(())
+    }
+}
@@ -1,62 +1,133 @@
-use super::{dec, enc};
-use crate::{Msg, TrptError};
-use bytes::BytesMut;
-use chacha20::cipher::StreamCipher;
-use chacha20::ChaCha20;
+use super::{dec, enc, CipherState, AEAD_TAG_LEN};
+use crate::{
+    handshake::SessionKeys, Envelope, Msg, RequestId, TrptError, NO_REQUEST_ID,
+};
+use bytes::{Buf, BufMut, BytesMut};
 use tokio_util::codec::{Decoder, Encoder};
 
+// Upper bound of a sealed frame, so that a length prefix from the wire
+// cannot make us buffer without limit
+pub const MAX_SEALED_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+const LEN_PREFIX_LEN: usize = 4;
+
+const REQUEST_ID_LEN: usize = 8;
+
+// A frame is sealed as a whole and goes on the wire as a 4-byte (big endian)
+// length followed by the ciphertext and its tag. Inside the seal, the frame is
+// preceded by the 8-byte (big endian) request id, if both sides have agreed
+// on having one.
 pub struct UpgradedP2PCodec {
-    pub(crate) cipher: ChaCha20,
+    send_cipher: CipherState,
+    recv_cipher: CipherState,
+    has_req_ids: bool,
 }
 
-impl Encoder<Msg> for UpgradedP2PCodec {
+impl UpgradedP2PCodec {
+    pub(crate) fn new(
+        session_keys: &SessionKeys,
+        rekey_interval: u64,
+        has_req_ids: bool,
+    ) -> UpgradedP2PCodec {
+        UpgradedP2PCodec {
+            send_cipher: CipherState::new(
+                session_keys.send_key,
+                rekey_interval,
+            ),
+            recv_cipher: CipherState::new(
+                session_keys.recv_key,
+                rekey_interval,
+            ),
+            has_req_ids,
+        }
+    }
+
+    pub(crate) fn has_req_ids(&self) -> bool {
+        self.has_req_ids
+    }
+}
+
+impl Encoder<Envelope> for UpgradedP2PCodec {
     type Error = TrptError;
 
     fn encode(
         &mut self,
-        item: Msg,
+        item: Envelope,
         dst: &mut BytesMut,
     ) -> Result<(), TrptError> {
-        enc::encode_into_frame(item, dst)?;
+        let mut plaintext = BytesMut::new();
 
-        // let t = dst.to_vec();
+        if self.has_req_ids {
+            plaintext.put_u64(item.req_id);
+        }
 
-        self.cipher.apply_keystream(dst);
+        enc::encode_into_frame(item.msg, &mut plaintext)?;
 
-        // println!(
-        //     "\n666 upgraded encoded!!, id: {}, r: {}, \noriginal buf: {:?}\nbuf: {:?}",
-        //     self.id,
-        //     rand,
-        //     t,
-        //     dst.to_vec()
-        // );
+        let ciphertext = self.send_cipher.encrypt(&plaintext)?;
+
+        if ciphertext.len() > MAX_SEALED_FRAME_LEN {
+            return Err(format!(
+                "Sealed frame is too big, len: {}",
+                ciphertext.len()
+            )
+            .into());
+        }
+
+        dst.reserve(LEN_PREFIX_LEN + ciphertext.len());
+        dst.put_u32(ciphertext.len() as u32);
+        dst.put_slice(&ciphertext);
 
         return Ok(());
     }
 }
 
 impl Decoder for UpgradedP2PCodec {
-    type Item = Msg;
+    type Item = Envelope;
     type Error = TrptError;
 
     fn decode(
         &mut self,
         src: &mut BytesMut,
     ) -> Result<Option<Self::Item>, TrptError> {
-        // let t = src.to_vec();
+        if src.len() < LEN_PREFIX_LEN {
+            return Ok(None);
+        }
+
+        let mut len_bytes = [0; LEN_PREFIX_LEN];
+        len_bytes.copy_from_slice(&src[..LEN_PREFIX_LEN]);
+        let len = u32::from_be_bytes(len_bytes) as usize;
+
+        if len < AEAD_TAG_LEN || len > MAX_SEALED_FRAME_LEN {
+            return Err(format!("Sealed frame has invalid len: {}", len).into());
+        }
+
+        if src.len() < LEN_PREFIX_LEN + len {
+            src.reserve(LEN_PREFIX_LEN + len - src.len());
+
+            return Ok(None);
+        }
+
+        src.advance(LEN_PREFIX_LEN);
+        let ciphertext = src.split_to(len);
+
+        let plaintext = self.recv_cipher.decrypt(&ciphertext)?;
 
-        self.cipher.apply_keystream(src);
+        let mut buf = BytesMut::from(plaintext.as_slice());
 
-        // println!(
-        //     "\n1313 upgraded decoded, id: {}\noriginal buf: {:?}\nsrc: {:?}",
-        //     self.id,
-        //     t,
-        //     src.to_vec()
-        // );
+        let req_id: RequestId = if self.has_req_ids {
+            if buf.len() < REQUEST_ID_LEN {
+                return Err("Sealed frame does not hold a request id".into());
+            }
 
-        let msg = dec::decode_into_msg(src);
+            buf.get_u64()
+        } else {
+            NO_REQUEST_ID
+        };
 
-        msg
+        match dec::decode_into_msg(&mut buf)? {
+            Some(msg) => Ok(Some(Envelope { req_id, msg })),
+            None => Err("Sealed frame does not hold an entire msg".into()),
+        }
     }
 }
 
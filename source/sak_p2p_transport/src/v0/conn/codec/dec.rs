@@ -1,6 +1,7 @@
 use crate::{
-    BlockAckMsg, BlockHashSyncMsg, BlockSynMsg, HandshakeMsg, Msg, MsgType,
-    PingMsg, TrptError, TxAckMsg, TxHashSyncMsg, TxSynMsg,
+    BlockAckMsg, BlockHashSyncMsg, BlockSynMsg, BlockTxsMsg, BlockTxsReqMsg,
+    CompactBlockSynMsg, DisconnectMsg, HandshakeMsg, Msg, MsgType, PingMsg,
+    TrptError, TxAckMsg, TxHashSyncMsg, TxSynMsg,
 };
 use bytes::BytesMut;
 use sak_p2p_frame::{frame_io, Parse};
@@ -65,10 +66,31 @@ pub(super) fn decode_into_msg(
                 let block_ack = BlockAckMsg::from_parse(&mut parse)?;
                 Msg::BlockAck(block_ack)
             }
+            MsgType::COMPACT_BLOCK_SYN => {
+                let compact_block_syn =
+                    CompactBlockSynMsg::from_parse(&mut parse)?;
+                Msg::CompactBlockSyn(compact_block_syn)
+            }
+            MsgType::BLOCK_TXS_REQ => {
+                let block_txs_req = BlockTxsReqMsg::from_parse(&mut parse)?;
+                Msg::BlockTxsReq(block_txs_req)
+            }
+            MsgType::BLOCK_TXS => {
+                let block_txs = BlockTxsMsg::from_parse(&mut parse)?;
+                Msg::BlockTxs(block_txs)
+            }
             MsgType::PING => {
                 let ping = PingMsg::from_parse(&mut parse)?;
                 Msg::Ping(ping)
             }
+            MsgType::PONG => {
+                let pong = PingMsg::from_parse(&mut parse)?;
+                Msg::Pong(pong)
+            }
+            MsgType::DISCONNECT => {
+                let disconnect = DisconnectMsg::from_parse(&mut parse)?;
+                Msg::Disconnect(disconnect)
+            }
             _ => {
                 return Err(format!(
                     "Frame does have invalid msg_type, type: {}",
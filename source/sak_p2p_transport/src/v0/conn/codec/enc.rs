@@ -7,7 +7,8 @@ pub(super) fn encode_into_frame(
     dst: &mut BytesMut,
 ) -> Result<&'static str, TrptError> {
     let (frame, msg_type) = match item {
-        Msg::Ping(ping) => (ping.into_frame(), MsgType::PING),
+        Msg::Ping(ping) => (ping.into_ping_frame(), MsgType::PING),
+        Msg::Pong(pong) => (pong.into_pong_frame(), MsgType::PONG),
         Msg::HandshakeSyn(handshake) => {
             (handshake.into_syn_frame(), MsgType::HANDSHAKE_SYN)
         }
@@ -32,6 +33,10 @@ pub(super) fn encode_into_frame(
             (sync_block.into_frame(), MsgType::BLOCK_SYN)
         }
         Msg::BlockAck(m) => (m.into_frame(), MsgType::BLOCK_ACK),
+        Msg::CompactBlockSyn(m) => (m.into_frame(), MsgType::COMPACT_BLOCK_SYN),
+        Msg::BlockTxsReq(m) => (m.into_frame(), MsgType::BLOCK_TXS_REQ),
+        Msg::BlockTxs(m) => (m.into_frame(), MsgType::BLOCK_TXS),
+        Msg::Disconnect(m) => (m.into_frame(), MsgType::DISCONNECT),
     };
 
     match frame_io::write_frame(dst, &frame) {
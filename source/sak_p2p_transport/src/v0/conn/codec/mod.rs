@@ -1,5 +1,8 @@
+mod cipher;
 mod codec;
 mod dec;
 mod enc;
 
+pub use cipher::DEFAULT_REKEY_INTERVAL;
+pub(crate) use cipher::*;
 pub use codec::*;
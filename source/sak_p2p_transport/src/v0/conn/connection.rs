@@ -1,7 +1,8 @@
 use super::codec::P2PCodec;
-use crate::{TrptError, UpgradedConn, UpgradedP2PCodec};
-use chacha20::{cipher::KeyIvInit, ChaCha20};
-use sak_crypto::{PublicKey, SharedSecret};
+use crate::{
+    handshake::{capability, NegotiatedProtocol, SessionKeys},
+    TrptError, UpgradedConn, UpgradedP2PCodec,
+};
 use std::net::SocketAddr;
 use tokio::net::TcpStream;
 use tokio_util::codec::Framed;
@@ -34,16 +35,16 @@ impl Conn {
 
     pub async fn upgrade(
         self,
-        shared_secret: SharedSecret,
-        nonce: &[u8],
+        session_keys: SessionKeys,
+        rekey_interval: u64,
         her_public_key: &String,
+        protocol: &NegotiatedProtocol,
     ) -> Result<UpgradedConn, TrptError> {
-        let cipher = ChaCha20::new(
-            shared_secret.as_bytes().as_slice().into(),
-            nonce.into(),
-        );
+        let has_req_ids = protocol.supports(capability::REQUEST_ID);
 
-        let socket = self.socket.map_codec(|_| UpgradedP2PCodec { cipher });
+        let socket = self.socket.map_codec(|_| {
+            UpgradedP2PCodec::new(&session_keys, rekey_interval, has_req_ids)
+        });
 
         let conn_id = format!(
             "{}-{}",
@@ -1,9 +1,48 @@
 use crate::{Msg, TrptError, UpgradedP2PCodec};
-use futures::{SinkExt, StreamExt};
-use std::net::SocketAddr;
-use tokio::net::TcpStream;
+use futures::{
+    stream::{SplitSink, SplitStream},
+    SinkExt, StreamExt,
+};
+use log::{debug, warn};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    net::TcpStream,
+    sync::{mpsc, oneshot, Mutex},
+    task::JoinHandle,
+};
 use tokio_util::codec::Framed;
 
+// Msgs that are not responses wait here for the peer node routine
+pub(crate) const INBOUND_QUEUE_CAPACITY: usize = 64;
+
+// Msgs that do not fit in the queue are held back here, as the conn keeps
+// reading off the socket for the responses behind them. A peer that gets
+// this far ahead of the routine is dropped.
+pub(crate) const MAX_INBOUND_BACKLOG: usize = 256;
+
+// Ids of the requests the initiator of the conn sends are odd, and those of
+// the other side even, so that the two never collide. 0 is for msgs that are
+// neither a request nor a response.
+pub type RequestId = u64;
+
+pub const NO_REQUEST_ID: RequestId = 0;
+
+// A msg as it goes over an upgraded conn. A response carries the id of the
+// request it answers.
+#[derive(Debug)]
+pub struct Envelope {
+    pub req_id: RequestId,
+    pub msg: Msg,
+}
+
 pub struct SendReceipt {
     __created_by_conn: bool,
 }
@@ -12,10 +51,23 @@ pub struct RecvReceipt {
     __created_by_conn: bool,
 }
 
+type PendingRequests = Arc<Mutex<HashMap<RequestId, oneshot::Sender<Msg>>>>;
+
+// Reading off the socket happens in a task of its own, which hands each
+// response to the request waiting for it. Everything else is read with
+// next_msg(). Any number of requests can be in flight at the same time.
 pub struct UpgradedConn {
     socket_addr: SocketAddr,
     conn_id: String,
-    socket: Framed<TcpStream, UpgradedP2PCodec>,
+    sink: Mutex<SplitSink<Framed<TcpStream, UpgradedP2PCodec>, Envelope>>,
+    inbound_rx: Mutex<mpsc::Receiver<Result<Envelope, TrptError>>>,
+    pending_reqs: PendingRequests,
+    next_req_id: AtomicU64,
+    has_req_ids: bool,
+    // A peer that does not put request ids on the wire gets one request at a
+    // time, the response to which is the next one she sends
+    legacy_req_lock: Mutex<()>,
+    dispatch_handle: JoinHandle<()>,
 }
 
 impl UpgradedConn {
@@ -23,21 +75,152 @@ impl UpgradedConn {
         socket_addr: SocketAddr,
         socket: Framed<TcpStream, UpgradedP2PCodec>,
         conn_id: String,
-        _is_initiator: bool,
+        is_initiator: bool,
     ) -> UpgradedConn {
+        let has_req_ids = socket.codec().has_req_ids();
+
+        let (sink, stream) = socket.split();
+
+        let (inbound_tx, inbound_rx) = mpsc::channel(INBOUND_QUEUE_CAPACITY);
+
+        let pending_reqs: PendingRequests =
+            Arc::new(Mutex::new(HashMap::new()));
+
+        let dispatch_handle = {
+            let pending_reqs = pending_reqs.clone();
+            let conn_id = conn_id.clone();
+
+            tokio::spawn(async move {
+                dispatch(
+                    stream,
+                    inbound_tx,
+                    pending_reqs,
+                    is_initiator,
+                    has_req_ids,
+                    conn_id,
+                )
+                .await;
+            })
+        };
+
         let upgraded_conn = UpgradedConn {
             socket_addr,
-            socket,
             conn_id,
+            sink: Mutex::new(sink),
+            inbound_rx: Mutex::new(inbound_rx),
+            pending_reqs,
+            next_req_id: AtomicU64::new(if is_initiator { 1 } else { 2 }),
+            has_req_ids,
+            legacy_req_lock: Mutex::new(()),
+            dispatch_handle,
         };
 
         upgraded_conn
     }
 
-    pub async fn send(&mut self, msg: Msg) -> Result<SendReceipt, TrptError> {
+    // Msg that does not expect a response
+    pub async fn send(&self, msg: Msg) -> Result<SendReceipt, TrptError> {
+        self.send_envelope(Envelope {
+            req_id: NO_REQUEST_ID,
+            msg,
+        })
+        .await
+    }
+
+    pub async fn respond(
+        &self,
+        req_id: RequestId,
+        msg: Msg,
+    ) -> Result<SendReceipt, TrptError> {
+        self.send_envelope(Envelope { req_id, msg }).await
+    }
+
+    // Resolves with the msg she responds with, unless it takes longer than
+    // the timeout
+    pub async fn request(
+        &self,
+        msg: Msg,
+        timeout: Duration,
+    ) -> Result<(Msg, RecvReceipt), TrptError> {
+        let _legacy_req_guard = if self.has_req_ids {
+            None
+        } else {
+            Some(self.legacy_req_lock.lock().await)
+        };
+
         let msg_type = msg.to_string();
 
-        match self.socket.send(msg).await {
+        let req_id = self.next_req_id.fetch_add(2, Ordering::SeqCst);
+
+        let (resp_tx, resp_rx) = oneshot::channel();
+
+        {
+            let mut pending_reqs = self.pending_reqs.lock().await;
+            pending_reqs.insert(req_id, resp_tx);
+        }
+
+        if let Err(err) = self.send_envelope(Envelope { req_id, msg }).await {
+            self.pending_reqs.lock().await.remove(&req_id);
+
+            return Err(err);
+        }
+
+        let resp = match tokio::time::timeout(timeout, resp_rx).await {
+            Ok(Ok(m)) => m,
+            Ok(Err(_)) => {
+                return Err(format!(
+                    "Conn has closed before the response, msg: {}, \
+                    conn_id: {}",
+                    msg_type, self.conn_id
+                )
+                .into());
+            }
+            Err(_) => {
+                self.pending_reqs.lock().await.remove(&req_id);
+
+                return Err(format!(
+                    "Request has timed out, msg: {}, conn_id: {}, \
+                    timeout: {:?}",
+                    msg_type, self.conn_id, timeout
+                )
+                .into());
+            }
+        };
+
+        let receipt = RecvReceipt {
+            __created_by_conn: true,
+        };
+
+        Ok((resp, receipt))
+    }
+
+    // Next msg that is not a response to a request of ours. None once the
+    // conn is closed.
+    pub async fn next_msg(
+        &self,
+    ) -> (Option<Result<Envelope, TrptError>>, RecvReceipt) {
+        let msg = self.inbound_rx.lock().await.recv().await;
+
+        let receipt = RecvReceipt {
+            __created_by_conn: true,
+        };
+
+        (msg, receipt)
+    }
+
+    pub(crate) async fn get_pending_request_count(&self) -> usize {
+        self.pending_reqs.lock().await.len()
+    }
+
+    async fn send_envelope(
+        &self,
+        envelope: Envelope,
+    ) -> Result<SendReceipt, TrptError> {
+        let msg_type = envelope.msg.to_string();
+
+        let mut sink = self.sink.lock().await;
+
+        match sink.send(envelope).await {
             Ok(_) => (),
             Err(err) => {
                 return Err(format!(
@@ -54,16 +237,115 @@ impl UpgradedConn {
 
         Ok(receipt)
     }
+}
 
-    pub async fn next_msg(
-        &mut self,
-    ) -> (Option<Result<Msg, TrptError>>, RecvReceipt) {
-        let msg = self.socket.next().await;
+impl Drop for UpgradedConn {
+    fn drop(&mut self) {
+        self.dispatch_handle.abort();
+    }
+}
 
-        let receipt = RecvReceipt {
-            __created_by_conn: true,
-        };
+async fn dispatch(
+    mut stream: SplitStream<Framed<TcpStream, UpgradedP2PCodec>>,
+    inbound_tx: mpsc::Sender<Result<Envelope, TrptError>>,
+    pending_reqs: PendingRequests,
+    is_initiator: bool,
+    has_req_ids: bool,
+    conn_id: String,
+) {
+    let is_mine = |req_id: RequestId| {
+        req_id != NO_REQUEST_ID && (req_id % 2 == 1) == is_initiator
+    };
 
-        (msg, receipt)
+    let mut backlog: VecDeque<Result<Envelope, TrptError>> = VecDeque::new();
+
+    loop {
+        tokio::select! {
+            biased;
+
+            permit = inbound_tx.reserve(), if !backlog.is_empty() => {
+                let permit = match permit {
+                    Ok(p) => p,
+                    Err(_) => break,
+                };
+
+                if let Some(e) = backlog.pop_front() {
+                    permit.send(e);
+                }
+            },
+            maybe_envelope = stream.next() => {
+                let maybe_envelope = match maybe_envelope {
+                    Some(e) => e,
+                    None => break,
+                };
+
+                let resp_tx = match &maybe_envelope {
+                    Ok(envelope) if has_req_ids => {
+                        if is_mine(envelope.req_id) {
+                            let resp_tx = pending_reqs
+                                .lock()
+                                .await
+                                .remove(&envelope.req_id);
+
+                            if resp_tx.is_none() {
+                                debug!(
+                                    "Response has come after the request \
+                                    is given up, discarding, msg: {}, \
+                                    conn_id: {}",
+                                    envelope.msg, conn_id,
+                                );
+
+                                continue;
+                            }
+
+                            resp_tx
+                        } else {
+                            None
+                        }
+                    }
+                    Ok(envelope) if envelope.msg.is_response() => {
+                        let mut pending_reqs = pending_reqs.lock().await;
+
+                        let oldest = pending_reqs.keys().min().map(|k| *k);
+
+                        oldest.and_then(|k| pending_reqs.remove(&k))
+                    }
+                    _ => None,
+                };
+
+                let maybe_envelope = match (resp_tx, maybe_envelope) {
+                    (Some(resp_tx), Ok(envelope)) => {
+                        let _ = resp_tx.send(envelope.msg);
+
+                        continue;
+                    }
+                    (_, e) => e,
+                };
+
+                if backlog.len() >= MAX_INBOUND_BACKLOG {
+                    warn!(
+                        "Peer is too far ahead of us, closing the conn, \
+                        conn_id: {}",
+                        conn_id,
+                    );
+
+                    backlog.clear();
+
+                    break;
+                }
+
+                backlog.push_back(maybe_envelope);
+            }
+        }
+    }
+
+    // Requests still waiting are woken up with an error
+    pending_reqs.lock().await.clear();
+
+    // What she has said before closing is still read
+    for e in backlog {
+        if inbound_tx.send(e).await.is_err() {
+            break;
+        }
     }
 }
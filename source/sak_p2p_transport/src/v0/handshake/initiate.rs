@@ -1,11 +1,13 @@
-use crate::HandshakeMsg;
-use crate::{Conn, Msg, Transport};
+use super::{
+    derive_session_keys, parse_public_key, EphemeralKey, ProtocolInfo,
+};
+use crate::{Conn, DisconnectReason, Msg, Transport, DEFAULT_REKEY_INTERVAL};
+use crate::{HandshakeMsg, TrptError};
 use futures::SinkExt;
 use futures::StreamExt;
 use sak_p2p_id::Identity;
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::RwLock;
 
 #[derive(Error, Debug)]
 pub enum HandshakeInitError {
@@ -65,12 +67,39 @@ pub enum HandshakeInitError {
 
     #[error("Could not connect connection, err: {err}")]
     ConnectionCreateFail { err: String },
+
+    #[error(
+        "Handshake ack is from someone else, expected public_key: \
+        {expected}, public_key: {public_key}"
+    )]
+    UnexpectedPeer {
+        expected: String,
+        public_key: String,
+    },
+
+    #[error("Could not derive session keys, err: {err}")]
+    SessionKeyDeriveFail { err: TrptError },
+
+    #[error(
+        "Peer has refused the handshake, reason: {reason}, detail: {detail}"
+    )]
+    PeerDisconnected {
+        reason: DisconnectReason,
+        detail: String,
+    },
+
+    #[error("Peer is not compatible, reason: {reason}, detail: {detail}")]
+    IncompatiblePeer {
+        reason: DisconnectReason,
+        detail: String,
+    },
 }
 
 pub struct HandshakeInitArgs {
     pub identity: Arc<Identity>,
     pub conn: Conn,
     pub public_key_str: String,
+    pub protocol_info: Arc<ProtocolInfo>,
 }
 
 pub async fn initiate_handshake(
@@ -80,12 +109,17 @@ pub async fn initiate_handshake(
         identity,
         mut conn,
         public_key_str,
+        protocol_info,
     } = handshake_init_args;
 
-    let handshake_msg = match HandshakeMsg::new(
+    let ephemeral_key = EphemeralKey::generate();
+
+    let handshake_syn = match HandshakeMsg::new(
         identity.p2p_port,
         identity.credential.public_key_str.clone(),
-        public_key_str,
+        public_key_str.clone(),
+        ephemeral_key.public_key_str.clone(),
+        protocol_info.as_ref().clone(),
     ) {
         Ok(h) => h,
         Err(err) => {
@@ -93,7 +127,11 @@ pub async fn initiate_handshake(
         }
     };
 
-    match conn.socket.send(Msg::HandshakeSyn(handshake_msg)).await {
+    match conn
+        .socket
+        .send(Msg::HandshakeSyn(handshake_syn.clone()))
+        .await
+    {
         Ok(_) => (),
         Err(err) => {
             return Err(HandshakeInitError::FrameWriteFail {
@@ -104,13 +142,18 @@ pub async fn initiate_handshake(
 
     let handshake_ack = match conn.socket.next().await {
         Some(maybe_msg) => match maybe_msg {
-            Ok(msg) => {
-                if let Msg::HandshakeAck(h) = msg {
-                    h
-                } else {
+            Ok(msg) => match msg {
+                Msg::HandshakeAck(h) => h,
+                Msg::Disconnect(d) => {
+                    return Err(HandshakeInitError::PeerDisconnected {
+                        reason: d.reason,
+                        detail: d.detail,
+                    });
+                }
+                _ => {
                     return Err(HandshakeInitError::HandshakeAckWrongArrived);
                 }
-            }
+            },
             Err(err) => {
                 return Err(HandshakeInitError::HandshakeAckParseFail {
                     err: err.to_string(),
@@ -122,26 +165,73 @@ pub async fn initiate_handshake(
         }
     };
 
-    let her_public_key_str = handshake_ack.src_public_key_str;
-    let my_secret_key = &identity.credential.secret_key;
-    let her_public_key =
-        match sak_crypto::convert_public_key_str_into_public_key(
-            &her_public_key_str,
-        ) {
+    if handshake_ack.src_public_key_str != public_key_str {
+        return Err(HandshakeInitError::UnexpectedPeer {
+            expected: public_key_str,
+            public_key: handshake_ack.src_public_key_str,
+        });
+    }
+
+    let negotiated_protocol = match protocol_info
+        .negotiate(&handshake_ack.protocol_info)
+    {
+        Ok(p) => p,
+        Err(disconnect) => {
+            // Best effort, the connection is dropped either way
+            let _ = conn.socket.send(Msg::Disconnect(disconnect.clone())).await;
+
+            return Err(HandshakeInitError::IncompatiblePeer {
+                reason: disconnect.reason,
+                detail: disconnect.detail,
+            });
+        }
+    };
+
+    let her_public_key_str = handshake_ack.src_public_key_str.clone();
+
+    let her_public_key = match parse_public_key(&her_public_key_str) {
+        Ok(pk) => pk,
+        Err(err) => {
+            return Err(HandshakeInitError::PublicKeyCreateFail {
+                public_key: her_public_key_str.clone(),
+                err: err.to_string(),
+            })
+        }
+    };
+
+    let her_ephemeral_public_key =
+        match parse_public_key(&handshake_ack.ephemeral_public_key_str) {
             Ok(pk) => pk,
             Err(err) => {
                 return Err(HandshakeInitError::PublicKeyCreateFail {
-                    public_key: her_public_key_str.clone(),
-                    err,
+                    public_key: handshake_ack.ephemeral_public_key_str.clone(),
+                    err: err.to_string(),
                 })
             }
         };
 
-    let shared_secret =
-        sak_crypto::make_shared_secret(my_secret_key, her_public_key);
+    let session_keys = match derive_session_keys(
+        true,
+        &identity.credential.secret_key,
+        &ephemeral_key,
+        her_public_key,
+        her_ephemeral_public_key,
+        &handshake_syn,
+        &handshake_ack,
+    ) {
+        Ok(k) => k,
+        Err(err) => {
+            return Err(HandshakeInitError::SessionKeyDeriveFail { err });
+        }
+    };
 
     let upgraded_conn = match conn
-        .upgrade(shared_secret, &[0; 12], &her_public_key_str)
+        .upgrade(
+            session_keys,
+            DEFAULT_REKEY_INTERVAL,
+            &her_public_key_str,
+            &negotiated_protocol,
+        )
         .await
     {
         Ok(c) => c,
@@ -153,7 +243,8 @@ pub async fn initiate_handshake(
     };
 
     let transport = Transport {
-        conn: RwLock::new(upgraded_conn),
+        conn: upgraded_conn,
+        protocol: negotiated_protocol,
     };
 
     return Ok(transport);
@@ -1,5 +1,10 @@
 mod initiate;
+mod protocol;
 mod receive;
+mod session;
 
 pub use initiate::*;
+pub use protocol::*;
 pub use receive::*;
+pub use session::SessionKeys;
+pub(crate) use session::*;
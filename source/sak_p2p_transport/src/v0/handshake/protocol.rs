@@ -0,0 +1,120 @@
+use crate::{DisconnectMsg, DisconnectReason};
+
+// Bumped whenever a msg is added or its frame changes
+pub const PROTOCOL_VERSION: u16 = 4;
+
+// Oldest version this build can still talk to
+pub const MIN_PROTOCOL_VERSION: u16 = 1;
+
+pub mod capability {
+    // TxHashSyn, TxSyn and their acks
+    pub const TX_SYNC: &str = "tx_sync";
+
+    // BlockHashSyn, BlockSyn and their acks
+    pub const BLOCK_SYNC: &str = "block_sync";
+
+    // Ping and Pong, since protocol version 2
+    pub const KEEPALIVE: &str = "keepalive";
+
+    // CompactBlockSyn, BlockTxsReq and BlockTxs, since protocol version 3
+    pub const COMPACT_BLOCK: &str = "compact_block";
+
+    // Request id in each upgraded frame, since protocol version 4. Without
+    // it, one request is in flight at a time.
+    pub const REQUEST_ID: &str = "request_id";
+}
+
+// What a node tells its peer about itself in the handshake
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolInfo {
+    pub network_id: String,
+    pub genesis_block_hash: String,
+    pub protocol_version: u16,
+    pub capabilities: Vec<String>,
+}
+
+impl ProtocolInfo {
+    // Current protocol version with every capability this build has
+    pub fn new(network_id: String, genesis_block_hash: String) -> ProtocolInfo {
+        ProtocolInfo {
+            network_id,
+            genesis_block_hash,
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: vec![
+                capability::TX_SYNC.to_string(),
+                capability::BLOCK_SYNC.to_string(),
+                capability::KEEPALIVE.to_string(),
+                capability::COMPACT_BLOCK.to_string(),
+                capability::REQUEST_ID.to_string(),
+            ],
+        }
+    }
+
+    // Both sides run the same check on each other's info, so they agree on
+    // the outcome without another round trip
+    pub(crate) fn negotiate(
+        &self,
+        her_info: &ProtocolInfo,
+    ) -> Result<NegotiatedProtocol, DisconnectMsg> {
+        if self.network_id != her_info.network_id {
+            return Err(DisconnectMsg {
+                reason: DisconnectReason::NetworkMismatch,
+                detail: format!(
+                    "network_id: {}, her network_id: {}",
+                    self.network_id, her_info.network_id
+                ),
+            });
+        }
+
+        if self.genesis_block_hash != her_info.genesis_block_hash {
+            return Err(DisconnectMsg {
+                reason: DisconnectReason::GenesisMismatch,
+                detail: format!(
+                    "genesis_block_hash: {}, her genesis_block_hash: {}",
+                    self.genesis_block_hash, her_info.genesis_block_hash
+                ),
+            });
+        }
+
+        let protocol_version =
+            std::cmp::min(self.protocol_version, her_info.protocol_version);
+
+        if protocol_version < MIN_PROTOCOL_VERSION {
+            return Err(DisconnectMsg {
+                reason: DisconnectReason::ProtocolVersionMismatch,
+                detail: format!(
+                    "protocol_version: {}, her protocol_version: {}, \
+                    min_protocol_version: {}",
+                    self.protocol_version,
+                    her_info.protocol_version,
+                    MIN_PROTOCOL_VERSION,
+                ),
+            });
+        }
+
+        let capabilities = self
+            .capabilities
+            .iter()
+            .filter(|c| her_info.capabilities.contains(c))
+            .map(|c| c.to_owned())
+            .collect();
+
+        Ok(NegotiatedProtocol {
+            protocol_version,
+            capabilities,
+        })
+    }
+}
+
+// What both sides of a connection have agreed on
+#[derive(Debug, Clone)]
+pub struct NegotiatedProtocol {
+    pub protocol_version: u16,
+    pub capabilities: Vec<String>,
+}
+
+impl NegotiatedProtocol {
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+}
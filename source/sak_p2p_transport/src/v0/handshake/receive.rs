@@ -1,4 +1,7 @@
-use crate::{Conn, Msg, Transport};
+use super::{
+    derive_session_keys, parse_public_key, EphemeralKey, ProtocolInfo,
+};
+use crate::{Conn, DisconnectReason, Msg, Transport, DEFAULT_REKEY_INTERVAL};
 use crate::{HandshakeMsg, TrptError};
 use futures::SinkExt;
 use futures::StreamExt;
@@ -7,7 +10,6 @@ use sak_p2p_id::Identity;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::RwLock;
 
 #[derive(Error, Debug)]
 pub enum HandshakeRecvError {
@@ -55,17 +57,30 @@ pub enum HandshakeRecvError {
 
     #[error("Could not connect connection, err: {err}")]
     ConnectionCreateFail { err: String },
+
+    #[error("Could not derive session keys, err: {err}")]
+    SessionKeyDeriveFail { err: TrptError },
+
+    #[error("Peer is not compatible, reason: {reason}, detail: {detail}")]
+    IncompatiblePeer {
+        reason: DisconnectReason,
+        detail: String,
+    },
 }
 
 pub struct HandshakeRecvArgs {
     pub identity: Arc<Identity>,
+    pub protocol_info: Arc<ProtocolInfo>,
 }
 
 pub async fn receive_handshake(
     handshake_recv_args: HandshakeRecvArgs,
     mut conn: Conn,
 ) -> Result<(Transport, String), HandshakeRecvError> {
-    let HandshakeRecvArgs { identity } = handshake_recv_args;
+    let HandshakeRecvArgs {
+        identity,
+        protocol_info,
+    } = handshake_recv_args;
 
     let handshake_syn = match conn.socket.next().await {
         Some(maybe_msg) => match maybe_msg {
@@ -86,27 +101,70 @@ pub async fn receive_handshake(
         }
     };
 
-    let HandshakeMsg {
-        instance_id,
-        src_p2p_port: _,
-        src_public_key_str: her_public_key_str,
-        dst_public_key_str: my_public_key_str,
-    } = handshake_syn;
+    let my_public_key_str = &handshake_syn.dst_public_key_str;
+    let her_public_key_str = &handshake_syn.src_public_key_str;
 
-    if my_public_key_str != identity.credential.public_key_str {
+    if my_public_key_str != &identity.credential.public_key_str {
         return Err(HandshakeRecvError::UnmatchedMyPublicKey {
-            public_key: my_public_key_str,
+            public_key: my_public_key_str.clone(),
         });
     }
 
-    let handshake_msg = HandshakeMsg {
-        instance_id: instance_id.clone(),
+    let negotiated_protocol =
+        match protocol_info.negotiate(&handshake_syn.protocol_info) {
+            Ok(p) => p,
+            Err(disconnect) => {
+                // Tell her why, so that she does not keep dialing blindly
+                if let Err(err) =
+                    conn.socket.send(Msg::Disconnect(disconnect.clone())).await
+                {
+                    warn!("Failed to send disconnect msg, err: {}", err);
+                }
+
+                return Err(HandshakeRecvError::IncompatiblePeer {
+                    reason: disconnect.reason,
+                    detail: disconnect.detail,
+                });
+            }
+        };
+
+    let her_public_key = match parse_public_key(her_public_key_str) {
+        Ok(pk) => pk,
+        Err(err) => {
+            return Err(HandshakeRecvError::PublicKeyCreateFail {
+                public_key: her_public_key_str.clone(),
+                err: err.to_string(),
+            })
+        }
+    };
+
+    let her_ephemeral_public_key =
+        match parse_public_key(&handshake_syn.ephemeral_public_key_str) {
+            Ok(pk) => pk,
+            Err(err) => {
+                return Err(HandshakeRecvError::PublicKeyCreateFail {
+                    public_key: handshake_syn.ephemeral_public_key_str.clone(),
+                    err: err.to_string(),
+                })
+            }
+        };
+
+    let ephemeral_key = EphemeralKey::generate();
+
+    let handshake_ack = HandshakeMsg {
+        instance_id: handshake_syn.instance_id.clone(),
         src_p2p_port: identity.p2p_port,
         src_public_key_str: my_public_key_str.clone(),
         dst_public_key_str: her_public_key_str.clone(),
+        ephemeral_public_key_str: ephemeral_key.public_key_str.clone(),
+        protocol_info: protocol_info.as_ref().clone(),
     };
 
-    match conn.socket.send(Msg::HandshakeAck(handshake_msg)).await {
+    match conn
+        .socket
+        .send(Msg::HandshakeAck(handshake_ack.clone()))
+        .await
+    {
         Ok(_) => (),
         Err(err) => {
             return Err(HandshakeRecvError::AckSendFail {
@@ -115,26 +173,30 @@ pub async fn receive_handshake(
         }
     };
 
-    let my_secret_key = &identity.credential.secret_key;
-
-    let her_public_key =
-        match sak_crypto::convert_public_key_str_into_public_key(
-            &her_public_key_str,
-        ) {
-            Ok(pk) => pk,
-            Err(err) => {
-                return Err(HandshakeRecvError::PublicKeyCreateFail {
-                    public_key: her_public_key_str,
-                    err,
-                })
-            }
-        };
+    let session_keys = match derive_session_keys(
+        false,
+        &identity.credential.secret_key,
+        &ephemeral_key,
+        her_public_key,
+        her_ephemeral_public_key,
+        &handshake_syn,
+        &handshake_ack,
+    ) {
+        Ok(k) => k,
+        Err(err) => {
+            return Err(HandshakeRecvError::SessionKeyDeriveFail { err });
+        }
+    };
 
-    let shared_secret =
-        sak_crypto::make_shared_secret(my_secret_key, her_public_key);
+    let her_public_key_str = her_public_key_str.clone();
 
     let upgraded_conn = match conn
-        .upgrade(shared_secret, &[0; 12], &her_public_key_str)
+        .upgrade(
+            session_keys,
+            DEFAULT_REKEY_INTERVAL,
+            &her_public_key_str,
+            &negotiated_protocol,
+        )
         .await
     {
         Ok(c) => c,
@@ -146,7 +208,8 @@ pub async fn receive_handshake(
     };
 
     let transport = Transport {
-        conn: RwLock::new(upgraded_conn),
+        conn: upgraded_conn,
+        protocol: negotiated_protocol,
     };
 
     return Ok((transport, her_public_key_str));
@@ -0,0 +1,154 @@
+use crate::{HandshakeMsg, TrptError};
+use sak_crypto::sha3::{Digest, Sha3_256};
+use sak_crypto::{PublicKey, SakKey, SecretKey, ToEncodedPoint};
+
+const SESSION_KEY_INFO: &'static [u8] = b"saksaha_p2p_session_keys";
+
+// Keys of the two directions of a connection, as seen by one side
+pub struct SessionKeys {
+    pub(crate) send_key: [u8; 32],
+    pub(crate) recv_key: [u8; 32],
+}
+
+// Generated per handshake and dropped right after the session keys are
+// derived, which keeps past sessions secret even if a static key leaks
+pub(crate) struct EphemeralKey {
+    pub(crate) secret_key: SecretKey,
+    pub(crate) public_key_str: String,
+}
+
+impl EphemeralKey {
+    pub(crate) fn generate() -> EphemeralKey {
+        let (secret_key, public_key) = SakKey::generate();
+
+        let public_key_str = sak_crypto::encode_hex(
+            &public_key.to_encoded_point(false).to_bytes(),
+        );
+
+        EphemeralKey {
+            secret_key,
+            public_key_str,
+        }
+    }
+}
+
+pub(crate) fn parse_public_key(
+    public_key_str: &String,
+) -> Result<PublicKey, TrptError> {
+    match sak_crypto::convert_public_key_str_into_public_key(public_key_str) {
+        Ok(pk) => Ok(pk),
+        Err(err) => Err(format!(
+            "Cannot create public key, public_key: {}, err: {}",
+            public_key_str, err
+        )
+        .into()),
+    }
+}
+
+// Both sides mix the same three Diffie-Hellman results, i.e. ephemeral-
+// ephemeral for forward secrecy and the two static-ephemeral ones, which
+// only the holders of the static keys can compute. The transcript of the
+// handshake is the salt, so a tampered handshake ends in different keys.
+pub(crate) fn derive_session_keys(
+    is_initiator: bool,
+    my_secret_key: &SecretKey,
+    my_ephemeral_key: &EphemeralKey,
+    her_public_key: PublicKey,
+    her_ephemeral_public_key: PublicKey,
+    handshake_syn: &HandshakeMsg,
+    handshake_ack: &HandshakeMsg,
+) -> Result<SessionKeys, TrptError> {
+    let ee = sak_crypto::make_shared_secret(
+        &my_ephemeral_key.secret_key,
+        her_ephemeral_public_key,
+    );
+
+    let my_eph_her_static = sak_crypto::make_shared_secret(
+        &my_ephemeral_key.secret_key,
+        her_public_key,
+    );
+
+    let my_static_her_eph =
+        sak_crypto::make_shared_secret(my_secret_key, her_ephemeral_public_key);
+
+    // (initiator's ephemeral, responder's static) comes first on both sides
+    let (es, se) = if is_initiator {
+        (my_eph_her_static, my_static_her_eph)
+    } else {
+        (my_static_her_eph, my_eph_her_static)
+    };
+
+    let mut ikm = vec![];
+    ikm.extend_from_slice(ee.as_bytes());
+    ikm.extend_from_slice(es.as_bytes());
+    ikm.extend_from_slice(se.as_bytes());
+
+    let transcript_hash = make_transcript_hash(handshake_syn, handshake_ack);
+
+    let okm = sak_crypto::make_hkdf_sha256(
+        &transcript_hash,
+        &ikm,
+        SESSION_KEY_INFO,
+        64,
+    )?;
+
+    let mut initiator_key = [0; 32];
+    initiator_key.copy_from_slice(&okm[..32]);
+
+    let mut responder_key = [0; 32];
+    responder_key.copy_from_slice(&okm[32..]);
+
+    let session_keys = if is_initiator {
+        SessionKeys {
+            send_key: initiator_key,
+            recv_key: responder_key,
+        }
+    } else {
+        SessionKeys {
+            send_key: responder_key,
+            recv_key: initiator_key,
+        }
+    };
+
+    Ok(session_keys)
+}
+
+fn make_transcript_hash(
+    handshake_syn: &HandshakeMsg,
+    handshake_ack: &HandshakeMsg,
+) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+
+    for h in [handshake_syn, handshake_ack] {
+        let port = h.src_p2p_port.to_be_bytes();
+        let protocol_version = h.protocol_info.protocol_version.to_be_bytes();
+        let capability_count =
+            (h.protocol_info.capabilities.len() as u32).to_be_bytes();
+
+        let mut fields: Vec<&[u8]> = vec![
+            h.instance_id.as_bytes(),
+            &port,
+            h.src_public_key_str.as_bytes(),
+            h.dst_public_key_str.as_bytes(),
+            h.ephemeral_public_key_str.as_bytes(),
+            h.protocol_info.network_id.as_bytes(),
+            h.protocol_info.genesis_block_hash.as_bytes(),
+            &protocol_version,
+            &capability_count,
+        ];
+
+        // Covered as well, so that capabilities cannot be stripped on the
+        // way to downgrade the connection
+        for c in &h.protocol_info.capabilities {
+            fields.push(c.as_bytes());
+        }
+
+        // Length prefixed, so that fields cannot be shifted into each other
+        for f in fields {
+            hasher.update(&(f.len() as u32).to_be_bytes());
+            hasher.update(f);
+        }
+    }
+
+    hasher.finalize().to_vec()
+}
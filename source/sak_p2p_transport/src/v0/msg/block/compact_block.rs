@@ -0,0 +1,287 @@
+use crate::{tx_utils, utils, MsgType, TrptError};
+use bytes::Bytes;
+use sak_p2p_frame::{Frame, Parse};
+use sak_types::{Block, BlockHash, BlockHeight, Tx};
+use std::collections::HashMap;
+
+// Leading characters of a tx hash that stand for it in a compact block
+pub const SHORT_TX_ID_LEN: usize = 16;
+
+pub fn make_short_tx_id(tx_hash: &str) -> String {
+    match tx_hash.get(..SHORT_TX_ID_LEN) {
+        Some(s) => s.to_string(),
+        None => tx_hash.to_string(),
+    }
+}
+
+// Full tx hash for each short id, out of the hashes the caller has. One that
+// is not there, or that could be more than one tx, is left as None.
+pub fn resolve_short_tx_ids(
+    short_tx_ids: &Vec<String>,
+    tx_hashes: &Vec<String>,
+) -> Vec<Option<String>> {
+    let mut candidates: HashMap<String, Vec<&String>> = HashMap::new();
+
+    for h in tx_hashes {
+        candidates.entry(make_short_tx_id(h)).or_default().push(h);
+    }
+
+    short_tx_ids
+        .iter()
+        .map(|id| match candidates.get(id) {
+            Some(hs) if hs.len() == 1 => Some(hs[0].clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// Block header with its txs given as short ids. The receiver fills them in
+// from her own pool, and the block hash tells her whether she got it right.
+#[derive(Debug, Clone)]
+pub struct CompactBlock {
+    pub validator_sig: String,
+    pub witness_sigs: Vec<String>,
+    pub created_at: String,
+    pub block_height: BlockHeight,
+    pub merkle_rt: [u8; 32],
+    pub block_hash: BlockHash,
+    pub short_tx_ids: Vec<String>,
+}
+
+impl CompactBlock {
+    pub fn from_block(block: &Block) -> CompactBlock {
+        CompactBlock {
+            validator_sig: block.validator_sig.clone(),
+            witness_sigs: block.witness_sigs.clone(),
+            created_at: block.created_at.clone(),
+            block_height: block.block_height,
+            merkle_rt: block.merkle_rt,
+            block_hash: block.get_block_hash().clone(),
+            short_tx_ids: block
+                .tx_hashes
+                .iter()
+                .map(|h| make_short_tx_id(h))
+                .collect(),
+        }
+    }
+
+    // Block whose tx hashes are the ones resolved from the short ids, in order
+    pub fn into_block(self, tx_hashes: Vec<String>) -> Block {
+        Block::new(
+            self.validator_sig,
+            tx_hashes,
+            self.witness_sigs,
+            self.created_at,
+            self.block_height,
+            self.merkle_rt,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct CompactBlockSynMsg {
+    pub blocks: Vec<CompactBlock>,
+}
+
+impl CompactBlockSynMsg {
+    pub(crate) fn from_parse(
+        parse: &mut Parse,
+    ) -> Result<CompactBlockSynMsg, TrptError> {
+        let block_count = parse.next_int()?;
+
+        let mut blocks = Vec::with_capacity(block_count as usize);
+
+        for _ in 0..block_count {
+            let validator_sig = {
+                let v = parse.next_bytes()?;
+                std::str::from_utf8(&v)?.to_string()
+            };
+
+            let created_at = {
+                let v = parse.next_bytes()?;
+                std::str::from_utf8(&v)?.to_string()
+            };
+
+            let merkle_rt = {
+                let b = parse.next_bytes()?;
+                utils::convert_bytes_into_u8_32(b)?
+            };
+
+            let block_height = parse.next_int()? as u128;
+
+            let block_hash = {
+                let v = parse.next_bytes()?;
+                std::str::from_utf8(&v)?.to_string()
+            };
+
+            let witness_sig_count = parse.next_int()?;
+            let mut witness_sigs =
+                Vec::with_capacity(witness_sig_count as usize);
+
+            for _ in 0..witness_sig_count {
+                let v = parse.next_bytes()?;
+                witness_sigs.push(std::str::from_utf8(&v)?.to_string());
+            }
+
+            let short_tx_id_count = parse.next_int()?;
+            let mut short_tx_ids =
+                Vec::with_capacity(short_tx_id_count as usize);
+
+            for _ in 0..short_tx_id_count {
+                let v = parse.next_bytes()?;
+                short_tx_ids.push(std::str::from_utf8(&v)?.to_string());
+            }
+
+            blocks.push(CompactBlock {
+                validator_sig,
+                witness_sigs,
+                created_at,
+                block_height,
+                merkle_rt,
+                block_hash,
+                short_tx_ids,
+            });
+        }
+
+        let m = CompactBlockSynMsg { blocks };
+
+        Ok(m)
+    }
+
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+
+        frame.push_bulk(Bytes::from(MsgType::COMPACT_BLOCK_SYN));
+        frame.push_int(self.blocks.len() as u128);
+
+        for block in self.blocks {
+            frame.push_bulk(Bytes::from(block.validator_sig));
+            frame.push_bulk(Bytes::from(block.created_at));
+            frame.push_bulk(Bytes::copy_from_slice(&block.merkle_rt));
+            frame.push_int(block.block_height as u128);
+            frame.push_bulk(Bytes::from(block.block_hash));
+
+            frame.push_int(block.witness_sigs.len() as u128);
+
+            for witness_sig in block.witness_sigs {
+                frame.push_bulk(Bytes::from(witness_sig));
+            }
+
+            frame.push_int(block.short_tx_ids.len() as u128);
+
+            for short_tx_id in block.short_tx_ids {
+                frame.push_bulk(Bytes::from(short_tx_id));
+            }
+        }
+
+        frame
+    }
+}
+
+// Txs of compact blocks that could not be found in the pool, as the position
+// of each in its block
+#[derive(Debug)]
+pub struct BlockTxsReqMsg {
+    pub reqs: Vec<(BlockHash, Vec<u128>)>,
+}
+
+impl BlockTxsReqMsg {
+    pub(crate) fn from_parse(
+        parse: &mut Parse,
+    ) -> Result<BlockTxsReqMsg, TrptError> {
+        let block_count = parse.next_int()?;
+
+        let mut reqs = Vec::with_capacity(block_count as usize);
+
+        for _ in 0..block_count {
+            let block_hash = {
+                let v = parse.next_bytes()?;
+                std::str::from_utf8(&v)?.to_string()
+            };
+
+            let idx_count = parse.next_int()?;
+            let mut tx_indices = Vec::with_capacity(idx_count as usize);
+
+            for _ in 0..idx_count {
+                tx_indices.push(parse.next_int()?);
+            }
+
+            reqs.push((block_hash, tx_indices));
+        }
+
+        let m = BlockTxsReqMsg { reqs };
+
+        Ok(m)
+    }
+
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+
+        frame.push_bulk(Bytes::from(MsgType::BLOCK_TXS_REQ));
+        frame.push_int(self.reqs.len() as u128);
+
+        for (block_hash, tx_indices) in self.reqs {
+            frame.push_bulk(Bytes::from(block_hash));
+            frame.push_int(tx_indices.len() as u128);
+
+            for idx in tx_indices {
+                frame.push_int(idx);
+            }
+        }
+
+        frame
+    }
+}
+
+#[derive(Debug)]
+pub struct BlockTxsMsg {
+    pub block_txs: Vec<(BlockHash, Vec<Tx>)>,
+}
+
+impl BlockTxsMsg {
+    pub(crate) fn from_parse(
+        parse: &mut Parse,
+    ) -> Result<BlockTxsMsg, TrptError> {
+        let block_count = parse.next_int()?;
+
+        let mut block_txs = Vec::with_capacity(block_count as usize);
+
+        for _ in 0..block_count {
+            let block_hash = {
+                let v = parse.next_bytes()?;
+                std::str::from_utf8(&v)?.to_string()
+            };
+
+            let tx_count = parse.next_int()?;
+            let mut txs = Vec::with_capacity(tx_count as usize);
+
+            for _ in 0..tx_count {
+                txs.push(tx_utils::parse_tx(parse)?);
+            }
+
+            block_txs.push((block_hash, txs));
+        }
+
+        let m = BlockTxsMsg { block_txs };
+
+        Ok(m)
+    }
+
+    pub(crate) fn into_frame(self) -> Frame {
+        let mut frame = Frame::array();
+
+        frame.push_bulk(Bytes::from(MsgType::BLOCK_TXS));
+        frame.push_int(self.block_txs.len() as u128);
+
+        for (block_hash, txs) in self.block_txs {
+            frame.push_bulk(Bytes::from(block_hash));
+            frame.push_int(txs.len() as u128);
+
+            for tx in txs {
+                tx_utils::put_tx_into_frame(&mut frame, tx);
+            }
+        }
+
+        frame
+    }
+}
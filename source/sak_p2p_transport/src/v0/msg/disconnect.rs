@@ -0,0 +1,84 @@
+use crate::{MsgType, TrptError};
+use bytes::{BufMut, Bytes, BytesMut};
+use sak_p2p_frame::{Frame, Parse};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisconnectReason {
+    NetworkMismatch,
+    GenesisMismatch,
+    ProtocolVersionMismatch,
+    Unknown,
+}
+
+impl DisconnectReason {
+    fn to_code(&self) -> u128 {
+        match self {
+            DisconnectReason::NetworkMismatch => 1,
+            DisconnectReason::GenesisMismatch => 2,
+            DisconnectReason::ProtocolVersionMismatch => 3,
+            DisconnectReason::Unknown => 0,
+        }
+    }
+
+    // Reasons added by newer builds are read as `Unknown`
+    fn from_code(code: u128) -> DisconnectReason {
+        match code {
+            1 => DisconnectReason::NetworkMismatch,
+            2 => DisconnectReason::GenesisMismatch,
+            3 => DisconnectReason::ProtocolVersionMismatch,
+            _ => DisconnectReason::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisconnectReason::NetworkMismatch => write!(f, "network_mismatch"),
+            DisconnectReason::GenesisMismatch => write!(f, "genesis_mismatch"),
+            DisconnectReason::ProtocolVersionMismatch => {
+                write!(f, "protocol_version_mismatch")
+            }
+            DisconnectReason::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DisconnectMsg {
+    pub reason: DisconnectReason,
+    pub detail: String,
+}
+
+impl DisconnectMsg {
+    pub(crate) fn from_parse(
+        parse: &mut Parse,
+    ) -> Result<DisconnectMsg, TrptError> {
+        let reason = DisconnectReason::from_code(parse.next_int()?);
+
+        let detail = {
+            let k = parse.next_bytes()?;
+            std::str::from_utf8(k.as_ref())?.into()
+        };
+
+        let m = DisconnectMsg { reason, detail };
+
+        Ok(m)
+    }
+
+    pub(crate) fn into_frame(&self) -> Frame {
+        let mut frame = Frame::array();
+
+        let detail_bytes = {
+            let mut b = BytesMut::new();
+            b.put(self.detail.as_bytes());
+            b
+        };
+
+        frame.push_bulk(Bytes::from(MsgType::DISCONNECT));
+        frame.push_int(self.reason.to_code());
+        frame.push_bulk(detail_bytes.into());
+
+        frame
+    }
+}
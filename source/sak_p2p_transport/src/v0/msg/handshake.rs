@@ -1,14 +1,17 @@
-use crate::{MsgType, TrptError};
+use crate::{handshake::ProtocolInfo, MsgType, TrptError};
 use bytes::{BufMut, Bytes, BytesMut};
 use sak_p2p_frame::{Frame, Parse};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HandshakeMsg {
     pub instance_id: String,
     pub src_p2p_port: u16,
     pub src_public_key_str: String,
     pub dst_public_key_str: String,
+    // Fresh for every handshake
+    pub ephemeral_public_key_str: String,
+    pub protocol_info: ProtocolInfo,
 }
 
 impl HandshakeMsg {
@@ -16,6 +19,8 @@ impl HandshakeMsg {
         src_p2p_port: u16,
         src_public_key_str: String,
         dst_public_key_str: String,
+        ephemeral_public_key_str: String,
+        protocol_info: ProtocolInfo,
     ) -> Result<HandshakeMsg, String> {
         let since_the_epoch = match SystemTime::now().duration_since(UNIX_EPOCH)
         {
@@ -36,6 +41,8 @@ impl HandshakeMsg {
             src_p2p_port,
             src_public_key_str,
             dst_public_key_str,
+            ephemeral_public_key_str,
+            protocol_info,
         })
     }
 
@@ -60,11 +67,46 @@ impl HandshakeMsg {
             std::str::from_utf8(k.as_ref())?.into()
         };
 
+        let ephemeral_public_key_str: String = {
+            let k = parse.next_bytes()?;
+            std::str::from_utf8(k.as_ref())?.into()
+        };
+
+        let network_id: String = {
+            let k = parse.next_bytes()?;
+            std::str::from_utf8(k.as_ref())?.into()
+        };
+
+        let genesis_block_hash: String = {
+            let k = parse.next_bytes()?;
+            std::str::from_utf8(k.as_ref())?.into()
+        };
+
+        let protocol_version = parse.next_int()? as u16;
+
+        let capability_count = parse.next_int()?;
+
+        let mut capabilities = vec![];
+
+        for _ in 0..capability_count {
+            let k = parse.next_bytes()?;
+            capabilities.push(std::str::from_utf8(k.as_ref())?.into());
+        }
+
+        let protocol_info = ProtocolInfo {
+            network_id,
+            genesis_block_hash,
+            protocol_version,
+            capabilities,
+        };
+
         let h = HandshakeMsg {
             instance_id,
             src_p2p_port,
             src_public_key_str,
             dst_public_key_str,
+            ephemeral_public_key_str,
+            protocol_info,
         };
 
         Ok(h)
@@ -91,11 +133,35 @@ impl HandshakeMsg {
             b
         };
 
+        let ephemeral_public_key_bytes = {
+            let mut b = BytesMut::new();
+            b.put(self.ephemeral_public_key_str.as_bytes());
+            b
+        };
+
         frame.push_bulk(Bytes::from(msg_type));
         frame.push_int(self.src_p2p_port as u128);
         frame.push_bulk(instance_id_bytes.into());
         frame.push_bulk(src_public_key_bytes.into());
         frame.push_bulk(dst_public_key_bytes.into());
+        frame.push_bulk(ephemeral_public_key_bytes.into());
+
+        let ProtocolInfo {
+            network_id,
+            genesis_block_hash,
+            protocol_version,
+            capabilities,
+        } = &self.protocol_info;
+
+        frame.push_bulk(Bytes::from(network_id.clone()));
+        frame.push_bulk(Bytes::from(genesis_block_hash.clone()));
+        frame.push_int(*protocol_version as u128);
+        frame.push_int(capabilities.len() as u128);
+
+        for c in capabilities {
+            frame.push_bulk(Bytes::from(c.clone()));
+        }
+
         frame
     }
 
@@ -1,4 +1,5 @@
 mod block;
+mod disconnect;
 mod handshake;
 mod msg;
 mod msg_type;
@@ -7,6 +8,7 @@ mod tx;
 pub(crate) mod tx_utils;
 
 pub use block::*;
+pub use disconnect::*;
 pub use handshake::*;
 pub use msg::Msg;
 pub use msg_type::*;
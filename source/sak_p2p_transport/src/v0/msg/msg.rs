@@ -1,6 +1,7 @@
 use crate::{
-    BlockAckMsg, BlockHashSyncMsg, BlockSynMsg, HandshakeMsg, PingMsg,
-    TxAckMsg, TxHashSyncMsg, TxSynMsg,
+    BlockAckMsg, BlockHashSyncMsg, BlockSynMsg, BlockTxsMsg, BlockTxsReqMsg,
+    CompactBlockSynMsg, DisconnectMsg, HandshakeMsg, PingMsg, TxAckMsg,
+    TxHashSyncMsg, TxSynMsg,
 };
 
 #[derive(Debug)]
@@ -25,7 +26,31 @@ pub enum Msg {
 
     BlockAck(BlockAckMsg),
 
+    CompactBlockSyn(CompactBlockSynMsg),
+
+    BlockTxsReq(BlockTxsReqMsg),
+
+    BlockTxs(BlockTxsMsg),
+
     Ping(PingMsg),
+
+    Pong(PingMsg),
+
+    Disconnect(DisconnectMsg),
+}
+
+impl Msg {
+    // Msgs that only ever come as the answer to a request
+    pub fn is_response(&self) -> bool {
+        match self {
+            Msg::TxHashAck(_)
+            | Msg::TxAck(_)
+            | Msg::BlockHashAck(_)
+            | Msg::BlockAck(_)
+            | Msg::BlockTxs(_) => true,
+            _ => false,
+        }
+    }
 }
 
 impl std::fmt::Display for Msg {
@@ -41,7 +66,12 @@ impl std::fmt::Display for Msg {
             Msg::BlockHashAck(_) => write!(f, "block_hash_ack"),
             Msg::BlockSyn(_) => write!(f, "block_syn"),
             Msg::BlockAck(_) => write!(f, "block_ack"),
+            Msg::CompactBlockSyn(_) => write!(f, "compact_block_syn"),
+            Msg::BlockTxsReq(_) => write!(f, "block_txs_req"),
+            Msg::BlockTxs(_) => write!(f, "block_txs"),
             Msg::Ping(_) => write!(f, "ping"),
+            Msg::Pong(_) => write!(f, "pong"),
+            Msg::Disconnect(_) => write!(f, "disconnect"),
         }
     }
 }
@@ -19,5 +19,15 @@ pub mod MsgType {
 
     pub const BLOCK_ACK: &str = "block_ack";
 
+    pub const COMPACT_BLOCK_SYN: &str = "compact_block_syn";
+
+    pub const BLOCK_TXS_REQ: &str = "block_txs_req";
+
+    pub const BLOCK_TXS: &str = "block_txs";
+
     pub const PING: &str = "ping";
+
+    pub const PONG: &str = "pong";
+
+    pub const DISCONNECT: &str = "disconnect";
 }
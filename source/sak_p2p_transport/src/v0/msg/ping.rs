@@ -16,10 +16,19 @@ impl PingMsg {
         Ok(m)
     }
 
-    pub(crate) fn into_frame(&self) -> Frame {
+    pub(crate) fn into_ping_frame(&self) -> Frame {
+        self.into_frame(MsgType::PING)
+    }
+
+    // A pong carries back the nonce of the ping it answers
+    pub(crate) fn into_pong_frame(&self) -> Frame {
+        self.into_frame(MsgType::PONG)
+    }
+
+    fn into_frame(&self, msg_type: &'static str) -> Frame {
         let mut frame = Frame::array();
 
-        frame.push_bulk(Bytes::from(MsgType::PING));
+        frame.push_bulk(Bytes::from(msg_type));
 
         frame.push_int(self.nonce as u128);
 
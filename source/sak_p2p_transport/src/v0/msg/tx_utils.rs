@@ -1,7 +1,7 @@
 use crate::{utils, TrptError};
 use bytes::Bytes;
 use sak_p2p_frame::{Frame, Parse};
-use sak_types::{MintTx, MintTxCandidate, PourTx, PourTxCandidate, Tx};
+use sak_types::{MintTx, MintTxCandidate, PourTx, PourTxCandidate, Tx, TxType};
 
 pub(crate) fn parse_mint_tx_candidate(
     parse: &mut Parse,
@@ -238,3 +238,33 @@ pub(crate) fn put_pour_tx_into_frame(frame: &mut Frame, tx: PourTx) {
     frame.push_int(tx.cm_idx_1);
     frame.push_int(tx.cm_idx_2);
 }
+
+pub(crate) fn parse_tx(parse: &mut Parse) -> Result<Tx, TrptError> {
+    let tx_type = {
+        let p = parse.next_bytes()?;
+
+        let t = match p[..].get(0) {
+            Some(v) => v,
+            None => {
+                return Err(format!("Invalid tx type to parse, tx_type").into())
+            }
+        };
+        TxType::from(*t)
+    };
+
+    match tx_type {
+        TxType::Mint => parse_mint_tx(parse),
+        TxType::Pour => parse_pour_tx(parse),
+        _ => {
+            Err(format!("Invalid tx type to parse, tx_type: {:?}", tx_type)
+                .into())
+        }
+    }
+}
+
+pub(crate) fn put_tx_into_frame(frame: &mut Frame, tx: Tx) {
+    match tx {
+        Tx::Mint(t) => put_mint_tx_into_frame(frame, t),
+        Tx::Pour(t) => put_pour_tx_into_frame(frame, t),
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{
+    handshake::SessionKeys, make_short_tx_id, resolve_short_tx_ids,
+    BlockTxsReqMsg, CompactBlock, CompactBlockSynMsg, Envelope, Msg, PingMsg,
+    UpgradedP2PCodec, NO_REQUEST_ID,
+};
+use bytes::BytesMut;
+use sak_types::Block;
+use tokio_util::codec::{Decoder, Encoder};
+
+fn make_codec_pair(
+    rekey_interval: u64,
+) -> (UpgradedP2PCodec, UpgradedP2PCodec) {
+    let initiator_keys = SessionKeys {
+        send_key: [1; 32],
+        recv_key: [2; 32],
+    };
+
+    let responder_keys = SessionKeys {
+        send_key: [2; 32],
+        recv_key: [1; 32],
+    };
+
+    (
+        UpgradedP2PCodec::new(&initiator_keys, rekey_interval, true),
+        UpgradedP2PCodec::new(&responder_keys, rekey_interval, true),
+    )
+}
+
+fn envelope(msg: Msg) -> Envelope {
+    Envelope {
+        req_id: NO_REQUEST_ID,
+        msg,
+    }
+}
+
+fn encode_ping(codec: &mut UpgradedP2PCodec, nonce: u128) -> BytesMut {
+    let mut buf = BytesMut::new();
+
+    codec
+        .encode(envelope(Msg::Ping(PingMsg { nonce })), &mut buf)
+        .expect("ping should be encoded");
+
+    buf
+}
+
+fn decode_ping(codec: &mut UpgradedP2PCodec, buf: &mut BytesMut) -> u128 {
+    match codec.decode(buf) {
+        Ok(Some(Envelope {
+            msg: Msg::Ping(p), ..
+        })) => p.nonce,
+        _ => panic!("ping should be decoded"),
+    }
+}
+
+#[test]
+fn test_upgraded_codec_rekeys_in_step() {
+    let (mut initiator, mut responder) = make_codec_pair(2);
+
+    for nonce in 0..7 {
+        let mut buf = encode_ping(&mut initiator, nonce);
+
+        assert_eq!(decode_ping(&mut responder, &mut buf), nonce);
+        assert!(buf.is_empty());
+
+        let mut buf = encode_ping(&mut responder, nonce + 100);
+
+        assert_eq!(decode_ping(&mut initiator, &mut buf), nonce + 100);
+    }
+}
+
+#[test]
+fn test_upgraded_codec_waits_for_the_entire_frame() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let buf = encode_ping(&mut initiator, 1);
+
+    let mut partial = BytesMut::from(&buf[..buf.len() - 1]);
+
+    assert!(responder.decode(&mut partial).unwrap().is_none());
+
+    partial.extend_from_slice(&buf[buf.len() - 1..]);
+
+    assert_eq!(decode_ping(&mut responder, &mut partial), 1);
+}
+
+#[test]
+fn test_upgraded_codec_does_not_repeat_keystream() {
+    let (mut initiator, _) = make_codec_pair(0);
+
+    let buf_1 = encode_ping(&mut initiator, 1);
+    let buf_2 = encode_ping(&mut initiator, 1);
+
+    assert_ne!(buf_1, buf_2);
+}
+
+#[test]
+fn test_upgraded_codec_rejects_tampered_frame() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let mut buf = encode_ping(&mut initiator, 1);
+
+    let last = buf.len() - 1;
+    buf[last] ^= 1;
+
+    assert!(responder.decode(&mut buf).is_err());
+}
+
+#[test]
+fn test_upgraded_codec_rejects_replayed_frame() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let buf = encode_ping(&mut initiator, 1);
+
+    let mut replayed = buf.clone();
+
+    let mut buf = buf;
+    assert_eq!(decode_ping(&mut responder, &mut buf), 1);
+
+    assert!(responder.decode(&mut replayed).is_err());
+}
+
+#[test]
+fn test_upgraded_codec_rejects_reflected_frame() {
+    let (mut initiator, _) = make_codec_pair(0);
+
+    let mut buf = encode_ping(&mut initiator, 1);
+
+    // Sent back to where it came from, it is under the wrong key
+    assert!(initiator.decode(&mut buf).is_err());
+}
+
+#[test]
+fn test_upgraded_codec_tells_pong_from_ping() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let mut buf = BytesMut::new();
+
+    initiator
+        .encode(envelope(Msg::Pong(PingMsg { nonce: 7 })), &mut buf)
+        .expect("pong should be encoded");
+
+    match responder.decode(&mut buf) {
+        Ok(Some(Envelope {
+            msg: Msg::Pong(p), ..
+        })) => assert_eq!(p.nonce, 7),
+        _ => panic!("pong should be decoded"),
+    }
+}
+
+#[test]
+fn test_compact_block_is_rebuilt_into_the_same_block() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let tx_hashes: Vec<String> = vec!["a".repeat(64), "b".repeat(64)];
+
+    let block = Block::new(
+        String::from("validator_sig"),
+        tx_hashes.clone(),
+        vec![String::from("witness_sig")],
+        String::from("created_at"),
+        7,
+        [3; 32],
+    );
+
+    let mut buf = BytesMut::new();
+
+    initiator
+        .encode(
+            envelope(Msg::CompactBlockSyn(CompactBlockSynMsg {
+                blocks: vec![CompactBlock::from_block(&block)],
+            })),
+            &mut buf,
+        )
+        .expect("compact block should be encoded");
+
+    let compact_block = match responder.decode(&mut buf) {
+        Ok(Some(Envelope {
+            msg: Msg::CompactBlockSyn(mut m),
+            ..
+        })) => m.blocks.remove(0),
+        _ => panic!("compact block should be decoded"),
+    };
+
+    let short_tx_ids: Vec<String> =
+        tx_hashes.iter().map(|h| make_short_tx_id(h)).collect();
+
+    assert_eq!(compact_block.short_tx_ids, short_tx_ids);
+    assert_eq!(&compact_block.block_hash, block.get_block_hash());
+
+    let rebuilt = compact_block.into_block(tx_hashes);
+
+    assert_eq!(rebuilt, block);
+
+    let mut buf = BytesMut::new();
+
+    initiator
+        .encode(
+            envelope(Msg::BlockTxsReq(BlockTxsReqMsg {
+                reqs: vec![(block.get_block_hash().clone(), vec![1])],
+            })),
+            &mut buf,
+        )
+        .expect("block txs req should be encoded");
+
+    match responder.decode(&mut buf) {
+        Ok(Some(Envelope {
+            msg: Msg::BlockTxsReq(m),
+            ..
+        })) => {
+            assert_eq!(m.reqs, vec![(block.get_block_hash().clone(), vec![1])])
+        }
+        _ => panic!("block txs req should be decoded"),
+    }
+}
+
+#[test]
+fn test_short_tx_ids_that_are_ambiguous_are_not_resolved() {
+    let a = "a".repeat(64);
+    let b = "b".repeat(64);
+
+    // Same short id as b, differing only past it
+    let b_twin = format!("{}f", "b".repeat(63));
+
+    let short_tx_ids = vec![
+        make_short_tx_id(&a),
+        make_short_tx_id(&b),
+        make_short_tx_id(&"c".repeat(64)),
+    ];
+
+    let resolved =
+        resolve_short_tx_ids(&short_tx_ids, &vec![a.clone(), b.clone()]);
+    assert_eq!(resolved, vec![Some(a.clone()), Some(b.clone()), None]);
+
+    let resolved =
+        resolve_short_tx_ids(&short_tx_ids, &vec![a.clone(), b, b_twin]);
+    assert_eq!(resolved, vec![Some(a), None, None]);
+}
+
+#[test]
+fn test_upgraded_codec_carries_the_request_id() {
+    let (mut initiator, mut responder) = make_codec_pair(0);
+
+    let mut buf = BytesMut::new();
+
+    initiator
+        .encode(
+            Envelope {
+                req_id: 7,
+                msg: Msg::Ping(PingMsg { nonce: 1 }),
+            },
+            &mut buf,
+        )
+        .expect("ping should be encoded");
+
+    match responder.decode(&mut buf) {
+        Ok(Some(Envelope { req_id, .. })) => assert_eq!(req_id, 7),
+        _ => panic!("ping should be decoded"),
+    }
+
+    // Peers that have not agreed on request ids do not see one
+    let keys = SessionKeys {
+        send_key: [1; 32],
+        recv_key: [1; 32],
+    };
+
+    let mut legacy = UpgradedP2PCodec::new(&keys, 0, false);
+
+    let mut buf = BytesMut::new();
+
+    legacy
+        .encode(
+            Envelope {
+                req_id: 7,
+                msg: Msg::Ping(PingMsg { nonce: 1 }),
+            },
+            &mut buf,
+        )
+        .expect("ping should be encoded");
+
+    let mut legacy = UpgradedP2PCodec::new(&keys, 0, false);
+
+    match legacy.decode(&mut buf) {
+        Ok(Some(Envelope {
+            req_id,
+            msg: Msg::Ping(p),
+        })) => {
+            assert_eq!(req_id, NO_REQUEST_ID);
+            assert_eq!(p.nonce, 1);
+        }
+        _ => panic!("ping should be decoded"),
+    }
+}
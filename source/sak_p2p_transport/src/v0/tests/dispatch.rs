@@ -0,0 +1,202 @@
+use crate::{
+    handshake::SessionKeys, BlockAckMsg, Envelope, Msg, PingMsg, UpgradedConn,
+    UpgradedP2PCodec, INBOUND_QUEUE_CAPACITY, NO_REQUEST_ID,
+};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::codec::Framed;
+
+const TIMEOUT: Duration = Duration::from_millis(2000);
+
+async fn make_conn_pair(has_req_ids: bool) -> (UpgradedConn, UpgradedConn) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (stream_1, accepted) =
+        tokio::join!(TcpStream::connect(addr), listener.accept());
+
+    let stream_1 = stream_1.unwrap();
+    let (stream_2, _) = accepted.unwrap();
+
+    let keys_1 = SessionKeys {
+        send_key: [1; 32],
+        recv_key: [2; 32],
+    };
+
+    let keys_2 = SessionKeys {
+        send_key: [2; 32],
+        recv_key: [1; 32],
+    };
+
+    let conn_1 = UpgradedConn::init(
+        addr,
+        Framed::new(stream_1, UpgradedP2PCodec::new(&keys_1, 0, has_req_ids)),
+        String::from("conn_1"),
+        true,
+    )
+    .await;
+
+    let conn_2 = UpgradedConn::init(
+        addr,
+        Framed::new(stream_2, UpgradedP2PCodec::new(&keys_2, 0, has_req_ids)),
+        String::from("conn_2"),
+        false,
+    )
+    .await;
+
+    (conn_1, conn_2)
+}
+
+fn ping(nonce: u128) -> Msg {
+    Msg::Ping(PingMsg { nonce })
+}
+
+async fn next_envelope(conn: &UpgradedConn) -> Envelope {
+    let (maybe_msg, _) = conn.next_msg().await;
+
+    maybe_msg
+        .expect("conn should be open")
+        .expect("msg should be valid")
+}
+
+fn get_nonce(msg: Msg) -> u128 {
+    match msg {
+        Msg::Ping(p) | Msg::Pong(p) => p.nonce,
+        _ => panic!("Only ping or pong is expected, msg: {}", msg),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_responses_find_their_requests_in_any_order() {
+    let (conn_1, conn_2) = make_conn_pair(true).await;
+
+    let responder = async {
+        let req_1 = next_envelope(&conn_2).await;
+        let req_2 = next_envelope(&conn_2).await;
+
+        // Something she says on her own, in the middle of the requests
+        conn_2.send(ping(100)).await.unwrap();
+
+        for req in [req_2, req_1] {
+            let nonce = get_nonce(req.msg);
+
+            conn_2
+                .respond(req.req_id, Msg::Pong(PingMsg { nonce }))
+                .await
+                .unwrap();
+        }
+    };
+
+    let (resp_1, resp_2, _) = tokio::join!(
+        conn_1.request(ping(1), TIMEOUT),
+        conn_1.request(ping(2), TIMEOUT),
+        responder,
+    );
+
+    assert_eq!(get_nonce(resp_1.unwrap().0), 1);
+    assert_eq!(get_nonce(resp_2.unwrap().0), 2);
+
+    let unsolicited = next_envelope(&conn_1).await;
+
+    assert_eq!(unsolicited.req_id, NO_REQUEST_ID);
+    assert_eq!(get_nonce(unsolicited.msg), 100);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_request_times_out_and_late_response_is_dropped() {
+    let (conn_1, conn_2) = make_conn_pair(true).await;
+
+    let res = conn_1.request(ping(1), Duration::from_millis(200)).await;
+
+    assert!(res.is_err());
+    assert_eq!(conn_1.get_pending_request_count().await, 0);
+
+    let req = next_envelope(&conn_2).await;
+
+    conn_2
+        .respond(req.req_id, Msg::Pong(PingMsg { nonce: 1 }))
+        .await
+        .unwrap();
+
+    conn_2.send(ping(2)).await.unwrap();
+
+    // The late pong never shows up as a msg of her own
+    let envelope = next_envelope(&conn_1).await;
+
+    assert_eq!(get_nonce(envelope.msg), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_legacy_peer_gets_the_next_response() {
+    let (conn_1, conn_2) = make_conn_pair(false).await;
+
+    let responder = async {
+        let req = next_envelope(&conn_2).await;
+
+        assert_eq!(req.req_id, NO_REQUEST_ID);
+
+        conn_2.send(ping(3)).await.unwrap();
+
+        conn_2
+            .respond(req.req_id, Msg::BlockAck(BlockAckMsg {}))
+            .await
+            .unwrap();
+    };
+
+    let (resp, _) = tokio::join!(conn_1.request(ping(1), TIMEOUT), responder);
+
+    match resp.unwrap().0 {
+        Msg::BlockAck(_) => (),
+        m => panic!("Block ack is expected, msg: {}", m),
+    };
+
+    assert_eq!(get_nonce(next_envelope(&conn_1).await.msg), 3);
+}
+
+// Each side answers the request of the other only after it has sent more msgs
+// than the other side's queue holds, none of which are read in the meantime
+async fn answer_after_a_flood(conn: &UpgradedConn, flood_len: usize) {
+    let req = loop {
+        let envelope = next_envelope(conn).await;
+
+        if envelope.req_id != NO_REQUEST_ID {
+            break envelope;
+        }
+    };
+
+    for idx in 0..flood_len {
+        conn.send(ping(1000 + idx as u128)).await.unwrap();
+    }
+
+    let nonce = get_nonce(req.msg);
+
+    conn.respond(req.req_id, Msg::Pong(PingMsg { nonce }))
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_responses_are_delivered_while_both_queues_are_full() {
+    let (conn_1, conn_2) = make_conn_pair(true).await;
+
+    let flood_len = INBOUND_QUEUE_CAPACITY + 8;
+
+    let (resp_1, resp_2, _, _) = tokio::join!(
+        conn_1.request(ping(1), TIMEOUT),
+        conn_2.request(ping(2), TIMEOUT),
+        answer_after_a_flood(&conn_1, flood_len),
+        answer_after_a_flood(&conn_2, flood_len),
+    );
+
+    assert_eq!(get_nonce(resp_1.unwrap().0), 1);
+    assert_eq!(get_nonce(resp_2.unwrap().0), 2);
+
+    // Nothing of the flood is lost
+    for conn in [&conn_1, &conn_2] {
+        for idx in 0..flood_len {
+            let envelope = next_envelope(conn).await;
+
+            assert_eq!(get_nonce(envelope.msg), 1000 + idx as u128);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Conn;
 use crate::Transport;
-use crate::{handshake::*, Msg, PingMsg};
+use crate::{handshake::*, DisconnectReason, Msg, PingMsg};
 use futures::{SinkExt, StreamExt};
 use sak_p2p_id::Identity;
 use std::sync::Arc;
@@ -36,24 +36,12 @@ async fn connect_to_endpoint(endpoint: &String) -> Conn {
     }
 }
 
-async fn make_test_context() -> (
-    Arc<Identity>,
-    u16,
-    String,
-    //
-    Arc<Identity>,
-    u16,
-    String,
-    TcpListener,
-) {
-    let ip = "127.0.0.1";
-
-    let p2p_port_1 = 35501;
-    let disc_port_1 = 35518;
-
-    let p2p_port_2 = 35502;
-    let disc_port_2 = 35520;
-
+fn make_identities(
+    p2p_port_1: u16,
+    disc_port_1: u16,
+    p2p_port_2: u16,
+    disc_port_2: u16,
+) -> (Arc<Identity>, Arc<Identity>) {
     let identity_1 = Arc::new(
         Identity::new(
             &String::from(
@@ -94,6 +82,30 @@ async fn make_test_context() -> (
         .unwrap(),
     );
 
+    (identity_1, identity_2)
+}
+
+async fn make_test_context() -> (
+    Arc<Identity>,
+    u16,
+    String,
+    //
+    Arc<Identity>,
+    u16,
+    String,
+    TcpListener,
+) {
+    let ip = "127.0.0.1";
+
+    let p2p_port_1 = 35501;
+    let disc_port_1 = 35518;
+
+    let p2p_port_2 = 35502;
+    let disc_port_2 = 35520;
+
+    let (identity_1, identity_2) =
+        make_identities(p2p_port_1, disc_port_1, p2p_port_2, disc_port_2);
+
     let endpoint_1 = format!("{}:{}", ip, p2p_port_1);
     let endpoint_2 = format!("{}:{}", ip, p2p_port_2);
 
@@ -112,6 +124,15 @@ async fn make_test_context() -> (
     )
 }
 
+fn make_protocol_info(network_id: &str) -> Arc<ProtocolInfo> {
+    let protocol_info = ProtocolInfo::new(
+        network_id.to_string(),
+        String::from("test_genesis_block_hash"),
+    );
+
+    Arc::new(protocol_info)
+}
+
 async fn accept(p2p_socket: TcpListener) -> Result<TcpStream, String> {
     loop {
         match p2p_socket.accept().await {
@@ -137,6 +158,7 @@ async fn handshake_init(
         identity: my_identity.clone(),
         conn,
         public_key_str: (*her_identity).credential.public_key_str.clone(),
+        protocol_info: make_protocol_info("test"),
     };
 
     let transport = match initiate_handshake(handshake_init_args).await {
@@ -180,6 +202,7 @@ async fn handshake_recv(
 
     let handshake_recv_args = HandshakeRecvArgs {
         identity: my_identity.to_owned(),
+        protocol_info: make_protocol_info("test"),
     };
 
     log::debug!(
@@ -242,11 +265,9 @@ async fn test_handshake_works() {
 
         println!("preparing to send msg,");
 
-        let mut conn_1_lock = transport_1.conn.write().await;
-
         let msg = PingMsg { nonce: rand };
 
-        conn_1_lock.send(Msg::Ping(msg)).await.unwrap();
+        transport_1.conn.send(Msg::Ping(msg)).await.unwrap();
     });
 
     let identity_2_clone = identity_2.clone();
@@ -256,13 +277,11 @@ async fn test_handshake_works() {
         let (transport_2, _) =
             handshake_recv(tcp_listener_2, identity_2_clone).await;
 
-        let mut conn_2_lock = transport_2.conn.write().await;
-
-        let (maybe_msg, _) = conn_2_lock.next_msg().await;
+        let (maybe_msg, _) = transport_2.conn.next_msg().await;
 
         let ping = match maybe_msg {
             Some(maybe_msg) => match maybe_msg {
-                Ok(msg) => match msg {
+                Ok(envelope) => match envelope.msg {
                     Msg::Ping(p) => {
                         println!("ping: {:?}, rand received!", p);
 
@@ -290,3 +309,105 @@ async fn test_handshake_works() {
 
     let _ = tokio::join!(t1, t2);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_handshake_refuses_other_network() {
+    sak_test_utils::init_test_log();
+
+    let (identity_1, identity_2) = make_identities(35503, 35518, 35504, 35520);
+
+    let (tcp_listener, socket_addr) =
+        sak_utils_net::bind_tcp_socket(None).await.unwrap();
+
+    let endpoint = format!("127.0.0.1:{}", socket_addr.port());
+
+    let identity_2_clone = identity_2.clone();
+
+    let t_recv = tokio::spawn(async move {
+        let tcp_stream = accept(tcp_listener).await.unwrap();
+        let conn = Conn::new(tcp_stream, false).unwrap();
+
+        let handshake_recv_args = HandshakeRecvArgs {
+            identity: identity_2_clone,
+            protocol_info: make_protocol_info("test_2"),
+        };
+
+        receive_handshake(handshake_recv_args, conn).await
+    });
+
+    let conn = connect_to_endpoint(&endpoint).await;
+
+    let handshake_init_args = HandshakeInitArgs {
+        identity: identity_1,
+        conn,
+        public_key_str: identity_2.credential.public_key_str.clone(),
+        protocol_info: make_protocol_info("test"),
+    };
+
+    match initiate_handshake(handshake_init_args).await {
+        Err(HandshakeInitError::PeerDisconnected { reason, .. }) => {
+            assert_eq!(reason, DisconnectReason::NetworkMismatch);
+        }
+        Err(err) => panic!("Unexpected error, err: {}", err),
+        Ok(_) => panic!("Handshake should be refused"),
+    };
+
+    match t_recv.await.unwrap() {
+        Err(HandshakeRecvError::IncompatiblePeer { reason, .. }) => {
+            assert_eq!(reason, DisconnectReason::NetworkMismatch);
+        }
+        Err(err) => panic!("Unexpected error, err: {}", err),
+        Ok(_) => panic!("Handshake should be refused"),
+    };
+}
+
+#[test]
+fn test_protocol_negotiation() {
+    let mine = make_protocol_info("test");
+
+    let mut hers = mine.as_ref().clone();
+    hers.capabilities = vec![capability::TX_SYNC.to_string()];
+
+    let negotiated = mine.negotiate(&hers).unwrap();
+
+    assert!(negotiated.supports(capability::TX_SYNC));
+    assert!(!negotiated.supports(capability::BLOCK_SYNC));
+
+    // A peer that predates keepalive
+    let mut hers = mine.as_ref().clone();
+    hers.protocol_version = 1;
+    hers.capabilities = vec![
+        capability::TX_SYNC.to_string(),
+        capability::BLOCK_SYNC.to_string(),
+    ];
+
+    let negotiated = mine.negotiate(&hers).unwrap();
+
+    assert_eq!(negotiated.protocol_version, 1);
+    assert!(!negotiated.supports(capability::KEEPALIVE));
+
+    // A peer that predates compact blocks
+    let mut hers = mine.as_ref().clone();
+    hers.protocol_version = 2;
+    hers.capabilities.retain(|c| {
+        c != capability::COMPACT_BLOCK && c != capability::REQUEST_ID
+    });
+
+    let negotiated = mine.negotiate(&hers).unwrap();
+
+    assert!(negotiated.supports(capability::KEEPALIVE));
+    assert!(!negotiated.supports(capability::COMPACT_BLOCK));
+    assert!(!negotiated.supports(capability::REQUEST_ID));
+
+    let mut hers = mine.as_ref().clone();
+    hers.genesis_block_hash = String::from("other_genesis_block_hash");
+
+    let disconnect = mine.negotiate(&hers).unwrap_err();
+    assert_eq!(disconnect.reason, DisconnectReason::GenesisMismatch);
+
+    let mut hers = mine.as_ref().clone();
+    hers.protocol_version = MIN_PROTOCOL_VERSION - 1;
+
+    let disconnect = mine.negotiate(&hers).unwrap_err();
+    assert_eq!(disconnect.reason, DisconnectReason::ProtocolVersionMismatch);
+}
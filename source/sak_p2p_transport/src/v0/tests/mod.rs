@@ -1,2 +1,4 @@
 mod cipher;
+mod codec;
+mod dispatch;
 mod handshake;
@@ -1,6 +1,6 @@
-use crate::UpgradedConn;
-use tokio::sync::RwLock;
+use crate::{handshake::NegotiatedProtocol, UpgradedConn};
 
 pub struct Transport {
-    pub conn: RwLock<UpgradedConn>,
+    pub conn: UpgradedConn,
+    pub protocol: NegotiatedProtocol,
 }
@@ -6,7 +6,7 @@ use sak_crypto::{
 use std::fs::File;
 use std::io::Write;
 
-const PARAM_FILE_NAME: &str = "mimc_params_1_to_2";
+const PARAM_FILE_NAME: &str = "mimc_params_1_to_2_v1";
 
 pub struct CoinProofCircuit1to2 {
     pub hasher: Hasher,
@@ -17,6 +17,10 @@ pub struct CoinProofCircuit1to2 {
 
     pub coin_2_new: NewCoin,
 
+    // Hash of the public key that signs the pour tx. It is a public input so
+    // that the proof cannot be reused under someone else's signature
+    pub author_pk_hash: Option<Scalar>,
+
     pub constants: Vec<Scalar>,
 }
 
@@ -45,6 +49,7 @@ pub(crate) fn get_mimc_params_1_to_2(
                 coin_1_old,
                 coin_1_new,
                 coin_2_new,
+                author_pk_hash: None,
                 constants: constants.to_vec(),
             };
 
@@ -82,6 +87,7 @@ impl Circuit<Scalar> for CoinProofCircuit1to2 {
         let r_1_old = self.coin_1_old.r.or(Some(Scalar::default()));
         let s_1_old = self.coin_1_old.s.or(Some(Scalar::default()));
         let v_1_old = self.coin_1_old.v.or(Some(Scalar::default()));
+        let author_pk_hash = self.author_pk_hash.or(Some(Scalar::default()));
 
         check_cm_commitments(
             cs,
@@ -179,6 +185,11 @@ impl Circuit<Scalar> for CoinProofCircuit1to2 {
                 || "cm_2_new",
                 || cm_2_new.ok_or(SynthesisError::AssignmentMissing),
             )?;
+
+            cs.alloc_input(
+                || "author_pk_hash",
+                || author_pk_hash.ok_or(SynthesisError::AssignmentMissing),
+            )?;
         }
 
         Ok(())
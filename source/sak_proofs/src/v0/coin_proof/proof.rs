@@ -45,6 +45,7 @@ impl CoinProof {
         coin_1_old: OldCoin,
         coin_1_new: NewCoin,
         coin_2_new: NewCoin,
+        author_pk_hash: Scalar,
     ) -> Result<Proof<Bls12>, ProofError> {
         let hasher = Hasher::new();
         let constants = hasher.get_mimc_constants().to_vec();
@@ -56,6 +57,7 @@ impl CoinProof {
             coin_1_old,
             coin_1_new,
             coin_2_new,
+            author_pk_hash: Some(author_pk_hash),
             constants,
         };
 
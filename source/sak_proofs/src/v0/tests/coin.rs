@@ -11,7 +11,7 @@ use std::io::Write;
 use type_extension::U8Array;
 
 const TEST_TREE_DEPTH: u32 = 4;
-const PARAM_FILE_NAME: &str = "mimc_params";
+const PARAM_FILE_NAME: &str = "mimc_params_v1";
 
 pub struct TestContext {
     pub hasher: Hasher,
@@ -314,6 +314,7 @@ pub fn get_test_params(constants: &[Scalar]) -> Parameters<Bls12> {
                 coin_1_old,
                 coin_1_new,
                 coin_2_new,
+                author_pk_hash: None,
                 constants: constants.to_vec(),
             };
 
@@ -337,6 +338,7 @@ fn make_proof(
     coin_1_old: OldCoin,
     coin_1_new: NewCoin,
     coin_2_new: NewCoin,
+    author_pk_hash: Scalar,
 ) -> Result<Proof<Bls12>, ProofError> {
     let hasher = Hasher::new();
 
@@ -348,6 +350,7 @@ fn make_proof(
         coin_1_old,
         coin_1_new,
         coin_2_new,
+        author_pk_hash: Some(author_pk_hash),
         constants,
     };
 
@@ -389,12 +392,7 @@ fn verify_proof(
     }
 }
 
-#[tokio::test(flavor = "multi_thread")]
-pub async fn test_coin_ownership_default() {
-    sak_test_utils::init_test_log();
-
-    let test_context = make_test_context();
-
+fn make_coins(test_context: &TestContext) -> (OldCoin, NewCoin, NewCoin) {
     let coin_1_old = OldCoin {
         addr_pk: Some(test_context.addr_pk_1_old),
         addr_sk: Some(test_context.addr_sk_1_old),
@@ -422,13 +420,28 @@ pub async fn test_coin_ownership_default() {
         v: Some(test_context.v_2),
     };
 
-    let proof = make_proof(coin_1_old, coin_1_new, coin_2_new).unwrap();
+    (coin_1_old, coin_1_new, coin_2_new)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn test_coin_ownership_default() {
+    sak_test_utils::init_test_log();
+
+    let test_context = make_test_context();
+
+    let (coin_1_old, coin_1_new, coin_2_new) = make_coins(&test_context);
+
+    let author_pk_hash = ScalarExt::parse_u64(1).unwrap();
+
+    let proof =
+        make_proof(coin_1_old, coin_1_new, coin_2_new, author_pk_hash).unwrap();
 
     let public_inputs: Vec<Scalar> = vec![
         test_context.merkle_rt,
         test_context.sn_1,
         test_context.cm_1,
         test_context.cm_2,
+        author_pk_hash,
     ];
 
     assert_eq!(
@@ -436,3 +449,32 @@ pub async fn test_coin_ownership_default() {
         true
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn test_coin_proof_is_bound_to_author() {
+    sak_test_utils::init_test_log();
+
+    let test_context = make_test_context();
+
+    let (coin_1_old, coin_1_new, coin_2_new) = make_coins(&test_context);
+
+    let author_pk_hash = ScalarExt::parse_u64(1).unwrap();
+
+    let proof =
+        make_proof(coin_1_old, coin_1_new, coin_2_new, author_pk_hash).unwrap();
+
+    let other_author_pk_hash = ScalarExt::parse_u64(2).unwrap();
+
+    let public_inputs: Vec<Scalar> = vec![
+        test_context.merkle_rt,
+        test_context.sn_1,
+        test_context.cm_1,
+        test_context.cm_2,
+        other_author_pk_hash,
+    ];
+
+    assert_eq!(
+        verify_proof(proof, &public_inputs, &test_context.hasher),
+        false
+    );
+}
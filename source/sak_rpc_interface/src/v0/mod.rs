@@ -1,24 +1,114 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
 
 pub const JSON_RPC_2: &'static str = "2.0";
 
+// Error codes defined by the JSON-RPC 2.0 spec
+pub const PARSE_ERROR: i64 = -32700;
+
+pub const INVALID_REQUEST: i64 = -32600;
+
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+pub const INVALID_PARAMS: i64 = -32602;
+
+pub const INTERNAL_ERROR: i64 = -32603;
+
+// Application errors, taken from the range the spec reserves for servers
+pub const APPLICATION_ERROR: i64 = -32000;
+
+pub const UNAUTHORIZED: i64 = -32001;
+
+pub const METHOD_NOT_ALLOWED: i64 = -32002;
+
+// Data asked for used to be in the ledger, but the node has pruned it
+pub const LEDGER_PRUNED: i64 = -32003;
+
+pub const RATE_LIMITED: i64 = -32005;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonResponse<R: Serialize> {
     pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<JsonRPCError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<R>,
-    pub id: String,
+    pub id: JsonRPCId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonRequest {
     pub jsonrpc: String,
     pub method: String,
-    pub params: Option<Vec<u8>>,
-    pub id: String,
+    pub params: Option<Value>,
+    #[serde(default)]
+    pub id: JsonRPCId,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum JsonRPCId {
+    Num(Number),
+    Str(String),
+    Null,
+}
+
+impl Default for JsonRPCId {
+    fn default() -> Self {
+        JsonRPCId::Null
+    }
+}
+
+impl From<&str> for JsonRPCId {
+    fn from(id: &str) -> Self {
+        JsonRPCId::Str(id.to_string())
+    }
+}
+
+impl From<String> for JsonRPCId {
+    fn from(id: String) -> Self {
+        JsonRPCId::Str(id)
+    }
+}
+
+impl From<u64> for JsonRPCId {
+    fn from(id: u64) -> Self {
+        JsonRPCId::Num(id.into())
+    }
+}
+
+impl std::fmt::Display for JsonRPCId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonRPCId::Num(n) => write!(f, "{}", n),
+            JsonRPCId::Str(s) => write!(f, "{}", s),
+            JsonRPCId::Null => write!(f, "null"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct JsonRPCError {
-    pub msg: String,
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl JsonRPCError {
+    pub fn new(code: i64, message: String) -> JsonRPCError {
+        JsonRPCError {
+            code,
+            message,
+            data: None,
+        }
+    }
 }
+
+impl std::fmt::Display for JsonRPCError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (code: {})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for JsonRPCError {}
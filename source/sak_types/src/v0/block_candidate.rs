@@ -21,18 +21,19 @@ impl BlockCandidate {
     ) -> (Block, Vec<Tx>) {
         let mut txs: Vec<Tx> = Vec::new();
         let mut tx_hashes: Vec<String> = vec![];
-        // let mut block_cm_count: u128 = 0;
+        let mut block_cm_count: u128 = 0;
 
-        for (idx, tc) in self.tx_candidates.into_iter().enumerate() {
-            // let cm_count = tc.get_cm_count();
+        for tc in self.tx_candidates.into_iter() {
+            let cm_count = tc.get_cm_count();
 
+            // Each tx takes as many cm indices as the cms it has
             let tx = tc.upgrade(
                 // next_tx_height + i as u128
-                next_cm_idx + idx as u128,
+                next_cm_idx + block_cm_count,
             );
             let tx_hash = tx.get_tx_hash();
 
-            // block_cm_count += cm_count;
+            block_cm_count += cm_count;
             tx_hashes.push(tx_hash.to_owned());
             txs.push(tx);
         }
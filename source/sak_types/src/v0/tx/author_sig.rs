@@ -0,0 +1,148 @@
+use crate::TypesError;
+use sak_crypto::{
+    Scalar, ScalarExt, SecretKey, Signature, SigningKey, ToEncodedPoint,
+};
+use std::convert::TryInto;
+
+const AUTHOR_SIG_DELIMITER: char = '.';
+
+// The author signature of a tx is the public key of the signer and the DER
+// encoded signature over the signing payload of the tx, each hex encoded and
+// joined by a delimiter, e.g. "04ab...cd.3045...ef"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorSig {
+    pub public_key_str: String,
+    pub sig: Signature,
+}
+
+impl AuthorSig {
+    pub fn sign(secret_key: &SecretKey, payload: &[u8]) -> AuthorSig {
+        let public_key_str = sak_crypto::encode_hex(
+            &secret_key.public_key().to_encoded_point(false).to_bytes(),
+        );
+
+        let sig =
+            sak_crypto::make_signature(SigningKey::from(secret_key), payload);
+
+        AuthorSig {
+            public_key_str,
+            sig,
+        }
+    }
+
+    pub fn parse(author_sig: &String) -> Result<AuthorSig, TypesError> {
+        let (public_key_str, sig_str) =
+            match author_sig.split_once(AUTHOR_SIG_DELIMITER) {
+                Some(s) => s,
+                None => {
+                    return Err(format!(
+                        "author_sig should be a public key and a signature, \
+                        author_sig: {}",
+                        author_sig
+                    )
+                    .into());
+                }
+            };
+
+        let sig_bytes = decode_hex_str(sig_str)?;
+
+        let sig = match Signature::from_der(&sig_bytes) {
+            Ok(s) => s,
+            Err(err) => {
+                return Err(format!(
+                    "Error parsing author_sig into a signature, err: {}",
+                    err
+                )
+                .into());
+            }
+        };
+
+        Ok(AuthorSig {
+            public_key_str: public_key_str.to_string(),
+            sig,
+        })
+    }
+
+    pub fn verify(&self, payload: &[u8]) -> Result<(), TypesError> {
+        let public_key_bytes: [u8; 65] =
+            match decode_hex_str(&self.public_key_str)?.try_into() {
+                Ok(b) => b,
+                Err(_) => {
+                    return Err(format!(
+                        "Author public key should be 65 bytes"
+                    )
+                    .into());
+                }
+            };
+
+        let verifying_key =
+            sak_crypto::convert_public_key_to_verifying_key(public_key_bytes)?;
+
+        match sak_crypto::verify(verifying_key, payload, &self.sig) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(format!("Invalid author_sig, err: {}", err).into()),
+        }
+    }
+}
+
+impl std::fmt::Display for AuthorSig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.public_key_str,
+            AUTHOR_SIG_DELIMITER,
+            sak_crypto::encode_hex(&self.sig.to_der().to_bytes()),
+        )
+    }
+}
+
+// A pour proof takes the hash of its author's public key as a public input,
+// which ties the proof to the key that signs the tx
+pub fn make_author_pk_hash(
+    public_key_str: &String,
+) -> Result<Scalar, TypesError> {
+    let public_key_bytes = decode_hex_str(public_key_str)?;
+
+    let h = sak_crypto::compute_hash(&[public_key_bytes]);
+
+    let h: [u8; 32] = match decode_hex_str(&h)?.try_into() {
+        Ok(b) => b,
+        Err(_) => {
+            return Err(format!("Public key hash should be 32 bytes").into());
+        }
+    };
+
+    let s = ScalarExt::parse_arr_wide(&h, &[0; 32])?;
+
+    Ok(s)
+}
+
+// Every field is length prefixed, so two different txs never end up with the
+// same payload
+pub(crate) fn make_signing_payload(domain: &str, fields: &[&[u8]]) -> Vec<u8> {
+    let mut payload = vec![];
+
+    payload.extend_from_slice(&(domain.len() as u64).to_be_bytes());
+    payload.extend_from_slice(domain.as_bytes());
+
+    for field in fields {
+        payload.extend_from_slice(&(field.len() as u64).to_be_bytes());
+        payload.extend_from_slice(field);
+    }
+
+    payload
+}
+
+fn decode_hex_str(s: &str) -> Result<Vec<u8>, TypesError> {
+    if !s.is_ascii() || s.len() % 2 != 0 {
+        return Err(format!("Not a hex string, s: {}", s).into());
+    }
+
+    match sak_crypto::decode_hex(&s.to_string()) {
+        Ok(b) => Ok(b),
+        Err(err) => {
+            Err(format!("Not a hex string, s: {}, err: {}", s, err).into())
+        }
+    }
+}
@@ -1,10 +1,14 @@
+use super::author_sig::make_signing_payload;
 use super::utils;
 use super::CmIdx;
-use crate::{Cm, PourTxCandidate, TxCandidate};
+use crate::{AuthorSig, Cm, PourTxCandidate, TxCandidate, TypesError};
 use crate::{Tx, TxCtrOp, TxType, WASM_MAGIC_NUMBER};
+use sak_crypto::SecretKey;
 use serde::{Deserialize, Serialize};
 use type_extension::U8Arr32;
 
+const MINT_TX_SIGNING_DOMAIN: &str = "saksaha-mint-tx-v0";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MintTx {
     //
@@ -131,6 +135,46 @@ impl MintTxCandidate {
         vec![self.cm_1]
     }
 
+    // Every field but author_sig, which is made out of this payload
+    pub fn get_signing_payload(&self) -> Vec<u8> {
+        make_signing_payload(
+            MINT_TX_SIGNING_DOMAIN,
+            &[
+                self.created_at.as_bytes(),
+                &self.data,
+                self.ctr_addr.as_bytes(),
+                &self.cm_1,
+                &self.v,
+                &self.k,
+                &self.s,
+            ],
+        )
+    }
+
+    pub fn sign(self, secret_key: &SecretKey) -> MintTxCandidate {
+        let author_sig =
+            AuthorSig::sign(secret_key, &self.get_signing_payload());
+
+        MintTxCandidate::new(
+            self.created_at,
+            self.data,
+            author_sig.to_string(),
+            Some(self.ctr_addr),
+            self.cm_1,
+            self.v,
+            self.k,
+            self.s,
+        )
+    }
+
+    pub fn verify_author_sig(&self) -> Result<AuthorSig, TypesError> {
+        let author_sig = AuthorSig::parse(&self.author_sig)?;
+
+        author_sig.verify(&self.get_signing_payload())?;
+
+        Ok(author_sig)
+    }
+
     pub fn upgrade(
         self,
         // tx_height: u128,
@@ -1,3 +1,4 @@
+mod author_sig;
 mod mint_tx;
 mod pour_tx;
 mod testing;
@@ -6,6 +7,7 @@ mod tx_candidate;
 mod tx_type;
 mod utils;
 
+pub use author_sig::*;
 pub use mint_tx::*;
 pub use pour_tx::*;
 pub use testing::*;
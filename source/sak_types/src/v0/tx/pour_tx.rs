@@ -1,9 +1,13 @@
+use super::author_sig::make_signing_payload;
 use super::utils;
 use super::CmIdx;
-use crate::{Cm, Sn, Tx, TxCtrOp, TxType};
+use crate::{AuthorSig, Cm, Sn, Tx, TxCtrOp, TxType, TypesError};
+use sak_crypto::SecretKey;
 use serde::{Deserialize, Serialize};
 use type_extension::U8Arr32;
 
+const POUR_TX_SIGNING_DOMAIN: &str = "saksaha-pour-tx-v0";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PourTx {
     //
@@ -161,6 +165,50 @@ impl PourTxCandidate {
     pub fn get_cms(&self) -> Vec<Cm> {
         vec![self.cm_1, self.cm_2]
     }
+
+    // Every field but author_sig, which is made out of this payload
+    pub fn get_signing_payload(&self) -> Vec<u8> {
+        make_signing_payload(
+            POUR_TX_SIGNING_DOMAIN,
+            &[
+                self.created_at.as_bytes(),
+                &self.data,
+                self.ctr_addr.as_bytes(),
+                &self.pi,
+                &self.sn_1,
+                &self.cm_1,
+                &self.cm_2,
+                &self.merkle_rt,
+            ],
+        )
+    }
+
+    // The proof has to be generated with the hash of this secret key's
+    // public key, see make_author_pk_hash()
+    pub fn sign(self, secret_key: &SecretKey) -> PourTxCandidate {
+        let author_sig =
+            AuthorSig::sign(secret_key, &self.get_signing_payload());
+
+        PourTxCandidate::new(
+            self.created_at,
+            self.data,
+            author_sig.to_string(),
+            Some(self.ctr_addr),
+            self.pi,
+            self.sn_1,
+            self.cm_1,
+            self.cm_2,
+            self.merkle_rt,
+        )
+    }
+
+    pub fn verify_author_sig(&self) -> Result<AuthorSig, TypesError> {
+        let author_sig = AuthorSig::parse(&self.author_sig)?;
+
+        author_sig.verify(&self.get_signing_payload())?;
+
+        Ok(author_sig)
+    }
 }
 
 impl std::fmt::Display for PourTxCandidate {
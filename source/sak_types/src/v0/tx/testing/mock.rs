@@ -1,4 +1,5 @@
 use super::TxCandidate;
+use super::{get_dummy_author_pk_hash, get_dummy_author_secret_key};
 use crate::TypesError;
 use crate::{MintTxCandidate, PourTxCandidate, Tx, WASM_MAGIC_NUMBER};
 use sak_crypto::Hasher;
@@ -234,23 +235,28 @@ pub fn mock_pour_tc_1() -> Result<TxCandidate, TypesError> {
         v: Some(v_2),
     };
 
-    let pi =
-        CoinProof::generate_proof_1_to_2(coin_1_old, coin_1_new, coin_2_new)
-            .unwrap();
+    let pi = CoinProof::generate_proof_1_to_2(
+        coin_1_old,
+        coin_1_new,
+        coin_2_new,
+        get_dummy_author_pk_hash(),
+    )
+    .unwrap();
 
     let pi_serialized = CoinProof::serialize_pi(&pi)?;
 
     let pour_tc = PourTxCandidate::new(
         "created_at".to_string(),
         vec![],
-        "author_sig".to_string(),
+        String::new(),
         None,
         pi_serialized,
         sn_1.to_bytes(),
         cm_1.to_bytes(),
         cm_2.to_bytes(),
         merkle_rt.to_bytes(),
-    );
+    )
+    .sign(&get_dummy_author_secret_key());
 
     let c = TxCandidate::Pour(pour_tc);
 
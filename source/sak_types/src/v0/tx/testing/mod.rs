@@ -1,11 +1,16 @@
 mod mock;
 
 use super::TxCandidate;
-use crate::{MintTxCandidate, PourTxCandidate, Tx, WASM_MAGIC_NUMBER};
+use crate::{
+    make_author_pk_hash, MintTxCandidate, PourTxCandidate, Tx,
+    WASM_MAGIC_NUMBER,
+};
 pub use mock::*;
 use sak_crypto::Hasher;
 use sak_crypto::Scalar;
 use sak_crypto::ScalarExt;
+use sak_crypto::SecretKey;
+use sak_crypto::ToEncodedPoint;
 use type_extension::U8Arr32;
 use type_extension::U8Array;
 
@@ -14,6 +19,31 @@ pub(crate) const VALIDATOR_CTR_ADDR: &'static str = "test_validator_1";
 pub(crate) const VALIDATOR: &[u8] =
     include_bytes!("../../../../../prebuild/sak_validator.postprocess.wasm");
 
+// Secret of the dev validator, which is also the mint authority of the dev
+// network. Every dummy tx is signed with it
+const DUMMY_AUTHOR_SECRET: &'static str =
+    "7297b903877a957748b74068d63d6d5661481975240\
+    99fc1df5cd9e8814c66c7";
+
+pub fn get_dummy_author_secret_key() -> SecretKey {
+    let secret_bytes =
+        sak_crypto::decode_hex(&DUMMY_AUTHOR_SECRET.to_string()).unwrap();
+
+    SecretKey::from_bytes(secret_bytes).unwrap()
+}
+
+// Public input a proof needs to go with a tx signed by the dummy author
+pub fn get_dummy_author_pk_hash() -> Scalar {
+    let public_key_str = sak_crypto::encode_hex(
+        &get_dummy_author_secret_key()
+            .public_key()
+            .to_encoded_point(false)
+            .to_bytes(),
+    );
+
+    make_author_pk_hash(&public_key_str).unwrap()
+}
+
 fn get_addr_sk_1() -> U8Arr32 {
     [
         213, 142, 186, 101, 114, 0, 81, 8, 38, 83, 254, 23, 201, 180, 239, 177,
@@ -111,13 +141,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_custom_1"),
             validator_wasm,
-            String::from("author_sig_mint_custom_1"),
+            String::new(),
             Some(VALIDATOR_CTR_ADDR.to_string()),
             cm,
             v,
             k,
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_1() -> MintTxCandidate {
@@ -144,13 +175,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_1"),
             validator_wasm,
-            String::from("author_sig_mint_1"),
+            String::new(),
             Some(VALIDATOR_CTR_ADDR.to_string()),
             cm.to_bytes(),
             v,
             k.to_bytes(),
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_2() -> MintTxCandidate {
@@ -173,13 +205,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_2"),
             vec![2],
-            String::from("author_sig_mint_2"),
+            String::new(),
             None,
             cm.to_bytes(),
             v,
             k.to_bytes(),
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_3() -> MintTxCandidate {
@@ -206,13 +239,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_3"),
             vec![3],
-            String::from("author_sig_mint_3"),
+            String::new(),
             None,
             cm.to_bytes(),
             v,
             k.to_bytes(),
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_4() -> MintTxCandidate {
@@ -239,13 +273,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_4"),
             vec![4],
-            String::from("author_sig_mint_4"),
+            String::new(),
             None,
             cm.to_bytes(),
             v,
             k.to_bytes(),
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_deploying_contract(
@@ -275,13 +310,14 @@ impl MintTxCandidate {
         MintTxCandidate::new(
             String::from("created_at_mint_3"),
             contract_data,
-            String::from("author_sig_mint_3"),
+            String::new(),
             Some(ctrt_addr),
             cm.to_bytes(),
             v,
             k.to_bytes(),
             s,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 }
 
@@ -358,7 +394,7 @@ impl PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_1"),
             WASM_MAGIC_NUMBER.to_vec(),
-            String::from("author_sig_1"),
+            String::new(),
             Some(String::from("ctr_addr_1")),
             vec![11, 11, 11],
             U8Array::new_empty_32(),
@@ -366,13 +402,14 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_2() -> PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_2"),
             vec![22, 22, 22],
-            String::from("author_sig_2"),
+            String::new(),
             Some(String::from("ctr_addr_2")),
             vec![22, 22, 22],
             U8Array::new_empty_32(),
@@ -380,13 +417,14 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_3() -> PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_3"),
             vec![33, 33, 33],
-            String::from("author_sig_3"),
+            String::new(),
             Some(String::from("ctr_addr_3")),
             vec![22, 22, 22],
             U8Array::new_empty_32(),
@@ -394,13 +432,14 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_4() -> PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_4"),
             vec![44, 44, 44],
-            String::from("author_sig_4"),
+            String::new(),
             Some(String::from("ctr_addr_4")),
             vec![44, 44, 44],
             U8Array::new_empty_32(),
@@ -408,13 +447,14 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_5(cm: [u8; 32]) -> PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_4"),
             vec![44, 44, 44],
-            String::from("author_sig_4"),
+            String::new(),
             Some(String::from("ctr_addr_4")),
             vec![44, 44, 44],
             U8Array::new_empty_32(),
@@ -422,6 +462,7 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_valid(
@@ -434,7 +475,7 @@ impl PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_test"),
             vec![44, 44, 44],
-            String::from("author_sig_test"),
+            String::new(),
             Some(String::from("ctr_addr_test")),
             pi,
             sn_1,
@@ -442,13 +483,14 @@ impl PourTxCandidate {
             cm_2,
             merkle_rt,
         )
+        .sign(&get_dummy_author_secret_key())
     }
 
     pub fn new_dummy_validator_ctrt() -> PourTxCandidate {
         PourTxCandidate::new(
             String::from("created_at_4"),
             vec![44, 44, 44],
-            String::from("author_sig_4"),
+            String::new(),
             Some(String::from("ctr_addr_4")),
             vec![44, 44, 44],
             U8Array::new_empty_32(),
@@ -456,6 +498,7 @@ impl PourTxCandidate {
             U8Array::new_empty_32(),
             U8Array::new_empty_32(),
         )
+        .sign(&get_dummy_author_secret_key())
     }
 }
 
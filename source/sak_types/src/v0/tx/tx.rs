@@ -28,6 +28,20 @@ impl Tx {
         }
     }
 
+    pub fn get_ctr_addr(&self) -> &String {
+        match &self {
+            Tx::Mint(t) => &t.tx_candidate.ctr_addr,
+            Tx::Pour(t) => &t.tx_candidate.ctr_addr,
+        }
+    }
+
+    pub fn get_ctr_op(&self) -> TxCtrOp {
+        match &self {
+            Tx::Mint(t) => t.tx_candidate.get_ctr_op(),
+            Tx::Pour(t) => t.tx_candidate.get_ctr_op(),
+        }
+    }
+
     pub fn get_cm_count(&self) -> usize {
         match &self {
             Tx::Mint(t) => [&t.tx_candidate.cm_1].len(),
@@ -77,3 +91,9 @@ pub enum TxCtrOp {
     ContractDeploy,
     None,
 }
+
+impl TxCtrOp {
+    pub fn new(ctr_addr: &String, data: &Vec<u8>) -> TxCtrOp {
+        super::utils::get_ctr_op(ctr_addr, data)
+    }
+}
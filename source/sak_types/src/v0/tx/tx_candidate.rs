@@ -1,4 +1,7 @@
-use crate::{Cm, CmIdx, MintTxCandidate, PourTxCandidate, Tx, TxCtrOp};
+use crate::{
+    AuthorSig, Cm, CmIdx, MintTxCandidate, PourTxCandidate, Tx, TxCtrOp,
+    TypesError,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone)]
@@ -56,4 +59,11 @@ impl TxCandidate {
             TxCandidate::Pour(c) => c.get_cms(),
         }
     }
+
+    pub fn verify_author_sig(&self) -> Result<AuthorSig, TypesError> {
+        match &self {
+            TxCandidate::Mint(c) => c.verify_author_sig(),
+            TxCandidate::Pour(c) => c.verify_author_sig(),
+        }
+    }
 }
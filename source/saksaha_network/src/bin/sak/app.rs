@@ -68,7 +68,7 @@ pub(super) fn create_app<'a>() -> Command<'a> {
             Arg::new("disc-table-capacity") //
                 .long("disc-table-capacity")
                 .takes_value(true)
-                .long_help("P2P discovery table capacity (size)"),
+                .long_help("P2P discovery table bucket size (nodes per bucket)"),
         )
         .arg(
             Arg::new("cfg-profile") //
@@ -79,6 +79,15 @@ pub(super) fn create_app<'a>() -> Command<'a> {
                     e.g. 'dev_local_1'",
                 ),
         )
+        .arg(
+            Arg::new("network-id") //
+                .long("network-id")
+                .takes_value(true)
+                .long_help(
+                    "Network this node belongs to, e.g. 'dev_local'. Peers \n\
+                    on another network are disconnected in the handshake",
+                ),
+        )
         .arg(
             Arg::new("disc-dial-interval") //
                 .long("disc-dial-interval")
@@ -135,6 +144,16 @@ pub(super) fn create_app<'a>() -> Command<'a> {
                     e.g. 50",
                 ),
         )
+        .arg(
+            Arg::new("p2p-outbound-peer-capacity") //
+                .long("p2p-outbound-peer-capacity")
+                .takes_value(true)
+                .long_help(
+                    "Number of the peer table slots kept for the peers this \n\
+                    node dials, the rest being left to the ones dialing it \n\
+                    e.g. 10",
+                ),
+        )
         .arg(
             Arg::new("p2p-max-conn-count") //
                 .long("p2p-max-conn-count")
@@ -168,12 +187,52 @@ pub(super) fn create_app<'a>() -> Command<'a> {
                     short url: 127.0.0.1:3030",
                 ),
         )
+        .arg(
+            Arg::new("external-addr") //
+                .long("external-addr")
+                .takes_value(true)
+                .long_help(
+                    "Endpoint this node is reachable at from outside its own \n\
+                    network, advertised to the peers. Ports left out are the \n\
+                    local ones\n\
+                    e.g. 1.2.3.4, 1.2.3.4:35518, 1.2.3.4:35518:35519 \n\
+                    (ip:disc_port:p2p_port)",
+                ),
+        )
         .arg(
             Arg::new("miner") //
                 .long("miner")
                 .takes_value(false)
                 .long_help("Launch node as a miner"),
         )
+        .arg(
+            Arg::new("in-memory-db") //
+                .long("in-memory-db")
+                .takes_value(false)
+                .long_help(
+                    "Keep the ledger in memory instead of on disk. \n\
+                    Everything is lost once the node stops",
+                ),
+        )
+        .arg(
+            Arg::new("rpc-max-batch-size") //
+                .long("rpc-max-batch-size")
+                .takes_value(true)
+                .long_help(
+                    "Max number of requests in a JSON-RPC batch, \n\
+                    e.g. 100",
+                ),
+        )
+        .arg(
+            Arg::new("prune-keep-blocks") //
+                .long("prune-keep-blocks")
+                .takes_value(true)
+                .long_help(
+                    "Prune tx bodies of every block but the latest ones, \n\
+                    e.g. 1000. The commitment tree, nullifiers and contract \n\
+                    states are kept. Pruned txs can no longer be queried",
+                ),
+        )
         .arg(
             Arg::new("mine-interval") //
                 .long("mine-interval")
@@ -219,4 +278,90 @@ pub(super) fn create_app<'a>() -> Command<'a> {
                     in milliseconds e.g. 5000",
                 ),
         )
+        .subcommand(
+            Command::new("snapshot")
+                .about("Export or import a ledger snapshot")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("export")
+                        .about(
+                            "Export the ledger snapshot at the latest block \
+                            height. The node should not be running",
+                        )
+                        .arg(
+                            Arg::new("app-prefix") //
+                                .long("app-prefix")
+                                .takes_value(true)
+                                .required(true)
+                                .long_help(
+                                    "App prefix of the ledger to export",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("block-height") //
+                                .long("block-height")
+                                .takes_value(true)
+                                .long_help(
+                                    "Block height of the snapshot. The latest \n\
+                                    height if not given",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("file") //
+                                .long("file")
+                                .takes_value(true)
+                                .required(true)
+                                .long_help(
+                                    "Path to which the snapshot is written",
+                                ),
+                        ),
+                )
+                .subcommand(
+                    Command::new("import")
+                        .about(
+                            "Import a ledger snapshot into an empty ledger. \
+                            The node should not be running",
+                        )
+                        .arg(
+                            Arg::new("app-prefix") //
+                                .long("app-prefix")
+                                .takes_value(true)
+                                .required(true)
+                                .long_help(
+                                    "App prefix of the ledger to import into",
+                                ),
+                        )
+                        .arg(
+                            Arg::new("file") //
+                                .long("file")
+                                .takes_value(true)
+                                .required(true)
+                                .long_help("Path of the snapshot to import"),
+                        )
+                        .arg(
+                            Arg::new("trusted-block-hash") //
+                                .long("trusted-block-hash")
+                                .takes_value(true)
+                                .required(true)
+                                .long_help(
+                                    "Hash of the block at the snapshot \n\
+                                    height, obtained from a trusted source",
+                                ),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("fsck")
+                .about(
+                    "Check the integrity of the ledger by re-deriving \
+                    every block. The node should not be running",
+                )
+                .arg(
+                    Arg::new("app-prefix") //
+                        .long("app-prefix")
+                        .takes_value(true)
+                        .required(true)
+                        .long_help("App prefix of the ledger to check"),
+                ),
+        )
 }
@@ -1,4 +1,5 @@
 use super::app;
+use clap::ArgMatches;
 
 #[derive(Debug)]
 pub(crate) struct CLIArgs {
@@ -10,21 +11,48 @@ pub(crate) struct CLIArgs {
     pub(crate) p2p_task_interval: Option<u16>,
     pub(crate) p2p_task_queue_capacity: Option<u16>,
     pub(crate) p2p_peer_table_capacity: Option<i16>,
+    pub(crate) p2p_outbound_peer_capacity: Option<i16>,
     pub(crate) p2p_max_conn_count: Option<u16>,
     pub(crate) p2p_dial_interval: Option<u16>,
     pub(crate) app_prefix: Option<String>,
     pub(crate) rpc_port: Option<u16>,
+    pub(crate) rpc_max_batch_size: Option<usize>,
     pub(crate) p2p_port: Option<u16>,
     pub(crate) addr_expire_duration: Option<u64>,
     pub(crate) addr_monitor_interval: Option<u64>,
     pub(crate) cfg_profile: Option<String>,
+    pub(crate) network_id: Option<String>,
     pub(crate) miner: bool,
+    pub(crate) in_memory_db: bool,
+    pub(crate) prune_keep_blocks: Option<u128>,
     pub(crate) mine_interval: Option<u64>,
     pub(crate) node_task_min_interval: Option<u64>,
     pub(crate) peer_register_interval: Option<u64>,
     pub(crate) tx_sync_interval: Option<u64>,
     pub(crate) block_sync_interval: Option<u64>,
     pub(crate) bootstrap_urls: Option<Vec<String>>,
+    pub(crate) external_addr: Option<String>,
+    pub(crate) snapshot_cmd: Option<SnapshotCmd>,
+    pub(crate) fsck_cmd: Option<FsckCmd>,
+}
+
+#[derive(Debug)]
+pub(crate) enum SnapshotCmd {
+    Export {
+        app_prefix: String,
+        block_height: Option<u128>,
+        file: String,
+    },
+    Import {
+        app_prefix: String,
+        file: String,
+        trusted_block_hash: String,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct FsckCmd {
+    pub(crate) app_prefix: String,
 }
 
 pub(crate) fn get_args() -> Result<CLIArgs, String> {
@@ -45,6 +73,22 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
         None => None,
     };
 
+    let rpc_max_batch_size = match matches.value_of("rpc-max-batch-size") {
+        Some(n) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => Some(n),
+            Ok(_) => {
+                return Err(format!("Max batch size should be at least 1"));
+            }
+            Err(err) => {
+                return Err(format!(
+                    "Cannot parse rpc max batch size (usize), err: {}",
+                    err,
+                ));
+            }
+        },
+        None => None,
+    };
+
     let disc_port = match matches.value_of("disc-port") {
         Some(p) => match p.parse::<u16>() {
             Ok(port) => Some(port),
@@ -76,11 +120,21 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
         None => None,
     };
 
+    let network_id = match matches.value_of("network-id") {
+        Some(m) => Some(String::from(m)),
+        None => None,
+    };
+
     let bootstrap_urls = match matches.values_of("bootstrap-urls") {
         Some(b) => Some(b.map(str::to_string).collect()),
         None => None,
     };
 
+    let external_addr = match matches.value_of("external-addr") {
+        Some(a) => Some(String::from(a)),
+        None => None,
+    };
+
     let disc_dial_interval = match matches.value_of("disc-dial-interval") {
         Some(i) => match i.parse::<u16>() {
             Ok(interval) => Some(interval),
@@ -176,6 +230,21 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
             None => None,
         };
 
+    let p2p_outbound_peer_capacity =
+        match matches.value_of("p2p-outbound-peer-capacity") {
+            Some(i) => match i.parse::<i16>() {
+                Ok(c) => Some(c),
+                Err(err) => {
+                    return Err(format!(
+                        "Cannot parse p2p outbound peer capacity (i16), \
+                        err: {}",
+                        err,
+                    ))
+                }
+            },
+            None => None,
+        };
+
     let p2p_max_conn_count = match matches.value_of("p2p-max-conn-count") {
         Some(i) => match i.parse::<u16>() {
             Ok(interval) => Some(interval),
@@ -231,6 +300,24 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
 
     let miner = matches.is_present("miner");
 
+    let in_memory_db = matches.is_present("in-memory-db");
+
+    let prune_keep_blocks = match matches.value_of("prune-keep-blocks") {
+        Some(n) => match n.parse::<u128>() {
+            Ok(n) if n > 0 => Some(n),
+            Ok(_) => {
+                return Err(format!("At least one block has to be kept"));
+            }
+            Err(err) => {
+                return Err(format!(
+                    "Cannot parse prune keep blocks (u128), err: {}",
+                    err,
+                ));
+            }
+        },
+        None => None,
+    };
+
     let mine_interval = match matches.value_of("mine-interval") {
         Some(d) => match d.parse::<u64>() {
             Ok(d) => Some(d),
@@ -303,6 +390,25 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
         None => None,
     };
 
+    let snapshot_cmd = match matches.subcommand() {
+        Some(("snapshot", snapshot_matches)) => {
+            Some(get_snapshot_cmd(snapshot_matches)?)
+        }
+        _ => None,
+    };
+
+    let fsck_cmd = match matches.subcommand() {
+        Some(("fsck", fsck_matches)) => {
+            let app_prefix = match fsck_matches.value_of("app-prefix") {
+                Some(ap) => String::from(ap),
+                None => return Err(format!("app-prefix should be provided")),
+            };
+
+            Some(FsckCmd { app_prefix })
+        }
+        _ => None,
+    };
+
     Ok(CLIArgs {
         disc_port,
         disc_dial_interval,
@@ -312,20 +418,90 @@ pub(crate) fn get_args() -> Result<CLIArgs, String> {
         p2p_task_interval,
         p2p_task_queue_capacity,
         p2p_peer_table_capacity,
+        p2p_outbound_peer_capacity,
         p2p_max_conn_count,
         p2p_dial_interval,
         rpc_port,
+        rpc_max_batch_size,
         p2p_port,
         addr_expire_duration,
         addr_monitor_interval,
         cfg_profile,
+        network_id,
         bootstrap_urls,
+        external_addr,
         miner,
+        in_memory_db,
+        prune_keep_blocks,
         mine_interval,
         node_task_min_interval,
         peer_register_interval,
         tx_sync_interval,
         block_sync_interval,
         app_prefix,
+        snapshot_cmd,
+        fsck_cmd,
     })
 }
+
+fn get_snapshot_cmd(matches: &ArgMatches) -> Result<SnapshotCmd, String> {
+    match matches.subcommand() {
+        Some(("export", m)) => {
+            let app_prefix = match m.value_of("app-prefix") {
+                Some(ap) => String::from(ap),
+                None => return Err(format!("app-prefix should be provided")),
+            };
+
+            let block_height = match m.value_of("block-height") {
+                Some(h) => match h.parse::<u128>() {
+                    Ok(h) => Some(h),
+                    Err(err) => {
+                        return Err(format!(
+                            "Cannot parse block height (u128), err: {}",
+                            err,
+                        ));
+                    }
+                },
+                None => None,
+            };
+
+            let file = match m.value_of("file") {
+                Some(f) => String::from(f),
+                None => return Err(format!("file should be provided")),
+            };
+
+            Ok(SnapshotCmd::Export {
+                app_prefix,
+                block_height,
+                file,
+            })
+        }
+        Some(("import", m)) => {
+            let app_prefix = match m.value_of("app-prefix") {
+                Some(ap) => String::from(ap),
+                None => return Err(format!("app-prefix should be provided")),
+            };
+
+            let file = match m.value_of("file") {
+                Some(f) => String::from(f),
+                None => return Err(format!("file should be provided")),
+            };
+
+            let trusted_block_hash = match m.value_of("trusted-block-hash") {
+                Some(h) => String::from(h),
+                None => {
+                    return Err(format!(
+                        "trusted-block-hash should be provided"
+                    ));
+                }
+            };
+
+            Ok(SnapshotCmd::Import {
+                app_prefix,
+                file,
+                trusted_block_hash,
+            })
+        }
+        _ => Err(format!("Unknown snapshot subcommand")),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::cli::FsckCmd;
+use sak_dist_ledger::LedgerDB;
+use sak_logger::{tinfo, twarn};
+use sak_vm::VM;
+
+pub(crate) fn run(fsck_cmd: FsckCmd) -> Result<(), String> {
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(format!("runtime fail, err: {:?}", err));
+        }
+    };
+
+    runtime.block_on(async { check_integrity(fsck_cmd.app_prefix).await })
+}
+
+async fn check_integrity(app_prefix: String) -> Result<(), String> {
+    let ledger_db = match LedgerDB::init(&app_prefix).await {
+        Ok(d) => d,
+        Err(err) => {
+            return Err(format!("Cannot open the ledger, err: {}", err));
+        }
+    };
+
+    let vm = VM::init()?;
+
+    let report = match ledger_db.check_integrity(&vm).await {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(format!("Cannot check the ledger, err: {}", err));
+        }
+    };
+
+    for inconsistency in &report.inconsistencies {
+        twarn!("saksaha", "sak", "Inconsistency found, {}", inconsistency);
+    }
+
+    if !report.is_consistent() {
+        return Err(format!(
+            "Ledger is inconsistent, app_prefix: {}, inconsistency count: {}",
+            app_prefix,
+            report.inconsistencies.len(),
+        ));
+    }
+
+    tinfo!(
+        "saksaha",
+        "sak",
+        "Ledger is consistent, app_prefix: {}, latest_block_height: {:?}, \
+        block count: {}, tx count: {}",
+        app_prefix,
+        report.latest_block_height,
+        report.block_count,
+        report.tx_count,
+    );
+
+    Ok(())
+}
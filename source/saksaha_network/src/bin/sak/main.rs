@@ -1,5 +1,7 @@
 mod app;
 mod cli;
+mod fsck;
+mod snapshot;
 
 use crate::cli::CLIArgs;
 use sak_logger::{terr, tinfo, RUST_LOG_ENV};
@@ -34,6 +36,26 @@ fn main() {
         }
     };
 
+    if let Some(snapshot_cmd) = cli_args.snapshot_cmd {
+        if let Err(err) = snapshot::run(snapshot_cmd) {
+            terr!("saksaha", "sak", "Snapshot command failed, err: {}", err);
+
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    if let Some(fsck_cmd) = cli_args.fsck_cmd {
+        if let Err(err) = fsck::run(fsck_cmd) {
+            terr!("saksaha", "sak", "Fsck command failed, err: {}", err);
+
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
     let system = System {};
 
     let sys_run_args = SystemRunArgs {
@@ -45,15 +67,21 @@ fn main() {
         p2p_task_interval: cli_args.p2p_task_interval,
         p2p_task_queue_capacity: cli_args.p2p_task_queue_capacity,
         p2p_peer_table_capacity: cli_args.p2p_peer_table_capacity,
+        p2p_outbound_peer_capacity: cli_args.p2p_outbound_peer_capacity,
         p2p_max_conn_count: cli_args.p2p_max_conn_count,
         p2p_dial_interval: cli_args.p2p_dial_interval,
         p2p_port: cli_args.p2p_port,
         rpc_port: cli_args.rpc_port,
+        rpc_max_batch_size: cli_args.rpc_max_batch_size,
         addr_expire_duration: cli_args.addr_expire_duration,
         addr_monitor_interval: cli_args.addr_monitor_interval,
         bootstrap_urls: cli_args.bootstrap_urls,
+        external_addr: cli_args.external_addr,
         cfg_profile: cli_args.cfg_profile,
+        network_id: cli_args.network_id,
         miner: cli_args.miner,
+        in_memory_db: cli_args.in_memory_db,
+        prune_keep_blocks: cli_args.prune_keep_blocks,
         mine_interval: cli_args.mine_interval,
         node_task_min_interval: cli_args.node_task_min_interval,
         peer_register_interval: cli_args.peer_register_interval,
@@ -0,0 +1,123 @@
+use crate::cli::SnapshotCmd;
+use sak_dist_ledger::{LedgerDB, LedgerSnapshot};
+use sak_logger::tinfo;
+
+pub(crate) fn run(snapshot_cmd: SnapshotCmd) -> Result<(), String> {
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(r) => r,
+        Err(err) => {
+            return Err(format!("runtime fail, err: {:?}", err));
+        }
+    };
+
+    runtime.block_on(async {
+        match snapshot_cmd {
+            SnapshotCmd::Export {
+                app_prefix,
+                block_height,
+                file,
+            } => export_snapshot(app_prefix, block_height, file).await,
+            SnapshotCmd::Import {
+                app_prefix,
+                file,
+                trusted_block_hash,
+            } => import_snapshot(app_prefix, file, trusted_block_hash).await,
+        }
+    })
+}
+
+async fn export_snapshot(
+    app_prefix: String,
+    block_height: Option<u128>,
+    file: String,
+) -> Result<(), String> {
+    let ledger_db = match LedgerDB::init(&app_prefix).await {
+        Ok(d) => d,
+        Err(err) => {
+            return Err(format!("Cannot open the ledger, err: {}", err));
+        }
+    };
+
+    let snapshot = match ledger_db.export_snapshot(block_height) {
+        Ok(s) => s,
+        Err(err) => {
+            return Err(format!("Cannot export a snapshot, err: {}", err));
+        }
+    };
+
+    let bytes = match snapshot.to_bytes() {
+        Ok(b) => b,
+        Err(err) => {
+            return Err(format!("Cannot serialize a snapshot, err: {}", err));
+        }
+    };
+
+    if let Err(err) = std::fs::write(&file, bytes) {
+        return Err(format!(
+            "Cannot write a snapshot, file: {}, err: {}",
+            file, err
+        ));
+    }
+
+    tinfo!(
+        "saksaha",
+        "sak",
+        "Exported a snapshot, file: {}, block_height: {}, block_hash: {}",
+        file,
+        snapshot.block_height,
+        snapshot.block_hash,
+    );
+
+    Ok(())
+}
+
+async fn import_snapshot(
+    app_prefix: String,
+    file: String,
+    trusted_block_hash: String,
+) -> Result<(), String> {
+    let bytes = match std::fs::read(&file) {
+        Ok(b) => b,
+        Err(err) => {
+            return Err(format!(
+                "Cannot read a snapshot, file: {}, err: {}",
+                file, err
+            ));
+        }
+    };
+
+    let snapshot = match LedgerSnapshot::from_bytes(&bytes) {
+        Ok(s) => s,
+        Err(err) => {
+            return Err(format!("Cannot deserialize a snapshot, err: {}", err));
+        }
+    };
+
+    let ledger_db = match LedgerDB::init(&app_prefix).await {
+        Ok(d) => d,
+        Err(err) => {
+            return Err(format!("Cannot open the ledger, err: {}", err));
+        }
+    };
+
+    let block_height =
+        match ledger_db.import_snapshot(&snapshot, &trusted_block_hash) {
+            Ok(h) => h,
+            Err(err) => {
+                return Err(format!("Cannot import a snapshot, err: {}", err));
+            }
+        };
+
+    tinfo!(
+        "saksaha",
+        "sak",
+        "Imported a snapshot, app_prefix: {}, block_height: {}",
+        app_prefix,
+        block_height,
+    );
+
+    Ok(())
+}
@@ -15,6 +15,8 @@ impl Blockchain {
         tx_sync_interval: Option<u64>,
         genesis_block: Option<GenesisBlock>,
         block_sync_interval: Option<u64>,
+        in_memory_db: bool,
+        prune_keep_blocks: Option<u128>,
         identity: Arc<Identity>,
     ) -> Result<Blockchain, SaksahaError> {
         let (gen_block_candidate, consensus) = {
@@ -43,6 +45,8 @@ impl Blockchain {
             genesis_block: Some(gen_block_candidate),
             consensus,
             block_sync_interval,
+            in_memory_db,
+            prune_keep_blocks,
         };
 
         let dist_ledger = {
@@ -56,6 +60,15 @@ impl Blockchain {
         Ok(blockchain)
     }
 
+    pub(crate) async fn get_genesis_block_hash(
+        &self,
+    ) -> Result<String, SaksahaError> {
+        match self.dist_ledger.apis.get_block_by_height(&0).await? {
+            Some(b) => Ok(b.get_block_hash().to_string()),
+            None => Err(format!("Genesis block is not persisted").into()),
+        }
+    }
+
     pub async fn run(&self) {
         self.dist_ledger.run().await;
     }
@@ -2,21 +2,20 @@ use async_trait::async_trait;
 use sak_contract_std::{CtrCallType, CtrRequest};
 use sak_dist_ledger::{Consensus, ConsensusError, DistLedgerApis};
 use sak_p2p_id::Identity;
-use sak_types::{BlockCandidate, TxCandidate};
+use sak_types::{BlockCandidate, BlockHeight, MintTxCandidate, TxCandidate};
 use std::{collections::HashMap, sync::Arc};
+use type_extension::U8Array;
 
 pub struct Pos {
     pub validator_ctr_addr: String,
     pub identity: Arc<Identity>,
 }
 
-#[async_trait]
-impl Consensus for Pos {
-    async fn do_consensus(
+impl Pos {
+    async fn get_validator(
         &self,
         dist_ledger_apis: &DistLedgerApis,
-        tx_candidates: Vec<TxCandidate>,
-    ) -> Result<BlockCandidate, ConsensusError> {
+    ) -> Result<String, ConsensusError> {
         let request = CtrRequest {
             req_type: "get_validator".to_string(),
             args: vec![],
@@ -24,7 +23,7 @@ impl Consensus for Pos {
         };
 
         let validator = match dist_ledger_apis
-            .query_ctr(&self.validator_ctr_addr, request)
+            .query_ctr(&self.validator_ctr_addr, request, None)
             .await
         {
             Ok(v) => v,
@@ -39,6 +38,19 @@ impl Consensus for Pos {
 
         let validator_str: String = serde_json::from_slice(&validator)?;
 
+        Ok(validator_str)
+    }
+}
+
+#[async_trait]
+impl Consensus for Pos {
+    async fn do_consensus(
+        &self,
+        dist_ledger_apis: &DistLedgerApis,
+        tx_candidates: Vec<TxCandidate>,
+    ) -> Result<BlockCandidate, ConsensusError> {
+        let validator_str = self.get_validator(dist_ledger_apis).await?;
+
         if self.identity.credential.public_key_str == validator_str {
             let bc = BlockCandidate {
                 validator_sig: String::from("1"),
@@ -52,4 +64,42 @@ impl Consensus for Pos {
 
         return Err("Not a valid validator".into());
     }
+
+    // Genesis allocations and mints of no value, e.g. the ones that only
+    // deploy or call a contract, are always allowed. Any other issuance has
+    // to be authored by the validator, which is the mint authority
+    async fn authorize_mint(
+        &self,
+        dist_ledger_apis: &DistLedgerApis,
+        tc: &MintTxCandidate,
+        block_height: BlockHeight,
+    ) -> Result<(), ConsensusError> {
+        if block_height == 0 {
+            return Ok(());
+        }
+
+        if tc.v == U8Array::new_empty_32() {
+            return Ok(());
+        }
+
+        // A signed cm would otherwise be minted again with another tx. The
+        // ledger sees to the ones minted earlier in the same block
+        if dist_ledger_apis.get_cm_idx_by_cm(&tc.cm_1).await?.is_some() {
+            return Err("cm has already been minted".into());
+        }
+
+        let author_sig = tc.verify_author_sig()?;
+
+        let validator_str = self.get_validator(dist_ledger_apis).await?;
+
+        if author_sig.public_key_str != validator_str {
+            return Err(format!(
+                "Mint tx is not authored by the mint authority, author: {}",
+                author_sig.public_key_str,
+            )
+            .into());
+        }
+
+        Ok(())
+    }
 }
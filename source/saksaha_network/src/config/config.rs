@@ -1,8 +1,11 @@
 use super::profiled::ProfiledConfig;
 use crate::{pconfig::PConfig, system::SystemRunArgs};
+use hyper_rpc_router::{MethodFilter, RPCAuth, RateLimit};
 use log::{info, warn};
 use sak_p2p_addr::UnknownAddr;
 
+const DEFAULT_NETWORK_ID: &str = "saksaha_dev";
+
 #[derive(Debug)]
 pub(crate) struct Config {
     pub(crate) app_prefix: String,
@@ -25,21 +28,30 @@ pub(crate) struct P2PConfig {
     pub(crate) p2p_dial_interval: Option<u16>,
     pub(crate) p2p_max_conn_count: Option<u16>,
     pub(crate) p2p_peer_table_capacity: Option<i16>,
+    pub(crate) p2p_outbound_peer_capacity: Option<i16>,
     pub(crate) p2p_port: Option<u16>,
     pub(crate) addr_expire_duration: Option<u64>,
     pub(crate) addr_monitor_interval: Option<u64>,
     pub(crate) bootstrap_addrs: Vec<UnknownAddr>,
+    pub(crate) external_addr: Option<String>,
     pub(crate) secret: String,
     pub(crate) public_key_str: String,
+    pub(crate) network_id: String,
 }
 
 #[derive(Debug)]
 pub(crate) struct RPCConfig {
     pub(crate) rpc_port: Option<u16>,
+    pub(crate) rpc_max_batch_size: Option<usize>,
+    pub(crate) rpc_auth: Option<RPCAuth>,
+    pub(crate) method_filter: MethodFilter,
+    pub(crate) rate_limit: Option<RateLimit>,
 }
 
 #[derive(Debug)]
-pub(crate) struct DBConfig {}
+pub(crate) struct DBConfig {
+    pub(crate) in_memory_db: bool,
+}
 
 #[derive(Debug)]
 pub(crate) struct NodeConfig {
@@ -53,6 +65,7 @@ pub(crate) struct NodeConfig {
 pub(crate) struct BlockchainConfig {
     pub(crate) tx_sync_interval: Option<u64>,
     pub(crate) block_sync_interval: Option<u64>,
+    pub(crate) prune_keep_blocks: Option<u128>,
 }
 
 impl Config {
@@ -117,11 +130,59 @@ impl Config {
 
         let rpc_port = profiled_config.rpc.rpc_port.or(sys_run_args.rpc_port);
 
+        let network_id = match &sys_run_args.network_id {
+            Some(n) => n.clone(),
+            None => DEFAULT_NETWORK_ID.to_string(),
+        };
+
+        let rpc_auth = match (&pconfig.rpc.auth_token, &pconfig.rpc.hmac_secret)
+        {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "Only one of rpc auth_token and hmac_secret can be set"
+                ));
+            }
+            (Some(token), None) => Some(RPCAuth::Bearer {
+                token: token.clone(),
+            }),
+            (None, Some(secret)) => {
+                // decode_hex takes two chars at a time
+                let decoded = if secret.len() % 2 == 0 && secret.is_ascii() {
+                    sak_crypto::decode_hex(secret).ok()
+                } else {
+                    None
+                };
+
+                let secret = match decoded {
+                    Some(s) if !s.is_empty() => s,
+                    _ => {
+                        return Err(format!(
+                            "rpc hmac_secret should be a non-empty hex string"
+                        ));
+                    }
+                };
+
+                Some(RPCAuth::Hmac { secret })
+            }
+            (None, None) => None,
+        };
+
+        let method_filter = MethodFilter::new(
+            pconfig.rpc.allowed_methods.clone(),
+            pconfig.rpc.denied_methods.clone(),
+        );
+
+        let rate_limit = pconfig.rpc.rate_limit_per_sec.map(|r| RateLimit {
+            requests_per_sec: r,
+            burst: pconfig.rpc.rate_limit_burst.unwrap_or(r),
+        });
+
         let conf = Config {
             app_prefix: app_prefix.clone(),
             blockchain: BlockchainConfig {
                 tx_sync_interval: sys_run_args.tx_sync_interval,
                 block_sync_interval: sys_run_args.block_sync_interval,
+                prune_keep_blocks: sys_run_args.prune_keep_blocks,
             },
             node: NodeConfig {
                 miner,
@@ -129,8 +190,16 @@ impl Config {
                 node_task_min_interval: sys_run_args.node_task_min_interval,
                 peer_register_interval: sys_run_args.peer_register_interval,
             },
-            db: DBConfig {},
-            rpc: RPCConfig { rpc_port },
+            db: DBConfig {
+                in_memory_db: sys_run_args.in_memory_db,
+            },
+            rpc: RPCConfig {
+                rpc_port,
+                rpc_max_batch_size: sys_run_args.rpc_max_batch_size,
+                rpc_auth,
+                method_filter,
+                rate_limit,
+            },
             p2p: P2PConfig {
                 disc_port,
                 disc_dial_interval: sys_run_args.disc_dial_interval,
@@ -140,13 +209,17 @@ impl Config {
                 p2p_task_interval: sys_run_args.p2p_task_interval,
                 p2p_task_queue_capacity: sys_run_args.p2p_task_queue_capacity,
                 p2p_peer_table_capacity: sys_run_args.p2p_peer_table_capacity,
+                p2p_outbound_peer_capacity: sys_run_args
+                    .p2p_outbound_peer_capacity,
                 p2p_dial_interval: sys_run_args.p2p_dial_interval,
                 p2p_port: sys_run_args.p2p_port,
                 p2p_max_conn_count: sys_run_args.p2p_max_conn_count,
                 addr_expire_duration: sys_run_args.addr_expire_duration,
                 addr_monitor_interval: sys_run_args.addr_monitor_interval,
+                external_addr: sys_run_args.external_addr.clone(),
                 secret,
                 public_key_str,
+                network_id,
                 bootstrap_addrs,
             },
         };
@@ -1,5 +1,6 @@
 use super::{ProfiledConfig, ProfiledP2PConfig};
 use crate::config::{NodeConfig, RPCConfig};
+use hyper_rpc_router::MethodFilter;
 use sak_p2p_addr::{AddrStatus, UnknownAddr};
 
 pub(super) fn get_config() -> ProfiledConfig {
@@ -103,6 +104,10 @@ pub(super) fn get_config() -> ProfiledConfig {
         },
         rpc: RPCConfig {
             rpc_port: Some(34418),
+            rpc_max_batch_size: None,
+            rpc_auth: None,
+            method_filter: MethodFilter::default(),
+            rate_limit: None,
         },
     };
 }
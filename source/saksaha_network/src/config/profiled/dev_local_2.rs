@@ -1,5 +1,6 @@
 use super::{ProfiledConfig, ProfiledP2PConfig};
 use crate::config::{NodeConfig, RPCConfig};
+use hyper_rpc_router::MethodFilter;
 use sak_p2p_addr::{AddrStatus, UnknownAddr};
 
 pub(super) fn get_config() -> ProfiledConfig {
@@ -55,6 +56,12 @@ pub(super) fn get_config() -> ProfiledConfig {
             node_task_min_interval: None,
             peer_register_interval: None,
         },
-        rpc: RPCConfig { rpc_port: None },
+        rpc: RPCConfig {
+            rpc_port: None,
+            rpc_max_batch_size: None,
+            rpc_auth: None,
+            method_filter: MethodFilter::default(),
+            rate_limit: None,
+        },
     };
 }
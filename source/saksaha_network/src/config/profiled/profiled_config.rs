@@ -2,6 +2,7 @@ use super::dev_local_1;
 use super::dev_local_2;
 use crate::config::NodeConfig;
 use crate::config::RPCConfig;
+use hyper_rpc_router::MethodFilter;
 use sak_p2p_addr::UnknownAddr;
 
 pub(crate) struct ProfiledConfig {
@@ -48,7 +49,13 @@ impl ProfiledConfig {
                 node_task_min_interval: None,
                 peer_register_interval: None,
             },
-            rpc: RPCConfig { rpc_port: None },
+            rpc: RPCConfig {
+                rpc_port: None,
+                rpc_max_batch_size: None,
+                rpc_auth: None,
+                method_filter: MethodFilter::default(),
+                rate_limit: None,
+            },
         }
     }
 }
@@ -81,6 +81,7 @@ impl LocalNode {
 
                 let peer_node = PeerNode {
                     peer: peer.clone(),
+                    peer_table: self.peer_table.clone(),
                     machine,
                     node_task_min_interval: self.node_task_interval.clone(),
                 };
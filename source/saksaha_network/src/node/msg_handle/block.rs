@@ -1,14 +1,17 @@
+use super::BLOCK_REQUEST_TIMEOUT;
 use crate::{machine::Machine, node::SaksahaNodeError};
 use log::{debug, info, warn};
+use sak_dist_ledger::{InvalidBlockError, LedgerError};
+use sak_p2p_peertable::{Peer, PeerBehavior};
 use sak_p2p_transport::{
-    BlockAckMsg, BlockSynMsg, Msg, RecvReceipt, SendReceipt, UpgradedConn,
+    BlockAckMsg, BlockSynMsg, Msg, RecvReceipt, RequestId, SendReceipt,
+    UpgradedConn,
 };
 use sak_types::{BlockHash, BlockHeight};
-use std::sync::Arc;
-use tokio::sync::RwLockWriteGuard;
+use std::{sync::Arc, time::Duration};
 
 pub(in crate::node) async fn send_block_syn(
-    mut conn_lock: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
     new_blocks: Vec<(BlockHeight, BlockHash)>,
     machine: &Arc<Machine>,
 ) -> Result<RecvReceipt, SaksahaNodeError> {
@@ -37,16 +40,15 @@ pub(in crate::node) async fn send_block_syn(
         blocks_to_send.push((block, txs));
     }
 
-    conn_lock
-        .send(Msg::BlockSyn(BlockSynMsg {
-            blocks: blocks_to_send,
-        }))
+    let (msg, receipt) = conn
+        .request(
+            Msg::BlockSyn(BlockSynMsg {
+                blocks: blocks_to_send,
+            }),
+            Duration::from_millis(BLOCK_REQUEST_TIMEOUT),
+        )
         .await?;
 
-    let (msg, receipt) = conn_lock.next_msg().await;
-
-    let msg = msg.ok_or(format!("block syn needs to be followed by ack"))??;
-
     let _block_ack_msg = match msg {
         Msg::BlockAck(m) => m,
         _ => {
@@ -60,9 +62,11 @@ pub(in crate::node) async fn send_block_syn(
 }
 
 pub(in crate::node) async fn recv_block_syn(
+    req_id: RequestId,
     block_syn_msg: BlockSynMsg,
     machine: &Arc<Machine>,
-    mut conn: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
+    peer: &Arc<Peer>,
 ) -> Result<SendReceipt, SaksahaNodeError> {
     let blocks = block_syn_msg.blocks;
 
@@ -78,17 +82,54 @@ pub(in crate::node) async fn recv_block_syn(
             return Err("received not continuous block height".into());
         }
 
-        machine
+        let tx_hashes = block.tx_hashes.clone();
+        let block_hash = block.get_block_hash().clone();
+
+        let res = machine
             .blockchain
             .dist_ledger
             .apis
             .sync_block(block, txs)
-            .await?;
+            .await;
+
+        match res {
+            Ok(_) => {
+                peer.report_behavior(PeerBehavior::ValidBlock).await;
+
+                peer.mark_known_txs(&tx_hashes).await;
+                peer.mark_known_blocks(&[block_hash]).await;
+            }
+            Err(err) => {
+                if is_invalid_block_of_hers(machine, &err, &block_hash) {
+                    peer.report_behavior(PeerBehavior::InvalidBlock).await;
+                }
+
+                return Err(err.into());
+            }
+        };
     }
 
     let block_ack_msg = Msg::BlockAck(BlockAckMsg {});
 
-    let receipt = conn.send(block_ack_msg).await?;
+    let receipt = conn.respond(req_id, block_ack_msg).await?;
 
     Ok(receipt)
 }
+
+// Only a block that fails a check she could have run herself counts against
+// her. The same block may have been persisted by another handler in the
+// meantime, and then its mints fail as already minted.
+pub(super) fn is_invalid_block_of_hers(
+    machine: &Arc<Machine>,
+    err: &LedgerError,
+    block_hash: &BlockHash,
+) -> bool {
+    if !InvalidBlockError::is_invalid_block(err) {
+        return false;
+    }
+
+    match machine.blockchain.dist_ledger.apis.get_block(block_hash) {
+        Ok(b) => b.is_none(),
+        Err(_) => false,
+    }
+}
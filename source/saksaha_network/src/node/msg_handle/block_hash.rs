@@ -1,31 +1,34 @@
+use super::REQUEST_TIMEOUT;
 use crate::{
     machine::Machine,
     node::{task::NodeTask, SaksahaNodeError},
 };
 use log::{debug, info, warn};
+use sak_p2p_peertable::Peer;
 use sak_p2p_transport::{
-    BlockHashSyncMsg, Msg, RecvReceipt, SendReceipt, UpgradedConn,
+    BlockHashSyncMsg, Msg, RecvReceipt, RequestId, SendReceipt, UpgradedConn,
 };
 use sak_task_queue::TaskQueue;
 use sak_types::{BlockHash, BlockHeight};
-use std::sync::Arc;
-use tokio::sync::RwLockWriteGuard;
+use std::{sync::Arc, time::Duration};
 
 pub(in crate::node) async fn send_block_hash_syn(
-    mut conn_lock: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
     new_blocks: Vec<(BlockHeight, BlockHash)>,
     task_queue: &Arc<TaskQueue<NodeTask>>,
+    peer: &Arc<Peer>,
 ) -> Result<RecvReceipt, SaksahaNodeError> {
-    conn_lock
-        .send(Msg::BlockHashSyn(BlockHashSyncMsg {
-            new_blocks: new_blocks.clone(),
-        }))
-        .await?;
+    let block_hashes: Vec<BlockHash> =
+        new_blocks.iter().map(|(_, h)| h.clone()).collect();
 
-    let (msg, receipt) = conn_lock.next_msg().await;
+    peer.mark_known_blocks(&block_hashes).await;
 
-    let msg =
-        msg.ok_or(format!("block hash syn needs to be followed by ack"))??;
+    let (msg, receipt) = conn
+        .request(
+            Msg::BlockHashSyn(BlockHashSyncMsg { new_blocks }),
+            Duration::from_millis(REQUEST_TIMEOUT),
+        )
+        .await?;
 
     let block_hash_ack_msg = match msg {
         Msg::BlockHashAck(m) => m,
@@ -47,13 +50,20 @@ pub(in crate::node) async fn send_block_hash_syn(
 }
 
 pub(in crate::node) async fn recv_block_hash_syn(
+    req_id: RequestId,
     block_hash_syn_msg: BlockHashSyncMsg,
     machine: &Arc<Machine>,
-    mut conn: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
+    peer: &Arc<Peer>,
 ) -> Result<SendReceipt, SaksahaNodeError> {
     let new_blocks = block_hash_syn_msg.new_blocks;
 
-    let (_, latest_block_hash) = machine
+    let block_hashes: Vec<BlockHash> =
+        new_blocks.iter().map(|(_, h)| h.clone()).collect();
+
+    peer.mark_known_blocks(&block_hashes).await;
+
+    let (latest_block_height, latest_block_hash) = machine
         .blockchain
         .dist_ledger
         .apis
@@ -67,17 +77,22 @@ pub(in crate::node) async fn recv_block_hash_syn(
         latest_block_hash, new_blocks,
     );
 
+    // Blocks at heights this node already has are not asked for, whatever
+    // she claims them to be
     let mut blocks_to_req = vec![];
     for (height, block_hash) in new_blocks {
-        if block_hash != latest_block_hash {
+        if height > latest_block_height && block_hash != latest_block_hash {
             blocks_to_req.push((height, block_hash));
         }
     }
 
     let receipt = conn
-        .send(Msg::BlockHashAck(BlockHashSyncMsg {
-            new_blocks: blocks_to_req,
-        }))
+        .respond(
+            req_id,
+            Msg::BlockHashAck(BlockHashSyncMsg {
+                new_blocks: blocks_to_req,
+            }),
+        )
         .await?;
 
     Ok(receipt)
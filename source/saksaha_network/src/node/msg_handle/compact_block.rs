@@ -0,0 +1,320 @@
+use super::{
+    block::is_invalid_block_of_hers, BLOCK_REQUEST_TIMEOUT, REQUEST_TIMEOUT,
+};
+use crate::{machine::Machine, node::SaksahaNodeError};
+use log::{debug, warn};
+use sak_p2p_peertable::{Peer, PeerBehavior};
+use sak_p2p_transport::{
+    resolve_short_tx_ids, BlockAckMsg, BlockTxsMsg, BlockTxsReqMsg,
+    CompactBlock, CompactBlockSynMsg, Msg, RecvReceipt, RequestId, SendReceipt,
+    UpgradedConn,
+};
+use sak_types::{BlockHash, BlockHeight, TxCandidate};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::OwnedSemaphorePermit;
+
+pub(in crate::node) async fn send_compact_block_syn(
+    conn: &UpgradedConn,
+    new_blocks: Vec<(BlockHeight, BlockHash)>,
+    machine: &Arc<Machine>,
+    peer: &Arc<Peer>,
+) -> Result<RecvReceipt, SaksahaNodeError> {
+    let block_hashes: Vec<&BlockHash> = new_blocks
+        .iter()
+        .map(|(_, block_hash)| block_hash)
+        .collect();
+
+    let blocks = machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .get_blocks(block_hashes)
+        .await?;
+
+    for block in &blocks {
+        peer.mark_known_txs(&block.tx_hashes).await;
+        peer.mark_known_blocks(&[block.get_block_hash().clone()])
+            .await;
+    }
+
+    let compact_blocks = blocks.iter().map(CompactBlock::from_block).collect();
+
+    // She may ask for the txs she lacks before she acks
+    let (msg, receipt) = conn
+        .request(
+            Msg::CompactBlockSyn(CompactBlockSynMsg {
+                blocks: compact_blocks,
+            }),
+            Duration::from_millis(BLOCK_REQUEST_TIMEOUT),
+        )
+        .await?;
+
+    match msg {
+        Msg::BlockAck(_) => {}
+        _ => {
+            return Err(
+                format!("Only block ack should arrive at this point").into()
+            );
+        }
+    };
+
+    Ok(receipt)
+}
+
+pub(in crate::node) async fn recv_block_txs_req(
+    req_id: RequestId,
+    block_txs_req_msg: BlockTxsReqMsg,
+    machine: &Arc<Machine>,
+    conn: &UpgradedConn,
+) -> Result<SendReceipt, SaksahaNodeError> {
+    let apis = &machine.blockchain.dist_ledger.apis;
+
+    let mut block_txs = Vec::with_capacity(block_txs_req_msg.reqs.len());
+
+    for (block_hash, tx_indices) in block_txs_req_msg.reqs {
+        let block =
+            apis.get_blocks(vec![&block_hash])
+                .await?
+                .pop()
+                .ok_or(format!(
+                    "Txs requested of an unknown block, block_hash: {}",
+                    block_hash
+                ))?;
+
+        let mut tx_hashes = Vec::with_capacity(tx_indices.len());
+
+        for idx in tx_indices {
+            let tx_hash = block.tx_hashes.get(idx as usize).ok_or(format!(
+                "Tx index out of range, block_hash: {}, idx: {}",
+                block_hash, idx
+            ))?;
+
+            tx_hashes.push(tx_hash.clone());
+        }
+
+        let txs = apis.get_txs(&tx_hashes).await?;
+
+        block_txs.push((block_hash, txs));
+    }
+
+    let receipt = conn
+        .respond(req_id, Msg::BlockTxs(BlockTxsMsg { block_txs }))
+        .await?;
+
+    Ok(receipt)
+}
+
+pub(in crate::node) async fn recv_compact_block_syn(
+    req_id: RequestId,
+    compact_block_syn_msg: CompactBlockSynMsg,
+    permit: OwnedSemaphorePermit,
+    machine: &Arc<Machine>,
+    conn: &UpgradedConn,
+    peer: &Arc<Peer>,
+) -> Result<SendReceipt, SaksahaNodeError> {
+    let apis = &machine.blockchain.dist_ledger.apis;
+
+    let pool_tx_hashes = apis.get_tx_pool_hashes().await;
+
+    let mut resolved_blocks =
+        Vec::with_capacity(compact_block_syn_msg.blocks.len());
+    let mut reqs = vec![];
+
+    for compact_block in compact_block_syn_msg.blocks {
+        let resolved =
+            resolve_short_tx_ids(&compact_block.short_tx_ids, &pool_tx_hashes);
+
+        let missing: Vec<u128> = resolved
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.is_none())
+            .map(|(idx, _)| idx as u128)
+            .collect();
+
+        if !missing.is_empty() {
+            reqs.push((compact_block.block_hash.clone(), missing));
+        }
+
+        resolved_blocks.push((compact_block, resolved));
+    }
+
+    let mut permit = Some(permit);
+
+    let mut fetched_txs = if !reqs.is_empty() {
+        debug!(
+            "Compact blocks miss txs, requesting them, block count: {}",
+            reqs.len()
+        );
+
+        // She may be waiting on us to read her msgs to get to this request
+        drop(permit.take());
+
+        request_block_txs(reqs, conn, peer).await?
+    } else {
+        HashMap::new()
+    };
+
+    let mut latest_block_height = apis.get_latest_block_height()?.unwrap_or(0);
+
+    for (compact_block, resolved) in resolved_blocks {
+        let block_hash = compact_block.block_hash.clone();
+
+        if compact_block.block_height != (latest_block_height + 1) {
+            return Err("received not continuous block height".into());
+        }
+
+        let tx_candidates = {
+            let pool_txs = apis
+                .get_txs_from_pool(
+                    resolved.iter().filter_map(|r| r.clone()).collect(),
+                )
+                .await;
+
+            let mut pool_txs: HashMap<String, TxCandidate> = pool_txs
+                .into_iter()
+                .map(|tc| (tc.get_tx_hash().to_string(), tc))
+                .collect();
+
+            let mut fetched = fetched_txs
+                .remove(&block_hash)
+                .unwrap_or(vec![])
+                .into_iter();
+
+            let mut tcs = Vec::with_capacity(resolved.len());
+
+            for r in resolved {
+                let tc = match r {
+                    Some(tx_hash) => pool_txs.remove(&tx_hash),
+                    None => fetched.next(),
+                };
+
+                match tc {
+                    Some(tc) => tcs.push(tc),
+                    None => {
+                        return Err(format!(
+                            "Could not put together the compact block, \
+                            block_hash: {}",
+                            block_hash
+                        )
+                        .into());
+                    }
+                };
+            }
+
+            tcs
+        };
+
+        let tx_count = tx_candidates.len();
+
+        let (block, tx_candidates) = {
+            let block = compact_block.clone().into_block(
+                tx_candidates
+                    .iter()
+                    .map(|tc| tc.get_tx_hash().to_string())
+                    .collect(),
+            );
+
+            if block.get_block_hash() == &block_hash {
+                (block, tx_candidates)
+            } else {
+                // A short id may have stood for a different tx of the same
+                // prefix, so all of them are asked for in full
+                warn!(
+                    "Compact block has been put together wrong, requesting \
+                    all of its txs, block_hash: {}",
+                    block_hash
+                );
+
+                drop(permit.take());
+
+                let reqs =
+                    vec![(block_hash.clone(), (0..tx_count as u128).collect())];
+
+                let tx_candidates = request_block_txs(reqs, conn, peer)
+                    .await?
+                    .remove(&block_hash)
+                    .unwrap_or(vec![]);
+
+                let block = compact_block.into_block(
+                    tx_candidates
+                        .iter()
+                        .map(|tc| tc.get_tx_hash().to_string())
+                        .collect(),
+                );
+
+                if tx_candidates.len() != tx_count
+                    || block.get_block_hash() != &block_hash
+                {
+                    return Err(format!(
+                        "Compact block hash mismatch, block_hash: {}",
+                        block_hash
+                    )
+                    .into());
+                }
+
+                (block, tx_candidates)
+            }
+        };
+
+        let tx_hashes = block.tx_hashes.clone();
+
+        match apis.sync_block_candidates(block, tx_candidates).await {
+            Ok(_) => {
+                peer.report_behavior(PeerBehavior::ValidBlock).await;
+            }
+            Err(err) => {
+                if is_invalid_block_of_hers(machine, &err, &block_hash) {
+                    peer.report_behavior(PeerBehavior::InvalidBlock).await;
+                }
+
+                return Err(err.into());
+            }
+        };
+
+        peer.mark_known_txs(&tx_hashes).await;
+        peer.mark_known_blocks(&[block_hash]).await;
+
+        latest_block_height += 1;
+    }
+
+    let receipt = conn.respond(req_id, Msg::BlockAck(BlockAckMsg {})).await?;
+
+    Ok(receipt)
+}
+
+async fn request_block_txs(
+    reqs: Vec<(BlockHash, Vec<u128>)>,
+    conn: &UpgradedConn,
+    peer: &Arc<Peer>,
+) -> Result<HashMap<BlockHash, Vec<TxCandidate>>, SaksahaNodeError> {
+    let (msg, _) = conn
+        .request(
+            Msg::BlockTxsReq(BlockTxsReqMsg { reqs }),
+            Duration::from_millis(REQUEST_TIMEOUT),
+        )
+        .await?;
+
+    let block_txs_msg = match msg {
+        Msg::BlockTxs(m) => m,
+        _ => {
+            peer.report_behavior(PeerBehavior::UnexpectedMsg).await;
+
+            return Err(
+                format!("Only block txs should arrive at this point").into()
+            );
+        }
+    };
+
+    let fetched_txs = block_txs_msg
+        .block_txs
+        .into_iter()
+        .map(|(block_hash, txs)| {
+            (
+                block_hash,
+                txs.into_iter().map(|tx| tx.downgrade()).collect(),
+            )
+        })
+        .collect();
+
+    Ok(fetched_txs)
+}
@@ -1,5 +1,7 @@
 mod block;
 mod block_hash;
+mod compact_block;
+mod ping;
 mod tx;
 mod tx_hash;
 
@@ -7,29 +9,39 @@ use super::task::NodeTask;
 use crate::{machine::Machine, SaksahaError};
 pub(in crate::node) use block::*;
 pub(in crate::node) use block_hash::*;
-use futures::{stream::SplitSink, SinkExt};
+pub(in crate::node) use compact_block::*;
 use log::{debug, info, warn};
-use sak_p2p_peertable::Peer;
-use sak_p2p_transport::{
-    BlockHashSyncMsg, BlockSynMsg, Msg, SendReceipt, TxHashSyncMsg, TxSynMsg,
-    UpgradedConn, UpgradedP2PCodec,
-};
+use sak_p2p_peertable::{Peer, PeerBehavior};
+use sak_p2p_transport::{Envelope, Msg, SendReceipt, UpgradedConn};
 use sak_task_queue::TaskQueue;
 use std::sync::Arc;
-use tokio::{net::TcpStream, sync::RwLockWriteGuard};
+use tokio::sync::OwnedSemaphorePermit;
 pub(in crate::node) use tx::*;
 pub(in crate::node) use tx_hash::*;
 
-pub(in crate::node) async fn handle_msg<'a>(
-    msg: Msg,
+// How long she has to respond to a request, in milliseconds
+const REQUEST_TIMEOUT: u64 = 10_000;
+
+// Blocks take longer, as she writes them, and may first have to ask for the
+// txs of a compact block
+const BLOCK_REQUEST_TIMEOUT: u64 = 30_000;
+
+// The permit is held until the msg is handled, unless the handler has to
+// wait on her
+pub(in crate::node) async fn handle_msg(
+    envelope: Envelope,
+    permit: OwnedSemaphorePermit,
     machine: &Arc<Machine>,
-    conn: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
     task_queue: &Arc<TaskQueue<NodeTask>>,
     peer: &Arc<Peer>,
 ) -> Result<(), SaksahaError> {
+    let Envelope { req_id, msg } = envelope;
+
     let _: SendReceipt = match msg {
         Msg::TxHashSyn(tx_hash_syn) => {
             tx_hash::recv_tx_hash_syn(
+                req_id,
                 tx_hash_syn,
                 machine,
                 conn,
@@ -38,15 +50,47 @@ pub(in crate::node) async fn handle_msg<'a>(
             )
             .await?
         }
-        Msg::TxSyn(tx_syn) => tx::recv_tx_syn(tx_syn, machine, conn).await?,
+        Msg::TxSyn(tx_syn) => {
+            tx::recv_tx_syn(req_id, tx_syn, machine, conn, peer).await?
+        }
         Msg::BlockHashSyn(block_hash_syn) => {
-            block_hash::recv_block_hash_syn(block_hash_syn, machine, conn)
-                .await?
+            block_hash::recv_block_hash_syn(
+                req_id,
+                block_hash_syn,
+                machine,
+                conn,
+                peer,
+            )
+            .await?
         }
         Msg::BlockSyn(block_syn_msg) => {
-            block::recv_block_syn(block_syn_msg, machine, conn).await?
+            block::recv_block_syn(req_id, block_syn_msg, machine, conn, peer)
+                .await?
         }
+        Msg::CompactBlockSyn(compact_block_syn_msg) => {
+            compact_block::recv_compact_block_syn(
+                req_id,
+                compact_block_syn_msg,
+                permit,
+                machine,
+                conn,
+                peer,
+            )
+            .await?
+        }
+        Msg::BlockTxsReq(block_txs_req_msg) => {
+            compact_block::recv_block_txs_req(
+                req_id,
+                block_txs_req_msg,
+                machine,
+                conn,
+            )
+            .await?
+        }
+        Msg::Ping(ping_msg) => ping::recv_ping(req_id, ping_msg, conn).await?,
         _ => {
+            peer.report_behavior(PeerBehavior::UnexpectedMsg).await;
+
             return Err(format!(
                 "Msg not valid at this stage, discarding, msg: {:?}",
                 msg
@@ -0,0 +1,16 @@
+use crate::node::SaksahaNodeError;
+use sak_p2p_transport::{Msg, PingMsg, RequestId, SendReceipt, UpgradedConn};
+
+pub(in crate::node) async fn recv_ping(
+    req_id: RequestId,
+    ping_msg: PingMsg,
+    conn: &UpgradedConn,
+) -> Result<SendReceipt, SaksahaNodeError> {
+    let pong_msg = Msg::Pong(PingMsg {
+        nonce: ping_msg.nonce,
+    });
+
+    let receipt = conn.respond(req_id, pong_msg).await?;
+
+    Ok(receipt)
+}
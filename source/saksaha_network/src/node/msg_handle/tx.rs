@@ -1,14 +1,15 @@
+use super::REQUEST_TIMEOUT;
 use crate::{machine::Machine, node::SaksahaNodeError};
 use log::{debug, info, warn};
+use sak_p2p_peertable::{Peer, PeerBehavior};
 use sak_p2p_transport::{
-    Msg, RecvReceipt, SendReceipt, TxAckMsg, TxSynMsg, UpgradedConn,
+    Msg, RecvReceipt, RequestId, SendReceipt, TxAckMsg, TxSynMsg, UpgradedConn,
 };
 use sak_types::TxHash;
-use std::sync::Arc;
-use tokio::sync::RwLockWriteGuard;
+use std::{sync::Arc, time::Duration};
 
-pub(in crate::node) async fn send_tx_syn<'a>(
-    mut conn_lock: RwLockWriteGuard<'a, UpgradedConn>,
+pub(in crate::node) async fn send_tx_syn(
+    conn: &UpgradedConn,
     tx_hashes: Vec<TxHash>,
     machine: &Arc<Machine>,
 ) -> Result<RecvReceipt, SaksahaNodeError> {
@@ -21,12 +22,9 @@ pub(in crate::node) async fn send_tx_syn<'a>(
 
     let tx_syn_msg = Msg::TxSyn(TxSynMsg { tx_candidates });
 
-    conn_lock.send(tx_syn_msg).await?;
-
-    let (msg, receipt) = conn_lock.next_msg().await;
-
-    let msg =
-        msg.ok_or(format!("tx syn needs to be followed by tx syn ack"))??;
+    let (msg, receipt) = conn
+        .request(tx_syn_msg, Duration::from_millis(REQUEST_TIMEOUT))
+        .await?;
 
     let _tx_ack = match msg {
         Msg::TxAck(m) => m,
@@ -41,20 +39,40 @@ pub(in crate::node) async fn send_tx_syn<'a>(
 }
 
 pub(in crate::node) async fn recv_tx_syn(
+    req_id: RequestId,
     tx_syn: TxSynMsg,
     machine: &Machine,
-    mut conn: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
+    peer: &Arc<Peer>,
 ) -> Result<SendReceipt, SaksahaNodeError> {
-    machine
+    let tx_count = tx_syn.tx_candidates.len();
+
+    let tx_hashes: Vec<TxHash> = tx_syn
+        .tx_candidates
+        .iter()
+        .map(|tc| tc.get_tx_hash().to_string())
+        .collect();
+
+    peer.mark_known_txs(&tx_hashes).await;
+
+    let invalid_tx_count = machine
         .blockchain
         .dist_ledger
         .apis
         .insert_into_pool(tx_syn.tx_candidates)
         .await;
 
+    for _ in 0..invalid_tx_count {
+        peer.report_behavior(PeerBehavior::InvalidTx).await;
+    }
+
+    if invalid_tx_count == 0 && tx_count > 0 {
+        peer.report_behavior(PeerBehavior::ValidTx).await;
+    }
+
     let tx_ack_msg = Msg::TxAck(TxAckMsg {});
 
-    let receipt = conn.send(tx_ack_msg).await?;
+    let receipt = conn.respond(req_id, tx_ack_msg).await?;
 
     Ok(receipt)
 }
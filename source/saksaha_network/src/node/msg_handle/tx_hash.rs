@@ -1,3 +1,4 @@
+use super::REQUEST_TIMEOUT;
 use crate::{
     machine::Machine,
     node::{task::NodeTask, SaksahaNodeError},
@@ -5,25 +6,27 @@ use crate::{
 use log::{debug, info, warn};
 use sak_p2p_peertable::Peer;
 use sak_p2p_transport::{
-    Msg, RecvReceipt, SendReceipt, TxHashSyncMsg, UpgradedConn,
+    Msg, RecvReceipt, RequestId, SendReceipt, TxHashSyncMsg, UpgradedConn,
 };
 use sak_task_queue::TaskQueue;
 use sak_types::TxHash;
-use std::sync::Arc;
-use tokio::sync::RwLockWriteGuard;
+use std::{sync::Arc, time::Duration};
 
 pub(in crate::node) async fn send_tx_hash_syn(
-    mut conn_lock: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
     tx_hashes: Vec<TxHash>,
     task_queue: &Arc<TaskQueue<NodeTask>>,
+    peer: &Arc<Peer>,
 ) -> Result<RecvReceipt, SaksahaNodeError> {
-    let _receipt = conn_lock
-        .send(Msg::TxHashSyn(TxHashSyncMsg { tx_hashes }))
-        .await?;
-
-    let (msg, receipt) = conn_lock.next_msg().await;
+    // She either has these or is about to ask for them
+    peer.mark_known_txs(&tx_hashes).await;
 
-    let msg = msg.ok_or("tx hash ack should arrive as reply")??;
+    let (msg, receipt) = conn
+        .request(
+            Msg::TxHashSyn(TxHashSyncMsg { tx_hashes }),
+            Duration::from_millis(REQUEST_TIMEOUT),
+        )
+        .await?;
 
     let tx_hash_ack = match msg {
         Msg::TxHashAck(m) => m,
@@ -46,12 +49,15 @@ pub(in crate::node) async fn send_tx_hash_syn(
 }
 
 pub(in crate::node) async fn recv_tx_hash_syn(
+    req_id: RequestId,
     tx_hash_syn_msg: TxHashSyncMsg,
     machine: &Arc<Machine>,
-    mut conn: RwLockWriteGuard<'_, UpgradedConn>,
+    conn: &UpgradedConn,
     task_queue: &Arc<TaskQueue<NodeTask>>,
     peer: &Arc<Peer>,
 ) -> Result<SendReceipt, SaksahaNodeError> {
+    peer.mark_known_txs(&tx_hash_syn_msg.tx_hashes).await;
+
     let txs_to_request = machine
         .blockchain
         .dist_ledger
@@ -60,9 +66,12 @@ pub(in crate::node) async fn recv_tx_hash_syn(
         .await;
 
     let receipt = conn
-        .send(Msg::TxHashAck(TxHashSyncMsg {
-            tx_hashes: txs_to_request,
-        }))
+        .respond(
+            req_id,
+            Msg::TxHashAck(TxHashSyncMsg {
+                tx_hashes: txs_to_request,
+            }),
+        )
         .await?;
 
     Ok(receipt)
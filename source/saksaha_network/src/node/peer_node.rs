@@ -4,14 +4,35 @@ use crate::{
     machine::Machine,
     node::event_handle::{self, LedgerEventRoutine},
 };
+use chrono::Utc;
 use log::{debug, error, warn};
-use sak_p2p_peertable::{Peer, PeerStatus};
+use sak_p2p_peertable::{Peer, PeerBehavior, PeerTable};
+use sak_p2p_transport::{handshake::capability, Msg, PingMsg};
 use sak_task_queue::TaskQueue;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+// A peer silent for this long is pinged
+const PING_INTERVAL: u64 = 10_000;
+
+// A peer silent for this long is considered gone
+const PEER_IDLE_TIMEOUT: i64 = 30_000;
+
+// Tasks of ours that are run against a peer at the same time, each in a task
+// of its own
+const MAX_CONCURRENT_TASKS: usize = 8;
+
+// Msgs of a peer that are handled at the same time, each in a task of its
+// own. Past this, she is not read from until one is done. A handler lets go
+// of its permit before it waits on her, or the two of us could end up waiting
+// on each other.
+const MAX_CONCURRENT_MSGS: usize = 8;
 
 pub(in crate::node) struct PeerNode {
     pub peer: Arc<Peer>,
+    pub peer_table: Arc<PeerTable>,
     pub machine: Arc<Machine>,
     pub node_task_min_interval: Duration,
 }
@@ -53,39 +74,166 @@ impl PeerNode {
             });
         }
 
-        loop {
-            let mut conn_lock = self.peer.get_transport().conn.write().await;
+        let keepalive = self
+            .peer
+            .get_transport()
+            .protocol
+            .supports(capability::KEEPALIVE);
+
+        let mut ping_interval = tokio::time::interval_at(
+            Instant::now() + Duration::from_millis(PING_INTERVAL),
+            Duration::from_millis(PING_INTERVAL),
+        );
+
+        let mut ping_nonce: u128 = 0;
+        let mut pending_ping: Option<(u128, Instant)> = None;
+
+        let task_semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_TASKS));
+
+        let msg_semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_MSGS));
 
+        let conn = &self.peer.get_transport().conn;
+
+        loop {
+            // A permit is taken before waiting on what it is for, so that
+            // running out of one kind does not keep the other waiting
             tokio::select! {
-                task = node_task_queue.pop_front() => {
+                (permit, task) = async {
+                    let permit = task_semaphore.clone().acquire_owned().await;
+
+                    (permit, node_task_queue.pop_front().await)
+                } => {
+                    let permit = permit?;
                     let task = task?;
 
-                    task::handle_task(task,
-                        &node_task_queue, conn_lock, &self.machine).await;
+                    let node_task_queue = node_task_queue.clone();
+                    let machine = self.machine.clone();
+                    let peer = self.peer.clone();
+
+                    tokio::spawn(async move {
+                        task::handle_task(
+                            task,
+                            &node_task_queue,
+                            &machine,
+                            &peer,
+                        ).await;
+
+                        drop(permit);
+                    });
                 },
-                (maybe_msg, _) = conn_lock.next_msg() => {
+                _ = self.peer.closed() => {
+                    return Err(
+                        format!("Peer has been evicted, her_public_key: {}",
+                            self.peer.get_public_key_short()
+                        )
+                        .into());
+                },
+                _ = ping_interval.tick(), if keepalive => {
+                    let idle_ms = (Utc::now() - self.peer.get_last_seen().await)
+                        .num_milliseconds();
+
+                    if idle_ms > PEER_IDLE_TIMEOUT {
+                        warn!(
+                            "Peer has been idle for too long, \
+                            her_public_key: {}, idle_ms: {}",
+                            self.peer.get_public_key_short(),
+                            idle_ms,
+                        );
+
+                        self.peer_table.disconnect_peer(&self.peer).await;
+
+                        return Err(
+                            format!("Peer has gone idle, her_public_key: {}",
+                                self.peer.get_public_key_short()
+                            )
+                            .into());
+                    }
+
+                    // Peers in the middle of a conversation need no ping
+                    if idle_ms < (PING_INTERVAL / 2) as i64 {
+                        continue;
+                    }
+
+                    ping_nonce += 1;
+
+                    match conn
+                        .send(Msg::Ping(PingMsg { nonce: ping_nonce }))
+                        .await
+                    {
+                        Ok(_) => {
+                            pending_ping = Some((ping_nonce, Instant::now()));
+                        }
+                        Err(err) => {
+                            warn!("Failed to send ping, err: {}", err);
+                        }
+                    };
+                },
+                (permit, (maybe_msg, _)) = async {
+                    let permit = msg_semaphore.clone().acquire_owned().await;
+
+                    (permit, conn.next_msg().await)
+                } => {
+                    let permit = permit?;
+
                     match maybe_msg {
                         Some(maybe_msg) => match maybe_msg {
-                            Ok(msg) => {
-                                let _ = msg_handle::handle_msg(
-                                    msg,
-                                    &self.machine,
-                                    conn_lock,
-                                    &node_task_queue,
-                                    &self.peer,
-                                )
-                                .await;
+                            Ok(envelope) => {
+                                self.peer.touch().await;
+
+                                match &envelope.msg {
+                                    Msg::Pong(pong_msg) => {
+                                        if let Some((nonce, sent_at)) =
+                                            pending_ping
+                                        {
+                                            if nonce == pong_msg.nonce {
+                                                self.peer
+                                                    .set_rtt(sent_at.elapsed())
+                                                    .await;
+
+                                                pending_ping = None;
+                                            }
+                                        }
+                                    }
+                                    _ => {
+                                        let machine = self.machine.clone();
+                                        let node_task_queue =
+                                            node_task_queue.clone();
+                                        let peer = self.peer.clone();
+
+                                        tokio::spawn(async move {
+                                            if let Err(err) =
+                                                msg_handle::handle_msg(
+                                                    envelope,
+                                                    permit,
+                                                    &machine,
+                                                    &peer.get_transport().conn,
+                                                    &node_task_queue,
+                                                    &peer,
+                                                )
+                                                .await
+                                            {
+                                                debug!(
+                                                    "Msg handle failed, \
+                                                    err: {}",
+                                                    err
+                                                );
+                                            }
+                                        });
+                                    }
+                                };
                             }
                             Err(err) => {
                                 warn!("Failed to parse the msg, err: {}", err);
+
+                                self.peer.report_behavior(
+                                    PeerBehavior::MalformedMsg,
+                                ).await;
                             }
                         },
                         None => {
                             warn!("Peer has ended the connection");
 
-                            self.peer.set_peer_status(
-                                PeerStatus::Disconnected,
-                            ).await;
+                            self.peer_table.disconnect_peer(&self.peer).await;
 
                             return Err(
                                 format!("Peer has ended the connection, \
@@ -98,6 +246,29 @@ impl PeerNode {
 
                 }
             }
+
+            if self.peer.should_be_banned().await {
+                let reputation = self.peer.get_reputation().await;
+
+                if let Err(err) = self
+                    .peer_table
+                    .ban_peer(
+                        &self.peer,
+                        format!("reputation fell to {}", reputation),
+                    )
+                    .await
+                {
+                    error!("Failed to persist the ban list, err: {}", err);
+                }
+
+                return Err(format!(
+                    "Peer has been banned, her_public_key: {}, \
+                    reputation: {}",
+                    self.peer.get_public_key_short(),
+                    reputation,
+                )
+                .into());
+            }
         }
     }
 
@@ -1,32 +1,69 @@
 use super::NodeTask;
 use crate::{machine::Machine, node::msg_handle};
 use log::{debug, error, warn};
-use sak_p2p_transport::UpgradedConn;
+use sak_p2p_peertable::Peer;
+use sak_p2p_transport::handshake::capability;
 use sak_task_queue::TaskQueue;
 use std::sync::Arc;
-use tokio::sync::RwLockWriteGuard;
 
-pub(in crate::node) async fn handle_task<'a>(
+pub(in crate::node) async fn handle_task(
     task: NodeTask,
     task_queue: &Arc<TaskQueue<NodeTask>>,
-    conn_lock: RwLockWriteGuard<'a, UpgradedConn>,
     machine: &Arc<Machine>,
+    peer: &Arc<Peer>,
 ) {
     let task_type = task.to_string();
 
+    let conn = &peer.get_transport().conn;
+
+    let protocol = &peer.get_transport().protocol;
+
+    let capability = task.get_required_capability();
+
+    if !protocol.supports(capability) {
+        debug!(
+            "Peer has not agreed on the capability, discarding task, \
+            task: {}, capability: {}",
+            task_type, capability,
+        );
+
+        return;
+    }
+
     let res = match task {
         NodeTask::SendTxHashSyn { tx_hashes } => {
-            msg_handle::send_tx_hash_syn(conn_lock, tx_hashes, task_queue).await
+            // Hashes she has announced, or has been told of, are left out
+            let tx_hashes = peer.filter_known_txs(tx_hashes).await;
+
+            if tx_hashes.is_empty() {
+                return;
+            }
+
+            msg_handle::send_tx_hash_syn(conn, tx_hashes, task_queue, peer)
+                .await
         }
         NodeTask::SendTxSyn { tx_hashes } => {
-            msg_handle::send_tx_syn(conn_lock, tx_hashes, &machine).await
+            msg_handle::send_tx_syn(conn, tx_hashes, &machine).await
         }
         NodeTask::SendBlockHashSyn { new_blocks } => {
-            msg_handle::send_block_hash_syn(conn_lock, new_blocks, task_queue)
+            let new_blocks = peer.filter_known_blocks(new_blocks).await;
+
+            if new_blocks.is_empty() {
+                return;
+            }
+
+            msg_handle::send_block_hash_syn(conn, new_blocks, task_queue, peer)
                 .await
         }
         NodeTask::SendBlockSyn { new_blocks } => {
-            msg_handle::send_block_syn(conn_lock, new_blocks, &machine).await
+            if protocol.supports(capability::COMPACT_BLOCK) {
+                msg_handle::send_compact_block_syn(
+                    conn, new_blocks, &machine, peer,
+                )
+                .await
+            } else {
+                msg_handle::send_block_syn(conn, new_blocks, &machine).await
+            }
         }
     };
 
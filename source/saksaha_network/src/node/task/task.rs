@@ -1,3 +1,4 @@
+use sak_p2p_transport::handshake::capability;
 use sak_types::{BlockHash, BlockHeight, TxHash};
 
 #[derive(Debug)]
@@ -16,6 +17,20 @@ pub(in crate::node) enum NodeTask {
     },
 }
 
+impl NodeTask {
+    // Peer has to have agreed on this in the handshake to receive the msg
+    pub(in crate::node) fn get_required_capability(&self) -> &'static str {
+        match self {
+            Self::SendTxHashSyn { .. } | Self::SendTxSyn { .. } => {
+                capability::TX_SYNC
+            }
+            Self::SendBlockHashSyn { .. } | Self::SendBlockSyn { .. } => {
+                capability::BLOCK_SYNC
+            }
+        }
+    }
+}
+
 impl std::fmt::Display for NodeTask {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
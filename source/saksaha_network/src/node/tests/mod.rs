@@ -1,4 +1,5 @@
 mod p2p_block_sync;
+mod p2p_keepalive;
 mod p2p_marshal_tx_pool;
 mod p2p_stream_cipher;
 mod utils;
@@ -0,0 +1,84 @@
+use super::utils::{self, TestContext};
+use crate::tests::TestUtil;
+use std::time::Duration;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_idle_peers_ping_each_other() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test_1", "test_2"]);
+
+    let app_prefix_vec = vec![String::from("test_1"), String::from("test_2")];
+
+    let test_context_1 = utils::make_test_context(
+        app_prefix_vec[0].to_string(),
+        Some(35519),
+        Some(35518),
+        String::from(
+            "7297b903877a957748b74068d63d6d5661481975240\
+            99fc1df5cd9e8814c66c7",
+        ),
+        String::from(
+            "045739d074b8722891c307e8e75c9607e0b55a80778\
+            b42ef5f4640d4949dbf3992f6083b729baef9e9545c4\
+            e95590616fd382662a09653f2a966ff524989ae8c0f",
+        ),
+        false,
+    )
+    .await;
+
+    let TestContext {
+        p2p_host: p2p_host_1,
+        local_node: local_node_1,
+        identity: identity_1,
+        ..
+    } = test_context_1;
+
+    let test_context_2 = utils::make_test_context(
+        app_prefix_vec[1].to_string(),
+        Some(35521),
+        Some(35520),
+        String::from(
+            "aa99cfd91cc6f3b541d28f3e0707f9c7bcf05cf495308294786\
+                    ca450b501b5f2",
+        ),
+        String::from(
+            "\
+                    04240874d8c323c22a571f735e835ed2\
+                    f0619893a3989e557b1c9b4c699ac92b\
+                    84d0dc478108629c0353f2876941f90d\
+                    4b36346bcc19c6b625422adffb53b3a6af",
+        ),
+        false,
+    )
+    .await;
+
+    let TestContext {
+        p2p_host: p2p_host_2,
+        local_node: local_node_2,
+        peer_table: peer_table_2,
+        ..
+    } = test_context_2;
+
+    tokio::spawn(async move {
+        tokio::join!(p2p_host_1.run(), local_node_1.run());
+    });
+
+    tokio::spawn(async move {
+        tokio::join!(p2p_host_2.run(), local_node_2.run());
+    });
+
+    // Handshake, then a whole ping interval of silence
+    tokio::time::sleep(Duration::from_secs(16)).await;
+
+    let peer = peer_table_2
+        .get_mapped_peer(&identity_1.credential.public_key_str)
+        .await
+        .expect("Peer should be mapped");
+
+    assert!(peer.get_rtt().await.is_some());
+
+    let liveness = peer_table_2.get_peer_liveness().await;
+
+    assert_eq!(liveness.len(), 1);
+    assert!(liveness[0].rtt_ms.is_some());
+}
@@ -9,6 +9,7 @@ use sak_p2p_addr::AddrStatus;
 use sak_p2p_addr::UnknownAddr;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use std::sync::Arc;
 
 pub(crate) struct TestContext {
@@ -58,7 +59,7 @@ pub(crate) async fn make_test_context(
     };
 
     let p2p_peer_table = {
-        let ps = PeerTable::init(None)
+        let ps = PeerTable::init(None, None, None)
             .await
             .expect("Peer table should be initialized");
 
@@ -88,6 +89,27 @@ pub(crate) async fn make_test_context(
         status: AddrStatus::Initialized,
     }];
 
+    let blockchain = Blockchain::init(
+        app_prefix,
+        None,
+        None,
+        None,
+        true,
+        None,
+        identity.clone(),
+    )
+    .await
+    .unwrap();
+
+    let protocol_info = {
+        let genesis_block_hash =
+            blockchain.get_genesis_block_hash().await.unwrap();
+
+        let p = ProtocolInfo::new(String::from("test"), genesis_block_hash);
+
+        Arc::new(p)
+    };
+
     let p2p_host_args = P2PHostArgs {
         addr_expire_duration: None,
         addr_monitor_interval: None,
@@ -103,19 +125,17 @@ pub(crate) async fn make_test_context(
         p2p_port,
         p2p_max_conn_count: None,
         bootstrap_addrs,
+        addr_book_path: None,
+        external_addr: None,
         identity: identity.clone(),
         peer_table: p2p_peer_table.clone(),
+        protocol_info,
     };
 
     let p2p_host = P2PHost::init(p2p_host_args)
         .await
         .expect("P2P Host should be initialized");
 
-    let blockchain =
-        Blockchain::init(app_prefix, None, None, None, identity.clone())
-            .await
-            .unwrap();
-
     let machine = {
         let m = Machine { blockchain };
 
@@ -3,6 +3,7 @@ use log::error;
 use sak_p2p_discovery::AddrsIterator;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use sak_task_queue::TaskQueue;
 use std::{
     sync::Arc,
@@ -18,6 +19,7 @@ pub(crate) struct HandshakeDialLoop {
     pub(crate) addrs_iter: AddrsIterator,
     pub(crate) identity: Arc<Identity>,
     pub(crate) peer_table: Arc<PeerTable>,
+    pub(crate) protocol_info: Arc<ProtocolInfo>,
 }
 
 impl HandshakeDialLoop {
@@ -45,6 +47,7 @@ impl HandshakeDialLoop {
                         addr,
                         identity: self.identity.clone(),
                         peer_table: self.peer_table.clone(),
+                        protocol_info: self.protocol_info.clone(),
                     };
 
                     let p2p_task_queue = self.p2p_task_queue.clone();
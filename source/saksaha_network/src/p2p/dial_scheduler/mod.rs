@@ -1,11 +1,14 @@
 mod handshake;
+mod redial;
 
 use super::task::P2PTask;
 use handshake::HandshakeDialLoop;
 use log::info;
+use redial::RedialLoop;
 use sak_p2p_discovery::AddrsIterator;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use sak_task_queue::TaskQueue;
 use std::sync::Arc;
 
@@ -15,11 +18,13 @@ pub(crate) struct P2PDialSchedulerArgs {
     pub(crate) addrs_iter: AddrsIterator,
     pub(crate) identity: Arc<Identity>,
     pub(crate) peer_table: Arc<PeerTable>,
+    pub(crate) protocol_info: Arc<ProtocolInfo>,
 }
 
 pub(crate) struct P2PDialScheduler {
     p2p_task_queue: Arc<TaskQueue<P2PTask>>,
     handshake_dial_loop: Arc<HandshakeDialLoop>,
+    redial_loop: Arc<RedialLoop>,
 }
 
 impl P2PDialScheduler {
@@ -30,6 +35,7 @@ impl P2PDialScheduler {
             addrs_iter,
             identity,
             peer_table,
+            protocol_info,
         } = p2p_dial_schd_args;
 
         let handshake_dial_loop = {
@@ -37,8 +43,20 @@ impl P2PDialScheduler {
                 p2p_task_queue: p2p_task_queue.clone(),
                 p2p_dial_interval,
                 addrs_iter,
+                identity: identity.clone(),
+                peer_table: peer_table.clone(),
+                protocol_info: protocol_info.clone(),
+            };
+
+            Arc::new(l)
+        };
+
+        let redial_loop = {
+            let l = RedialLoop {
+                p2p_task_queue: p2p_task_queue.clone(),
                 identity,
                 peer_table,
+                protocol_info,
             };
 
             Arc::new(l)
@@ -47,6 +65,7 @@ impl P2PDialScheduler {
         let d = P2PDialScheduler {
             p2p_task_queue: p2p_task_queue.clone(),
             handshake_dial_loop,
+            redial_loop,
         };
 
         info!(
@@ -59,6 +78,6 @@ impl P2PDialScheduler {
     }
 
     pub async fn run(&self) {
-        self.handshake_dial_loop.run().await;
+        tokio::join!(self.handshake_dial_loop.run(), self.redial_loop.run(),);
     }
 }
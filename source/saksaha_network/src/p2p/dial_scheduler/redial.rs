@@ -0,0 +1,145 @@
+use crate::p2p::task::P2PTask;
+use log::{debug, error, warn};
+use sak_p2p_addr::AddrStatus;
+use sak_p2p_discovery::DiscAddr;
+use sak_p2p_id::Identity;
+use sak_p2p_peertable::{PeerDirection, PeerTable};
+use sak_p2p_transport::handshake::ProtocolInfo;
+use sak_task_queue::TaskQueue;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::time::Instant;
+
+const REDIAL_BASE_DELAY: u64 = 2000;
+const REDIAL_MAX_DELAY: u64 = 60_000;
+const MAX_REDIAL_ATTEMPTS: u32 = 6;
+const REDIAL_CHECK_INTERVAL: u64 = 1000;
+
+struct RedialState {
+    addr: Arc<DiscAddr>,
+    direction: PeerDirection,
+    attempts: u32,
+    next_at: Instant,
+}
+
+// Peers that have dropped out in good standing are dialed again with
+// exponential backoff, until they are back in the table or we give up.
+pub(crate) struct RedialLoop {
+    pub(crate) p2p_task_queue: Arc<TaskQueue<P2PTask>>,
+    pub(crate) identity: Arc<Identity>,
+    pub(crate) peer_table: Arc<PeerTable>,
+    pub(crate) protocol_info: Arc<ProtocolInfo>,
+}
+
+impl RedialLoop {
+    pub(crate) async fn run(&self) {
+        let mut redials: HashMap<String, RedialState> = HashMap::new();
+
+        let mut check_interval =
+            tokio::time::interval(Duration::from_millis(REDIAL_CHECK_INTERVAL));
+
+        loop {
+            tokio::select! {
+                addr = self.peer_table.next_redial_addr() => {
+                    match addr {
+                        Ok((addr, direction)) => {
+                            let public_key_str =
+                                addr.known_addr.public_key_str.clone();
+
+                            debug!(
+                                "Scheduling a redial, public_key: {}",
+                                addr.get_public_key_short(),
+                            );
+
+                            redials.insert(public_key_str, RedialState {
+                                addr,
+                                direction,
+                                attempts: 0,
+                                next_at: Instant::now()
+                                    + get_backoff_delay(0),
+                            });
+                        }
+                        Err(err) => {
+                            error!(
+                                "Error (fatal) getting next redial addr, \
+                                err: {}",
+                                err,
+                            );
+
+                            return;
+                        }
+                    }
+                },
+                _ = check_interval.tick() => {
+                    self.redial_due(&mut redials).await;
+                }
+            }
+        }
+    }
+
+    async fn redial_due(&self, redials: &mut HashMap<String, RedialState>) {
+        let now = Instant::now();
+        let mut finished = vec![];
+
+        for (public_key_str, state) in redials.iter_mut() {
+            if self
+                .peer_table
+                .get_mapped_peer(public_key_str)
+                .await
+                .is_some()
+            {
+                finished.push(public_key_str.clone());
+                continue;
+            }
+
+            if state.next_at > now {
+                continue;
+            }
+
+            if state.attempts >= MAX_REDIAL_ATTEMPTS {
+                warn!(
+                    "Giving up redialing a peer, public_key: {}, \
+                    attempts: {}",
+                    state.addr.get_public_key_short(),
+                    state.attempts,
+                );
+
+                let mut addr_status_lock =
+                    state.addr.known_addr.status.write().await;
+                *addr_status_lock = AddrStatus::Disconnected;
+
+                finished.push(public_key_str.clone());
+                continue;
+            }
+
+            // Only the side that has dialed her in the first place dials
+            // again, so that the two do not collide and a peer that cannot
+            // be reached is not dialed. The other still counts the attempts
+            // to know when to give up.
+            if state.direction == PeerDirection::Outbound {
+                let task = P2PTask::InitiateHandshake {
+                    addr: state.addr.clone(),
+                    identity: self.identity.clone(),
+                    peer_table: self.peer_table.clone(),
+                    protocol_info: self.protocol_info.clone(),
+                };
+
+                if let Err(err) = self.p2p_task_queue.push_back(task).await {
+                    error!("Error enqueueing a p2p redial task, err: {}", err);
+                }
+            }
+
+            state.attempts += 1;
+            state.next_at = now + get_backoff_delay(state.attempts);
+        }
+
+        for public_key_str in finished {
+            redials.remove(&public_key_str);
+        }
+    }
+}
+
+fn get_backoff_delay(attempts: u32) -> Duration {
+    let delay = REDIAL_BASE_DELAY.saturating_mul(1 << attempts.min(16));
+
+    Duration::from_millis(delay.min(REDIAL_MAX_DELAY))
+}
@@ -3,12 +3,14 @@ use super::task::runtime::P2PTaskRuntime;
 use super::task::P2PTask;
 use super::P2PMonitor;
 use super::{dial_scheduler::P2PDialScheduler, server::Server};
+use log::error;
 use sak_p2p_addr::UnknownAddr;
 use sak_p2p_discovery::{Discovery, DiscoveryArgs};
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use sak_task_queue::TaskQueue;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::net::{TcpListener, UdpSocket};
 
 const P2P_TASK_QUEUE_CAPACITY: usize = 10;
@@ -36,9 +38,12 @@ pub(crate) struct P2PHostArgs {
     pub(crate) p2p_port: u16,
     pub(crate) p2p_max_conn_count: Option<u16>,
     pub(crate) bootstrap_addrs: Vec<UnknownAddr>,
+    pub(crate) addr_book_path: Option<PathBuf>,
+    pub(crate) external_addr: Option<String>,
     pub(crate) identity: Arc<Identity>,
     pub(crate) disc_socket: UdpSocket,
     pub(crate) peer_table: Arc<PeerTable>,
+    pub(crate) protocol_info: Arc<ProtocolInfo>,
 }
 
 impl P2PHost {
@@ -77,6 +82,8 @@ impl P2PHost {
                 udp_socket: p2p_host_args.disc_socket,
                 p2p_port: p2p_host_args.p2p_port,
                 bootstrap_addrs: p2p_host_args.bootstrap_addrs,
+                addr_book_path: p2p_host_args.addr_book_path,
+                external_addr: p2p_host_args.external_addr,
             };
 
             let (disc, disc_port) = Discovery::init(disc_args).await?;
@@ -84,6 +91,15 @@ impl P2PHost {
             (Arc::new(disc), disc_port)
         };
 
+        // Bans outlive the peers, the addr book is told of the ones still
+        // in effect so that it does not dial them
+        for ban_entry in p2p_host_args.peer_table.get_ban_list().await {
+            p2p_discovery
+                .addr_table
+                .ban_addr(&ban_entry.public_key_str, ban_entry.banned_until)
+                .await;
+        }
+
         let p2p_server = {
             let s = Server::new(
                 p2p_host_args.p2p_max_conn_count,
@@ -91,6 +107,7 @@ impl P2PHost {
                 p2p_host_args.identity.clone(),
                 p2p_host_args.peer_table.clone(),
                 p2p_discovery.addr_table.clone(),
+                p2p_host_args.protocol_info.clone(),
             );
 
             s
@@ -105,6 +122,7 @@ impl P2PHost {
                 addrs_iter,
                 identity: p2p_host_args.identity.clone(),
                 peer_table: p2p_host_args.peer_table.clone(),
+                protocol_info: p2p_host_args.protocol_info.clone(),
             };
 
             let s = P2PDialScheduler::init(p2p_dial_schd_args);
@@ -130,9 +148,32 @@ impl P2PHost {
             self.p2p_task_runtime.run(),
             self.p2p_server.run(),
             self.p2p_dial_scheduler.run(),
+            self.relay_bans(),
         );
     }
 
+    // Peers banned at runtime are kept out of the addr book as well
+    async fn relay_bans(&self) {
+        loop {
+            let ban_entry = match self.peer_table.next_ban_entry().await {
+                Ok(e) => e,
+                Err(err) => {
+                    error!(
+                        "Error (fatal) getting next ban entry, err: {}",
+                        err
+                    );
+
+                    return;
+                }
+            };
+
+            self.p2p_discovery
+                .addr_table
+                .ban_addr(&ban_entry.public_key_str, ban_entry.banned_until)
+                .await;
+        }
+    }
+
     pub(crate) fn get_p2p_monitor(&self) -> P2PMonitor {
         let monitor = P2PMonitor {
             peer_table: self.peer_table.clone(),
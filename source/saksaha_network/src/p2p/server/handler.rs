@@ -1,11 +1,11 @@
 use chrono::Utc;
 use futures::StreamExt;
-use log::{error, warn};
+use log::warn;
 use sak_p2p_discovery::AddrTable;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::{Peer, PeerStatus, PeerTable};
 use sak_p2p_transport::{
-    handshake::{self, HandshakeRecvArgs},
+    handshake::{self, HandshakeRecvArgs, ProtocolInfo},
     Conn, Msg,
 };
 use std::sync::Arc;
@@ -22,15 +22,21 @@ impl Handler {
         identity: Arc<Identity>,
         peer_table: Arc<PeerTable>,
         addr_table: Arc<AddrTable>,
+        protocol_info: Arc<ProtocolInfo>,
     ) {
-        let handshake_recv_args = HandshakeRecvArgs { identity };
+        let handshake_recv_args = HandshakeRecvArgs {
+            identity,
+            protocol_info,
+        };
+
+        let her_ip = conn.socket_addr.ip().to_string();
 
-        let peer_slot_guard = match peer_table.get_empty_slot().await {
+        let peer_slot_guard = match peer_table.get_inbound_slot(&her_ip).await {
             Ok(s) => s,
             Err(err) => {
-                error!(
-                    "Empty slot is not available in the peer table, err: {}",
-                    err
+                warn!(
+                    "Turning away an inbound connection, ip: {}, err: {}",
+                    her_ip, err,
                 );
                 return;
             }
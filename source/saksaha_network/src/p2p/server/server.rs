@@ -3,7 +3,7 @@ use log::{debug, warn};
 use sak_p2p_discovery::AddrTable;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
-use sak_p2p_transport::Conn;
+use sak_p2p_transport::{handshake::ProtocolInfo, Conn};
 use std::{sync::Arc, time::Duration};
 use tokio::{
     net::{TcpListener, TcpStream},
@@ -18,6 +18,7 @@ pub(crate) struct Server {
     identity: Arc<Identity>,
     peer_table: Arc<PeerTable>,
     addr_table: Arc<AddrTable>,
+    protocol_info: Arc<ProtocolInfo>,
 }
 
 impl Server {
@@ -27,6 +28,7 @@ impl Server {
         identity: Arc<Identity>,
         peer_table: Arc<PeerTable>,
         addr_table: Arc<AddrTable>,
+        protocol_info: Arc<ProtocolInfo>,
     ) -> Server {
         let p2p_max_conn_count = match p2p_max_conn_count {
             Some(c) => c.into(),
@@ -41,6 +43,7 @@ impl Server {
             identity,
             peer_table,
             addr_table,
+            protocol_info,
         }
     }
 
@@ -105,9 +108,12 @@ impl Server {
             let identity = self.identity.clone();
             let peer_table = self.peer_table.clone();
             let addr_table = self.addr_table.clone();
+            let protocol_info = self.protocol_info.clone();
 
             tokio::spawn(async move {
-                handler.run(conn, identity, peer_table, addr_table).await;
+                handler
+                    .run(conn, identity, peer_table, addr_table, protocol_info)
+                    .await;
             });
         }
     }
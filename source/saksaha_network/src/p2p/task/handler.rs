@@ -1,5 +1,5 @@
 use crate::p2p::task::P2PTask;
-use log::{debug, error, warn};
+use log::{debug, warn};
 use sak_p2p_peertable::{Peer, PeerStatus};
 use sak_p2p_transport::{
     handshake::{self, HandshakeInitArgs},
@@ -14,6 +14,7 @@ pub(crate) async fn run(task: P2PTask) {
             addr,
             identity,
             peer_table,
+            protocol_info,
         } => {
             let known_addr = &addr.known_addr;
 
@@ -28,18 +29,30 @@ pub(crate) async fn run(task: P2PTask) {
                 return;
             }
 
-            let peer_slot_guard = match peer_table.get_empty_slot().await {
-                Ok(p) => p,
-                Err(err) => {
-                    error!(
-                        "Fatal error. Empty slot is not available in the \
-                        peer table, err: {}",
-                        err
-                    );
+            if peer_table.is_banned(&known_addr.public_key_str).await {
+                debug!(
+                    "Peer is banned, abandoning handshake init task, \
+                    public_key: {}",
+                    &known_addr.public_key_str,
+                );
 
-                    return;
-                }
-            };
+                return;
+            }
+
+            let peer_slot_guard =
+                match peer_table.get_outbound_slot(&known_addr.ip).await {
+                    Ok(p) => p,
+                    Err(err) => {
+                        debug!(
+                            "Cannot take an outbound slot, abandoning \
+                            handshake init task, public_key: {}, err: {}",
+                            known_addr.get_public_ket_short(),
+                            err,
+                        );
+
+                        return;
+                    }
+                };
 
             let endpoint = known_addr.get_p2p_endpoint();
 
@@ -90,6 +103,7 @@ pub(crate) async fn run(task: P2PTask) {
                 identity,
                 conn,
                 public_key_str: known_addr.public_key_str.clone(),
+                protocol_info,
             };
 
             let transport = match handshake::initiate_handshake(
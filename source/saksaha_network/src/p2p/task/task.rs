@@ -1,6 +1,7 @@
 use sak_p2p_discovery::DiscAddr;
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use std::sync::Arc;
 
 pub(crate) enum P2PTask {
@@ -8,6 +9,7 @@ pub(crate) enum P2PTask {
         addr: Arc<DiscAddr>,
         identity: Arc<Identity>,
         peer_table: Arc<PeerTable>,
+        protocol_info: Arc<ProtocolInfo>,
     },
 }
 
@@ -1,2 +1,16 @@
 mod stream_cipher;
 mod trpt_handshake;
+
+use sak_p2p_transport::handshake::ProtocolInfo;
+use std::sync::Arc;
+
+// Hosts in these tests run without a ledger, any genesis block hash will do
+// as long as they all share it
+fn make_protocol_info() -> Arc<ProtocolInfo> {
+    let p = ProtocolInfo::new(
+        String::from("test"),
+        String::from("test_genesis_block_hash"),
+    );
+
+    Arc::new(p)
+}
@@ -1,3 +1,4 @@
+use super::make_protocol_info;
 use crate::p2p::{P2PHost, P2PHostArgs};
 use crate::{
     blockchain::Blockchain,
@@ -38,7 +39,7 @@ async fn create_dummy_client(
     };
 
     let p2p_peer_table = {
-        let ps = PeerTable::init(None)
+        let ps = PeerTable::init(None, None, None)
             .await
             .expect("Peer table should be initialized");
 
@@ -82,9 +83,12 @@ async fn create_dummy_client(
         p2p_port: p2p_port.port(),
         p2p_max_conn_count: None,
         bootstrap_addrs,
+        addr_book_path: None,
+        external_addr: None,
         identity: identity.clone(),
         disc_socket,
         peer_table: p2p_peer_table.clone(),
+        protocol_info: make_protocol_info(),
     };
 
     let p2p_host = {
@@ -136,7 +140,7 @@ async fn create_client(
     );
 
     let p2p_peer_table = {
-        let ps = PeerTable::init(None)
+        let ps = PeerTable::init(None, None, None)
             .await
             .expect("Peer table should be initialized");
 
@@ -190,6 +194,8 @@ async fn create_client(
             udp_socket: disc_socket,
             p2p_port: p2p_port.port(),
             bootstrap_addrs,
+            addr_book_path: None,
+            external_addr: None,
         };
 
         let (d, _) = Discovery::init(disc_args)
@@ -206,6 +212,7 @@ async fn create_client(
             identity.clone(),
             p2p_peer_table.clone(),
             p2p_discovery.addr_table.clone(),
+            make_protocol_info(),
         );
         Arc::new(s)
     };
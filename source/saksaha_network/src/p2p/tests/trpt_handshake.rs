@@ -1,3 +1,4 @@
+use super::make_protocol_info;
 use crate::{
     p2p::{P2PHost, P2PHostArgs},
     tests::TestUtil,
@@ -25,7 +26,7 @@ async fn create_dummy_client(
     };
 
     let p2p_peer_table = {
-        let ps = PeerTable::init(None)
+        let ps = PeerTable::init(None, None, None)
             .await
             .expect("Peer table should be initialized");
 
@@ -69,9 +70,12 @@ async fn create_dummy_client(
         p2p_port: p2p_port.port(),
         p2p_max_conn_count: None,
         bootstrap_addrs,
+        addr_book_path: None,
+        external_addr: None,
         identity: identity.clone(),
         disc_socket,
         peer_table: p2p_peer_table.clone(),
+        protocol_info: make_protocol_info(),
     };
 
     let p2p_host = {
@@ -4,6 +4,10 @@ use std::path::PathBuf;
 
 const CONFIG_FILE_NAME: &str = "config.yml";
 
+const BAN_LIST_FILE_NAME: &str = "ban_list.json";
+
+const ADDR_BOOK_FILE_NAME: &str = "addr_book.json";
+
 pub fn get_config_path(app_prefix: &String) -> Result<PathBuf, SaksahaError> {
     let app_path = sak_fs::get_app_root_path("saksaha")?.join(app_prefix);
 
@@ -19,3 +23,19 @@ pub fn get_config_file_path(
 
     Ok(config_path)
 }
+
+pub fn get_ban_list_file_path(
+    app_prefix: &String,
+) -> Result<PathBuf, SaksahaError> {
+    let app_path = get_config_path(app_prefix)?;
+
+    Ok(app_path.join(BAN_LIST_FILE_NAME))
+}
+
+pub fn get_addr_book_file_path(
+    app_prefix: &String,
+) -> Result<PathBuf, SaksahaError> {
+    let app_path = get_config_path(app_prefix)?;
+
+    Ok(app_path.join(ADDR_BOOK_FILE_NAME))
+}
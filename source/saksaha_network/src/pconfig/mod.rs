@@ -1,4 +1,5 @@
 mod fs;
 mod pconfig;
 
+pub use fs::{get_addr_book_file_path, get_ban_list_file_path};
 pub use pconfig::*;
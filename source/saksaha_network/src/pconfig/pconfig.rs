@@ -8,6 +8,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PConfig {
     pub p2p: PersistedP2PConfig,
+    // Config files written before this section existed have none
+    #[serde(default)]
+    pub rpc: PersistedRPCConfig,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -20,6 +23,17 @@ pub struct PersistedP2PConfig {
     pub disc_port: Option<u16>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PersistedRPCConfig {
+    // At most one of auth_token (bearer) and hmac_secret (hex) is set
+    pub auth_token: Option<String>,
+    pub hmac_secret: Option<String>,
+    pub allowed_methods: Option<Vec<String>>,
+    pub denied_methods: Option<Vec<String>>,
+    pub rate_limit_per_sec: Option<u32>,
+    pub rate_limit_burst: Option<u32>,
+}
+
 impl PConfig {
     pub fn new(app_prefix: &String) -> Result<PConfig, SaksahaError> {
         info!("Loading persisted config...");
@@ -82,6 +96,7 @@ impl PConfig {
                 p2p_port: None,
                 disc_port: None,
             },
+            rpc: PersistedRPCConfig::default(),
         };
 
         pconf
@@ -1,6 +1,7 @@
 // mod router;
 mod routes;
 mod rpc;
+mod ws;
 
 #[cfg(test)]
 mod tests;
@@ -19,18 +19,52 @@ pub(in crate::rpc) fn get_routes(
                 Box::pin(v0::send_pour_tx(route_state, params, sys_handle))
             }),
         },
+        Path {
+            method: "simulate_tx",
+            handler: Box::new(|route_state, params, sys_handle| {
+                Box::pin(v0::simulate_tx(route_state, params, sys_handle))
+            }),
+        },
         Path {
             method: "get_status",
             handler: Box::new(|route_state, params, sys_handle| {
                 Box::pin(v0::get_status(route_state, params, sys_handle))
             }),
         },
+        Path {
+            method: "get_ban_list",
+            handler: Box::new(|route_state, params, sys_handle| {
+                Box::pin(v0::get_ban_list(route_state, params, sys_handle))
+            }),
+        },
         Path {
             method: "get_tx",
             handler: Box::new(|route_state, params, sys_handle| {
                 Box::pin(v0::get_tx(route_state, params, sys_handle))
             }),
         },
+        Path {
+            method: "get_txs_by_block",
+            handler: Box::new(|route_state, params, sys_handle| {
+                Box::pin(v0::get_txs_by_block(route_state, params, sys_handle))
+            }),
+        },
+        Path {
+            method: "get_ctr_call_history",
+            handler: Box::new(|route_state, params, sys_handle| {
+                Box::pin(v0::get_ctr_call_history(
+                    route_state,
+                    params,
+                    sys_handle,
+                ))
+            }),
+        },
+        Path {
+            method: "get_tx_by_cm",
+            handler: Box::new(|route_state, params, sys_handle| {
+                Box::pin(v0::get_tx_by_cm(route_state, params, sys_handle))
+            }),
+        },
         Path {
             method: "get_block",
             handler: Box::new(|route_state, params, sys_handle| {
@@ -1,10 +1,12 @@
+use super::make_ledger_error;
 use crate::system::SystemHandle;
 use hyper::{Body, Response};
 use hyper_rpc_router::{
     make_error_response, make_success_response, require_params_parsed,
     require_some_params, Params, RouteState,
 };
-use sak_types::Block;
+use sak_dist_ledger::PageDirection;
+use sak_types::{Block, BlockHeight};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -47,7 +49,7 @@ pub(in crate::rpc) async fn get_block(
             return make_error_response(
                 route_state.resp,
                 Some(route_state.id),
-                err.into(),
+                make_ledger_error(err),
             );
         }
     }
@@ -55,13 +57,16 @@ pub(in crate::rpc) async fn get_block(
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(in crate::rpc) struct GetBlockListRequest {
-    pub offset: Option<u128>,
-    pub limit: Option<u128>,
+    pub from_height: Option<BlockHeight>,
+    #[serde(default)]
+    pub direction: PageDirection,
+    pub limit: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(in crate::rpc) struct GetBlockListResponse {
     pub block_list: Vec<Block>,
+    pub next: Option<BlockHeight>,
 }
 
 pub(in crate::rpc) async fn get_block_list(
@@ -82,11 +87,14 @@ pub(in crate::rpc) async fn get_block_list(
         .blockchain
         .dist_ledger
         .apis
-        .get_block_list(rb.offset, rb.limit)
+        .get_block_list(rb.from_height, rb.direction, rb.limit)
         .await
     {
-        Ok(block_list) => {
-            let get_block_resp = GetBlockListResponse { block_list };
+        Ok(page) => {
+            let get_block_resp = GetBlockListResponse {
+                block_list: page.block_list,
+                next: page.next,
+            };
 
             return make_success_response(route_state, get_block_resp);
         }
@@ -94,7 +102,7 @@ pub(in crate::rpc) async fn get_block_list(
             return make_error_response(
                 route_state.resp,
                 Some(route_state.id),
-                err.into(),
+                make_ledger_error(err),
             );
         }
     }
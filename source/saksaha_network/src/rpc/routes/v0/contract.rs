@@ -1,3 +1,4 @@
+use super::make_ledger_error;
 use crate::system::SystemHandle;
 use hyper::{Body, Response};
 use hyper_rpc_router::{
@@ -5,6 +6,7 @@ use hyper_rpc_router::{
     require_some_params, Params, RouteState,
 };
 use sak_contract_std::CtrRequest;
+use sak_types::BlockHeight;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -12,6 +14,9 @@ use std::sync::Arc;
 pub(crate) struct QueryCtrRequest {
     pub ctr_addr: String,
     pub req: CtrRequest,
+    // The latest state is queried if not given
+    #[serde(default)]
+    pub block_height: Option<BlockHeight>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -37,7 +42,7 @@ pub(in crate::rpc) async fn query_ctr(
         .blockchain
         .dist_ledger
         .apis
-        .query_ctr(&rb.ctr_addr, rb.req)
+        .query_ctr(&rb.ctr_addr, rb.req, rb.block_height)
         .await
     {
         Ok(result) => {
@@ -50,7 +55,7 @@ pub(in crate::rpc) async fn query_ctr(
             return make_error_response(
                 route_state.resp,
                 Some(route_state.id),
-                err.into(),
+                make_ledger_error(err),
             );
         }
     }
@@ -1,3 +1,7 @@
+use hyper_rpc_router::RPCRouterError;
+use sak_dist_ledger::{LedgerError, LedgerPrunedError};
+use sak_rpc_interface::{JsonRPCError, LEDGER_PRUNED};
+
 mod block;
 mod contract;
 mod proof;
@@ -9,3 +13,13 @@ pub(in crate::rpc) use contract::*;
 pub(in crate::rpc) use proof::*;
 pub(in crate::rpc) use status::*;
 pub(in crate::rpc) use tx::*;
+
+// Pruned data is told apart from the data never having existed, so that
+// clients know to ask an archive node instead
+pub(in crate::rpc) fn make_ledger_error(err: LedgerError) -> RPCRouterError {
+    if LedgerPrunedError::is_pruned(&err) {
+        JsonRPCError::new(LEDGER_PRUNED, err.to_string()).into()
+    } else {
+        err
+    }
+}
@@ -1,6 +1,7 @@
 use crate::system::SystemHandle;
 use hyper::{Body, Response};
 use hyper_rpc_router::{make_success_response, Params, RouteState};
+use sak_p2p_peertable::{BanEntry, PeerLiveness};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -8,6 +9,7 @@ use std::sync::Arc;
 pub struct GetNodeStatusResponse {
     addr_vec: Vec<String>,
     peer_vec: Vec<String>,
+    peer_liveness: Vec<PeerLiveness>,
 }
 
 pub(in crate::rpc) async fn get_status(
@@ -24,8 +26,30 @@ pub(in crate::rpc) async fn get_status(
 
     let peer_vec = sys_handle.p2p_monitor.peer_table.get_status().await;
 
+    let peer_liveness =
+        sys_handle.p2p_monitor.peer_table.get_peer_liveness().await;
+
     return make_success_response(
         route_state,
-        GetNodeStatusResponse { addr_vec, peer_vec },
+        GetNodeStatusResponse {
+            addr_vec,
+            peer_vec,
+            peer_liveness,
+        },
     );
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetBanListResponse {
+    pub ban_list: Vec<BanEntry>,
+}
+
+pub(in crate::rpc) async fn get_ban_list(
+    route_state: RouteState,
+    _params: Params,
+    sys_handle: Arc<SystemHandle>,
+) -> Response<Body> {
+    let ban_list = sys_handle.p2p_monitor.peer_table.get_ban_list().await;
+
+    return make_success_response(route_state, GetBanListResponse { ban_list });
+}
@@ -1,10 +1,14 @@
+use super::make_ledger_error;
 use crate::system::SystemHandle;
 use hyper::{Body, Response};
 use hyper_rpc_router::{
     make_error_response, make_success_response, require_params_parsed,
     require_some_params, Params, RouteState,
 };
-use sak_types::{MintTxCandidate, PourTxCandidate, Tx, TxCandidate};
+use sak_dist_ledger::{TxLocation, TxPos};
+use sak_types::{
+    BlockHeight, Cm, CtrAddr, MintTxCandidate, PourTxCandidate, Tx, TxCandidate,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use type_extension::U8Arr32;
@@ -219,6 +223,196 @@ pub(in crate::rpc) async fn get_tx(
 
             return make_success_response(route_state, get_tx_resp);
         }
+        Err(err) => {
+            return make_error_response(
+                route_state.resp,
+                Some(route_state.id),
+                make_ledger_error(err),
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct GetTxsByBlockRequest {
+    pub block_height: BlockHeight,
+    pub from_pos: Option<TxPos>,
+    pub limit: Option<usize>,
+}
+
+pub(in crate::rpc) async fn get_txs_by_block(
+    route_state: RouteState,
+    params: Params,
+    sys_handle: Arc<SystemHandle>,
+) -> Response<Body> {
+    let params = require_some_params!(
+        route_state,
+        params,
+        "get_txs_by_block should contain params",
+    );
+
+    let rb: GetTxsByBlockRequest = require_params_parsed!(route_state, &params);
+
+    match sys_handle
+        .machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .get_txs_by_block(&rb.block_height, rb.from_pos, rb.limit)
+        .await
+    {
+        Ok(page) => {
+            return make_success_response(route_state, page);
+        }
+        Err(err) => {
+            return make_error_response(
+                route_state.resp,
+                Some(route_state.id),
+                make_ledger_error(err),
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct GetCtrCallHistoryRequest {
+    pub ctr_addr: CtrAddr,
+    pub from: Option<TxLocation>,
+    pub limit: Option<usize>,
+}
+
+pub(in crate::rpc) async fn get_ctr_call_history(
+    route_state: RouteState,
+    params: Params,
+    sys_handle: Arc<SystemHandle>,
+) -> Response<Body> {
+    let params = require_some_params!(
+        route_state,
+        params,
+        "get_ctr_call_history should contain params",
+    );
+
+    let rb: GetCtrCallHistoryRequest =
+        require_params_parsed!(route_state, &params);
+
+    match sys_handle
+        .machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .get_ctr_call_history(&rb.ctr_addr, rb.from, rb.limit)
+        .await
+    {
+        Ok(page) => {
+            return make_success_response(route_state, page);
+        }
+        Err(err) => {
+            return make_error_response(
+                route_state.resp,
+                Some(route_state.id),
+                make_ledger_error(err),
+            );
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct GetTxByCmRequest {
+    pub cm: Cm,
+}
+
+pub(in crate::rpc) async fn get_tx_by_cm(
+    route_state: RouteState,
+    params: Params,
+    sys_handle: Arc<SystemHandle>,
+) -> Response<Body> {
+    let params = require_some_params!(
+        route_state,
+        params,
+        "get_tx_by_cm should contain params",
+    );
+
+    let rb: GetTxByCmRequest = require_params_parsed!(route_state, &params);
+
+    match sys_handle
+        .machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .get_tx_by_cm(&rb.cm)
+        .await
+    {
+        Ok(tx) => {
+            let get_tx_resp = GetTxResponse { tx };
+
+            return make_success_response(route_state, get_tx_resp);
+        }
+        Err(err) => {
+            return make_error_response(
+                route_state.resp,
+                Some(route_state.id),
+                make_ledger_error(err),
+            );
+        }
+    }
+}
+
+// A tx candidate as it would be sent with `send_mint_tx` or `send_pour_tx`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "tx_type", rename_all = "snake_case")]
+pub(in crate::rpc) enum SimulateTxRequest {
+    Mint(SendMintTxRequest),
+    Pour(SendPourTxRequest),
+}
+
+pub(in crate::rpc) async fn simulate_tx(
+    route_state: RouteState,
+    params: Params,
+    sys_handle: Arc<SystemHandle>,
+) -> Response<Body> {
+    let params = require_some_params!(
+        route_state,
+        params,
+        "simulate_tx should contain params",
+    );
+
+    let rb: SimulateTxRequest = require_params_parsed!(route_state, &params);
+
+    let tx_candidate = match rb {
+        SimulateTxRequest::Mint(rb) => TxCandidate::Mint(MintTxCandidate::new(
+            rb.created_at,
+            rb.data,
+            rb.author_sig,
+            rb.ctr_addr,
+            rb.cm,
+            rb.v,
+            rb.k,
+            rb.s,
+        )),
+        SimulateTxRequest::Pour(rb) => TxCandidate::Pour(PourTxCandidate::new(
+            rb.created_at,
+            rb.data,
+            rb.author_sig,
+            rb.ctr_addr,
+            rb.pi,
+            rb.sn_1,
+            rb.cm_1,
+            rb.cm_2,
+            rb.merkle_rt,
+        )),
+    };
+
+    match sys_handle
+        .machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .simulate_tx(tx_candidate)
+        .await
+    {
+        Ok(simulation) => {
+            return make_success_response(route_state, simulation);
+        }
         Err(err) => {
             return make_error_response(
                 route_state.resp,
@@ -1,18 +1,32 @@
-use super::routes;
+use super::{routes, ws};
 use crate::{SaksahaError, SystemHandle};
-use hyper_rpc_router::Router;
-use hyper_server::{cors, Middleware, RPCServer};
+use hyper_rpc_router::{
+    make_auth_middleware, make_rate_limit_middleware, MethodFilter, RPCAuth,
+    RateLimit, RateLimiter, Router, DEFAULT_MAX_BATCH_SIZE,
+};
+use hyper_server::{
+    cors, is_ws_upgrade_request, upgrade_ws, Middleware, MiddlewareResult,
+    RPCServer,
+};
 use std::sync::Arc;
 use tokio::net::TcpListener;
 
 pub(crate) struct RPCArgs {
     pub sys_handle: Arc<SystemHandle>,
     pub rpc_socket: TcpListener,
+    pub max_batch_size: Option<usize>,
+    pub rpc_auth: Option<RPCAuth>,
+    pub method_filter: MethodFilter,
+    pub rate_limit: Option<RateLimit>,
 }
 
 pub(crate) struct RPC {
     sys_handle: Arc<SystemHandle>,
     rpc_socket: TcpListener,
+    max_batch_size: usize,
+    rpc_auth: Option<RPCAuth>,
+    method_filter: MethodFilter,
+    rate_limit: Option<RateLimit>,
     server: RPCServer,
 }
 
@@ -23,6 +37,12 @@ impl RPC {
         let rpc = RPC {
             sys_handle: rpc_args.sys_handle,
             rpc_socket: rpc_args.rpc_socket,
+            max_batch_size: rpc_args
+                .max_batch_size
+                .unwrap_or(DEFAULT_MAX_BATCH_SIZE),
+            rpc_auth: rpc_args.rpc_auth,
+            method_filter: rpc_args.method_filter,
+            rate_limit: rpc_args.rate_limit,
             server,
         };
 
@@ -30,15 +50,45 @@ impl RPC {
     }
 
     pub(crate) async fn run(self) -> Result<(), SaksahaError> {
+        let rate_limiter =
+            self.rate_limit.map(|r| Arc::new(RateLimiter::new(r)));
+
         let router = {
             let routes = routes::get_routes();
-            let router = Router::new(routes);
+            let mut router = Router::new(routes)
+                .with_max_batch_size(self.max_batch_size)
+                .with_method_filter(self.method_filter.clone());
+
+            if let Some(l) = &rate_limiter {
+                router = router.with_rate_limiter(l.clone());
+            }
 
             router
         };
 
         let cors = Middleware::new(Box::new(cors));
 
+        // Subscriptions are served over websocket on the same port
+        let method_filter = Arc::new(self.method_filter);
+
+        let ws_upgrade =
+            Middleware::new(Box::new(move |req, res, sys_handle| {
+                if is_ws_upgrade_request(&req) {
+                    let method_filter = method_filter.clone();
+
+                    return upgrade_ws(
+                        req,
+                        res,
+                        sys_handle,
+                        move |ws, sys_handle| {
+                            ws::run_session(ws, sys_handle, method_filter)
+                        },
+                    );
+                }
+
+                MiddlewareResult::Passing(req, res, sys_handle)
+            }));
+
         let route = {
             let m = Middleware::new(Box::new(move |req, res, ctx| {
                 router.route(req, res, ctx)
@@ -47,7 +97,20 @@ impl RPC {
             m
         };
 
-        let middlewares = vec![cors, route];
+        let mut middlewares = vec![cors];
+
+        // Rate limiting goes first so that guessing credentials is limited
+        // as well
+        if let Some(l) = rate_limiter {
+            middlewares.push(make_rate_limit_middleware(l));
+        }
+
+        if let Some(a) = self.rpc_auth {
+            middlewares.push(make_auth_middleware(a));
+        }
+
+        middlewares.push(ws_upgrade);
+        middlewares.push(route);
 
         self.server
             .run(self.rpc_socket, self.sys_handle, middlewares)
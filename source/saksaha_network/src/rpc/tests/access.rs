@@ -0,0 +1,307 @@
+use super::utils;
+use crate::tests::TestUtil;
+use hyper::{
+    header, Body, Client, HeaderMap, Method, Request, StatusCode, Uri,
+};
+use hyper_rpc_router::{
+    MethodFilter, RPCAuth, RateLimit, HMAC_SIGNATURE_HEADER,
+    HMAC_TIMESTAMP_HEADER,
+};
+use sak_rpc_interface::{
+    JsonResponse, METHOD_NOT_ALLOWED, RATE_LIMITED, UNAUTHORIZED,
+};
+use std::{
+    net::SocketAddr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const GET_BLOCK_LIST_BODY: &'static str = r#"{
+    "jsonrpc": "2.0",
+    "method": "get_block_list",
+    "params": { "from_height": null, "limit": 1 },
+    "id": 1
+}"#;
+
+async fn send_request(
+    rpc_socket_addr: SocketAddr,
+    body: &str,
+    headers: Vec<(&str, String)>,
+) -> (StatusCode, HeaderMap, JsonResponse<serde_json::Value>) {
+    let uri: Uri = {
+        let u = format!("http://localhost:{}", rpc_socket_addr.port());
+
+        u.parse().expect("URI should be made")
+    };
+
+    let mut builder = Request::builder().method(Method::POST).uri(uri);
+
+    for (k, v) in headers {
+        builder = builder.header(k, v);
+    }
+
+    let req = builder
+        .body(Body::from(body.to_string()))
+        .expect("request builder should be made");
+
+    let resp = Client::new().request(req).await.unwrap();
+
+    let status = resp.status();
+    let resp_headers = resp.headers().clone();
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    let json_response = serde_json::from_slice(&b).unwrap();
+
+    (status, resp_headers, json_response)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_bearer_auth() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let rpc_auth = RPCAuth::Bearer {
+        token: "test_token".to_string(),
+    };
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(
+            Some(rpc_auth),
+            MethodFilter::default(),
+            None,
+        )
+        .await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let cases = vec![
+        (vec![], Some(UNAUTHORIZED)),
+        (
+            vec![(header::AUTHORIZATION.as_str(), "Bearer wrong".to_string())],
+            Some(UNAUTHORIZED),
+        ),
+        (
+            vec![(
+                header::AUTHORIZATION.as_str(),
+                "Bearer test_token".to_string(),
+            )],
+            None,
+        ),
+    ];
+
+    for (headers, code) in cases {
+        let (status, _, json_response) =
+            send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, headers).await;
+
+        match code {
+            Some(c) => {
+                assert_eq!(status, StatusCode::UNAUTHORIZED);
+                assert_eq!(json_response.error.unwrap().code, c);
+            }
+            None => {
+                assert_eq!(status, StatusCode::OK);
+                assert!(json_response.result.is_some());
+            }
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_hmac_auth() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let secret = b"test_secret".to_vec();
+
+    let rpc_auth = RPCAuth::Hmac {
+        secret: secret.clone(),
+    };
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(
+            Some(rpc_auth),
+            MethodFilter::default(),
+            None,
+        )
+        .await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let sig = hyper_rpc_router::make_hmac_signature(
+        &secret,
+        now,
+        GET_BLOCK_LIST_BODY.as_bytes(),
+    );
+
+    let stale_sig = hyper_rpc_router::make_hmac_signature(
+        &secret,
+        now - 3600,
+        GET_BLOCK_LIST_BODY.as_bytes(),
+    );
+
+    let wrong_sig = hyper_rpc_router::make_hmac_signature(
+        b"wrong_secret",
+        now,
+        GET_BLOCK_LIST_BODY.as_bytes(),
+    );
+
+    let cases = vec![
+        (now, sig, true),
+        (now - 3600, stale_sig, false),
+        (now, wrong_sig, false),
+    ];
+
+    for (timestamp, sig, is_valid) in cases {
+        let headers = vec![
+            (HMAC_TIMESTAMP_HEADER, timestamp.to_string()),
+            (HMAC_SIGNATURE_HEADER, sig),
+        ];
+
+        let (status, _, json_response) =
+            send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, headers).await;
+
+        if is_valid {
+            assert_eq!(status, StatusCode::OK);
+            assert!(json_response.result.is_some());
+        } else {
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+            assert_eq!(json_response.error.unwrap().code, UNAUTHORIZED);
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_method_filter() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let method_filter =
+        MethodFilter::new(None, Some(vec!["get_block_list".to_string()]));
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(None, method_filter, None).await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let (status, _, json_response) =
+        send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, vec![]).await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(json_response.error.unwrap().code, METHOD_NOT_ALLOWED);
+
+    let (status, _, json_response) = send_request(
+        rpc_socket_addr,
+        r#"{ "jsonrpc": "2.0", "method": "get_status", "id": 2 }"#,
+        vec![],
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(json_response.error.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_rate_limit() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let rate_limit = RateLimit {
+        requests_per_sec: 1,
+        burst: 2,
+    };
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(
+            None,
+            MethodFilter::default(),
+            Some(rate_limit),
+        )
+        .await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    for _ in 0..2 {
+        let (status, _, _) =
+            send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, vec![]).await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, headers, json_response) =
+        send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, vec![]).await;
+
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(json_response.error.unwrap().code, RATE_LIMITED);
+    assert!(headers.contains_key(header::RETRY_AFTER));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_rate_limit_charges_each_entry_of_a_batch() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let rate_limit = RateLimit {
+        requests_per_sec: 1,
+        burst: 3,
+    };
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(
+            None,
+            MethodFilter::default(),
+            Some(rate_limit),
+        )
+        .await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let batch: Vec<serde_json::Value> = (0..5)
+        .map(|idx| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "get_status",
+                "id": idx,
+            })
+        })
+        .collect();
+
+    let uri: Uri = format!("http://localhost:{}", rpc_socket_addr.port())
+        .parse()
+        .expect("URI should be made");
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .body(Body::from(serde_json::Value::Array(batch).to_string()))
+        .expect("request builder should be made");
+
+    let resp = Client::new().request(req).await.unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    let json_responses: Vec<JsonResponse<serde_json::Value>> =
+        serde_json::from_slice(&b).unwrap();
+
+    assert_eq!(json_responses.len(), 5);
+
+    for (idx, json_response) in json_responses.into_iter().enumerate() {
+        if idx < 3 {
+            assert!(json_response.result.is_some());
+        } else {
+            assert_eq!(json_response.error.unwrap().code, RATE_LIMITED);
+        }
+    }
+
+    let (status, _, json_response) =
+        send_request(rpc_socket_addr, GET_BLOCK_LIST_BODY, vec![]).await;
+
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(json_response.error.unwrap().code, RATE_LIMITED);
+}
@@ -47,17 +47,18 @@ async fn test_call_contract() {
             ctr_call_type: CtrCallType::Query,
         };
 
-        let call_ctr_req = QueryCtrRequest { ctr_addr, req };
-        let params = serde_json::to_string(&call_ctr_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let call_ctr_req = QueryCtrRequest {
+            ctr_addr,
+            req,
+            block_height: Some(0),
+        };
+        let params = serde_json::to_value(&call_ctr_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "query_ctr".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -143,13 +144,13 @@ async fn test_call_contract() {
 //             tc_dummy.merkle_rt,
 //         );
 
-//         let params = serde_json::to_vec(&send_req).unwrap();
+//         let params = serde_json::to_value(&send_req).unwrap();
 
 //         let json_request = JsonRequest {
 //             jsonrpc: "2.0".to_string(),
 //             method: "send_pour_tx".to_string(),
 //             params: Some(params),
-//             id: "test_1".to_string(),
+//             id: "test_1".into(),
 //         };
 
 //         let str = serde_json::to_string(&json_request).unwrap();
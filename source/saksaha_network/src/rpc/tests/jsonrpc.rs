@@ -0,0 +1,217 @@
+use super::utils;
+use crate::{rpc::routes::v0::GetBlockListResponse, tests::TestUtil};
+use hyper::{body::Bytes, Body, Client, Method, Request, StatusCode, Uri};
+use hyper_rpc_router::DEFAULT_MAX_BATCH_SIZE;
+use sak_rpc_interface::{
+    JsonRPCId, JsonResponse, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND,
+    PARSE_ERROR,
+};
+use std::net::SocketAddr;
+
+async fn send_raw_body(
+    rpc_socket_addr: SocketAddr,
+    body: &str,
+) -> (StatusCode, Bytes) {
+    let uri: Uri = {
+        let u = format!("http://localhost:{}", rpc_socket_addr.port());
+
+        u.parse().expect("URI should be made")
+    };
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .body(Body::from(body.to_string()))
+        .expect("request builder should be made");
+
+    let resp = Client::new().request(req).await.unwrap();
+
+    let status = resp.status();
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    (status, b)
+}
+
+async fn send_raw_request(
+    rpc_socket_addr: SocketAddr,
+    body: &str,
+) -> (StatusCode, JsonResponse<serde_json::Value>) {
+    let (status, b) = send_raw_body(rpc_socket_addr, body).await;
+
+    let json_response = serde_json::from_slice(&b).unwrap();
+
+    (status, json_response)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_params_as_json_value_and_numeric_id() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let (status, json_response) = send_raw_request(
+        rpc_socket_addr,
+        r#"{
+            "jsonrpc": "2.0",
+            "method": "get_block_list",
+            "params": { "from_height": null, "limit": 1 },
+            "id": 7
+        }"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json_response.id, JsonRPCId::from(7));
+
+    let result: GetBlockListResponse =
+        serde_json::from_value(json_response.result.unwrap()).unwrap();
+
+    assert!(!result.block_list.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_standard_error_codes() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let cases = vec![
+        (
+            r#"{ "jsonrpc": "2.0", "method": "#,
+            PARSE_ERROR,
+            JsonRPCId::Null,
+        ),
+        (
+            r#"{ "jsonrpc": "2.0", "params": {}, "id": "a" }"#,
+            INVALID_REQUEST,
+            JsonRPCId::from("a"),
+        ),
+        (
+            r#"{ "jsonrpc": "2.0", "method": "get_block", "params": 1,
+                "id": 1 }"#,
+            INVALID_REQUEST,
+            JsonRPCId::from(1),
+        ),
+        (
+            r#"{ "jsonrpc": "2.0", "method": "no_such_method", "id": 2 }"#,
+            METHOD_NOT_FOUND,
+            JsonRPCId::from(2),
+        ),
+        (
+            r#"{ "jsonrpc": "2.0", "method": "get_block",
+                "params": { "block_hash": 3 }, "id": null }"#,
+            INVALID_PARAMS,
+            JsonRPCId::Null,
+        ),
+    ];
+
+    for (body, code, id) in cases {
+        let (_, json_response) = send_raw_request(rpc_socket_addr, body).await;
+
+        assert_eq!(json_response.error.unwrap().code, code);
+        assert_eq!(json_response.id, id);
+        assert!(json_response.result.is_none());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_batch_request() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let (status, b) = send_raw_body(
+        rpc_socket_addr,
+        r#"[
+            { "jsonrpc": "2.0", "method": "get_block_list",
+                "params": { "from_height": null, "limit": 1 }, "id": 1 },
+            { "jsonrpc": "2.0", "method": "get_block_list",
+                "params": { "from_height": null, "limit": 1 } },
+            { "jsonrpc": "2.0", "method": "no_such_method", "id": "b" },
+            1
+        ]"#,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+
+    let json_responses: Vec<JsonResponse<serde_json::Value>> =
+        serde_json::from_slice(&b).unwrap();
+
+    // The notification gets no response, the rest keep their order
+    assert_eq!(json_responses.len(), 3);
+
+    assert_eq!(json_responses[0].id, JsonRPCId::from(1));
+    assert!(json_responses[0].result.is_some());
+
+    assert_eq!(json_responses[1].id, JsonRPCId::from("b"));
+    assert_eq!(
+        json_responses[1].error.as_ref().unwrap().code,
+        METHOD_NOT_FOUND
+    );
+
+    assert_eq!(json_responses[2].id, JsonRPCId::Null);
+    assert_eq!(
+        json_responses[2].error.as_ref().unwrap().code,
+        INVALID_REQUEST
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_batch_request_invalid() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let too_large = {
+        let req = r#"{ "jsonrpc": "2.0", "method": "get_status", "id": 1 }"#;
+        let reqs = vec![req; DEFAULT_MAX_BATCH_SIZE + 1];
+
+        format!("[{}]", reqs.join(","))
+    };
+
+    for body in vec!["[]".to_string(), too_large] {
+        let (status, json_response) =
+            send_raw_request(rpc_socket_addr, &body).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json_response.error.unwrap().code, INVALID_REQUEST);
+        assert_eq!(json_response.id, JsonRPCId::Null);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_notification_gets_no_response() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let bodies = vec![
+        r#"{ "jsonrpc": "2.0", "method": "get_block_list",
+            "params": { "from_height": null, "limit": 1 } }"#,
+        r#"[{ "jsonrpc": "2.0", "method": "no_such_method" }]"#,
+    ];
+
+    for body in bodies {
+        let (status, b) = send_raw_body(rpc_socket_addr, body).await;
+
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(b.is_empty());
+    }
+}
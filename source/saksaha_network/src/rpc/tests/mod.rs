@@ -1,6 +1,9 @@
+mod access;
 mod block;
 mod contract;
+mod jsonrpc;
 mod proof;
 mod status;
+mod subscription;
 mod tx;
 mod utils;
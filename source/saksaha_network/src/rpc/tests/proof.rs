@@ -69,16 +69,13 @@ async fn test_rpc_client_handle_get_cm_idx() {
     let body = {
         let send_req = GetCmIdxRequest { cm: cms[0] };
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_cm_idx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
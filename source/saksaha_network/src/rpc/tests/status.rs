@@ -1,5 +1,8 @@
 use super::utils;
-use crate::{rpc::routes::v0::GetNodeStatusResponse, tests::TestUtil};
+use crate::{
+    rpc::routes::v0::{GetBanListResponse, GetNodeStatusResponse},
+    tests::TestUtil,
+};
 use hyper::{Body, Client, Method, Request, Uri};
 use sak_rpc_interface::{JsonRequest, JsonResponse};
 
@@ -28,7 +31,7 @@ async fn test_rpc_client_request_correct_status() {
             jsonrpc: "2.0".to_string(),
             method: "get_status".to_string(),
             params: None,
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -58,3 +61,55 @@ async fn test_rpc_client_request_correct_status() {
 
     // assert_eq!(&expected_tx_hash, tx_hash_from_res);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_client_request_ban_list_of_fresh_node() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    let client = Client::new();
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let uri: Uri = {
+        let u = format!(
+            "http://localhost:{}/apis/v0/get_ban_list",
+            rpc_socket_addr.port()
+        );
+
+        u.parse().expect("URI should be made")
+    };
+
+    let body = {
+        let json_request = JsonRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "get_ban_list".to_string(),
+            params: None,
+            id: "test_1".into(),
+        };
+
+        let str = serde_json::to_string(&json_request).unwrap();
+
+        Body::from(str)
+    };
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .body(body)
+        .expect("request builder should be made");
+
+    let resp = client.request(req).await.unwrap();
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    let json_response =
+        serde_json::from_slice::<JsonResponse<GetBanListResponse>>(&b)
+            .expect("Response should be parsed");
+
+    let result = json_response.result.expect("Ban list should be returned");
+
+    assert!(result.ban_list.is_empty());
+}
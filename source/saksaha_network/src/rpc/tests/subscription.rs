@@ -0,0 +1,221 @@
+use super::utils;
+use crate::blockchain::VALIDATOR_CTR_ADDR;
+use crate::rpc::ws::{
+    CtrStateResult, NewBlockResult, PendingTxResult, SubscriptionNotification,
+    SubscriptionTopic, UnsubscribeRequest,
+};
+use crate::tests::TestUtil;
+use futures::{SinkExt, StreamExt};
+use hyper_rpc_router::MethodFilter;
+use sak_dist_ledger::DistLedgerEvent;
+use sak_rpc_interface::{
+    JsonRPCId, JsonRequest, JsonResponse, METHOD_NOT_ALLOWED,
+};
+use serde_json::Value;
+use std::{collections::HashMap, time::Duration};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::Message, MaybeTlsStream, WebSocketStream,
+};
+
+type TestWsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn send_request(
+    ws: &mut TestWsStream,
+    method: &str,
+    params: Value,
+    id: u64,
+) -> JsonResponse<Value> {
+    let json_request = JsonRequest {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params: Some(params),
+        id: id.into(),
+    };
+
+    let msg = serde_json::to_string(&json_request).unwrap();
+
+    ws.send(Message::Text(msg)).await.unwrap();
+
+    let resp = read_text(ws).await;
+
+    let json_response: JsonResponse<Value> =
+        serde_json::from_str(&resp).unwrap();
+
+    assert_eq!(json_response.id, JsonRPCId::from(id));
+
+    json_response
+}
+
+async fn read_text(ws: &mut TestWsStream) -> String {
+    let msg = tokio::time::timeout(Duration::from_secs(5), ws.next())
+        .await
+        .expect("message should arrive in time")
+        .unwrap()
+        .unwrap();
+
+    match msg {
+        Message::Text(t) => t,
+        m => panic!("Expected a text message, msg: {:?}", m),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_subscribe_to_ledger_events() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let url = format!("ws://localhost:{}", rpc_socket_addr.port());
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    let topics = vec![
+        SubscriptionTopic::NewBlocks,
+        SubscriptionTopic::PendingTxs,
+        SubscriptionTopic::CtrState {
+            ctr_addr: VALIDATOR_CTR_ADDR.to_string(),
+        },
+    ];
+
+    let mut sub_ids = vec![];
+
+    for (idx, topic) in topics.iter().enumerate() {
+        let params = serde_json::to_value(topic).unwrap();
+
+        let json_response =
+            send_request(&mut ws, "subscribe", params, idx as u64).await;
+
+        let sub_id: String =
+            serde_json::from_value(json_response.result.unwrap()).unwrap();
+
+        sub_ids.push(sub_id);
+    }
+
+    let genesis_block = machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .get_block_by_height(&0)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let genesis_block_hash = genesis_block.get_block_hash().to_string();
+
+    {
+        let ledger_event_tx =
+            machine.blockchain.dist_ledger.ledger_event_tx.read().await;
+
+        ledger_event_tx
+            .send(DistLedgerEvent::NewBlocks(vec![(
+                0,
+                genesis_block_hash.clone(),
+            )]))
+            .unwrap();
+
+        ledger_event_tx
+            .send(DistLedgerEvent::TxPoolStat(vec!["tx_hash_1".to_string()]))
+            .unwrap();
+    }
+
+    let mut results = HashMap::new();
+
+    for _ in 0..3 {
+        let notification: SubscriptionNotification =
+            serde_json::from_str(&read_text(&mut ws).await).unwrap();
+
+        assert_eq!(notification.method, "subscription");
+
+        results.insert(notification.params.sub_id, notification.params.result);
+    }
+
+    let new_block: NewBlockResult =
+        serde_json::from_value(results.remove(&sub_ids[0]).unwrap()).unwrap();
+
+    assert_eq!(new_block.block.get_block_hash(), &genesis_block_hash);
+
+    let pending_tx: PendingTxResult =
+        serde_json::from_value(results.remove(&sub_ids[1]).unwrap()).unwrap();
+
+    assert_eq!(pending_tx.tx_hash, "tx_hash_1");
+
+    let ctr_state: CtrStateResult =
+        serde_json::from_value(results.remove(&sub_ids[2]).unwrap()).unwrap();
+
+    assert_eq!(ctr_state.ctr_addr, VALIDATOR_CTR_ADDR);
+    assert_eq!(ctr_state.block_hash, genesis_block_hash);
+    assert_eq!(ctr_state.tx_hashes.len(), 1);
+    assert!(ctr_state.ctr_state.is_some());
+
+    for (is_removed, id) in vec![(true, 3), (false, 4)] {
+        let params = serde_json::to_value(UnsubscribeRequest {
+            sub_id: sub_ids[0].clone(),
+        })
+        .unwrap();
+
+        let json_response =
+            send_request(&mut ws, "unsubscribe", params, id).await;
+
+        assert_eq!(json_response.result, Some(Value::Bool(is_removed)));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_subscribe_invalid_topic() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let url = format!("ws://localhost:{}", rpc_socket_addr.port());
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    let params = serde_json::json!({ "topic": "no_such_topic" });
+
+    let json_response = send_request(&mut ws, "subscribe", params, 1).await;
+
+    assert_eq!(
+        json_response.error.unwrap().code,
+        sak_rpc_interface::INVALID_PARAMS
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_ws_subscribe_is_subject_to_the_method_filter() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let method_filter =
+        MethodFilter::new(None, Some(vec!["subscribe".to_string()]));
+
+    let (rpc, rpc_socket_addr, _machine) =
+        utils::make_test_context_with_access(None, method_filter, None).await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let url = format!("ws://localhost:{}", rpc_socket_addr.port());
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+    let params = serde_json::to_value(SubscriptionTopic::NewBlocks).unwrap();
+
+    let json_response = send_request(&mut ws, "subscribe", params, 1).await;
+
+    assert_eq!(json_response.error.unwrap().code, METHOD_NOT_ALLOWED);
+
+    let params = serde_json::to_value(UnsubscribeRequest {
+        sub_id: "0".to_string(),
+    })
+    .unwrap();
+
+    let json_response = send_request(&mut ws, "unsubscribe", params, 2).await;
+
+    assert!(json_response.error.is_none());
+}
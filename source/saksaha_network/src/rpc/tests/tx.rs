@@ -1,13 +1,49 @@
 use super::utils;
 use crate::{
+    blockchain::VALIDATOR_CTR_ADDR,
     rpc::routes::v0::{GetTxRequest, SendMintTxRequest, SendPourTxRequest},
     tests::TestUtil,
 };
 use hyper::{Body, Client, Method, Request, Uri};
-use sak_rpc_interface::{JsonRequest, JsonResponse};
+use sak_crypto::SakKey;
+use sak_dist_ledger::{TxIndexPage, TxLocation, TxSimulation};
+use sak_rpc_interface::{JsonRequest, JsonResponse, LEDGER_PRUNED};
 use sak_types::{
     BlockCandidate, MintTxCandidate, PourTxCandidate, Tx, TxCandidate,
 };
+use serde_json::{json, Value};
+use std::{net::SocketAddr, time::Duration};
+
+async fn send_request(
+    rpc_socket_addr: SocketAddr,
+    method: &str,
+    params: Value,
+) -> JsonResponse<Value> {
+    let uri: Uri = {
+        let u = format!("http://localhost:{}", rpc_socket_addr.port());
+
+        u.parse().expect("URI should be made")
+    };
+
+    let json_request = JsonRequest {
+        jsonrpc: "2.0".to_string(),
+        method: method.to_string(),
+        params: Some(params),
+        id: "test_1".into(),
+    };
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .body(Body::from(serde_json::to_string(&json_request).unwrap()))
+        .expect("request builder should be made");
+
+    let resp = Client::new().request(req).await.unwrap();
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    serde_json::from_slice(&b).unwrap()
+}
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_rpc_client_request_correct_get_tx() {
@@ -70,16 +106,13 @@ async fn test_rpc_client_request_correct_get_tx() {
             hash: expected_tx_hash.clone(),
         };
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -168,16 +201,13 @@ async fn test_rpc_client_request_wrong_get_tx() {
             hash: false_tx_hash,
         };
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -235,16 +265,13 @@ async fn test_rpc_reqeust_correct_send_pour_tx() {
             tc_dummy.merkle_rt,
         );
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -301,16 +328,13 @@ async fn test_rpc_reqeust_wrong_send_pour_tx() {
     let body = {
         let send_req = String::from("False request");
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -339,7 +363,9 @@ async fn test_rpc_reqeust_correct_send_mint_tx() {
     sak_test_utils::init_test_log();
     TestUtil::init_test(vec!["test"]);
 
-    let tc_dummy = MintTxCandidate::new_dummy_2();
+    let tc_dummy = MintTxCandidate::new_dummy_2()
+        .sign(&utils::make_validator_secret_key());
+
     let expected_tc_hash = tc_dummy.get_tx_hash().clone();
 
     let (rpc, rpc_socket_addr, machine) = utils::make_test_context().await;
@@ -368,16 +394,13 @@ async fn test_rpc_reqeust_correct_send_mint_tx() {
             tc_dummy.s,
         );
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_mint_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -434,16 +457,13 @@ async fn test_rpc_reqeust_wrong_send_mint_tx() {
     let body = {
         let send_req = String::from("False request");
 
-        let params = serde_json::to_string(&send_req)
-            .unwrap()
-            .as_bytes()
-            .to_vec();
+        let params = serde_json::to_value(&send_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_mint_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -466,3 +486,270 @@ async fn test_rpc_reqeust_wrong_send_mint_tx() {
 
     assert!(json_response.result == None);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_reqeust_unauthorized_send_mint_tx() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    // Not signed by the mint authority
+    let tc_dummy = {
+        let (secret_key, _) = SakKey::generate();
+
+        MintTxCandidate::new_dummy_2().sign(&secret_key)
+    };
+
+    let (rpc, rpc_socket_addr, machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let client = Client::new();
+
+    let uri: Uri = {
+        let u = format!(
+            "http://localhost:{}/apis/v0/send_mint_tx",
+            rpc_socket_addr.port()
+        );
+        u.parse().expect("URI should be made")
+    };
+
+    let body = {
+        let send_req = SendMintTxRequest::new(
+            tc_dummy.created_at.clone(),
+            tc_dummy.data.clone(),
+            tc_dummy.author_sig.clone(),
+            Some(tc_dummy.ctr_addr.clone()),
+            tc_dummy.cm_1,
+            tc_dummy.v,
+            tc_dummy.k,
+            tc_dummy.s,
+        );
+
+        let params = serde_json::to_value(&send_req).unwrap();
+
+        let json_request = JsonRequest {
+            jsonrpc: "2.0".to_string(),
+            method: "send_mint_tx".to_string(),
+            params: Some(params),
+            id: "test_1".into(),
+        };
+
+        let str = serde_json::to_string(&json_request).unwrap();
+
+        Body::from(str)
+    };
+
+    let req = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .body(body)
+        .expect("request builder should be made");
+
+    let resp = client.request(req).await.unwrap();
+
+    let b = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+    let json_response =
+        serde_json::from_slice::<JsonResponse<String>>(&b).unwrap();
+
+    assert!(json_response.result == None);
+
+    let is_contain = machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .tx_pool_contains(tc_dummy.get_tx_hash())
+        .await;
+
+    assert_eq!(false, is_contain);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_request_tx_indexes() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, _machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "get_txs_by_block",
+        json!({ "block_height": 0, "limit": 3 }),
+    )
+    .await;
+
+    let page: TxIndexPage =
+        serde_json::from_value(json_response.result.unwrap()).unwrap();
+
+    assert_eq!(page.tx_hashes.len(), 3);
+    assert_eq!(
+        page.next,
+        Some(TxLocation {
+            block_height: 0,
+            tx_pos: 3
+        })
+    );
+
+    let deploy_tx_hash = page.tx_hashes[2].tx_hash.clone();
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "get_ctr_call_history",
+        json!({ "ctr_addr": VALIDATOR_CTR_ADDR }),
+    )
+    .await;
+
+    let page: TxIndexPage =
+        serde_json::from_value(json_response.result.unwrap()).unwrap();
+
+    assert_eq!(page.tx_hashes.len(), 1);
+    assert_eq!(page.tx_hashes[0].tx_hash, deploy_tx_hash);
+    assert_eq!(page.next, None);
+
+    let mint_tc = sak_types::mock_mint_tc_3();
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "get_tx_by_cm",
+        json!({ "cm": mint_tc.get_cms()[0] }),
+    )
+    .await;
+
+    let tx: Tx =
+        serde_json::from_value(json_response.result.unwrap()["tx"].clone())
+            .unwrap();
+
+    assert_eq!(tx.get_tx_hash(), mint_tc.get_tx_hash());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_request_simulate_tx() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, machine) = utils::make_test_context().await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let tc_dummy = PourTxCandidate::new_dummy_m1_to_p3_p4();
+    let expected_tc_hash = tc_dummy.get_tx_hash().clone();
+
+    let mut params = serde_json::to_value(&SendPourTxRequest::new(
+        tc_dummy.created_at,
+        tc_dummy.data,
+        tc_dummy.author_sig,
+        Some(tc_dummy.ctr_addr),
+        tc_dummy.pi,
+        tc_dummy.sn_1,
+        tc_dummy.cm_1,
+        tc_dummy.cm_2,
+        tc_dummy.merkle_rt,
+    ))
+    .unwrap();
+
+    params["tx_type"] = json!("pour");
+
+    let json_response =
+        send_request(rpc_socket_addr, "simulate_tx", params).await;
+
+    let simulation: TxSimulation =
+        serde_json::from_value(json_response.result.unwrap()).unwrap();
+
+    assert_eq!(simulation.tx_hash, expected_tc_hash);
+    assert!(simulation.next_merkle_rt.is_some());
+
+    let is_contain = machine
+        .blockchain
+        .dist_ledger
+        .apis
+        .tx_pool_contains(&expected_tc_hash)
+        .await;
+
+    assert!(!is_contain);
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "simulate_tx",
+        json!({ "tx_type": "unknown" }),
+    )
+    .await;
+
+    assert!(json_response.error.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rpc_request_pruned_tx_has_its_own_error_code() {
+    sak_test_utils::init_test_log();
+    TestUtil::init_test(vec!["test"]);
+
+    let (rpc, rpc_socket_addr, machine) =
+        utils::make_test_context_with_pruning(1).await;
+
+    tokio::spawn(async move { rpc.run().await });
+
+    let dist_ledger = &machine.blockchain.dist_ledger;
+
+    let mut tx_hashes = vec![];
+
+    for i in 1..3 as u8 {
+        let tc = sak_types::new_dummy_valid_pour(
+            vec![i, 1],
+            [i + 10; 32],
+            [i + 20; 32],
+            [i + 30; 32],
+            [0; 32],
+        );
+
+        tx_hashes.push(tc.get_tx_hash().clone());
+
+        let bc = BlockCandidate {
+            validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
+            tx_candidates: vec![tc],
+            witness_sigs: vec![String::from("1")],
+            created_at: format!("{}", i),
+        };
+
+        dist_ledger
+            .apis
+            .write_block(Some(bc))
+            .await
+            .expect("Block should be written");
+    }
+
+    // Blocks up to the height of 1 are pruned as soon as the runtime starts
+    dist_ledger.run().await;
+
+    let mut is_pruned = false;
+
+    for _ in 0..50 {
+        if dist_ledger.apis.get_tx(&tx_hashes[0]).await.is_err() {
+            is_pruned = true;
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    assert!(is_pruned, "Tx of the block at 1 should have been pruned");
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "get_tx",
+        json!({ "hash": tx_hashes[0] }),
+    )
+    .await;
+
+    assert_eq!(json_response.error.unwrap().code, LEDGER_PRUNED);
+
+    let json_response = send_request(
+        rpc_socket_addr,
+        "get_tx",
+        json!({ "hash": tx_hashes[1] }),
+    )
+    .await;
+
+    assert!(json_response.error.is_none());
+    assert!(json_response.result.unwrap()["tx"].is_object());
+}
@@ -3,14 +3,47 @@ use crate::rpc::{RPCArgs, RPC};
 use crate::system::SystemHandle;
 use crate::{blockchain::Blockchain, machine::Machine};
 use colored::*;
+use hyper_rpc_router::{MethodFilter, RPCAuth, RateLimit};
 use log::info;
-use sak_p2p_id::Identity;
+use sak_crypto::SecretKey;
+use sak_p2p_id::{Credential, Identity};
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use sak_types::{BlockCandidate, Tx, TxCandidate};
 use std::net::SocketAddr;
 use std::sync::Arc;
 
 pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
+    make_test_context_with_access(None, MethodFilter::default(), None).await
+}
+
+pub(crate) async fn make_test_context_with_access(
+    rpc_auth: Option<RPCAuth>,
+    method_filter: MethodFilter,
+    rate_limit: Option<RateLimit>,
+) -> (RPC, SocketAddr, Arc<Machine>) {
+    make_test_context_with_args(rpc_auth, method_filter, rate_limit, None).await
+}
+
+// Ledger of the context prunes once its runtime is run
+pub(crate) async fn make_test_context_with_pruning(
+    prune_keep_blocks: u128,
+) -> (RPC, SocketAddr, Arc<Machine>) {
+    make_test_context_with_args(
+        None,
+        MethodFilter::default(),
+        None,
+        Some(prune_keep_blocks),
+    )
+    .await
+}
+
+async fn make_test_context_with_args(
+    rpc_auth: Option<RPCAuth>,
+    method_filter: MethodFilter,
+    rate_limit: Option<RateLimit>,
+    prune_keep_blocks: Option<u128>,
+) -> (RPC, SocketAddr, Arc<Machine>) {
     let (disc_socket, disc_port) = {
         let (socket, socket_addr) =
             sak_utils_net::setup_udp_socket(None).await.unwrap();
@@ -48,9 +81,26 @@ pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
     };
 
     let blockchain = {
-        Blockchain::init("test".to_string(), None, None, None, identity.clone())
-            .await
-            .unwrap()
+        Blockchain::init(
+            "test".to_string(),
+            None,
+            None,
+            None,
+            true,
+            prune_keep_blocks,
+            identity.clone(),
+        )
+        .await
+        .unwrap()
+    };
+
+    let protocol_info = {
+        let genesis_block_hash =
+            blockchain.get_genesis_block_hash().await.unwrap();
+
+        let p = ProtocolInfo::new(String::from("test"), genesis_block_hash);
+
+        Arc::new(p)
     };
 
     let machine = {
@@ -60,7 +110,7 @@ pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
     };
 
     let p2p_peer_table = {
-        let ps = PeerTable::init(None)
+        let ps = PeerTable::init(None, None, None)
             .await
             .expect("Peer table should be initialized");
 
@@ -87,8 +137,11 @@ pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
             p2p_max_conn_count: None,
             p2p_port: p2p_socket_addr.port(),
             bootstrap_addrs: vec![],
+            addr_book_path: None,
+            external_addr: None,
             identity: identity.clone(),
             peer_table: p2p_peer_table,
+            protocol_info,
         };
 
         let p = P2PHost::init(p2p_host_args)
@@ -115,6 +168,10 @@ pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
         let rpc_args = RPCArgs {
             sys_handle,
             rpc_socket,
+            max_batch_size: None,
+            rpc_auth,
+            method_filter,
+            rate_limit,
         };
 
         RPC::init(rpc_args).expect("RPC should be initialized")
@@ -123,6 +180,25 @@ pub(crate) async fn make_test_context() -> (RPC, SocketAddr, Arc<Machine>) {
     (rpc, rpc_socket_addr, machine)
 }
 
+// Key of 'dev_local_1', the validator set in the genesis block
+pub(crate) fn make_validator_secret_key() -> SecretKey {
+    let secret = String::from(
+        "7297b903877a957748b74068d63d6d5661481975240\
+        99fc1df5cd9e8814c66c7",
+    );
+
+    let public_key_str = String::from(
+        "045739d074b8722891c307e8e75c9607e0b55a80778\
+        b42ef5f4640d4949dbf3992f6083b729baef9e9545c4\
+        e95590616fd382662a09653f2a966ff524989ae8c0f",
+    );
+
+    let credential = Credential::new(&secret, &public_key_str)
+        .expect("credential should be made");
+
+    credential.secret_key
+}
+
 pub fn make_dummy_tx_pour_block() -> BlockCandidate {
     let tx_pour_block = BlockCandidate {
         validator_sig: String::from("Ox6a03c8sbfaf3cb06"),
@@ -175,6 +251,8 @@ pub(crate) async fn make_blockchain() -> Blockchain {
         None,
         None,
         None,
+        true,
+        None,
         identity.clone(),
     )
     .await
@@ -0,0 +1,5 @@
+mod session;
+mod subscription;
+
+pub(in crate::rpc) use session::*;
+pub(in crate::rpc) use subscription::*;
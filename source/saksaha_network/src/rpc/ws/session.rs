@@ -0,0 +1,268 @@
+use super::{Subscription, SubscriptionTopic, UnsubscribeRequest};
+use crate::system::SystemHandle;
+use futures::{SinkExt, StreamExt};
+use hyper_rpc_router::MethodFilter;
+use hyper_server::{CloseCode, CloseFrame, WsMessage, WsStream};
+use log::{debug, warn};
+use sak_rpc_interface::{
+    JsonRPCError, JsonRPCId, JsonRequest, JsonResponse, APPLICATION_ERROR,
+    INVALID_PARAMS, INVALID_REQUEST, JSON_RPC_2, METHOD_NOT_ALLOWED,
+    METHOD_NOT_FOUND, PARSE_ERROR,
+};
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc};
+use tokio::{sync::mpsc, task::JoinHandle};
+
+pub(in crate::rpc) const SUBSCRIBE_METHOD: &'static str = "subscribe";
+
+pub(in crate::rpc) const UNSUBSCRIBE_METHOD: &'static str = "unsubscribe";
+
+pub(in crate::rpc) const OUTGOING_QUEUE_CAPACITY: usize = 256;
+
+pub(in crate::rpc) const MAX_SUBSCRIPTIONS_PER_CONN: usize = 32;
+
+struct Session {
+    sys_handle: Arc<SystemHandle>,
+    method_filter: Arc<MethodFilter>,
+    subscriptions: HashMap<String, JoinHandle<()>>,
+    next_sub_id: u64,
+    out_tx: mpsc::Sender<String>,
+    overflow_tx: mpsc::Sender<String>,
+}
+
+pub(in crate::rpc) async fn run_session(
+    ws: WsStream,
+    sys_handle: Arc<SystemHandle>,
+    method_filter: Arc<MethodFilter>,
+) {
+    let (mut ws_tx, mut ws_rx) = ws.split();
+
+    let (out_tx, mut out_rx) = mpsc::channel(OUTGOING_QUEUE_CAPACITY);
+
+    let (overflow_tx, mut overflow_rx) = mpsc::channel(1);
+
+    let mut session = Session {
+        sys_handle,
+        method_filter,
+        subscriptions: HashMap::new(),
+        next_sub_id: 0,
+        out_tx,
+        overflow_tx,
+    };
+
+    loop {
+        tokio::select! {
+            msg = ws_rx.next() => {
+                let msg = match msg {
+                    Some(Ok(m)) => m,
+                    Some(Err(err)) => {
+                        warn!("Error reading websocket message, err: {}", err);
+
+                        break;
+                    }
+                    None => break,
+                };
+
+                let resp = match msg {
+                    WsMessage::Text(t) => session.handle_request(&t).await,
+                    WsMessage::Close(_) => break,
+                    // Pings are answered by the websocket stream itself
+                    _ => continue,
+                };
+
+                if let Err(err) = ws_tx.send(WsMessage::Text(resp)).await {
+                    warn!("Error writing websocket message, err: {}", err);
+
+                    break;
+                }
+            }
+            msg = out_rx.recv() => {
+                let msg = match msg {
+                    Some(m) => m,
+                    None => break,
+                };
+
+                if let Err(err) = ws_tx.send(WsMessage::Text(msg)).await {
+                    warn!("Error writing websocket message, err: {}", err);
+
+                    break;
+                }
+            }
+            sub_id = overflow_rx.recv() => {
+                warn!(
+                    "Websocket client is too slow, closing, sub_id: {:?}",
+                    sub_id,
+                );
+
+                let close_frame = CloseFrame {
+                    code: CloseCode::Policy,
+                    reason: "Client is too slow to receive notifications"
+                        .into(),
+                };
+
+                let _ = ws_tx.send(WsMessage::Close(Some(close_frame))).await;
+
+                break;
+            }
+        }
+    }
+
+    debug!(
+        "Websocket session has ended, subscription count: {}",
+        session.subscriptions.len()
+    );
+
+    for (_, handle) in session.subscriptions.drain() {
+        handle.abort();
+    }
+}
+
+impl Session {
+    async fn handle_request(&mut self, msg: &str) -> String {
+        let (id, result) = match serde_json::from_str::<JsonRequest>(msg) {
+            Ok(req) => {
+                let result = if req.jsonrpc != JSON_RPC_2 {
+                    Err(JsonRPCError::new(
+                        INVALID_REQUEST,
+                        format!("jsonrpc should be {}", JSON_RPC_2),
+                    ))
+                } else if !self.method_filter.is_allowed(&req.method) {
+                    Err(JsonRPCError::new(
+                        METHOD_NOT_ALLOWED,
+                        format!(
+                            "Method is not allowed, method: {}",
+                            req.method
+                        ),
+                    ))
+                } else {
+                    match req.method.as_str() {
+                        SUBSCRIBE_METHOD => self.subscribe(req.params).await,
+                        UNSUBSCRIBE_METHOD => self.unsubscribe(req.params),
+                        _ => Err(JsonRPCError::new(
+                            METHOD_NOT_FOUND,
+                            "Method not found".into(),
+                        )),
+                    }
+                };
+
+                (req.id, result)
+            }
+            Err(err) => (
+                JsonRPCId::Null,
+                Err(JsonRPCError::new(
+                    PARSE_ERROR,
+                    format!("Failed to parse as json_request, err: {}", err),
+                )),
+            ),
+        };
+
+        let (result, error) = match result {
+            Ok(r) => (Some(r), None),
+            Err(err) => (None, Some(err)),
+        };
+
+        let resp = JsonResponse {
+            jsonrpc: JSON_RPC_2.into(),
+            error,
+            result,
+            id,
+        };
+
+        match serde_json::to_string(&resp) {
+            Ok(s) => s,
+            Err(err) => serde_json::json!({
+                "jsonrpc": JSON_RPC_2,
+                "error": JsonRPCError::new(APPLICATION_ERROR, err.to_string()),
+                "id": resp.id,
+            })
+            .to_string(),
+        }
+    }
+
+    async fn subscribe(
+        &mut self,
+        params: Option<Value>,
+    ) -> Result<Value, JsonRPCError> {
+        let topic: SubscriptionTopic = parse_params(params)?;
+
+        if self.subscriptions.len() >= MAX_SUBSCRIPTIONS_PER_CONN {
+            return Err(JsonRPCError::new(
+                APPLICATION_ERROR,
+                format!(
+                    "Too many subscriptions, max: {}",
+                    MAX_SUBSCRIPTIONS_PER_CONN
+                ),
+            ));
+        }
+
+        // Subscribed before the id goes out so that no event is missed
+        let ledger_event_rx = self
+            .sys_handle
+            .machine
+            .blockchain
+            .dist_ledger
+            .ledger_event_tx
+            .read()
+            .await
+            .subscribe();
+
+        let sub_id = format!("{:#x}", self.next_sub_id);
+        self.next_sub_id += 1;
+
+        let subscription = Subscription {
+            sub_id: sub_id.clone(),
+            topic,
+            ledger_event_rx,
+            sys_handle: self.sys_handle.clone(),
+            out_tx: self.out_tx.clone(),
+            overflow_tx: self.overflow_tx.clone(),
+        };
+
+        let handle = tokio::spawn(async move {
+            subscription.run().await;
+        });
+
+        self.subscriptions.insert(sub_id.clone(), handle);
+
+        Ok(Value::String(sub_id))
+    }
+
+    fn unsubscribe(
+        &mut self,
+        params: Option<Value>,
+    ) -> Result<Value, JsonRPCError> {
+        let req: UnsubscribeRequest = parse_params(params)?;
+
+        let is_removed = match self.subscriptions.remove(&req.sub_id) {
+            Some(handle) => {
+                handle.abort();
+
+                true
+            }
+            None => false,
+        };
+
+        Ok(Value::Bool(is_removed))
+    }
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(
+    params: Option<Value>,
+) -> Result<T, JsonRPCError> {
+    let params = match params {
+        Some(p) => p,
+        None => {
+            return Err(JsonRPCError::new(
+                INVALID_PARAMS,
+                "Request should contain params".into(),
+            ));
+        }
+    };
+
+    serde_json::from_value(params).map_err(|err| {
+        JsonRPCError::new(
+            INVALID_PARAMS,
+            format!("Cannot deserialize params, err: {}", err),
+        )
+    })
+}
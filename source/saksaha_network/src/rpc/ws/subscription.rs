@@ -0,0 +1,256 @@
+use crate::system::SystemHandle;
+use log::{debug, warn};
+use sak_contract_std::Storage;
+use sak_dist_ledger::{DistLedgerEvent, LedgerPrunedError};
+use sak_rpc_interface::JSON_RPC_2;
+use sak_types::{Block, BlockHash, BlockHeight, CtrAddr, TxHash};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::{
+    broadcast::{error::RecvError, Receiver},
+    mpsc::{error::TrySendError, Sender},
+};
+
+pub(in crate::rpc) const SUBSCRIPTION_METHOD: &'static str = "subscription";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "topic", rename_all = "snake_case")]
+pub(in crate::rpc) enum SubscriptionTopic {
+    NewBlocks,
+    PendingTxs,
+    CtrState { ctr_addr: CtrAddr },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct UnsubscribeRequest {
+    pub sub_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct SubscriptionNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: SubscriptionParams,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct SubscriptionParams {
+    pub sub_id: String,
+    pub result: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct NewBlockResult {
+    pub block: Block,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct PendingTxResult {
+    pub tx_hash: TxHash,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub(in crate::rpc) struct CtrStateResult {
+    pub ctr_addr: CtrAddr,
+    pub block_height: BlockHeight,
+    pub block_hash: BlockHash,
+    pub tx_hashes: Vec<TxHash>,
+    // State as of `block_height`; None if the history does not reach back
+    // that far
+    pub ctr_state: Option<Storage>,
+}
+
+pub(in crate::rpc) struct Subscription {
+    pub sub_id: String,
+    pub topic: SubscriptionTopic,
+    pub ledger_event_rx: Receiver<DistLedgerEvent>,
+    pub sys_handle: Arc<SystemHandle>,
+    pub out_tx: Sender<String>,
+    pub overflow_tx: Sender<String>,
+}
+
+impl Subscription {
+    // Notifications are never awaited on. If the outgoing queue of the
+    // connection is full, the client is not keeping up and the
+    // subscription reports an overflow instead
+    pub(in crate::rpc) async fn run(mut self) {
+        loop {
+            let ev = match self.ledger_event_rx.recv().await {
+                Ok(ev) => ev,
+                Err(RecvError::Lagged(n)) => {
+                    warn!(
+                        "Subscription has missed ledger events, sub_id: {}, \
+                        skipped: {}",
+                        self.sub_id, n,
+                    );
+
+                    continue;
+                }
+                Err(RecvError::Closed) => {
+                    debug!(
+                        "Ledger event channel is closed, sub_id: {}",
+                        self.sub_id
+                    );
+
+                    return;
+                }
+            };
+
+            for result in self.make_results(ev).await {
+                let notification = SubscriptionNotification {
+                    jsonrpc: JSON_RPC_2.into(),
+                    method: SUBSCRIPTION_METHOD.into(),
+                    params: SubscriptionParams {
+                        sub_id: self.sub_id.clone(),
+                        result,
+                    },
+                };
+
+                let msg = match serde_json::to_string(&notification) {
+                    Ok(m) => m,
+                    Err(err) => {
+                        warn!("Cannot serialize notification, err: {}", err);
+
+                        continue;
+                    }
+                };
+
+                match self.out_tx.try_send(msg) {
+                    Ok(_) => {}
+                    Err(TrySendError::Full(_)) => {
+                        let _ = self.overflow_tx.try_send(self.sub_id.clone());
+
+                        return;
+                    }
+                    Err(TrySendError::Closed(_)) => return,
+                }
+            }
+        }
+    }
+
+    async fn make_results(&self, ev: DistLedgerEvent) -> Vec<Value> {
+        let results = match (&self.topic, ev) {
+            (SubscriptionTopic::PendingTxs, DistLedgerEvent::TxPoolStat(h)) => {
+                h.into_iter()
+                    .map(|tx_hash| {
+                        serde_json::to_value(PendingTxResult { tx_hash })
+                    })
+                    .collect()
+            }
+            (SubscriptionTopic::NewBlocks, DistLedgerEvent::NewBlocks(b)) => {
+                self.get_blocks(b)
+                    .into_iter()
+                    .map(|block| serde_json::to_value(NewBlockResult { block }))
+                    .collect()
+            }
+            (
+                SubscriptionTopic::CtrState { ctr_addr },
+                DistLedgerEvent::NewBlocks(b),
+            ) => {
+                let mut results = vec![];
+
+                for block in self.get_blocks(b) {
+                    if let Some(r) =
+                        self.make_ctr_state_result(ctr_addr, block).await
+                    {
+                        results.push(serde_json::to_value(r));
+                    }
+                }
+
+                results
+            }
+            _ => vec![],
+        };
+
+        results
+            .into_iter()
+            .filter_map(|r| match r {
+                Ok(v) => Some(v),
+                Err(err) => {
+                    warn!("Cannot serialize subscription result, err: {}", err);
+
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_blocks(
+        &self,
+        new_blocks: Vec<(BlockHeight, BlockHash)>,
+    ) -> Vec<Block> {
+        let apis = &self.sys_handle.machine.blockchain.dist_ledger.apis;
+
+        new_blocks
+            .iter()
+            .filter_map(|(_, block_hash)| match apis.get_block(block_hash) {
+                Ok(b) => b,
+                Err(err) => {
+                    warn!(
+                        "Cannot get a new block, block_hash: {}, err: {}",
+                        block_hash, err
+                    );
+
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // Txs of a pruned block are gone, so its contract calls cannot be seen
+    async fn make_ctr_state_result(
+        &self,
+        ctr_addr: &CtrAddr,
+        block: Block,
+    ) -> Option<CtrStateResult> {
+        let apis = &self.sys_handle.machine.blockchain.dist_ledger.apis;
+
+        let txs = match apis.get_txs(&block.tx_hashes).await {
+            Ok(t) => t,
+            Err(err) => {
+                warn!(
+                    "Cannot get txs of a new block, block_hash: {}, err: {}",
+                    block.get_block_hash(),
+                    err
+                );
+
+                return None;
+            }
+        };
+
+        let tx_hashes: Vec<TxHash> = txs
+            .iter()
+            .filter(|tx| tx.get_ctr_addr() == ctr_addr)
+            .map(|tx| tx.get_tx_hash().to_string())
+            .collect();
+
+        if tx_hashes.is_empty() {
+            return None;
+        }
+
+        // The ledger may have moved on by the time the block is notified
+        let ctr_state =
+            match apis.get_ctr_state_at(ctr_addr, &block.block_height).await {
+                Ok(s) => s,
+                Err(err) if LedgerPrunedError::is_pruned(&err) => None,
+                Err(err) => {
+                    warn!(
+                        "Cannot get contract state, ctr_addr: {}, \
+                        block_height: {}, err: {}",
+                        ctr_addr, block.block_height, err
+                    );
+
+                    return None;
+                }
+            };
+
+        Some(CtrStateResult {
+            ctr_addr: ctr_addr.to_string(),
+            block_height: block.block_height,
+            block_hash: block.get_block_hash().to_string(),
+            tx_hashes,
+            ctr_state,
+        })
+    }
+}
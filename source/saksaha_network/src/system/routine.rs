@@ -7,7 +7,7 @@ use crate::config::ProfiledConfig;
 use crate::machine::Machine;
 use crate::node::LocalNode;
 use crate::p2p::{P2PHost, P2PHostArgs};
-use crate::pconfig::PConfig;
+use crate::pconfig::{self, PConfig};
 use crate::rpc::RPCArgs;
 use crate::rpc::RPC;
 use crate::system::SystemHandle;
@@ -15,6 +15,7 @@ use colored::Colorize;
 use log::{error, info};
 use sak_p2p_id::Identity;
 use sak_p2p_peertable::PeerTable;
+use sak_p2p_transport::handshake::ProtocolInfo;
 use std::sync::Arc;
 
 pub(super) struct Routine {
@@ -85,9 +86,19 @@ impl Routine {
 
         info!("Resolved config: {:?}", config);
 
+        let addr_book_path =
+            pconfig::get_addr_book_file_path(&config.app_prefix)?;
+
         let peer_table = {
-            let ps =
-                PeerTable::init(config.p2p.p2p_peer_table_capacity).await?;
+            let ban_list_path =
+                pconfig::get_ban_list_file_path(&config.app_prefix)?;
+
+            let ps = PeerTable::init(
+                config.p2p.p2p_peer_table_capacity,
+                config.p2p.p2p_outbound_peer_capacity,
+                Some(ban_list_path),
+            )
+            .await?;
 
             Arc::new(ps)
         };
@@ -151,6 +162,31 @@ impl Routine {
             Arc::new(i)
         };
 
+        let blockchain = {
+            let b = Blockchain::init(
+                config.app_prefix,
+                config.blockchain.tx_sync_interval,
+                None,
+                config.blockchain.block_sync_interval,
+                config.db.in_memory_db,
+                config.blockchain.prune_keep_blocks,
+                identity.clone(),
+            )
+            .await?;
+
+            b
+        };
+
+        let protocol_info = {
+            let genesis_block_hash =
+                blockchain.get_genesis_block_hash().await?;
+
+            let p =
+                ProtocolInfo::new(config.p2p.network_id, genesis_block_hash);
+
+            Arc::new(p)
+        };
+
         let p2p_host = {
             let p2p_host_args = P2PHostArgs {
                 addr_expire_duration: config.p2p.addr_expire_duration,
@@ -167,26 +203,16 @@ impl Routine {
                 p2p_max_conn_count: config.p2p.p2p_max_conn_count,
                 p2p_port,
                 bootstrap_addrs: config.p2p.bootstrap_addrs,
+                addr_book_path: Some(addr_book_path),
+                external_addr: config.p2p.external_addr,
                 identity: identity.clone(),
                 peer_table: peer_table.clone(),
+                protocol_info,
             };
 
             P2PHost::init(p2p_host_args).await?
         };
 
-        let blockchain = {
-            let b = Blockchain::init(
-                config.app_prefix,
-                config.blockchain.tx_sync_interval,
-                None,
-                config.blockchain.block_sync_interval,
-                identity.clone(),
-            )
-            .await?;
-
-            b
-        };
-
         let machine = {
             let m = Machine { blockchain };
 
@@ -225,6 +251,10 @@ impl Routine {
             let rpc_args = RPCArgs {
                 sys_handle,
                 rpc_socket,
+                max_batch_size: config.rpc.rpc_max_batch_size,
+                rpc_auth: config.rpc.rpc_auth.clone(),
+                method_filter: config.rpc.method_filter.clone(),
+                rate_limit: config.rpc.rate_limit,
             };
 
             RPC::init(rpc_args)?
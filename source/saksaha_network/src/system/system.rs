@@ -13,15 +13,21 @@ pub struct SystemRunArgs {
     pub p2p_task_interval: Option<u16>,
     pub p2p_task_queue_capacity: Option<u16>,
     pub p2p_peer_table_capacity: Option<i16>,
+    pub p2p_outbound_peer_capacity: Option<i16>,
     pub p2p_max_conn_count: Option<u16>,
     pub p2p_dial_interval: Option<u16>,
     pub rpc_port: Option<u16>,
+    pub rpc_max_batch_size: Option<usize>,
     pub p2p_port: Option<u16>,
     pub addr_expire_duration: Option<u64>,
     pub addr_monitor_interval: Option<u64>,
     pub bootstrap_urls: Option<Vec<String>>,
+    pub external_addr: Option<String>,
     pub cfg_profile: Option<String>,
+    pub network_id: Option<String>,
     pub miner: bool,
+    pub in_memory_db: bool,
+    pub prune_keep_blocks: Option<u128>,
     pub mine_interval: Option<u64>,
     pub node_task_min_interval: Option<u64>,
     pub peer_register_interval: Option<u64>,
@@ -2,12 +2,14 @@ use crate::SaksahaSDKError;
 use hyper::{Body, Client, Method, Request, Uri};
 use log::warn;
 use sak_contract_std::{CtrCallType, CtrRequest, RequestArgs};
-use sak_crypto::{Bls12, Circuit, Hasher, Proof, Scalar, ScalarExt};
+use sak_crypto::{
+    Bls12, Circuit, Hasher, Proof, Scalar, ScalarExt, SecretKey, ToEncodedPoint,
+};
 use sak_proofs::{
     MerkleTree, NewCoin, OldCoin, Path, ProofError, CM_TREE_DEPTH,
 };
 use sak_rpc_interface::{JsonRequest, JsonResponse};
-use sak_types::{Cm, CmIdx, Tx};
+use sak_types::{Cm, CmIdx, MintTxCandidate, PourTxCandidate, Tx};
 use serde::{Deserialize, Serialize};
 use std::time;
 use type_extension::{U8Arr32, U8Array};
@@ -113,7 +115,21 @@ impl SendMintTxRequest {
     }
 }
 
+// Public input of the pour proof, which binds the proof to the key that signs
+// the tx
+pub fn make_author_pk_hash(
+    secret_key: &SecretKey,
+) -> Result<Scalar, SaksahaSDKError> {
+    let public_key_str = sak_crypto::encode_hex(
+        &secret_key.public_key().to_encoded_point(false).to_bytes(),
+    );
+
+    sak_types::make_author_pk_hash(&public_key_str)
+}
+
+// pi has to be generated with make_author_pk_hash() of the same secret_key
 pub async fn send_tx_pour(
+    secret_key: &SecretKey,
     sn_1: U8Arr32,
     cm_1: U8Arr32,
     cm_2: U8Arr32,
@@ -129,30 +145,41 @@ pub async fn send_tx_pour(
 
     let body = {
         let ctr_request = serde_json::to_vec(&ctr_request)?;
-        let sig = String::from("author_sig_1");
         let created_at =
             String::from(format!("created_at_{:?}", time::SystemTime::now()));
 
-        // *** Need to change dummy values to real values
-        let send_req = SendPourTxRequest::new(
+        let tc = PourTxCandidate::new(
             created_at,
             ctr_request,
-            sig,
+            String::new(),
             Some(ctr_addr),
             pi,
             sn_1,
             cm_1,
             cm_2,
             merkle_rt,
+        )
+        .sign(secret_key);
+
+        let send_req = SendPourTxRequest::new(
+            tc.created_at,
+            tc.data,
+            tc.author_sig,
+            Some(tc.ctr_addr),
+            tc.pi,
+            tc.sn_1,
+            tc.cm_1,
+            tc.cm_2,
+            tc.merkle_rt,
         );
 
-        let params = serde_json::to_string(&send_req)?.as_bytes().to_vec();
+        let params = serde_json::to_value(&send_req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -176,6 +203,7 @@ pub async fn send_tx_pour(
 }
 
 pub async fn send_tx_mint(
+    secret_key: &SecretKey,
     ctr_addr: Option<String>,
     req_type: String,
     args: RequestArgs,
@@ -197,24 +225,36 @@ pub async fn send_tx_mint(
             ctr_call_type: CtrCallType::Execute,
         };
 
-        let send_req = SendMintTxRequest::new(
+        let tc = MintTxCandidate::new(
             String::from("created_at_1"),
             serde_json::to_vec(&req)?,
-            String::from("author_sig_1"),
+            String::new(),
             ctr_addr,
             cm,
             v,
             k,
             s,
+        )
+        .sign(secret_key);
+
+        let send_req = SendMintTxRequest::new(
+            tc.created_at,
+            tc.data,
+            tc.author_sig,
+            Some(tc.ctr_addr),
+            tc.cm_1,
+            tc.v,
+            tc.k,
+            tc.s,
         );
 
-        let params = serde_json::to_string(&send_req)?.as_bytes().to_vec();
+        let params = serde_json::to_value(&send_req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_mint_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -255,13 +295,13 @@ pub async fn query_ctr(
         };
 
         let send_req = QueryCtrRequest { ctr_addr, req };
-        let params = serde_json::to_vec(&send_req)?;
+        let params = serde_json::to_value(&send_req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "query_ctr".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -307,13 +347,13 @@ pub async fn get_cm_idx(
     let body = {
         let req = GetCmIdxRequest { cm };
 
-        let params = serde_json::to_string(&req)?.as_bytes().to_vec();
+        let params = serde_json::to_value(&req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_cm_idx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -373,13 +413,13 @@ pub async fn get_tx(
     let body = {
         let req = GetTxRequest { hash };
 
-        let params = serde_json::to_string(&req)?.as_bytes().to_vec();
+        let params = serde_json::to_value(&req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -478,13 +518,13 @@ pub async fn get_auth_path(
 
     let body = {
         let send_req = GetAuthPathRequest { cm_idx: idx };
-        let params = serde_json::to_string(&send_req)?.as_bytes().to_vec();
+        let params = serde_json::to_value(&send_req)?;
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "get_auth_path".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request)?;
@@ -335,6 +335,7 @@ pub async fn test_make_a_proof_and_verify_it() {
         coin_1_old, //
         coin_1_new, //
         coin_2_new,
+        sak_types::get_dummy_author_pk_hash(),
     )
     .unwrap();
 
@@ -1,5 +1,6 @@
 use super::routine::Routine;
 use crate::{Config, WalletCredential, WalletError};
+use hyper_rpc_router::{MethodFilter, RPCAuth, RateLimit};
 use log::error;
 
 pub struct App {}
@@ -9,6 +10,9 @@ pub const APP_NAME: &'static str = "saksaha-wallet";
 #[derive(Debug)]
 pub struct AppArgs {
     pub rpc_port: Option<u16>,
+    pub rpc_auth: Option<RPCAuth>,
+    pub rpc_rate_limit: Option<RateLimit>,
+    pub rpc_method_filter: MethodFilter,
     pub wallet_credential: WalletCredential,
     pub config: Config,
 }
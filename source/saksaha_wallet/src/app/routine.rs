@@ -27,7 +27,14 @@ impl Routine {
             Arc::new(w)
         };
 
-        let rpc = RPC::init(app_args.rpc_port, wallet).await?;
+        let rpc = RPC::init(
+            app_args.rpc_port,
+            app_args.rpc_auth,
+            app_args.rpc_rate_limit,
+            app_args.rpc_method_filter,
+            wallet,
+        )
+        .await?;
 
         tokio::spawn(async move {
             tokio::join!(rpc.run());
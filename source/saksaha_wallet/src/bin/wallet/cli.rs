@@ -4,6 +4,10 @@ use saksaha_wallet::WalletError;
 #[derive(Debug)]
 pub(crate) struct CLIArgs {
     pub(crate) rpc_port: Option<u16>,
+    pub(crate) rpc_auth_token: Option<String>,
+    pub(crate) rpc_rate_limit: Option<u32>,
+    pub(crate) rpc_allowed_methods: Option<Vec<String>>,
+    pub(crate) rpc_denied_methods: Option<Vec<String>>,
     pub(crate) public_key: Option<String>,
     pub(crate) secret: Option<String>,
     pub(crate) cfg_profile: Option<String>,
@@ -28,6 +32,36 @@ pub(crate) fn get_args() -> Result<CLIArgs, WalletError> {
         None => None,
     };
 
+    let rpc_auth_token = match matches.value_of("rpc-auth-token") {
+        Some(t) => Some(String::from(t)),
+        None => None,
+    };
+
+    let rpc_rate_limit = match matches.value_of("rpc-rate-limit") {
+        Some(r) => match r.parse::<u32>() {
+            Ok(r) if r > 0 => Some(r),
+            Ok(_) => {
+                return Err(format!("Rate limit should be at least 1").into());
+            }
+            Err(err) => {
+                return Err(format!(
+                    "Cannot parse rpc rate limit (u32), err: {}",
+                    err,
+                )
+                .into());
+            }
+        },
+        None => None,
+    };
+
+    let rpc_allowed_methods = matches
+        .value_of("rpc-allowed-methods")
+        .map(parse_method_list);
+
+    let rpc_denied_methods = matches
+        .value_of("rpc-denied-methods")
+        .map(parse_method_list);
+
     let public_key = match matches.value_of("public-key") {
         Some(m) => Some(String::from(m)),
         None => None,
@@ -45,12 +79,24 @@ pub(crate) fn get_args() -> Result<CLIArgs, WalletError> {
 
     Ok(CLIArgs {
         rpc_port,
+        rpc_auth_token,
+        rpc_rate_limit,
+        rpc_allowed_methods,
+        rpc_denied_methods,
         public_key,
         secret,
         cfg_profile,
     })
 }
 
+fn parse_method_list(methods: &str) -> Vec<String> {
+    methods
+        .split(',')
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect()
+}
+
 fn create_app<'a>() -> Command<'a> {
     command!()
         .version("0.0.1")
@@ -76,6 +122,42 @@ fn create_app<'a>() -> Command<'a> {
                     e.g. 21452",
                 ),
         )
+        .arg(
+            Arg::new("rpc-auth-token") //
+                .long("rpc-auth-token")
+                .takes_value(true)
+                .long_help(
+                    "Bearer token every RPC request has to carry in its \n\
+                    Authorization header",
+                ),
+        )
+        .arg(
+            Arg::new("rpc-rate-limit") //
+                .long("rpc-rate-limit")
+                .takes_value(true)
+                .long_help(
+                    "Max RPC requests per second from a single IP \n\
+                    e.g. 20",
+                ),
+        )
+        .arg(
+            Arg::new("rpc-allowed-methods") //
+                .long("rpc-allowed-methods")
+                .takes_value(true)
+                .long_help(
+                    "The only RPC methods served, comma separated \n\
+                    e.g. get_balance,update_coin_status",
+                ),
+        )
+        .arg(
+            Arg::new("rpc-denied-methods") //
+                .long("rpc-denied-methods")
+                .takes_value(true)
+                .long_help(
+                    "RPC methods not served, comma separated \n\
+                    e.g. send_pour_tx",
+                ),
+        )
         .arg(
             Arg::new("public-key") //
                 .long("public-key")
@@ -2,6 +2,7 @@ mod cli;
 mod credential;
 mod prompt;
 
+use hyper_rpc_router::{MethodFilter, RPCAuth, RateLimit};
 use log::info;
 use sak_logger::RUST_LOG_ENV;
 use saksaha_wallet::{App, AppArgs, Config, WalletError};
@@ -29,6 +30,17 @@ fn main() -> Result<(), WalletError> {
 
     let app_args = AppArgs {
         rpc_port: cli_args.rpc_port,
+        rpc_auth: cli_args
+            .rpc_auth_token
+            .map(|token| RPCAuth::Bearer { token }),
+        rpc_rate_limit: cli_args.rpc_rate_limit.map(|r| RateLimit {
+            requests_per_sec: r,
+            burst: r,
+        }),
+        rpc_method_filter: MethodFilter::new(
+            cli_args.rpc_allowed_methods,
+            cli_args.rpc_denied_methods,
+        ),
         wallet_credential,
         config,
     };
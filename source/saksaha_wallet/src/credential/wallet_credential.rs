@@ -1,6 +1,6 @@
 use crate::{WalletError, APP_NAME};
 use colored::Colorize;
-use sak_crypto::{SakKey, ToEncodedPoint};
+use sak_crypto::{SakKey, SecretKey, ToEncodedPoint};
 use sak_p2p_id::Credential;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -54,6 +54,13 @@ impl WalletCredential {
         Ok(c)
     }
 
+    // Key that signs the txs this wallet sends
+    pub fn get_secret_key(&self) -> Result<SecretKey, WalletError> {
+        let credential = Credential::new(&self.secret, &self.public_key)?;
+
+        Ok(credential.secret_key)
+    }
+
     pub fn persist(&self) -> Result<(), WalletError> {
         let app_path =
             sak_fs::get_app_root_path(APP_NAME)?.join(&self.acc_addr);
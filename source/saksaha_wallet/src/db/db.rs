@@ -67,13 +67,22 @@ impl WalletDB {
             }
         };
 
-        let schema = WalletDBSchema::new(kv_db.db_instance);
+        let schema = WalletDBSchema::new(kv_db);
 
         let wallet_db = WalletDB { schema };
 
         Ok(wallet_db)
     }
 
+    pub(crate) fn init_in_memory() -> WalletDB {
+        let kv_db =
+            KeyValueDatabase::new_in_memory(WalletDBSchema::get_cf_names());
+
+        let schema = WalletDBSchema::new(kv_db);
+
+        WalletDB { schema }
+    }
+
     pub fn get_db_path(acc_addr: &String) -> Result<PathBuf, WalletError> {
         let app_path =
             sak_fs::create_or_get_app_path(APP_NAME)?.join(&acc_addr);
@@ -2,24 +2,17 @@ use super::Raw;
 use crate::db::cfs;
 use crate::WalletError;
 use sak_crypto::{Scalar, ScalarExt};
-use sak_kv_db::DBIteratorWithThreadMode;
-use sak_kv_db::DBWithThreadMode;
-use sak_kv_db::MultiThreaded;
+use sak_kv_db::KVIterator;
 use sak_kv_db::WriteBatch;
 use sak_types::CoinIdx;
 use sak_types::CoinStatus;
 use sak_types::TxHash;
 
 impl Raw {
-    pub(crate) fn get_coin_iter(
-        &self,
-    ) -> Result<
-        DBIteratorWithThreadMode<DBWithThreadMode<MultiThreaded>>,
-        WalletError,
-    > {
+    pub(crate) fn get_coin_iter(&self) -> Result<KVIterator<'_>, WalletError> {
         let cf = self.make_cf_handle(&self.db, cfs::CM)?;
 
-        let iter = self.db.iterator_cf(&cf, sak_kv_db::IteratorMode::Start);
+        let iter = self.db.iterator_cf(&cf, sak_kv_db::IteratorMode::Start)?;
 
         Ok(iter)
     }
@@ -29,7 +22,8 @@ impl Raw {
     ) -> Result<Option<CoinIdx>, WalletError> {
         let cf = self.make_cf_handle(&self.db, cfs::CM)?;
 
-        let mut iter = self.db.iterator_cf(&cf, sak_kv_db::IteratorMode::End);
+        let mut iter =
+            self.db.iterator_cf(&cf, sak_kv_db::IteratorMode::End)?;
 
         match iter.next() {
             Some((c_idx, _cm)) => {
@@ -1,32 +1,38 @@
 use crate::db::{raw::Raw, schema::cfs};
-use sak_kv_db::{BoundColumnFamily, ColumnFamilyDescriptor, Options, DB};
-use std::sync::Arc;
+use sak_kv_db::{ColumnFamilyDescriptor, KeyValueDatabase, Options};
 
 pub(crate) struct WalletDBSchema {
     pub raw: Raw,
 }
 
 impl WalletDBSchema {
-    pub(crate) fn new(db: DB) -> WalletDBSchema {
+    pub(crate) fn new(db: KeyValueDatabase) -> WalletDBSchema {
         let raw = Raw { db };
 
         WalletDBSchema { raw }
     }
 
     pub(crate) fn make_cf_descriptors() -> Vec<ColumnFamilyDescriptor> {
+        Self::get_cf_names()
+            .into_iter()
+            .map(|n| ColumnFamilyDescriptor::new(n, Options::default()))
+            .collect()
+    }
+
+    pub(crate) fn get_cf_names() -> Vec<&'static str> {
         vec![
-            ColumnFamilyDescriptor::new(cfs::RHO, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::R, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::S, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::V, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::A_SK, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::A_PK, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::COIN_STATUS, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::USER_ID, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::CM_IDX, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::COIN_IDX, Options::default()),
-            ColumnFamilyDescriptor::new(cfs::TX_HASH, Options::default()),
+            cfs::RHO,
+            cfs::R,
+            cfs::S,
+            cfs::V,
+            cfs::A_SK,
+            cfs::A_PK,
+            cfs::COIN_STATUS,
+            cfs::USER_ID,
+            cfs::CM,
+            cfs::CM_IDX,
+            cfs::COIN_IDX,
+            cfs::TX_HASH,
         ]
     }
 }
@@ -31,7 +31,7 @@ pub(in crate::rpc) async fn get_balance(
         "get_balance should contain params",
     );
 
-    debug!("params: {:?}", String::from_utf8(params.clone()));
+    debug!("params: {}", params);
 
     let rb: GetBalanceRequest = require_params_parsed!(route_state, &params);
 
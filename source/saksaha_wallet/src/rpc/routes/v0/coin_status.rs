@@ -30,7 +30,7 @@ pub(in crate::rpc) async fn update_coin_status(
         "update_coin_status should contain params",
     );
 
-    // debug!("\tparams: {:?}", String::from_utf8(params.clone()));
+    // debug!("\tparams: {}", params);
 
     let rb: UpdateCoinStatusRequest =
         require_params_parsed!(route_state, &params);
@@ -4,7 +4,10 @@ use crate::{
     WalletError,
 };
 use colored::Colorize;
-use hyper_rpc_router::Router;
+use hyper_rpc_router::{
+    make_auth_middleware, make_rate_limit_middleware, MethodFilter, RPCAuth,
+    RateLimit, RateLimiter, Router,
+};
 use hyper_server::{cors, Middleware, RPCServer};
 use log::{error, info, warn};
 use std::{sync::Arc, time::Duration};
@@ -13,12 +16,18 @@ use tokio::net::TcpListener;
 pub(crate) struct RPC {
     rpc_port: u16,
     rpc_socket: TcpListener,
+    rpc_auth: Option<RPCAuth>,
+    rate_limit: Option<RateLimit>,
+    method_filter: MethodFilter,
     wallet: Arc<Wallet>,
 }
 
 impl RPC {
     pub async fn init(
         rpc_port: Option<u16>,
+        rpc_auth: Option<RPCAuth>,
+        rate_limit: Option<RateLimit>,
+        method_filter: MethodFilter,
         wallet: Arc<Wallet>,
     ) -> Result<RPC, WalletError> {
         let rpc_port = rpc_port.unwrap_or_else(|| {
@@ -47,6 +56,9 @@ impl RPC {
         let rpc = RPC {
             rpc_port: socket_addr.port(),
             rpc_socket,
+            rpc_auth,
+            rate_limit,
+            method_filter,
             wallet,
         };
 
@@ -56,9 +68,17 @@ impl RPC {
     pub async fn run(self) -> Result<(), WalletError> {
         info!("RPC server runs");
 
+        let rate_limiter =
+            self.rate_limit.map(|r| Arc::new(RateLimiter::new(r)));
+
         let router = {
             let routes = routes::get_routes();
-            let router = Router::new(routes);
+            let mut router =
+                Router::new(routes).with_method_filter(self.method_filter);
+
+            if let Some(l) = &rate_limiter {
+                router = router.with_rate_limiter(l.clone());
+            }
 
             router
         };
@@ -73,7 +93,17 @@ impl RPC {
             m
         };
 
-        let middlewares = vec![cors, route];
+        let mut middlewares = vec![cors];
+
+        if let Some(l) = rate_limiter {
+            middlewares.push(make_rate_limit_middleware(l));
+        }
+
+        if let Some(a) = self.rpc_auth {
+            middlewares.push(make_auth_middleware(a));
+        }
+
+        middlewares.push(route);
 
         let rpc_server = RPCServer {};
 
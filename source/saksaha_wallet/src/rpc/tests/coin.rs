@@ -42,13 +42,13 @@ async fn test_send_tx() {
             ctr_request,
         };
 
-        let params = serde_json::to_vec(&send_tx_req).unwrap();
+        let params = serde_json::to_value(&send_tx_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -6,6 +6,7 @@ use crate::{
 use envelope_contract::{request_type, SendMsgParams};
 use envelope_term::ENVELOPE_CTR_ADDR;
 use hyper::{Body, Client, Method, Request, Uri};
+use hyper_rpc_router::MethodFilter;
 use sak_contract_std::CtrRequest;
 use sak_rpc_interface::{JsonRequest, JsonResponse};
 use std::sync::Arc;
@@ -32,8 +33,7 @@ pub(crate) async fn mock_test_context() -> TestContext {
 
     let acc_addr = credential_manager.get_credential().acc_addr.clone();
 
-    let wallet_db =
-        WalletDB::init(&credential_manager.get_credential(), true).unwrap();
+    let wallet_db = WalletDB::init_in_memory();
 
     let wallet = {
         let mut w = Wallet::init(credential_manager, wallet_db, config)
@@ -43,7 +43,10 @@ pub(crate) async fn mock_test_context() -> TestContext {
         Arc::new(w)
     };
 
-    let rpc = RPC::init(None, wallet.clone()).await.unwrap();
+    let rpc =
+        RPC::init(None, None, None, MethodFilter::default(), wallet.clone())
+            .await
+            .unwrap();
 
     TestContext {
         wallet,
@@ -101,13 +104,13 @@ pub(crate) async fn send_msg_for_test(acc_addr: &String) {
             ctr_request,
         };
 
-        let params = serde_json::to_vec(&send_tx_req).unwrap();
+        let params = serde_json::to_value(&send_tx_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "send_pour_tx".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -151,13 +154,13 @@ pub(crate) async fn update_coin_status(acc_addr: &String) -> String {
             ctr_request,
         };
 
-        let params = serde_json::to_vec(&send_tx_req).unwrap();
+        let params = serde_json::to_value(&send_tx_req).unwrap();
 
         let json_request = JsonRequest {
             jsonrpc: "2.0".to_string(),
             method: "update_coin_status".to_string(),
             params: Some(params),
-            id: "test_1".to_string(),
+            id: "test_1".into(),
         };
 
         let str = serde_json::to_string(&json_request).unwrap();
@@ -148,12 +148,18 @@ impl Wallet {
             self.get_old_coin(coin, auth_path).await?
         };
 
+        let secret_key = self
+            .get_credential_manager()
+            .get_credential()
+            .get_secret_key()?;
+
         println!("[+] making proof...");
 
         let pi = CoinProof::generate_proof_1_to_2(
             old_coin,
             new_coin_1.extract(),
             new_coin_2.extract(),
+            saksaha::make_author_pk_hash(&secret_key)?,
         )?;
 
         let mut pi_ser = Vec::new();
@@ -162,6 +168,7 @@ impl Wallet {
         println!("[!] pi serialized, len: {}", pi_ser.len());
 
         let json_response = saksaha::send_tx_pour(
+            &secret_key,
             sn_1,
             new_coin_1.cm.to_bytes(),
             new_coin_2.cm.to_bytes(),